@@ -0,0 +1,256 @@
+use crate::sysinfo::{SessionStats, get_session_stats};
+use crate::tmux::TmuxClient;
+use anyhow::Result;
+use std::process::Command;
+use std::time::Duration;
+
+// Delay between the two stats samples taken by `trex check`, since CPU usage
+// is computed from the tick delta between consecutive samples.
+const CHECK_SAMPLE_INTERVAL: Duration = Duration::from_millis(1000);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertMetric {
+    Cpu, // percent, summed over all session processes
+    Mem, // resident memory in MB
+}
+
+/// A threshold that applies to every session whose name matches `session_glob`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    pub session_glob: String,
+    pub metric: AlertMetric,
+    pub threshold: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertViolation {
+    pub session: String,
+    pub rule: AlertRule,
+    pub value: f64,
+}
+
+impl AlertRule {
+    /* Parses a rule condition such as `cpu>250%`, `mem>2048MB`, or `mem>2GB`.
+     * CPU thresholds are percentages; memory thresholds default to MB. */
+    pub fn parse(session_glob: &str, condition: &str) -> Result<Self, String> {
+        let session_glob = session_glob.trim();
+        if session_glob.is_empty() {
+            return Err(format!("Skipped alert {condition}: missing session"));
+        }
+
+        let compact = condition.replace(' ', "").to_lowercase();
+        let (metric, value) = compact
+            .split_once('>')
+            .ok_or_else(|| format!("Skipped alert {condition}: expected <metric>><value>"))?;
+
+        let (metric, value) = match metric {
+            "cpu" => (AlertMetric::Cpu, value.trim_end_matches('%')),
+            "mem" | "memory" => (AlertMetric::Mem, value),
+            _ => {
+                return Err(format!(
+                    "Skipped alert {condition}: unknown metric {metric}"
+                ));
+            }
+        };
+
+        let threshold = match metric {
+            AlertMetric::Cpu => value.parse::<f64>().ok(),
+            AlertMetric::Mem => parse_mem_mb(value),
+        }
+        .filter(|threshold| threshold.is_finite() && *threshold >= 0.0)
+        .ok_or_else(|| format!("Skipped alert {condition}: invalid threshold {value}"))?;
+
+        Ok(Self {
+            session_glob: session_glob.to_string(),
+            metric,
+            threshold,
+        })
+    }
+
+    pub fn matches_session(&self, session_name: &str) -> bool {
+        glob_match(&self.session_glob, session_name)
+    }
+
+    // Returns the offending value when the stats exceed this rule's threshold.
+    pub fn exceeded_by(&self, stats: &SessionStats) -> Option<f64> {
+        let value = match self.metric {
+            AlertMetric::Cpu => stats.cpu_percent,
+            AlertMetric::Mem => stats.mem_mb as f64,
+        };
+        (value > self.threshold).then_some(value)
+    }
+
+    // Returns the rule condition in its config form, e.g. `cpu>250%`.
+    pub fn condition(&self) -> String {
+        match self.metric {
+            AlertMetric::Cpu => format!("cpu>{}%", self.threshold),
+            AlertMetric::Mem => format!("mem>{}MB", self.threshold),
+        }
+    }
+}
+
+impl AlertViolation {
+    pub fn message(&self) -> String {
+        let value = match self.rule.metric {
+            AlertMetric::Cpu => format!("cpu {:.1}%", self.value),
+            AlertMetric::Mem => format!("mem {}MB", self.value as u64),
+        };
+        format!(
+            "{}: {} ({} {})",
+            self.session,
+            value,
+            self.rule.session_glob,
+            self.rule.condition()
+        )
+    }
+}
+
+// Parses a lowercase memory size such as `2gb`, `512m`, or `512` into MB.
+pub(crate) fn parse_mem_mb(value: &str) -> Option<f64> {
+    if let Some(gb) = value.strip_suffix("gb").or_else(|| value.strip_suffix('g')) {
+        return gb.parse::<f64>().ok().map(|gb| gb * 1024.0);
+    }
+    let mb = value
+        .strip_suffix("mb")
+        .or_else(|| value.strip_suffix('m'))
+        .unwrap_or(value);
+    mb.parse().ok()
+}
+
+/* Matches a session name against a shell-style glob.
+ * Supports `*` (any run of characters) and `?` (any single character). */
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// Evaluates all rules that apply to a session against its current stats.
+pub fn evaluate(
+    rules: &[AlertRule],
+    session_name: &str,
+    stats: &SessionStats,
+) -> Vec<AlertViolation> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches_session(session_name))
+        .filter_map(|rule| {
+            rule.exceeded_by(stats).map(|value| AlertViolation {
+                session: session_name.to_string(),
+                rule: rule.clone(),
+                value,
+            })
+        })
+        .collect()
+}
+
+/* Samples every session twice and returns all threshold violations.
+ * Used by `trex check`; the first sample only primes the CPU delta. */
+pub fn check_sessions(rules: &[AlertRule]) -> Result<Vec<AlertViolation>> {
+    TmuxClient::check_installed()?;
+
+    let sessions = TmuxClient::list_sessions()?;
+    for session in &sessions {
+        let _ = get_session_stats(&session.name);
+    }
+    std::thread::sleep(CHECK_SAMPLE_INTERVAL);
+
+    let mut violations = Vec::new();
+    for session in &sessions {
+        let stats = get_session_stats(&session.name)?;
+        violations.extend(evaluate(rules, &session.name, &stats));
+    }
+
+    Ok(violations)
+}
+
+// Sends a desktop notification for a new violation. Failures are ignored
+// because notifications are optional and `notify-send` may not be installed.
+pub fn notify_desktop(violation: &AlertViolation) {
+    let _ = Command::new("notify-send")
+        .args(["--app-name=trex", "trex alert", &violation.message()])
+        .output();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(cpu_percent: f64, mem_mb: u64) -> SessionStats {
+        SessionStats {
+            cpu_percent,
+            mem_mb,
+            mem_percent: 0.0,
+            io_read_bps: 0,
+            io_write_bps: 0,
+        }
+    }
+
+    #[test]
+    fn parses_cpu_and_memory_conditions() {
+        let cpu = AlertRule::parse("ai-*", "cpu>250%").unwrap();
+        assert_eq!(cpu.metric, AlertMetric::Cpu);
+        assert_eq!(cpu.threshold, 250.0);
+
+        let mem = AlertRule::parse("*", "mem > 2GB").unwrap();
+        assert_eq!(mem.metric, AlertMetric::Mem);
+        assert_eq!(mem.threshold, 2048.0);
+
+        let mem_mb = AlertRule::parse("*", "mem>512MB").unwrap();
+        assert_eq!(mem_mb.threshold, 512.0);
+    }
+
+    #[test]
+    fn rejects_malformed_conditions() {
+        assert!(AlertRule::parse("*", "cpu<10").is_err());
+        assert!(AlertRule::parse("*", "disk>10").is_err());
+        assert!(AlertRule::parse("*", "cpu>lots").is_err());
+        assert!(AlertRule::parse(" ", "cpu>10").is_err());
+    }
+
+    #[test]
+    fn glob_matches_session_names() {
+        assert!(glob_match("ai-*", "ai-codex"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("dev?", "dev1"));
+        assert!(glob_match("*-api-*", "team-api-prod"));
+        assert!(!glob_match("ai-*", "main"));
+        assert!(!glob_match("dev?", "dev"));
+    }
+
+    #[test]
+    fn evaluates_only_matching_rules() {
+        let rules = vec![
+            AlertRule::parse("ai-*", "cpu>250%").unwrap(),
+            AlertRule::parse("*", "mem>1024").unwrap(),
+        ];
+
+        let violations = evaluate(&rules, "ai-codex", &stats(300.0, 100));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule.metric, AlertMetric::Cpu);
+
+        let violations = evaluate(&rules, "main", &stats(300.0, 2048));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule.metric, AlertMetric::Mem);
+    }
+}
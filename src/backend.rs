@@ -10,8 +10,9 @@ pub use dto::{
     BackendStats, SnapshotStatus, SnapshotSummary,
 };
 
+use crate::config::TrexConfig;
 use crate::git::GitStatus;
-use crate::process::find_ai_processes;
+use crate::process::find_ai_processes_with;
 use crate::sysinfo::get_session_stats;
 use crate::tmux::TmuxClient;
 use anyhow::Result;
@@ -28,8 +29,10 @@ pub fn collect_snapshot() -> Result<BackendSnapshot> {
     let generated_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
     let mut errors = Vec::new();
     let mut sessions = TmuxClient::list_sessions()?;
+    let config = TrexConfig::load();
+    let health_weights = config.health;
 
-    let ai_processes = match find_ai_processes() {
+    let ai_processes = match find_ai_processes_with(&config.agents) {
         Ok(processes) => processes,
         Err(error) => {
             errors.push(BackendError {
@@ -68,6 +71,7 @@ pub fn collect_snapshot() -> Result<BackendSnapshot> {
                 .get(&session.name)
                 .cloned()
                 .unwrap_or_default(),
+            &health_weights,
         ));
     }
 
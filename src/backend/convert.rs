@@ -1,27 +1,35 @@
 use super::dto::{BackendAgent, BackendGit, BackendHealth, BackendSession, BackendStats};
 use crate::git::GitStatus;
-use crate::health::{HealthLevel, HealthScore};
+use crate::health::{HealthScore, HealthWeights};
 use crate::process::{AiProcessInfo, ProcessState};
 use crate::sysinfo::SessionStats;
 use crate::tmux::{ActivityLevel, TmuxSession};
 use std::collections::HashMap;
 
 impl BackendSession {
-    pub(super) fn from_session(session: &TmuxSession, agents: Vec<BackendAgent>) -> Self {
-        let health = HealthScore::calculate(session);
+    pub(super) fn from_session(
+        session: &TmuxSession,
+        agents: Vec<BackendAgent>,
+        health_weights: &HealthWeights,
+    ) -> Self {
+        let health = HealthScore::calculate_with(session, health_weights);
 
         Self {
+            id: session.id.clone(),
             name: session.name.clone(),
             attached: session.attached,
+            clients: session.clients,
             windows: session.windows,
+            panes: session.panes,
             path: session.path.as_ref().map(|path| path.display().to_string()),
+            created: session.created,
             last_activity: session.last_activity,
             activity_level: session.activity_level().map(activity_level_name),
             activity_ago: session.activity_ago_string(),
             stats: session.stats.as_ref().map(BackendStats::from_stats),
             health: BackendHealth {
                 score: health.score,
-                level: health_level_name(health.level()),
+                level: health.level().name().to_string(),
             },
             git: session.git_status.as_ref().map(BackendGit::from_status),
             agents,
@@ -38,6 +46,7 @@ impl BackendAgent {
             activity_state: process_state_name(process.activity_state),
             pid: process.pid,
             child_ai_names: process.child_ai_names.clone(),
+            container: process.container.clone(),
         }
     }
 }
@@ -48,6 +57,8 @@ impl BackendStats {
             cpu_percent: stats.cpu_percent,
             mem_mb: stats.mem_mb,
             mem_percent: stats.mem_percent,
+            io_read_bps: stats.io_read_bps,
+            io_write_bps: stats.io_write_bps,
         }
     }
 }
@@ -84,15 +95,6 @@ pub(super) fn group_agents_by_session(
     groups
 }
 
-fn health_level_name(level: HealthLevel) -> String {
-    match level {
-        HealthLevel::Healthy => "healthy",
-        HealthLevel::Warning => "warning",
-        HealthLevel::Critical => "critical",
-    }
-    .to_string()
-}
-
 fn activity_level_name(level: ActivityLevel) -> String {
     match level {
         ActivityLevel::Active => "active",
@@ -106,6 +108,7 @@ fn process_state_name(state: ProcessState) -> String {
     match state {
         ProcessState::Running => "running",
         ProcessState::Waiting => "waiting",
+        ProcessState::Stopped => "stopped",
         ProcessState::Unknown => "unknown",
     }
     .to_string()
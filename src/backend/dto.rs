@@ -39,10 +39,14 @@ pub struct SnapshotSummary {
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BackendSession {
+    pub id: String,
     pub name: String,
     pub attached: bool,
+    pub clients: u32,
     pub windows: u32,
+    pub panes: u32,
     pub path: Option<String>,
+    pub created: Option<u64>,
     pub last_activity: Option<u64>,
     pub activity_level: Option<String>,
     pub activity_ago: Option<String>,
@@ -61,6 +65,7 @@ pub struct BackendAgent {
     pub activity_state: String,
     pub pid: u32,
     pub child_ai_names: Vec<String>,
+    pub container: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -69,6 +74,8 @@ pub struct BackendStats {
     pub cpu_percent: f64,
     pub mem_mb: u64,
     pub mem_percent: f64,
+    pub io_read_bps: u64,
+    pub io_write_bps: u64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -52,10 +52,14 @@ fn mock_session(
     dirty_count: u32,
 ) -> BackendSession {
     BackendSession {
+        id: String::new(),
         name: name.to_string(),
         attached,
+        clients: u32::from(attached),
         windows: 1,
+        panes: 1,
         path: None,
+        created: None,
         last_activity: None,
         activity_level: activity_level.map(str::to_string),
         activity_ago: None,
@@ -63,6 +67,8 @@ fn mock_session(
             cpu_percent,
             mem_mb,
             mem_percent: 0.0,
+            io_read_bps: 0,
+            io_write_bps: 0,
         }),
         health: BackendHealth {
             score: 100,
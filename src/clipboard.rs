@@ -0,0 +1,118 @@
+use anyhow::{Result, bail};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/* Copies text to the system clipboard. Uses wl-copy on Wayland and xclip
+ * or xsel on X11; when none is available (e.g. over SSH) it falls back to
+ * an OSC 52 escape sequence, which most modern terminals forward to the
+ * local clipboard. Returns the name of the method used. */
+pub fn copy(text: &str) -> Result<&'static str> {
+    let tools: [(&str, &str, &[&str]); 3] = [
+        ("WAYLAND_DISPLAY", "wl-copy", &[]),
+        ("DISPLAY", "xclip", &["-selection", "clipboard"]),
+        ("DISPLAY", "xsel", &["--clipboard", "--input"]),
+    ];
+
+    for (env, tool, args) in tools {
+        if std::env::var_os(env).is_some()
+            && which::which(tool).is_ok()
+            && pipe_to(tool, args, text).is_ok()
+        {
+            return Ok(tool);
+        }
+    }
+
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()?;
+    Ok("OSC 52")
+}
+
+// Runs a clipboard tool with the text on stdin.
+fn pipe_to(tool: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(tool)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+
+    if !child.wait()?.success() {
+        bail!("{} failed", tool);
+    }
+    Ok(())
+}
+
+// Builds the OSC 52 "set clipboard" escape sequence for the text.
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+// Standard base64 with padding; small enough not to need a crate.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+// Quotes a value for a POSIX shell command line when it needs quoting.
+pub fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ':' | '@'));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_base64_with_padding() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(
+            base64_encode(b"tmux attach -t api"),
+            "dG11eCBhdHRhY2ggLXQgYXBp"
+        );
+    }
+
+    #[test]
+    fn builds_osc52_sequence() {
+        assert_eq!(osc52_sequence("foo"), "\x1b]52;c;Zm9v\x07");
+    }
+
+    #[test]
+    fn quotes_only_when_needed() {
+        assert_eq!(shell_quote("api"), "api");
+        assert_eq!(shell_quote("my session"), "'my session'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
@@ -0,0 +1,1061 @@
+use crate::alert::AlertRule;
+use crate::directory::SourceKind;
+use crate::health::HealthWeights;
+use crate::policy::IdlePolicy;
+use crate::process::ScanOptions;
+use crate::tmux::ActivityThresholds;
+use crate::wm::WorkspaceRule;
+use serde::Deserialize;
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+/// User settings loaded from `~/.config/trex/config.toml`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrexConfig {
+    pub alerts: AlertConfig,
+    pub health: HealthWeights,
+    pub history: HistoryConfig,
+    pub preview: PreviewConfig,
+    pub directories: DirectoryConfig,
+    pub agents: ScanOptions, // `[agents]` agent detection options
+    pub throttle: ThrottleConfig,
+    pub policies: Vec<IdlePolicy>, // `[[policies]]` idle-session rules, first match wins
+    pub hub: bool,                 // return to the TUI after detaching, like `trex --hub`
+    pub auto_attach: bool,         // attach to a lone session without the TUI, like `trex --auto`
+    pub sort_flagged: bool,        // list sessions with a tmux bell/activity/silence flag first
+    pub hide_legend: bool, // `[ui] legend = false`: keep the icon legend out of the list title
+    pub git_ui: Option<String>, // command for the git UI launcher; None = DEFAULT_GIT_UI
+    pub git_ci: bool,      // `[git] ci = true`: PR/CI badges from the gh CLI
+    pub service_units: Vec<String>, // `[services] units` systemd user unit patterns for agents
+    pub environment: EnvironmentConfig,
+    pub wm: WmConfig,
+    pub power: PowerConfig,
+    pub activity: ActivityThresholds, // `[activity]` idle and dormant thresholds
+    pub warnings: Vec<String>,
+}
+
+/// Limits applied when throttling a session, from the `[throttle]` section.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThrottleConfig {
+    pub nice: i32,                // nice value applied to every session process
+    pub cpu_percent: Option<f64>, // cgroup CPU quota, 100 = one core
+    pub mem_mb: Option<u64>,      // cgroup memory ceiling
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            nice: 10,
+            cpu_percent: Some(100.0),
+            mem_mb: None,
+        }
+    }
+}
+
+// Git UI opened by the launcher when `[git] ui` is not set.
+pub const DEFAULT_GIT_UI: &str = "lazygit";
+
+// Allowed range for `[history] length`, in samples.
+pub const MIN_HISTORY_LENGTH: usize = 5;
+pub const MAX_HISTORY_LENGTH: usize = 600;
+
+/// Sparkline history settings from the `[history]` section.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistoryConfig {
+    pub length: usize,
+    pub scale: SparklineScale,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            length: 20,
+            scale: SparklineScale::Session,
+        }
+    }
+}
+
+/// Directory picker settings from the `[directories]` section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryConfig {
+    pub sources: Vec<SourceKind>, // merged in SourceKind::ALL order
+}
+
+impl Default for DirectoryConfig {
+    fn default() -> Self {
+        Self {
+            sources: SourceKind::ALL.to_vec(),
+        }
+    }
+}
+
+/// How sparkline bar heights are scaled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SparklineScale {
+    #[default]
+    Session, // each sparkline scales to its own maximum
+    Global, // all sessions share one maximum, so heights are comparable
+}
+
+// Allowed range for `[power] slowdown`.
+pub const MIN_POWER_SLOWDOWN: u32 = 1;
+pub const MAX_POWER_SLOWDOWN: u32 = 10;
+
+/* Battery saving from the `[power]` section: while saving, background
+ * refreshes run `slowdown` times less often and animations stop. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerConfig {
+    pub saver: PowerSaver,
+    pub slowdown: u32,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            saver: PowerSaver::Auto,
+            slowdown: 3,
+        }
+    }
+}
+
+/// When battery saving is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerSaver {
+    Auto, // while on battery
+    On,   // always
+    Off,  // never
+}
+
+/// Window manager integration from the `[wm]` section.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WmConfig {
+    pub focus: bool, // focus the terminal already showing a session instead of attaching again
+    pub workspaces: Vec<WorkspaceRule>, // `[[wm.workspaces]]`, first match wins
+}
+
+impl WmConfig {
+    // The workspace the first matching rule puts the session on.
+    pub fn workspace_for(&self, session_name: &str) -> Option<&WorkspaceRule> {
+        self.workspaces
+            .iter()
+            .find(|rule| rule.matches_session(session_name))
+    }
+}
+
+/* Variables `E` copies from trex's environment into a session, from the
+ * `[environment]` section: `NAME = "session"`, `"shells"`, or `"off"`. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvironmentConfig {
+    pub variables: Vec<(String, EnvRefresh)>,
+}
+
+impl Default for EnvironmentConfig {
+    fn default() -> Self {
+        Self {
+            variables: vec![
+                ("SSH_AUTH_SOCK".to_string(), EnvRefresh::Session),
+                ("DISPLAY".to_string(), EnvRefresh::Session),
+            ],
+        }
+    }
+}
+
+/// How far a refreshed variable reaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvRefresh {
+    Session, // the session environment, which new panes and windows start with
+    Shells,  // also exported in panes sitting at a shell prompt
+}
+
+// Allowed range for `[preview] size`, in percent of the session area.
+pub const MIN_PREVIEW_SIZE: u16 = 20;
+pub const MAX_PREVIEW_SIZE: u16 = 80;
+
+/// Session preview layout from the `[preview]` section.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewConfig {
+    pub size: u16, // percent of the session area given to the preview
+    pub orientation: PreviewOrientation,
+    pub remember: bool, // restore the last preview state on startup
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            size: 50,
+            orientation: PreviewOrientation::Horizontal,
+            remember: true,
+        }
+    }
+}
+
+/// Where the preview sits relative to the session list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreviewOrientation {
+    #[default]
+    Horizontal, // side by side, preview on the right
+    Vertical, // stacked, preview below
+}
+
+impl PreviewOrientation {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "horizontal" => Some(Self::Horizontal),
+            "vertical" => Some(Self::Vertical),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Horizontal => "horizontal",
+            Self::Vertical => "vertical",
+        }
+    }
+
+    pub fn flipped(self) -> Self {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlertConfig {
+    pub rules: Vec<AlertRule>,
+    pub notify: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawConfig {
+    alerts: Option<RawAlerts>,
+    health: Option<RawHealth>,
+    history: Option<RawHistory>,
+    preview: Option<RawPreview>,
+    directories: Option<RawDirectories>,
+    agents: Option<RawAgents>,
+    throttle: Option<RawThrottle>,
+    policies: Option<Vec<RawPolicy>>,
+    ui: Option<RawUi>,
+    git: Option<RawGit>,
+    services: Option<RawServices>,
+    environment: Option<std::collections::BTreeMap<String, String>>,
+    wm: Option<RawWm>,
+    power: Option<RawPower>,
+    activity: Option<RawActivity>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawActivity {
+    idle_after: Option<u64>,
+    dormant_after: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPower {
+    saver: Option<String>,
+    slowdown: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawWm {
+    focus: Option<bool>,
+    workspaces: Option<Vec<RawWorkspace>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawWorkspace {
+    session: Option<String>,
+    workspace: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawServices {
+    units: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawGit {
+    ui: Option<String>,
+    ci: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawUi {
+    hub: Option<bool>,
+    auto: Option<bool>,
+    sort_flagged: Option<bool>,
+    legend: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawThrottle {
+    nice: Option<i32>,
+    cpu: Option<String>,
+    memory: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawHistory {
+    length: Option<usize>,
+    scale: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDirectories {
+    sources: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawAgents {
+    containers: Option<bool>,
+    collapse: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPreview {
+    size: Option<u16>,
+    orientation: Option<String>,
+    remember: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawAlerts {
+    notify: Option<bool>,
+    rules: Option<Vec<RawAlertRule>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawHealth {
+    cpu_weight: Option<u8>,
+    mem_weight: Option<u8>,
+    activity_weight: Option<u8>,
+    multiplex_weight: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPolicy {
+    session: Option<String>,
+    idle: Option<String>,
+    action: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawAlertRule {
+    session: Option<String>,
+    when: Option<String>,
+}
+
+impl TrexConfig {
+    pub fn load() -> Self {
+        let mut config = Self::default();
+
+        if let Some(path) = user_config_path() {
+            config.load_file(&path);
+        }
+
+        config
+    }
+
+    fn load_file(&mut self, path: &Path) {
+        match fs::read_to_string(path) {
+            Ok(contents) => self.merge_str(&contents, &path.display().to_string()),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => {
+                self.warnings
+                    .push(format!("Could not read config {}: {}", path.display(), err))
+            }
+        }
+    }
+
+    fn merge_str(&mut self, contents: &str, source: &str) {
+        let raw = match toml::from_str::<RawConfig>(contents) {
+            Ok(raw) => raw,
+            Err(err) => {
+                self.warnings
+                    .push(format!("Could not parse config {}: {}", source, err));
+                return;
+            }
+        };
+
+        if let Some(alerts) = raw.alerts {
+            self.alerts.notify = alerts.notify.unwrap_or(false);
+
+            for rule in alerts.rules.unwrap_or_default() {
+                let session = rule.session.unwrap_or_else(|| "*".to_string());
+                let Some(when) = rule.when else {
+                    self.warnings
+                        .push(format!("Skipped alert for {session}: missing when"));
+                    continue;
+                };
+
+                match AlertRule::parse(&session, &when) {
+                    Ok(rule) => self.alerts.rules.push(rule),
+                    Err(warning) => self.warnings.push(warning),
+                }
+            }
+        }
+
+        if let Some(health) = raw.health {
+            let defaults = HealthWeights::default();
+            let weights = HealthWeights {
+                cpu: health.cpu_weight.unwrap_or(defaults.cpu),
+                mem: health.mem_weight.unwrap_or(defaults.mem),
+                activity: health.activity_weight.unwrap_or(defaults.activity),
+                multiplex: health.multiplex_weight.unwrap_or(defaults.multiplex),
+            };
+
+            if [
+                weights.cpu,
+                weights.mem,
+                weights.activity,
+                weights.multiplex,
+            ]
+            .iter()
+            .any(|weight| *weight > 100)
+            {
+                self.warnings
+                    .push("Skipped health weights: each weight must be 0-100".to_string());
+            } else {
+                self.health = weights;
+            }
+        }
+
+        if let Some(history) = raw.history {
+            self.merge_history(history);
+        }
+
+        if let Some(preview) = raw.preview {
+            self.merge_preview(preview);
+        }
+
+        if let Some(throttle) = raw.throttle {
+            self.merge_throttle(throttle);
+        }
+
+        if let Some(sources) = raw.directories.and_then(|directories| directories.sources) {
+            self.merge_directory_sources(&sources);
+        }
+
+        if let Some(agents) = raw.agents {
+            self.agents.containers = agents.containers.unwrap_or(self.agents.containers);
+            self.agents.collapse = agents.collapse.unwrap_or(self.agents.collapse);
+        }
+
+        for policy in raw.policies.unwrap_or_default() {
+            let session = policy.session.unwrap_or_else(|| "*".to_string());
+            let Some(idle) = policy.idle else {
+                self.warnings
+                    .push(format!("Skipped policy for {session}: missing idle"));
+                continue;
+            };
+            match IdlePolicy::parse(&session, &idle, policy.action.as_deref()) {
+                Ok(policy) => self.policies.push(policy),
+                Err(warning) => self.warnings.push(warning),
+            }
+        }
+
+        if let Some(ui) = raw.ui {
+            if let Some(hub) = ui.hub {
+                self.hub = hub;
+            }
+            if let Some(auto) = ui.auto {
+                self.auto_attach = auto;
+            }
+            if let Some(sort_flagged) = ui.sort_flagged {
+                self.sort_flagged = sort_flagged;
+            }
+            if let Some(legend) = ui.legend {
+                self.hide_legend = !legend;
+            }
+        }
+
+        if let Some(git) = raw.git {
+            if let Some(git_ui) = git.ui {
+                if git_ui.trim().is_empty() {
+                    self.warnings
+                        .push("Skipped git ui: command must not be empty".to_string());
+                } else {
+                    self.git_ui = Some(git_ui.trim().to_string());
+                }
+            }
+            if let Some(ci) = git.ci {
+                self.git_ci = ci;
+            }
+        }
+
+        if let Some(wm) = raw.wm {
+            self.wm.focus = wm.focus.unwrap_or(self.wm.focus);
+            for rule in wm.workspaces.unwrap_or_default() {
+                let session = rule.session.unwrap_or_else(|| "*".to_string());
+                match rule.workspace.map(|workspace| workspace.trim().to_string()) {
+                    Some(workspace) if !workspace.is_empty() => {
+                        self.wm.workspaces.push(WorkspaceRule {
+                            session_glob: session,
+                            workspace,
+                        })
+                    }
+                    _ => self.warnings.push(format!(
+                        "Skipped workspace for {session}: missing workspace"
+                    )),
+                }
+            }
+        }
+
+        if let Some(power) = raw.power {
+            self.merge_power(power);
+        }
+
+        if let Some(activity) = raw.activity {
+            self.merge_activity(activity);
+        }
+
+        for (name, refresh) in raw.environment.unwrap_or_default() {
+            self.merge_environment(name, &refresh);
+        }
+
+        for pattern in raw
+            .services
+            .and_then(|services| services.units)
+            .unwrap_or_default()
+        {
+            if pattern.trim().is_empty() {
+                self.warnings
+                    .push("Skipped service unit: pattern must not be empty".to_string());
+            } else {
+                self.service_units.push(pattern.trim().to_string());
+            }
+        }
+    }
+
+    // Returns the configured git UI command, falling back to lazygit.
+    pub fn git_ui_command(&self) -> &str {
+        self.git_ui.as_deref().unwrap_or(DEFAULT_GIT_UI)
+    }
+
+    fn merge_environment(&mut self, name: String, refresh: &str) {
+        let variables = &mut self.environment.variables;
+        variables.retain(|(existing, _)| *existing != name);
+        match refresh {
+            "session" => variables.push((name, EnvRefresh::Session)),
+            "shells" => variables.push((name, EnvRefresh::Shells)),
+            "off" => {}
+            _ => self.warnings.push(format!(
+                "Skipped environment {name}: expected session, shells, or off"
+            )),
+        }
+    }
+
+    // Unknown names are skipped; an empty or all-unknown list keeps every source.
+    fn merge_directory_sources(&mut self, names: &[String]) {
+        let mut sources = Vec::new();
+        for name in names {
+            match SourceKind::from_name(name.trim()) {
+                Some(kind) if !sources.contains(&kind) => sources.push(kind),
+                Some(_) => {}
+                None => self.warnings.push(format!(
+                    "Skipped directory source {name}: must be one of {}",
+                    SourceKind::ALL.map(|kind| kind.name()).join(", ")
+                )),
+            }
+        }
+        if !sources.is_empty() {
+            sources.sort();
+            self.directories.sources = sources;
+        }
+    }
+
+    fn merge_history(&mut self, history: RawHistory) {
+        if let Some(length) = history.length {
+            if (MIN_HISTORY_LENGTH..=MAX_HISTORY_LENGTH).contains(&length) {
+                self.history.length = length;
+            } else {
+                self.warnings.push(format!(
+                    "Skipped history length {length}: must be {MIN_HISTORY_LENGTH}-{MAX_HISTORY_LENGTH}"
+                ));
+            }
+        }
+
+        if let Some(scale) = history.scale {
+            match scale.as_str() {
+                "session" => self.history.scale = SparklineScale::Session,
+                "global" => self.history.scale = SparklineScale::Global,
+                _ => self.warnings.push(format!(
+                    "Skipped history scale {scale}: expected session or global"
+                )),
+            }
+        }
+    }
+
+    fn merge_preview(&mut self, preview: RawPreview) {
+        if let Some(size) = preview.size {
+            if (MIN_PREVIEW_SIZE..=MAX_PREVIEW_SIZE).contains(&size) {
+                self.preview.size = size;
+            } else {
+                self.warnings.push(format!(
+                    "Skipped preview size {size}: must be {MIN_PREVIEW_SIZE}-{MAX_PREVIEW_SIZE}"
+                ));
+            }
+        }
+
+        if let Some(orientation) = preview.orientation {
+            match PreviewOrientation::parse(&orientation) {
+                Some(orientation) => self.preview.orientation = orientation,
+                None => self.warnings.push(format!(
+                    "Skipped preview orientation {orientation}: expected horizontal or vertical"
+                )),
+            }
+        }
+
+        if let Some(remember) = preview.remember {
+            self.preview.remember = remember;
+        }
+    }
+
+    fn merge_power(&mut self, power: RawPower) {
+        if let Some(saver) = power.saver {
+            match saver.as_str() {
+                "auto" => self.power.saver = PowerSaver::Auto,
+                "on" => self.power.saver = PowerSaver::On,
+                "off" => self.power.saver = PowerSaver::Off,
+                _ => self.warnings.push(format!(
+                    "Skipped power saver {saver}: expected auto, on, or off"
+                )),
+            }
+        }
+
+        if let Some(slowdown) = power.slowdown {
+            if (MIN_POWER_SLOWDOWN..=MAX_POWER_SLOWDOWN).contains(&slowdown) {
+                self.power.slowdown = slowdown;
+            } else {
+                self.warnings.push(format!(
+                    "Skipped power slowdown {slowdown}: must be {MIN_POWER_SLOWDOWN}-{MAX_POWER_SLOWDOWN}"
+                ));
+            }
+        }
+    }
+
+    fn merge_activity(&mut self, activity: RawActivity) {
+        let idle_after = activity.idle_after.unwrap_or(self.activity.idle_after_mins);
+        let dormant_after = activity
+            .dormant_after
+            .unwrap_or(self.activity.dormant_after_mins);
+        if idle_after == 0 || dormant_after <= idle_after {
+            self.warnings.push(format!(
+                "Skipped activity thresholds {idle_after}/{dormant_after}: need 0 < idle_after < dormant_after"
+            ));
+            return;
+        }
+
+        self.activity = ActivityThresholds {
+            idle_after_mins: idle_after,
+            dormant_after_mins: dormant_after,
+        };
+    }
+
+    fn merge_throttle(&mut self, throttle: RawThrottle) {
+        if let Some(nice) = throttle.nice {
+            if (-20..=19).contains(&nice) {
+                self.throttle.nice = nice;
+            } else {
+                self.warnings
+                    .push(format!("Skipped throttle nice {nice}: must be -20 to 19"));
+            }
+        }
+
+        if let Some(cpu) = throttle.cpu {
+            match cpu.trim().trim_end_matches('%').parse::<f64>() {
+                Ok(percent) if percent > 0.0 && percent.is_finite() => {
+                    self.throttle.cpu_percent = Some(percent)
+                }
+                _ => self
+                    .warnings
+                    .push(format!("Skipped throttle cpu {cpu}: expected a percentage")),
+            }
+        }
+
+        if let Some(memory) = throttle.memory {
+            let compact = memory.replace(' ', "").to_lowercase();
+            match crate::alert::parse_mem_mb(&compact) {
+                Some(mb) if mb >= 1.0 => self.throttle.mem_mb = Some(mb as u64),
+                _ => self.warnings.push(format!(
+                    "Skipped throttle memory {memory}: expected a size like 4GB"
+                )),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn from_config_str(contents: &str) -> Self {
+        let mut config = Self::default();
+        config.merge_str(contents, "test");
+        config
+    }
+}
+
+pub fn user_config_path() -> Option<PathBuf> {
+    user_config_path_from_env(
+        std::env::var("XDG_CONFIG_HOME").ok().as_deref(),
+        std::env::var("HOME").ok().as_deref(),
+    )
+}
+
+fn user_config_path_from_env(xdg_config_home: Option<&str>, home: Option<&str>) -> Option<PathBuf> {
+    if let Some(xdg_config_home) = xdg_config_home
+        && !xdg_config_home.trim().is_empty()
+    {
+        return Some(PathBuf::from(xdg_config_home).join("trex/config.toml"));
+    }
+
+    home.filter(|home| !home.trim().is_empty())
+        .map(|home| PathBuf::from(home).join(".config/trex/config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::AlertMetric;
+
+    #[test]
+    fn parses_alert_rules() {
+        let config = TrexConfig::from_config_str(
+            r#"
+[alerts]
+notify = true
+
+[[alerts.rules]]
+session = "ai-*"
+when = "cpu>250%"
+
+[[alerts.rules]]
+when = "mem>4GB"
+"#,
+        );
+
+        assert!(config.alerts.notify);
+        assert_eq!(config.alerts.rules.len(), 2);
+        assert_eq!(config.alerts.rules[0].session_glob, "ai-*");
+        assert_eq!(config.alerts.rules[1].session_glob, "*");
+        assert_eq!(config.alerts.rules[1].metric, AlertMetric::Mem);
+        assert!(config.warnings.is_empty());
+    }
+
+    #[test]
+    fn parses_wm_focus() {
+        assert!(!TrexConfig::default().wm.focus);
+        let config = TrexConfig::from_config_str("[wm]\nfocus = true\n");
+        assert!(config.wm.focus);
+        assert!(config.warnings.is_empty());
+    }
+
+    #[test]
+    fn parses_power_saving() {
+        assert_eq!(TrexConfig::default().power.saver, PowerSaver::Auto);
+        let config = TrexConfig::from_config_str("[power]\nsaver = \"on\"\nslowdown = 5\n");
+        assert_eq!(config.power.saver, PowerSaver::On);
+        assert_eq!(config.power.slowdown, 5);
+
+        let config = TrexConfig::from_config_str("[power]\nsaver = \"eco\"\nslowdown = 0\n");
+        assert_eq!(config.power, PowerConfig::default());
+        assert_eq!(config.warnings.len(), 2);
+    }
+
+    #[test]
+    fn parses_activity_thresholds() {
+        let config =
+            TrexConfig::from_config_str("[activity]\nidle_after = 2\ndormant_after = 60\n");
+        assert_eq!(config.activity.idle_after_mins, 2);
+        assert_eq!(config.activity.dormant_after_mins, 60);
+        assert!(config.warnings.is_empty());
+
+        // A lone threshold is checked against the default for the other
+        let config = TrexConfig::from_config_str("[activity]\nidle_after = 45\n");
+        assert_eq!(config.activity, ActivityThresholds::default());
+        assert_eq!(config.warnings.len(), 1);
+
+        let config = TrexConfig::from_config_str("[activity]\nidle_after = 0\n");
+        assert_eq!(config.activity, ActivityThresholds::default());
+        assert_eq!(config.warnings.len(), 1);
+    }
+
+    #[test]
+    fn maps_sessions_to_workspaces_first_match_wins() {
+        let config = TrexConfig::from_config_str(
+            r#"
+[[wm.workspaces]]
+session = "agent-*"
+workspace = "4"
+
+[[wm.workspaces]]
+workspace = " 2 "
+
+[[wm.workspaces]]
+session = "docs"
+"#,
+        );
+        let workspace = |name| {
+            config
+                .wm
+                .workspace_for(name)
+                .map(|rule| rule.workspace.as_str())
+        };
+        assert_eq!(workspace("agent-api"), Some("4"));
+        assert_eq!(workspace("docs"), Some("2"));
+        assert_eq!(
+            config.warnings,
+            ["Skipped workspace for docs: missing workspace"]
+        );
+    }
+
+    #[test]
+    fn skips_invalid_alert_rules_with_warnings() {
+        let config = TrexConfig::from_config_str(
+            r#"
+[[alerts.rules]]
+session = "dev"
+
+[[alerts.rules]]
+session = "dev"
+when = "gpu>10"
+"#,
+        );
+
+        assert!(config.alerts.rules.is_empty());
+        assert_eq!(config.warnings.len(), 2);
+    }
+
+    #[test]
+    fn parses_idle_policies() {
+        let config = TrexConfig::from_config_str(
+            r#"
+[[policies]]
+session = "tmp-*"
+idle = "24h"
+action = "kill"
+
+[[policies]]
+idle = "3d"
+
+[[policies]]
+session = "ai-*"
+"#,
+        );
+
+        assert_eq!(config.policies.len(), 2);
+        assert_eq!(config.policies[0].action, crate::policy::PolicyAction::Kill);
+        assert_eq!(config.policies[1].session_glob, "*");
+        assert_eq!(config.warnings.len(), 1);
+    }
+
+    #[test]
+    fn parses_partial_health_weights() {
+        let config = TrexConfig::from_config_str(
+            r#"
+[health]
+cpu_weight = 60
+activity_weight = 0
+"#,
+        );
+
+        assert_eq!(
+            config.health,
+            HealthWeights {
+                cpu: 60,
+                mem: 40,
+                activity: 0,
+                multiplex: 10,
+            }
+        );
+        assert!(config.warnings.is_empty());
+    }
+
+    #[test]
+    fn rejects_out_of_range_health_weights() {
+        let config = TrexConfig::from_config_str("[health]\ncpu_weight = 150\n");
+
+        assert_eq!(config.health, HealthWeights::default());
+        assert_eq!(config.warnings.len(), 1);
+    }
+
+    #[test]
+    fn parses_history_settings() {
+        let config = TrexConfig::from_config_str("[history]\nlength = 60\nscale = \"global\"\n");
+
+        assert_eq!(
+            config.history,
+            HistoryConfig {
+                length: 60,
+                scale: SparklineScale::Global,
+            }
+        );
+        assert!(config.warnings.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_history_settings() {
+        let config = TrexConfig::from_config_str("[history]\nlength = 1\nscale = \"log\"\n");
+
+        assert_eq!(config.history, HistoryConfig::default());
+        assert_eq!(config.warnings.len(), 2);
+    }
+
+    #[test]
+    fn parses_preview_layout() {
+        let config = TrexConfig::from_config_str(
+            "[preview]\nsize = 35\norientation = \"vertical\"\nremember = false\n",
+        );
+
+        assert_eq!(
+            config.preview,
+            PreviewConfig {
+                size: 35,
+                orientation: PreviewOrientation::Vertical,
+                remember: false,
+            }
+        );
+        assert!(config.warnings.is_empty());
+
+        let config =
+            TrexConfig::from_config_str("[preview]\nsize = 95\norientation = \"diagonal\"\n");
+        assert_eq!(config.preview, PreviewConfig::default());
+        assert_eq!(config.warnings.len(), 2);
+    }
+
+    #[test]
+    fn parses_throttle_limits() {
+        let config =
+            TrexConfig::from_config_str("[throttle]\nnice = 15\ncpu = \"50%\"\nmemory = \"2GB\"\n");
+
+        assert_eq!(
+            config.throttle,
+            ThrottleConfig {
+                nice: 15,
+                cpu_percent: Some(50.0),
+                mem_mb: Some(2048),
+            }
+        );
+        assert!(config.warnings.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_throttle_limits() {
+        let config =
+            TrexConfig::from_config_str("[throttle]\nnice = 40\ncpu = \"fast\"\nmemory = \"0\"\n");
+
+        assert_eq!(config.throttle, ThrottleConfig::default());
+        assert_eq!(config.warnings.len(), 3);
+    }
+
+    #[test]
+    fn parses_git_ui_command() {
+        let config = TrexConfig::from_config_str("[git]\nui = \"gitui\"\n");
+        assert_eq!(config.git_ui_command(), "gitui");
+
+        let config = TrexConfig::from_config_str("");
+        assert_eq!(config.git_ui_command(), "lazygit");
+
+        let config = TrexConfig::from_config_str("[git]\nui = \"  \"\n");
+        assert_eq!(config.git_ui_command(), "lazygit");
+        assert_eq!(config.warnings.len(), 1);
+        assert!(!config.git_ci);
+
+        let config = TrexConfig::from_config_str("[git]\nci = true\n");
+        assert!(config.git_ci);
+        assert_eq!(config.git_ui_command(), "lazygit");
+    }
+
+    #[test]
+    fn parses_directory_sources() {
+        let config = TrexConfig::from_config_str("");
+        assert_eq!(config.directories.sources, SourceKind::ALL);
+
+        let config = TrexConfig::from_config_str(
+            "[directories]\nsources = [\"scan\", \"bookmarks\", \"fzf\"]\n",
+        );
+        assert_eq!(
+            config.directories.sources,
+            [SourceKind::Bookmarks, SourceKind::Scan]
+        );
+        assert_eq!(config.warnings.len(), 1);
+    }
+
+    #[test]
+    fn parses_agent_options() {
+        let config = TrexConfig::from_config_str("[agents]\ncontainers = true\ncollapse = false\n");
+        assert!(config.agents.containers);
+        assert!(!config.agents.collapse);
+
+        let config = TrexConfig::from_config_str("");
+        assert!(!config.agents.containers);
+        assert!(config.agents.collapse);
+    }
+
+    #[test]
+    fn parses_service_unit_patterns() {
+        let config = TrexConfig::from_config_str(
+            "[services]\nunits = [\"agent-*.service\", \" \", \"claude@*.service\"]\n",
+        );
+        assert_eq!(
+            config.service_units,
+            ["agent-*.service", "claude@*.service"]
+        );
+        assert_eq!(config.warnings.len(), 1);
+        assert!(TrexConfig::from_config_str("").service_units.is_empty());
+    }
+
+    #[test]
+    fn parses_environment_refresh() {
+        let config = TrexConfig::from_config_str(
+            "[environment]\nSSH_AUTH_SOCK = \"shells\"\nDISPLAY = \"off\"\nXAUTHORITY = \"session\"\nTERM = \"always\"\n",
+        );
+        assert_eq!(
+            config.environment.variables,
+            [
+                ("SSH_AUTH_SOCK".to_string(), EnvRefresh::Shells),
+                ("XAUTHORITY".to_string(), EnvRefresh::Session),
+            ]
+        );
+        assert_eq!(config.warnings.len(), 1);
+        assert_eq!(
+            TrexConfig::from_config_str("").environment,
+            EnvironmentConfig::default()
+        );
+    }
+
+    #[test]
+    fn parses_hub_mode() {
+        assert!(TrexConfig::from_config_str("[ui]\nhub = true\n").hub);
+        assert!(!TrexConfig::from_config_str("[ui]\n").hub);
+        assert!(TrexConfig::from_config_str("[ui]\nauto = true\n").auto_attach);
+        assert!(!TrexConfig::from_config_str("[ui]\nhub = true\n").auto_attach);
+        assert!(TrexConfig::from_config_str("[ui]\nsort_flagged = true\n").sort_flagged);
+        assert!(TrexConfig::from_config_str("[ui]\nlegend = false\n").hide_legend);
+        assert!(!TrexConfig::from_config_str("[ui]\n").hide_legend);
+    }
+
+    #[test]
+    fn invalid_config_keeps_defaults() {
+        let config = TrexConfig::from_config_str("not = [valid");
+
+        assert_eq!(config.alerts, AlertConfig::default());
+        assert_eq!(config.health, HealthWeights::default());
+        assert_eq!(config.warnings.len(), 1);
+    }
+
+    #[test]
+    fn builds_config_path_from_environment_values() {
+        assert_eq!(
+            user_config_path_from_env(Some("/tmp/config"), Some("/home/user")).unwrap(),
+            PathBuf::from("/tmp/config/trex/config.toml")
+        );
+        assert_eq!(
+            user_config_path_from_env(None, Some("/home/user")).unwrap(),
+            PathBuf::from("/home/user/.config/trex/config.toml")
+        );
+        assert!(user_config_path_from_env(None, None).is_none());
+    }
+}
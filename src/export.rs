@@ -0,0 +1,249 @@
+use crate::health::{HealthScore, HealthWeights};
+use crate::sysinfo::get_session_stats;
+use crate::tmux::{TmuxClient, TmuxSession};
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Gap between samples for `trex stats`, matching the TUI's session stats cadence
+pub const STATS_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsExport {
+    pub generated_at: u64,
+    pub interval_secs: u64, // time between history samples
+    pub sessions: Vec<SessionStatsExport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatsExport {
+    pub name: String,
+    pub cpu_percent: Option<f64>,
+    pub mem_mb: Option<u64>,
+    pub mem_percent: Option<f64>,
+    pub io_read_bps: Option<u64>,
+    pub io_write_bps: Option<u64>,
+    pub health_score: u8,
+    pub cpu_history: Vec<Option<u64>>, // oldest first, null for gaps
+    pub mem_history: Vec<Option<u64>>,
+}
+
+impl StatsExport {
+    /* Captures the current stats and sparkline history of each session.
+     * History samples are `interval` apart, newest last; paused sampling
+     * leaves gaps rather than shortening the timeline. */
+    pub fn from_sessions(
+        sessions: &[TmuxSession],
+        weights: &HealthWeights,
+        interval: Duration,
+    ) -> Self {
+        let generated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let sessions = sessions
+            .iter()
+            .map(|session| {
+                let stats = session.stats.as_ref();
+                SessionStatsExport {
+                    name: session.name.clone(),
+                    cpu_percent: stats.map(|stats| stats.cpu_percent),
+                    mem_mb: stats.map(|stats| stats.mem_mb),
+                    mem_percent: stats.map(|stats| stats.mem_percent),
+                    io_read_bps: stats.map(|stats| stats.io_read_bps),
+                    io_write_bps: stats.map(|stats| stats.io_write_bps),
+                    health_score: HealthScore::calculate_with(session, weights).score,
+                    cpu_history: session.cpu_history.clone(),
+                    mem_history: session.mem_history.clone(),
+                }
+            })
+            .collect();
+        Self {
+            generated_at,
+            interval_secs: interval.as_secs(),
+            sessions,
+        }
+    }
+
+    /* One row per session per history sample, oldest first, so the file
+     * loads straight into a spreadsheet or dataframe. The newest row of
+     * each session also carries the current memory share, IO, and health. */
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "session,seconds_ago,cpu_percent,mem_mb,mem_percent,io_read_bps,io_write_bps,health_score\n",
+        );
+        for session in &self.sessions {
+            let name = csv_field(&session.name);
+            let samples = session.cpu_history.len().max(session.mem_history.len());
+            for index in 0..samples.saturating_sub(1) {
+                let seconds_ago = (samples - 1 - index) as u64 * self.interval_secs;
+                out.push_str(&format!(
+                    "{},{},{},{},,,,\n",
+                    name,
+                    seconds_ago,
+                    optional(session.cpu_history.get(index).copied().flatten()),
+                    optional(session.mem_history.get(index).copied().flatten()),
+                ));
+            }
+            out.push_str(&format!(
+                "{},0,{},{},{},{},{},{}\n",
+                name,
+                optional(session.cpu_percent.map(|cpu| format!("{:.1}", cpu))),
+                optional(session.mem_mb),
+                optional(session.mem_percent.map(|mem| format!("{:.1}", mem))),
+                optional(session.io_read_bps),
+                optional(session.io_write_bps),
+                session.health_score,
+            ));
+        }
+        out
+    }
+
+    pub fn render(&self, format: ExportFormat) -> Result<String> {
+        Ok(match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => serde_json::to_string_pretty(self)? + "\n",
+        })
+    }
+
+    // Writes the export into `dir` as `trex-stats-<unix time>.<ext>`.
+    pub fn write_to_dir(&self, dir: &Path, format: ExportFormat) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!(
+            "trex-stats-{}.{}",
+            self.generated_at,
+            format.extension()
+        ));
+        std::fs::write(&path, self.render(format)?)?;
+        Ok(path)
+    }
+}
+
+/* Samples every session `samples` times, STATS_SAMPLE_INTERVAL apart,
+ * for `trex stats`. A priming sample comes first so the first CPU
+ * reading has a delta; sessions created meanwhile are not included. */
+pub fn collect_stats(samples: usize, weights: &HealthWeights) -> Result<StatsExport> {
+    TmuxClient::check_installed()?;
+
+    let mut sessions = TmuxClient::list_sessions()?;
+    for session in &sessions {
+        let _ = get_session_stats(&session.name);
+    }
+
+    for _ in 0..samples.max(1) {
+        std::thread::sleep(STATS_SAMPLE_INTERVAL);
+        for session in &mut sessions {
+            session.stats = get_session_stats(&session.name).ok();
+            let stats = session.stats.as_ref();
+            session
+                .cpu_history
+                .push(stats.map(|stats| stats.cpu_percent as u64));
+            session.mem_history.push(stats.map(|stats| stats.mem_mb));
+        }
+    }
+
+    Ok(StatsExport::from_sessions(
+        &sessions,
+        weights,
+        STATS_SAMPLE_INTERVAL,
+    ))
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+// Quotes a CSV field when it contains a separator, quote, or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysinfo::SessionStats;
+
+    fn session(name: &str) -> TmuxSession {
+        TmuxSession {
+            id: String::new(),
+            name: name.to_string(),
+            attached: false,
+            clients: 0,
+            windows: 1,
+            panes: 1,
+            size_constrained: false,
+            path: None,
+            created: None,
+            last_activity: None,
+            tags: Vec::new(),
+            note: None,
+            alerts: Default::default(),
+            git_status: None,
+            stats: Some(SessionStats {
+                cpu_percent: 42.0,
+                mem_mb: 512,
+                mem_percent: 3.2,
+                io_read_bps: 10,
+                io_write_bps: 20,
+            }),
+            cpu_history: vec![Some(10), None, Some(42)],
+            mem_history: vec![Some(500), None, Some(512)],
+        }
+    }
+
+    #[test]
+    fn csv_has_one_row_per_history_sample() {
+        let export = StatsExport::from_sessions(
+            &[session("agent,1")],
+            &HealthWeights::default(),
+            Duration::from_secs(2),
+        );
+        let csv = export.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "\"agent,1\",4,10,500,,,,");
+        assert_eq!(lines[2], "\"agent,1\",2,,,,,,");
+        assert!(lines[3].starts_with("\"agent,1\",0,42.0,512,3.2,10,20,"));
+    }
+
+    #[test]
+    fn json_keeps_history_gaps() {
+        let export = StatsExport::from_sessions(
+            &[session("agent")],
+            &HealthWeights::default(),
+            Duration::from_secs(2),
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&export.render(ExportFormat::Json).unwrap()).unwrap();
+
+        assert_eq!(json["intervalSecs"], 2);
+        assert_eq!(json["sessions"][0]["name"], "agent");
+        assert_eq!(
+            json["sessions"][0]["cpuHistory"],
+            serde_json::json!([10, null, 42])
+        );
+    }
+}
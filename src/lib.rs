@@ -1,9 +1,21 @@
+pub mod alert;
 pub mod backend;
-pub mod directory;
-pub mod git;
-pub mod health;
-pub mod process;
-pub mod sysinfo;
-pub mod template;
+pub mod clipboard;
+pub mod config;
+pub mod export;
+pub mod perf;
+pub mod policy;
+pub mod popup;
+pub mod query;
+pub mod report;
 pub mod theme;
-pub mod tmux;
+pub mod throttle;
+pub mod top;
+pub mod tui;
+pub mod wm;
+
+// The session model lives in trex-core; re-exported so existing paths keep working
+pub use trex_core::{
+    attach_log, ci, compose, container, devenv, directory, directory_cache, git, health, layouts,
+    logging, power, process, project, recent_commands, service, sysinfo, template, timeline, tmux,
+};
@@ -1,20 +1,118 @@
-pub use trex_cli::{directory, git, health, process, sysinfo, template, theme, tmux};
-mod tui;
+pub use trex_cli::{
+    alert, attach_log, clipboard, config, directory, export, git, health, logging, perf, popup,
+    process, query, report, sysinfo, template, theme, throttle, timeline, tmux, top, tui, wm,
+};
 
 use crate::git::GitStatus;
-use crate::tmux::{TmuxClient, find_matching_session_index};
-use crate::tui::app::SessionAction;
+use crate::tmux::{AttachMode, TmuxBackend, TmuxClient, TmuxSession, find_matching_session_index};
+use crate::tui::app::{SessionAction, SessionRef, StartupScreen, StartupView};
 
 use anyhow::{Result, bail};
 use std::fs::OpenOptions;
 use std::os::unix::io::AsRawFd;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// Counts allocations for the F12 performance HUD
+#[global_allocator]
+static ALLOCATOR: perf::CountingAllocator = perf::CountingAllocator;
+
+// Seconds between `trex top` refreshes, matching the TUI's session stats cadence
+const DEFAULT_TOP_INTERVAL_SECS: u64 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum StartupCommand {
     Interactive,
+    Hub,
     SnapshotJson,
+    Check,
+    PopupInstall,
+    Top {
+        interval_secs: u64,
+        once: bool,
+    },
+    Stats {
+        format: export::ExportFormat,
+        samples: usize,
+    },
+    Report {
+        period_secs: u64,
+        json: bool,
+    },
     Help,
     Version,
+    // Bad options for a command: what was wrong and the command's usage line
+    Usage {
+        error: String,
+        usage: &'static str,
+    },
+}
+
+// Removes a flag such as `--debug` from the arguments, returning whether it was present.
+// These combine with any other command, so they are handled before parsing.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != before
+}
+
+/* Removes the view flags from the arguments and returns the view they ask
+ * the TUI to open on. Like `--debug` they combine with `--hub`; the last
+ * screen flag wins, and a flag missing its value is dropped. */
+fn take_startup_view(args: &mut Vec<String>) -> StartupView {
+    let mut view = StartupView::default();
+    let mut rest = Vec::new();
+    let mut remaining = std::mem::take(args).into_iter();
+    while let Some(arg) = remaining.next() {
+        match arg.as_str() {
+            "--stats" => view.screen = StartupScreen::Stats,
+            "--charts" => view.screen = StartupScreen::Charts,
+            "--timeline" => view.screen = StartupScreen::Timeline,
+            "--expanded" => {
+                if let Some(name) = remaining.next() {
+                    view.screen = StartupScreen::Expanded(name);
+                }
+            }
+            "--filter" => view.filter = remaining.next(),
+            _ => rest.push(arg),
+        }
+    }
+    *args = rest;
+    view
+}
+
+// Subcommands, matched by parse_startup_command
+const SNAPSHOT: &str = "snapshot";
+const CHECK: &str = "check";
+const TOP: &str = "top";
+const STATS: &str = "stats";
+const REPORT: &str = "report";
+const POPUP_INSTALL: &str = "popup-install";
+
+const TOP_USAGE: &str = "trex top [--interval SECS] [--once]";
+const STATS_USAGE: &str = "trex stats --csv|--json [--samples N]";
+const REPORT_USAGE: &str = "trex report [--day|--week] [--json]";
+
+// Leading words that are commands; any other trailing words are a session query.
+const COMMANDS: [&str; 6] = [SNAPSHOT, CHECK, TOP, STATS, REPORT, POPUP_INSTALL];
+
+/* Removes a trailing session query from the arguments, the words after
+ * the last flag joined with spaces (`trex api`, `trex --hub my proj`), the
+ * way zoxide and tmux-sessionizer take theirs. Commands keep their own
+ * arguments. */
+fn take_query(args: &mut Vec<String>) -> Option<String> {
+    if args
+        .first()
+        .is_some_and(|first| COMMANDS.contains(&first.as_str()))
+    {
+        return None;
+    }
+    let start = args
+        .iter()
+        .rposition(|arg| arg.starts_with('-'))
+        .map_or(0, |idx| idx + 1);
+    if start == args.len() {
+        return None;
+    }
+    Some(args.split_off(start).join(" "))
 }
 
 fn parse_startup_command(args: &[String]) -> StartupCommand {
@@ -22,11 +120,96 @@ fn parse_startup_command(args: &[String]) -> StartupCommand {
         [] => StartupCommand::Interactive,
         [arg] if arg == "-h" || arg == "--help" => StartupCommand::Help,
         [arg] if arg == "-V" || arg == "--version" => StartupCommand::Version,
-        [command, flag] if command == "snapshot" && flag == "--json" => {
-            StartupCommand::SnapshotJson
+        [arg] if arg == "--hub" => StartupCommand::Hub,
+        [command, options @ ..] => match (command.as_str(), options) {
+            (CHECK, []) => StartupCommand::Check,
+            (POPUP_INSTALL, []) => StartupCommand::PopupInstall,
+            (SNAPSHOT, [flag]) if flag == "--json" => StartupCommand::SnapshotJson,
+            (STATS, options) => {
+                parse_stats_options(options).unwrap_or_else(|error| StartupCommand::Usage {
+                    error,
+                    usage: STATS_USAGE,
+                })
+            }
+            (TOP, options) => {
+                parse_top_options(options).unwrap_or_else(|error| StartupCommand::Usage {
+                    error,
+                    usage: TOP_USAGE,
+                })
+            }
+            (REPORT, options) => {
+                parse_report_options(options).unwrap_or_else(|error| StartupCommand::Usage {
+                    error,
+                    usage: REPORT_USAGE,
+                })
+            }
+            _ => StartupCommand::Interactive,
+        },
+    }
+}
+
+// Parses `trex top [--interval SECS] [--once]`, or says what is wrong.
+fn parse_top_options(options: &[String]) -> Result<StartupCommand, String> {
+    let mut interval_secs = DEFAULT_TOP_INTERVAL_SECS;
+    let mut once = false;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--once" => once = true,
+            "--interval" | "-n" => {
+                interval_secs = options
+                    .next()
+                    .and_then(|secs| secs.parse().ok())
+                    .filter(|secs| *secs > 0)
+                    .ok_or("--interval takes a number of seconds above 0")?;
+            }
+            _ => return Err(format!("unknown option {}", option)),
         }
-        _ => StartupCommand::Interactive,
     }
+    Ok(StartupCommand::Top {
+        interval_secs,
+        once,
+    })
+}
+
+// Parses `trex stats --csv|--json [--samples N]`, or says what is wrong.
+fn parse_stats_options(options: &[String]) -> Result<StartupCommand, String> {
+    let mut format = None;
+    let mut samples = 1;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--csv" => format = Some(export::ExportFormat::Csv),
+            "--json" => format = Some(export::ExportFormat::Json),
+            "--samples" => {
+                samples = options
+                    .next()
+                    .and_then(|count| count.parse().ok())
+                    .filter(|count| *count > 0)
+                    .ok_or("--samples takes a count above 0")?;
+            }
+            _ => return Err(format!("unknown option {}", option)),
+        }
+    }
+    Ok(StartupCommand::Stats {
+        format: format.ok_or("stats needs --csv or --json")?,
+        samples,
+    })
+}
+
+// Parses `trex report [--day|--week] [--json]`, or says what is wrong.
+fn parse_report_options(options: &[String]) -> Result<StartupCommand, String> {
+    let mut period_secs = report::WEEK_SECS;
+    let mut json = false;
+    for option in options {
+        match option.as_str() {
+            "--day" => period_secs = report::DAY_SECS,
+            "--week" => period_secs = report::WEEK_SECS,
+            "--json" => json = true,
+            _ => return Err(format!("unknown option {}", option)),
+        }
+    }
+    Ok(StartupCommand::Report { period_secs, json })
 }
 
 fn print_help() {
@@ -34,17 +217,40 @@ fn print_help() {
         concat!(
             "trex {} - tmux session manager\n\n",
             "Usage:\n",
-            "  trex\n",
+            "  trex [--hub|--popup] [--auto] [--debug] [--stats|--charts|--timeline|--expanded SESSION] [--filter QUERY]\n",
+            "  trex [--hub] QUERY...  Attach to the one session QUERY matches, else open filtered\n",
             "  trex snapshot --json\n",
+            "  trex check\n",
+            "  trex popup-install\n",
+            "  {}\n",
+            "  {}\n",
+            "  {}\n",
             "  trex --help\n",
             "  trex --version\n\n",
             "Commands:\n",
-            "  snapshot --json    Emit a read-only JSON snapshot\n\n",
+            "  snapshot --json    Emit a read-only JSON snapshot\n",
+            "  check              Exit non-zero when a configured alert threshold is exceeded\n",
+            "  popup-install      Add a prefix + T binding opening trex in a tmux popup to tmux.conf\n",
+            "  top                Print a refreshing table of sessions with CPU, memory, and health\n",
+            "  stats              Sample sessions N times (2s apart) and print their stats as CSV or JSON\n",
+            "  report             Summarize time attached, agent time, and CPU-hours per project\n\n",
             "Options:\n",
+            "      --hub          Return to trex after detaching from an attached session\n",
+            "      --auto         Attach right away when there is one session and no agent is waiting\n",
+            "      --popup        Run inside a tmux popup: switch the client to the chosen session\n",
+            "      --stats        Open on the stats overlay\n",
+            "      --charts       Open on the bar chart view\n",
+            "      --timeline     Open on the session timeline\n",
+            "      --expanded S   Open with session S expanded to its windows\n",
+            "      --filter Q     Open with the session filter set to Q\n",
+            "      --debug        Write a debug log to ~/.cache/trex/trex.log (or set TREX_LOG=<level>)\n",
             "  -h, --help         Show this help\n",
             "  -V, --version      Show version"
         ),
-        env!("CARGO_PKG_VERSION")
+        env!("CARGO_PKG_VERSION"),
+        TOP_USAGE,
+        STATS_USAGE,
+        REPORT_USAGE
     );
 }
 
@@ -52,6 +258,32 @@ fn print_version() {
     println!("trex {}", env!("CARGO_PKG_VERSION"));
 }
 
+/* Evaluates configured alert thresholds once and reports violations.
+ * Returns the process exit code: 0 when every session is within its
+ * thresholds, 1 when at least one threshold is exceeded. */
+fn run_check() -> Result<i32> {
+    let config = config::TrexConfig::load();
+    for warning in &config.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    if config.alerts.rules.is_empty() {
+        println!("No alert rules configured");
+        return Ok(0);
+    }
+
+    let violations = alert::check_sessions(&config.alerts.rules)?;
+    if violations.is_empty() {
+        println!("All sessions within alert thresholds");
+        return Ok(0);
+    }
+
+    for violation in &violations {
+        println!("ALERT {}", violation.message());
+    }
+    Ok(1)
+}
+
 /* Ensures stdin, stdout, and stderr are connected to a TTY.
  *
  * When running from keybindings or other non-standard contexts, the standard
@@ -83,13 +315,56 @@ fn ensure_terminal() -> Result<()> {
 }
 
 fn main() -> Result<()> {
-    let args = std::env::args().skip(1).collect::<Vec<_>>();
-    match parse_startup_command(&args) {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    let debug = take_flag(&mut args, "--debug");
+    let auto_flag = take_flag(&mut args, "--auto");
+    let popup = take_flag(&mut args, "--popup");
+    logging::init(debug);
+    let config = config::TrexConfig::load();
+    tmux::set_activity_thresholds(config.activity);
+    let mut view = take_startup_view(&mut args);
+    let mut query = take_query(&mut args);
+    if query.is_some() {
+        view.filter = query.clone();
+    }
+    let mut view = Some(view);
+    let command = parse_startup_command(&args);
+    let hub_flag = command == StartupCommand::Hub;
+    match command {
         StartupCommand::SnapshotJson => {
             let snapshot = trex_cli::backend::collect_snapshot()?;
             println!("{}", serde_json::to_string(&snapshot)?);
             return Ok(());
         }
+        StartupCommand::Check => {
+            let code = run_check()?;
+            std::process::exit(code);
+        }
+        StartupCommand::PopupInstall => {
+            popup::run_popup_install()?;
+            return Ok(());
+        }
+        StartupCommand::Top {
+            interval_secs,
+            once,
+        } => {
+            top::run_top(std::time::Duration::from_secs(interval_secs), once)?;
+            return Ok(());
+        }
+        StartupCommand::Stats { format, samples } => {
+            let export = export::collect_stats(samples, &config.health)?;
+            print!("{}", export.render(format)?);
+            return Ok(());
+        }
+        StartupCommand::Report { period_secs, json } => {
+            let report = report::collect_report(period_secs);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{}", report.render_table());
+            }
+            return Ok(());
+        }
         StartupCommand::Help => {
             print_help();
             return Ok(());
@@ -98,75 +373,382 @@ fn main() -> Result<()> {
             print_version();
             return Ok(());
         }
-        StartupCommand::Interactive => {}
+        StartupCommand::Usage { error, usage } => {
+            eprintln!("trex: {}", error);
+            eprintln!("Usage: {}", usage);
+            std::process::exit(2);
+        }
+        StartupCommand::Interactive | StartupCommand::Hub => {}
     }
 
     ensure_terminal()?;
 
     TmuxClient::check_installed()?;
 
-    if TmuxClient::is_inside_tmux() {
+    /* A popup runs over a client already inside tmux, which the actions
+     * then switch rather than attach; anywhere else inside tmux, attaching
+     * would nest sessions. */
+    if TmuxClient::is_inside_tmux() && !popup {
         bail!(
             "trex cannot be run from inside a tmux session.\nPlease run trex from outside tmux to manage your sessions."
         );
     }
 
-    let mut sessions = TmuxClient::list_sessions()?;
+    // The popup closes once trex exits, so it never acts as a hub
+    let hub = !popup && (hub_flag || config.hub);
+    // Like the startup view, `--auto` only applies before the first TUI run
+    let mut auto = auto_flag || config.auto_attach;
+    // A failed attach from a popup returns to it, so it never execs tmux
+    let mode = if hub || popup {
+        AttachMode::Child
+    } else {
+        AttachMode::Exec
+    };
+
+    /* In hub mode attach runs tmux as a child, so after the user detaches
+     * the loop reopens the TUI with a fresh session list. Otherwise attach
+     * replaces trex via exec and the loop runs once. */
+    let mut attached: Option<SessionRef> = None;
+    loop {
+        let sessions = load_sessions(&TmuxClient)?;
+        let preselect_index = preselection(&sessions, attached.as_ref());
+
+        /* The startup view only applies to the first run; the hub reopens on the
+         * list, and a run whose action failed where it was chosen, with why. */
+        let mut startup = view.take().unwrap_or_default();
+        startup.popup = popup;
+        let auto_target = if std::mem::take(&mut auto) && sessions.len() == 1 {
+            let agents = process::find_ai_processes_with(&config.agents).unwrap_or_default();
+            lone_session(&sessions, &agents)
+        } else {
+            None
+        };
+        let matched = query
+            .take()
+            .and_then(|query| {
+                let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+                tui::app::single_match(&sessions, &query, &mut matcher).map(SessionRef::of)
+            })
+            .or(auto_target)
+            .map(SessionAction::Attach);
+        let (mut action, resume) = match matched {
+            Some(action) => (action, StartupView::default()),
+            None => {
+                let Some(selected) = tui::run_tui_resumable(
+                    Box::new(TmuxClient),
+                    sessions,
+                    preselect_index,
+                    &startup,
+                )?
+                else {
+                    return Ok(());
+                };
+                selected
+            }
+        };
+        if let Err(error) = validate_action(&TmuxClient, &mut action) {
+            log::warn!("{}", error);
+            view = Some(StartupView {
+                error: Some(error),
+                ..resume
+            });
+            continue;
+        }
+        if let Some(wm) = focus_attached_terminal(&TmuxClient, &action, &config.wm) {
+            let session = attached_session(&action);
+            log::info!("focused {} through {}", session.name, wm);
+            if !hub {
+                return Ok(());
+            }
+            attached = Some(session);
+            continue;
+        }
+        /* The attach is logged before exec replaces trex, so its end is
+         * unknown; as a child, it's logged once the user detaches. */
+        let session = attached_session(&action);
+        let started = timeline::unix_now();
+        if mode == AttachMode::Exec {
+            log_attach(&session.name, started, None);
+        }
+        remember_directory(&action);
+        /* An exec only returns when it failed, and a child attach or a
+         * create can fail too; either way the user gets the TUI back where
+         * they were rather than a shell with the error. */
+        if let Err(error) = perform_action(&TmuxClient, action, mode) {
+            log::warn!("{:#}", error);
+            view = Some(StartupView {
+                error: Some(format!("{:#}", error)),
+                ..resume
+            });
+            continue;
+        }
+        if mode == AttachMode::Child {
+            log_attach(&session.name, started, Some(timeline::unix_now()));
+        }
+
+        if !hub {
+            return Ok(());
+        }
+        attached = Some(session);
+    }
+}
+
+// Lists sessions and fetches git status for all sessions with paths.
+fn load_sessions(tmux: &dyn TmuxBackend) -> Result<Vec<TmuxSession>> {
+    let mut sessions = tmux.list_sessions()?;
 
-    // Fetch git status for all sessions with paths
     for session in &mut sessions {
         if let Some(ref path) = session.path {
             session.git_status = Some(GitStatus::for_path(path));
         }
     }
 
-    let preselect_index = find_matching_session_index(&sessions);
+    Ok(sessions)
+}
+
+/* The session `--auto` attaches to without the TUI: the only session, as
+ * long as no agent in tmux is waiting for input. Anything else opens the
+ * dashboard, where the waiting agents show. */
+fn lone_session(sessions: &[TmuxSession], agents: &[process::AiProcessInfo]) -> Option<SessionRef> {
+    let [session] = sessions else {
+        return None;
+    };
+    let waiting = agents.iter().any(|agent| {
+        agent.activity_state == process::ProcessState::Waiting && agent.tmux_session.is_some()
+    });
+    (!waiting).then(|| SessionRef::of(session))
+}
+
+// The session an action ends up attached to. A new session is known only by name.
+fn attached_session(action: &SessionAction) -> SessionRef {
+    match action {
+        SessionAction::Attach(session)
+        | SessionAction::AttachTakeover(session)
+        | SessionAction::AttachReadOnly(session)
+        | SessionAction::AttachWindow(session, _)
+        | SessionAction::Mirror {
+            target: session, ..
+        } => session.clone(),
+        SessionAction::Create { name, .. }
+        | SessionAction::Clone { name, .. }
+        | SessionAction::LaunchAgent { name, .. } => SessionRef {
+            target: name.clone(),
+            name: name.clone(),
+        },
+    }
+}
+
+/* The session selected when the TUI opens: in the hub, the one the user
+ * just detached from, found by id so a rename while attached doesn't lose
+ * it; otherwise the session for the current directory. */
+fn preselection(sessions: &[TmuxSession], attached: Option<&SessionRef>) -> usize {
+    attached
+        .and_then(|attached| {
+            sessions
+                .iter()
+                .position(|session| session.target() == attached.target)
+                .or_else(|| {
+                    sessions
+                        .iter()
+                        .position(|session| session.name == attached.name)
+                })
+        })
+        .unwrap_or_else(|| find_matching_session_index(sessions))
+}
+
+// Adds an attach to the history `trex report` reads.
+fn log_attach(session: &str, start: u64, end: Option<u64>) {
+    let attach = attach_log::Attach {
+        session: session.to_string(),
+        start,
+        end,
+    };
+    if let Err(error) = attach_log::AttachLog::append(attach, timeline::unix_now()) {
+        log::warn!("attach history: {}", error);
+    }
+}
+
+/* With `[wm] focus` or a workspace mapped to the session, an attach to a
+ * session another terminal already shows brings that terminal up instead
+ * of attaching a second client, for setups with a terminal per session:
+ * the window manager switches to the session's workspace, then focuses the
+ * terminal. Returns the window manager that did it. */
+fn focus_attached_terminal(
+    tmux: &dyn TmuxBackend,
+    action: &SessionAction,
+    wm_config: &config::WmConfig,
+) -> Option<&'static str> {
+    let SessionAction::Attach(session) = action else {
+        return None;
+    };
+    let workspace = wm_config.workspace_for(&session.name);
+    if !wm_config.focus && workspace.is_none() {
+        return None;
+    }
+    let mut clients = tmux.list_clients(&session.target).ok()?;
+    if clients.is_empty() {
+        return None;
+    }
+    let wm = wm::WindowManager::detect()?;
+    let switched = workspace.is_some_and(|rule| wm.switch_workspace(&rule.workspace));
+    // The terminal used last is the one the user means
+    clients.sort_by_key(|client| std::cmp::Reverse(client.activity));
+    let focused = wm_config.focus
+        && clients
+            .iter()
+            .filter_map(|client| client.pid)
+            .any(|pid| wm.focus_process(pid));
+    (switched || focused).then(|| wm.name())
+}
+
+// Puts a new session's directory first in the picker's recent source.
+fn remember_directory(action: &SessionAction) {
+    if let SessionAction::Create { path, .. } | SessionAction::LaunchAgent { path, .. } = action
+        && let Err(error) = directory::record_recent_directory(path)
+    {
+        log::warn!("recent directories: {}", error);
+    }
+}
+
+/* Checks that what the action targets is still there, since sessions and
+ * windows can go away (an agent exits, another client kills them) between
+ * the TUI listing them and the action running. Existing sessions are
+ * found by id and take up their current name, in case one was renamed
+ * meanwhile. The message says what vanished, or why tmux couldn't be
+ * asked, for the TUI to show when it reopens. */
+fn validate_action(tmux: &dyn TmuxBackend, action: &mut SessionAction) -> Result<(), String> {
+    let sessions = tmux
+        .list_sessions()
+        .map_err(|error| format!("Could not list sessions: {:#}", error))?;
+    let exists = |name: &str| sessions.iter().any(|session| session.name == name);
+    let refresh = |session: &mut SessionRef| match sessions
+        .iter()
+        .find(|listed| listed.target() == session.target)
+    {
+        Some(listed) => {
+            session.name = listed.name.clone();
+            Ok(())
+        }
+        None => Err(format!("Session {} no longer exists", session.name)),
+    };
+    let require_free = |name: &str| {
+        if exists(name) {
+            Err(format!("Session {} already exists", name))
+        } else {
+            Ok(())
+        }
+    };
+    let require_dir = |path: &std::path::Path| {
+        if path.is_dir() {
+            Ok(())
+        } else {
+            Err(format!("Directory {} no longer exists", path.display()))
+        }
+    };
+    match action {
+        SessionAction::Attach(session)
+        | SessionAction::AttachTakeover(session)
+        | SessionAction::AttachReadOnly(session) => refresh(session),
+        SessionAction::Mirror { source, target } => refresh(source).and(refresh(target)),
+        SessionAction::AttachWindow(session, window) => {
+            refresh(session)?;
+            let windows = tmux.list_windows(&session.target).map_err(|error| {
+                format!("Could not list windows of {}: {:#}", session.name, error)
+            })?;
+            let listed = windows.iter().any(|listed| {
+                if window.id.is_empty() {
+                    listed.index == window.index
+                } else {
+                    listed.id == window.id
+                }
+            });
+            if listed {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Window {} of {} no longer exists",
+                    window.name, session.name
+                ))
+            }
+        }
+        // Create attaches when the session is already there
+        SessionAction::Create { path, .. } => require_dir(path),
+        SessionAction::Clone { source, name } => refresh(source).and(require_free(name)),
+        SessionAction::LaunchAgent { name, path, .. } => require_dir(path).and(require_free(name)),
+    }
+}
+
+/* Performs the tmux action selected in the TUI after the terminal is
+ * restored. From a popup, trex runs inside tmux, so attaches switch the
+ * client instead. */
+fn perform_action(tmux: &dyn TmuxBackend, action: SessionAction, mode: AttachMode) -> Result<()> {
+    match action {
+        SessionAction::Attach(session) => {
+            tmux.attach_or_switch(&session.target, mode)?;
+        }
+
+        SessionAction::AttachTakeover(session) => {
+            tmux.attach_detach_others(&session.target, mode)?;
+        }
 
-    match tui::run_tui_with_preselection(sessions, preselect_index)? {
-        Some(SessionAction::Attach(name)) => {
-            TmuxClient::attach_or_switch(&name)?;
+        SessionAction::AttachReadOnly(session) => {
+            tmux.attach_read_only(&session.target, mode)?;
         }
 
-        Some(SessionAction::AttachWindow(session_name, window_index)) => {
-            TmuxClient::attach_or_switch_window(&session_name, window_index)?;
+        SessionAction::Mirror { source, target } => {
+            tmux.link_active_window(&source.target, &target.target)?;
+            tmux.attach_or_switch(&target.target, mode)?;
         }
 
-        Some(SessionAction::Create {
+        SessionAction::AttachWindow(session, window) => {
+            tmux.attach_or_switch_to(&session.target, &window, mode)?;
+        }
+
+        SessionAction::Create {
             name,
             path,
             template,
-        }) => {
-            let existing_sessions = TmuxClient::list_sessions()?;
+            command,
+        } => {
+            let existing_sessions = tmux.list_sessions()?;
             let session_exists = existing_sessions.iter().any(|s| s.name == name);
 
             if !session_exists {
-                TmuxClient::new_session_from_template(&name, &path, &template)?;
+                match command {
+                    Some(command) => {
+                        tmux.new_session_with_command(&name, &path, &template, &command)?
+                    }
+                    None => tmux.new_session_from_template(&name, &path, &template)?,
+                }
             }
 
-            TmuxClient::attach(&name)?;
-        }
-
-        Some(SessionAction::Delete(name)) => {
-            TmuxClient::delete_session(&name)?;
-            println!("Deleted session: {}", name);
+            tmux.attach_or_switch(&name, mode)?;
         }
 
-        Some(SessionAction::DeleteAll) => {
-            TmuxClient::delete_all_sessions()?;
-            println!("Deleted all sessions");
+        SessionAction::Clone { source, name } => {
+            tmux.clone_session(&source.target, &name)?;
+            tmux.attach_or_switch(&name, mode)?;
         }
 
-        Some(SessionAction::Detach(name)) => {
-            TmuxClient::detach_session(&name)?;
-            println!("Detached from session: {}", name);
-        }
-
-        Some(SessionAction::DetachAll) => {
-            TmuxClient::detach_all_sessions()?;
-            println!("Detached all clients");
+        /* The agent runs in the session's first window; the editor window
+         * opens after it, so attach back to the agent's window. */
+        SessionAction::LaunchAgent {
+            name,
+            path,
+            agent,
+            editor,
+        } => {
+            let terminal = template::SessionTemplate::terminal();
+            tmux.new_session_with_command(&name, &path, &terminal, &agent)?;
+            let Some(editor) = editor else {
+                return tmux.attach_or_switch(&name, mode);
+            };
+            tmux.new_window_with_command(&name, "editor", &path, &editor)?;
+            match tmux.list_windows(&name)?.first() {
+                Some(window) => tmux.attach_or_switch_to(&name, window, mode)?,
+                None => tmux.attach_or_switch(&name, mode)?,
+            }
         }
-
-        None => {}
     }
 
     Ok(())
@@ -186,6 +768,14 @@ mod tests {
             parse_startup_command(&args(&["snapshot", "--json"])),
             StartupCommand::SnapshotJson
         );
+        assert_eq!(
+            parse_startup_command(&args(&["check"])),
+            StartupCommand::Check
+        );
+        assert_eq!(
+            parse_startup_command(&args(&["popup-install"])),
+            StartupCommand::PopupInstall
+        );
         assert_eq!(
             parse_startup_command(&args(&["--help"])),
             StartupCommand::Help
@@ -199,6 +789,410 @@ mod tests {
             parse_startup_command(&args(&["-V"])),
             StartupCommand::Version
         );
+        assert_eq!(
+            parse_startup_command(&args(&["--hub"])),
+            StartupCommand::Hub
+        );
+    }
+
+    #[test]
+    fn parses_top_options() {
+        assert_eq!(
+            parse_startup_command(&args(&["top"])),
+            StartupCommand::Top {
+                interval_secs: DEFAULT_TOP_INTERVAL_SECS,
+                once: false
+            }
+        );
+        assert_eq!(
+            parse_startup_command(&args(&["top", "--interval", "5", "--once"])),
+            StartupCommand::Top {
+                interval_secs: 5,
+                once: true
+            }
+        );
+        assert_eq!(
+            parse_startup_command(&args(&["top", "--interval", "0"])),
+            StartupCommand::Usage {
+                error: "--interval takes a number of seconds above 0".to_string(),
+                usage: TOP_USAGE
+            }
+        );
+        assert!(matches!(
+            parse_startup_command(&args(&["top", "--interval"])),
+            StartupCommand::Usage { .. }
+        ));
+    }
+
+    #[test]
+    fn parses_stats_options() {
+        assert_eq!(
+            parse_startup_command(&args(&["stats", "--csv"])),
+            StartupCommand::Stats {
+                format: export::ExportFormat::Csv,
+                samples: 1
+            }
+        );
+        assert_eq!(
+            parse_startup_command(&args(&["stats", "--samples", "30", "--json"])),
+            StartupCommand::Stats {
+                format: export::ExportFormat::Json,
+                samples: 30
+            }
+        );
+        assert_eq!(
+            parse_startup_command(&args(&["stats"])),
+            StartupCommand::Usage {
+                error: "stats needs --csv or --json".to_string(),
+                usage: STATS_USAGE
+            }
+        );
+        assert!(matches!(
+            parse_startup_command(&args(&["stats", "--csv", "--samples", "0"])),
+            StartupCommand::Usage { .. }
+        ));
+    }
+
+    #[test]
+    fn parses_report_options() {
+        assert_eq!(
+            parse_startup_command(&args(&["report"])),
+            StartupCommand::Report {
+                period_secs: report::WEEK_SECS,
+                json: false
+            }
+        );
+        assert_eq!(
+            parse_startup_command(&args(&["report", "--day", "--json"])),
+            StartupCommand::Report {
+                period_secs: report::DAY_SECS,
+                json: true
+            }
+        );
+        assert_eq!(
+            parse_startup_command(&args(&["report", "--month"])),
+            StartupCommand::Usage {
+                error: "unknown option --month".to_string(),
+                usage: REPORT_USAGE
+            }
+        );
+    }
+
+    #[test]
+    fn strips_debug_flag_before_parsing() {
+        let mut values = args(&["--debug", "--hub"]);
+        assert!(take_flag(&mut values, "--debug"));
+        assert_eq!(parse_startup_command(&values), StartupCommand::Hub);
+
+        let mut values = args(&["snapshot", "--json"]);
+        assert!(!take_flag(&mut values, "--debug"));
+        assert_eq!(values.len(), 2);
+    }
+
+    #[test]
+    fn auto_attaches_only_to_a_lone_session_without_waiting_agents() {
+        let one = tmux::MockTmux::with_sessions(&["api"])
+            .list_sessions()
+            .unwrap();
+        let two = tmux::MockTmux::with_sessions(&["api", "web"])
+            .list_sessions()
+            .unwrap();
+        assert_eq!(lone_session(&one, &[]), Some(SessionRef::of(&one[0])));
+        assert_eq!(lone_session(&two, &[]), None);
+        assert_eq!(lone_session(&[], &[]), None);
+
+        let mut agent = process::AiProcessInfo {
+            process_name: "claude".to_string(),
+            project_name: "api".to_string(),
+            tmux_session: Some("api".to_string()),
+            tmux_pane: Some("%1".to_string()),
+            activity_state: process::ProcessState::Running,
+            pid: 42,
+            child_ai_names: Vec::new(),
+            container: None,
+        };
+        assert_eq!(
+            lone_session(&one, std::slice::from_ref(&agent)).map(|session| session.name),
+            Some("api".to_string())
+        );
+        agent.activity_state = process::ProcessState::Waiting;
+        assert_eq!(lone_session(&one, &[agent]), None);
+    }
+
+    #[test]
+    fn takes_trailing_words_as_a_session_query() {
+        let mut values = args(&["--hub", "my", "proj"]);
+        assert_eq!(take_query(&mut values).as_deref(), Some("my proj"));
+        assert_eq!(parse_startup_command(&values), StartupCommand::Hub);
+
+        let mut values = args(&["api"]);
+        assert_eq!(take_query(&mut values).as_deref(), Some("api"));
+        assert!(values.is_empty());
+
+        // `popup-install` is a command, not a session query
+        let mut values = args(&["popup-install"]);
+        assert_eq!(take_query(&mut values), None);
+        assert_eq!(parse_startup_command(&values), StartupCommand::PopupInstall);
+
+        for command in [
+            &["stats", "--json"][..],
+            &["snapshot"],
+            &["popup-install"],
+            &["--hub"],
+            &[],
+        ] {
+            let mut values = args(command);
+            assert_eq!(take_query(&mut values), None);
+            assert_eq!(values.len(), command.len());
+        }
+    }
+
+    #[test]
+    fn strips_startup_view_flags_before_parsing() {
+        let mut values = args(&["--hub", "--stats", "--filter", "api", "--charts"]);
+        let view = take_startup_view(&mut values);
+        assert_eq!(view.screen, StartupScreen::Charts);
+        assert_eq!(view.filter.as_deref(), Some("api"));
+        assert_eq!(parse_startup_command(&values), StartupCommand::Hub);
+
+        let mut values = args(&["--expanded", "docs"]);
+        let view = take_startup_view(&mut values);
+        assert_eq!(view.screen, StartupScreen::Expanded("docs".to_string()));
+        assert!(values.is_empty());
+
+        let mut values = args(&["--filter"]);
+        assert_eq!(take_startup_view(&mut values), StartupView::default());
+
+        let mut values = args(&["stats", "--json"]);
+        assert_eq!(take_startup_view(&mut values), StartupView::default());
+        assert_eq!(values.len(), 2);
+    }
+
+    fn create_action(name: &str) -> SessionAction {
+        SessionAction::Create {
+            name: name.to_string(),
+            path: std::path::PathBuf::from("/tmp"),
+            template: template::SessionTemplate {
+                id: "terminal".to_string(),
+                name: "Terminal".to_string(),
+                description: String::new(),
+                layout: template::TemplateLayout::Single,
+                panes: Vec::new(),
+                focus_pane: 0,
+                windows: Vec::new(),
+            },
+            command: None,
+        }
+    }
+
+    #[test]
+    fn create_action_reuses_an_existing_session() {
+        let mock = tmux::MockTmux::with_sessions(&["dev"]);
+        perform_action(&mock, create_action("dev"), AttachMode::Child).unwrap();
+        assert_eq!(mock.calls(), vec!["list-sessions", "attach dev"]);
+
+        perform_action(&mock, create_action("api"), AttachMode::Child).unwrap();
+        assert_eq!(
+            mock.calls()[2..],
+            ["list-sessions", "new-session api (Terminal)", "attach api"]
+        );
+    }
+
+    #[test]
+    fn launch_agent_opens_the_editor_and_returns_to_the_agent() {
+        let mock = tmux::MockTmux::default();
+        let action = SessionAction::LaunchAgent {
+            name: "api".to_string(),
+            path: std::path::PathBuf::from("/work/api"),
+            agent: "claude".to_string(),
+            editor: Some("nvim".to_string()),
+        };
+        perform_action(&mock, action, AttachMode::Child).unwrap();
+        assert_eq!(
+            mock.calls(),
+            [
+                "new-session api (Terminal) claude",
+                "new-window api editor nvim",
+                "list-windows api",
+                "attach api",
+            ]
+        );
+    }
+
+    #[test]
+    fn create_action_runs_the_start_command() {
+        let mock = tmux::MockTmux::default();
+        let mut action = create_action("api");
+        if let SessionAction::Create { command, .. } = &mut action {
+            *command = Some("nvim".to_string());
+        }
+        perform_action(&mock, action, AttachMode::Child).unwrap();
+        assert_eq!(
+            mock.calls(),
+            [
+                "list-sessions",
+                "new-session api (Terminal) nvim",
+                "attach api"
+            ]
+        );
+    }
+
+    #[test]
+    fn mirror_links_then_attaches_to_target() {
+        let mock = tmux::MockTmux::with_sessions(&["agent", "main"]);
+        let sessions = mock.list_sessions().unwrap();
+        let action = SessionAction::Mirror {
+            source: SessionRef::of(&sessions[0]),
+            target: SessionRef::of(&sessions[1]),
+        };
+        perform_action(&mock, action, AttachMode::Child).unwrap();
+        assert_eq!(
+            mock.calls(),
+            vec!["list-sessions", "link-window $0 $1", "attach $1"]
+        );
+
+        let action = SessionAction::AttachReadOnly(SessionRef {
+            target: "gone".to_string(),
+            name: "gone".to_string(),
+        });
+        assert!(perform_action(&mock, action, AttachMode::Child).is_err());
+    }
+
+    #[test]
+    fn window_attach_targets_the_window_id() {
+        let mock = tmux::MockTmux::with_sessions(&["agent"]);
+        let mut window = tmux::window::parse_windows("1\x1fclaude\x1f1\x1fclaude")
+            .unwrap()
+            .remove(0);
+        let agent = SessionRef::of(&mock.list_sessions().unwrap()[0]);
+        let action = SessionAction::AttachWindow(agent.clone(), window.clone());
+        perform_action(&mock, action, AttachMode::Child).unwrap();
+
+        // With an id listed, the index renumbering may have changed goes unused
+        window.id = "@7".to_string();
+        let action = SessionAction::AttachWindow(agent, window);
+        perform_action(&mock, action, AttachMode::Child).unwrap();
+        assert_eq!(
+            mock.calls(),
+            vec!["list-sessions", "attach $0:1", "attach $0:@7"]
+        );
+    }
+
+    #[test]
+    fn actions_whose_target_vanished_are_refused() {
+        let mock = tmux::MockTmux::with_sessions(&["agent", "docs"]);
+        let window = tmux::window::parse_windows(
+            "1\x1fclaude\x1f1\x1fclaude\x1f0\x1f0\x1f0\x1f0\x1f0\x1f0\x1f@7",
+        )
+        .unwrap()
+        .remove(0);
+        mock.state()
+            .windows
+            .insert("agent".to_string(), vec![window.clone()]);
+
+        let agent = SessionRef::of(&mock.list_sessions().unwrap()[0]);
+        let mut attach = SessionAction::AttachWindow(agent.clone(), window.clone());
+        assert_eq!(validate_action(&mock, &mut attach), Ok(()));
+        mock.state().windows.insert("agent".to_string(), Vec::new());
+        assert_eq!(
+            validate_action(&mock, &mut attach),
+            Err("Window claude of agent no longer exists".to_string())
+        );
+
+        // Killed, then a new session takes the name: a different id
+        mock.state()
+            .sessions
+            .retain(|session| session.name != "agent");
+        mock.state().add_session("agent");
+        let mut attach = SessionAction::Attach(agent);
+        assert_eq!(
+            validate_action(&mock, &mut attach),
+            Err("Session agent no longer exists".to_string())
+        );
+        let docs = SessionRef::of(&mock.list_sessions().unwrap()[1]);
+        let mut clone = SessionAction::Clone {
+            source: docs.clone(),
+            name: "docs".to_string(),
+        };
+        assert_eq!(
+            validate_action(&mock, &mut clone),
+            Err("Session docs already exists".to_string())
+        );
+        let mut mirror = SessionAction::Mirror {
+            source: docs.clone(),
+            target: SessionRef::of(&mock.list_sessions().unwrap()[0]),
+        };
+        assert_eq!(validate_action(&mock, &mut mirror), Ok(()));
+        assert!(mock.calls().iter().all(|call| call.starts_with("list-")));
+
+        // A tmux failure is reported as itself, not as a vanished session
+        mock.state().list_error = Some(tmux::TmuxError::CommandFailed {
+            command: "list-sessions".to_string(),
+            stderr: "server exited unexpectedly".to_string(),
+        });
+        assert_eq!(
+            validate_action(&mock, &mut SessionAction::Attach(docs)),
+            Err(
+                "Could not list sessions: tmux list-sessions failed: server exited unexpectedly"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn attaches_follow_a_session_renamed_after_it_was_chosen() {
+        let mock = tmux::MockTmux::with_sessions(&["api", "docs"]);
+        let api = SessionRef::of(&mock.list_sessions().unwrap()[0]);
+        let mut attach = SessionAction::Attach(api);
+
+        // Renamed elsewhere, and the old name taken by another session
+        mock.rename_session("api", "backend");
+        mock.rename_session("docs", "api");
+        assert_eq!(validate_action(&mock, &mut attach), Ok(()));
+        assert_eq!(attached_session(&attach).name, "backend");
+        perform_action(&mock, attach, AttachMode::Child).unwrap();
+        assert_eq!(mock.calls().last().map(String::as_str), Some("attach $0"));
+    }
+
+    #[test]
+    fn hub_reopens_on_the_session_it_detached_from() {
+        let mock = tmux::MockTmux::with_sessions(&["api", "docs", "web"]);
+        let docs = SessionRef::of(&mock.list_sessions().unwrap()[1]);
+
+        // Renamed while attached, and its old name reused
+        mock.rename_session("docs", "notes");
+        mock.rename_session("web", "docs");
+        let sessions = mock.list_sessions().unwrap();
+        assert_eq!(sessions[preselection(&sessions, Some(&docs))].name, "notes");
+
+        // A session trex created is found by name
+        let created = attached_session(&SessionAction::Clone {
+            source: docs.clone(),
+            name: "web".to_string(),
+        });
+        let sessions = tmux::MockTmux::with_sessions(&["api", "web"])
+            .list_sessions()
+            .unwrap();
+        assert_eq!(preselection(&sessions, Some(&created)), 1);
+    }
+
+    #[test]
+    fn clone_creates_then_attaches_to_the_copy() {
+        let mock = tmux::MockTmux::with_sessions(&["agent"]);
+        let action = SessionAction::Clone {
+            source: SessionRef::of(&mock.list_sessions().unwrap()[0]),
+            name: "agent-2".to_string(),
+        };
+        perform_action(&mock, action, AttachMode::Child).unwrap();
+        assert_eq!(
+            mock.calls(),
+            vec![
+                "list-sessions",
+                "clone-session $0 agent-2",
+                "attach agent-2"
+            ]
+        );
+        assert_eq!(mock.session_names(), vec!["agent", "agent-2"]);
     }
 
     #[test]
@@ -0,0 +1,145 @@
+use crate::alert::glob_match;
+use crate::tmux::TmuxSession;
+
+/// What an idle policy does with a matching session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyAction {
+    Flag, // only mark the session in the list
+    Kill, // offer it in the review overlay, which kills on confirmation
+}
+
+/// A `[[policies]]` rule: sessions matching `session_glob` that have been
+/// dormant for at least `idle_secs` with no client attached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdlePolicy {
+    pub session_glob: String,
+    pub idle_secs: u64,
+    pub action: PolicyAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyMatch {
+    pub session: String,
+    pub target: String, // tmux target, so a rename doesn't redirect the kill
+    pub idle_secs: u64,
+    pub policy: IdlePolicy,
+}
+
+impl IdlePolicy {
+    /* Parses a policy from its config fields, e.g. session `tmp-*`, idle
+     * `24h`, action `kill`. The action defaults to flag. */
+    pub fn parse(session_glob: &str, idle: &str, action: Option<&str>) -> Result<Self, String> {
+        let session_glob = session_glob.trim();
+        if session_glob.is_empty() {
+            return Err(format!("Skipped policy idle={idle}: missing session"));
+        }
+        let idle_secs = parse_duration_secs(idle).ok_or_else(|| {
+            format!("Skipped policy for {session_glob}: invalid idle {idle}, expected e.g. 24h")
+        })?;
+        let action = match action.map(str::trim) {
+            None | Some("flag") => PolicyAction::Flag,
+            Some("kill") => PolicyAction::Kill,
+            Some(other) => {
+                return Err(format!(
+                    "Skipped policy for {session_glob}: unknown action {other}, expected flag or kill"
+                ));
+            }
+        };
+        Ok(Self {
+            session_glob: session_glob.to_string(),
+            idle_secs,
+            action,
+        })
+    }
+
+    // The policy in its config form, e.g. `tmp-* idle>24h kill`.
+    pub fn describe(&self) -> String {
+        let action = match self.action {
+            PolicyAction::Flag => "flag",
+            PolicyAction::Kill => "kill",
+        };
+        format!(
+            "{} idle>{} {}",
+            self.session_glob,
+            crate::tmux::format_elapsed(self.idle_secs),
+            action
+        )
+    }
+}
+
+// Parses a duration such as `90s`, `30m`, `24h`, or `7d` into seconds.
+pub fn parse_duration_secs(value: &str) -> Option<u64> {
+    let value = value.trim().to_lowercase();
+    let (number, unit) = value.split_at(value.find(|c: char| !c.is_ascii_digit())?);
+    let scale = match unit.trim() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return None,
+    };
+    number.parse::<u64>().ok()?.checked_mul(scale)
+}
+
+/* Returns the first matching policy for each detached session idle past
+ * its threshold. Sessions without an activity timestamp never match. */
+pub fn evaluate(policies: &[IdlePolicy], sessions: &[TmuxSession], now: u64) -> Vec<PolicyMatch> {
+    sessions
+        .iter()
+        .filter(|session| session.clients == 0)
+        .filter_map(|session| {
+            let idle_secs = now.saturating_sub(session.last_activity?);
+            let policy = policies.iter().find(|policy| {
+                glob_match(&policy.session_glob, &session.name) && idle_secs >= policy.idle_secs
+            })?;
+            Some(PolicyMatch {
+                session: session.name.clone(),
+                target: session.target().to_string(),
+                idle_secs,
+                policy: policy.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::{MockTmux, TmuxBackend};
+
+    #[test]
+    fn parses_policies_and_durations() {
+        let policy = IdlePolicy::parse("tmp-*", "24h", Some("kill")).unwrap();
+        assert_eq!(policy.idle_secs, 86400);
+        assert_eq!(policy.action, PolicyAction::Kill);
+        assert_eq!(policy.describe(), "tmp-* idle>1d kill");
+
+        assert_eq!(
+            IdlePolicy::parse("*", "90m", None).unwrap().action,
+            PolicyAction::Flag
+        );
+        assert_eq!(parse_duration_secs("7d"), Some(604800));
+        assert_eq!(parse_duration_secs("24"), None);
+        assert!(IdlePolicy::parse("*", "1w", None).is_err());
+        assert!(IdlePolicy::parse("*", "1h", Some("delete")).is_err());
+    }
+
+    #[test]
+    fn matches_only_detached_sessions_idle_long_enough() {
+        let mut sessions = MockTmux::with_sessions(&["tmp-a", "tmp-b", "tmp-c", "main"])
+            .list_sessions()
+            .unwrap();
+        let now = 100_000;
+        sessions[0].last_activity = Some(now - 90_000);
+        sessions[1].last_activity = Some(now - 90_000);
+        sessions[1].clients = 1;
+        sessions[2].last_activity = Some(now - 60);
+        sessions[3].last_activity = Some(now - 90_000);
+        let policies = vec![IdlePolicy::parse("tmp-*", "24h", Some("kill")).unwrap()];
+
+        let matches = evaluate(&policies, &sessions, now);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].session, "tmp-a");
+        assert_eq!(matches[0].idle_secs, 90_000);
+    }
+}
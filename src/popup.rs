@@ -0,0 +1,127 @@
+use anyhow::{Context, Result, bail};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/* The binding `trex popup-install` adds: prefix + T opens trex in a tmux
+ * popup over the current client. `-E` closes the popup when trex exits,
+ * which it does after switching the client to the chosen session. */
+pub const POPUP_BINDING: &str = "bind-key T display-popup -E -w 90% -h 90% \"trex --popup\"";
+
+const POPUP_COMMENT: &str = "# trex session picker in a popup (trex popup-install)";
+
+/* Appends POPUP_BINDING to the user's tmux.conf after asking on the
+ * terminal, then tells them how to load it. Does nothing when a
+ * `trex --popup` binding is already there. */
+pub fn run_popup_install() -> Result<()> {
+    let path = tmux_conf_path().context("Cannot locate tmux.conf: HOME is not set")?;
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => bail!("Cannot read {}: {}", path.display(), error),
+    };
+    if is_installed(&text) {
+        println!("{} already binds trex --popup", path.display());
+        return Ok(());
+    }
+
+    println!("Adding to {}:\n\n  {}\n", path.display(), POPUP_BINDING);
+    print!("Append it? [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    if !matches!(answer.trim(), "y" | "Y" | "yes") {
+        println!("Nothing written");
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Cannot write {}", path.display()))?;
+    file.write_all(appended(&text).as_bytes())?;
+    println!(
+        "Done. Reload with `tmux source-file {}`, then press prefix + T",
+        path.display()
+    );
+    Ok(())
+}
+
+// The lines to append, on a fresh line after a blank one when the file has content.
+fn appended(text: &str) -> String {
+    let separator = match text {
+        "" => "",
+        text if text.ends_with('\n') => "\n",
+        _ => "\n\n",
+    };
+    format!("{}{}\n{}\n", separator, POPUP_COMMENT, POPUP_BINDING)
+}
+
+// True when a non-comment line already runs `trex --popup`.
+fn is_installed(text: &str) -> bool {
+    text.lines()
+        .map(str::trim)
+        .any(|line| !line.starts_with('#') && line.contains("trex --popup"))
+}
+
+pub fn tmux_conf_path() -> Option<PathBuf> {
+    tmux_conf_path_from_env(
+        std::env::var("XDG_CONFIG_HOME").ok().as_deref(),
+        std::env::var("HOME").ok().as_deref(),
+        Path::exists,
+    )
+}
+
+/* The file tmux loads, searched in tmux's own order: ~/.tmux.conf, then
+ * $XDG_CONFIG_HOME/tmux/tmux.conf, then ~/.config/tmux/tmux.conf. With
+ * none present, ~/.tmux.conf is created. */
+fn tmux_conf_path_from_env(
+    xdg_config_home: Option<&str>,
+    home: Option<&str>,
+    exists: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    let home = PathBuf::from(home.filter(|home| !home.trim().is_empty())?);
+    let mut candidates = vec![home.join(".tmux.conf")];
+    if let Some(xdg_config_home) = xdg_config_home.filter(|dir| !dir.trim().is_empty()) {
+        candidates.push(PathBuf::from(xdg_config_home).join("tmux/tmux.conf"));
+    }
+    candidates.push(home.join(".config/tmux/tmux.conf"));
+    let default = candidates[0].clone();
+    Some(
+        candidates
+            .into_iter()
+            .find(|path| exists(path))
+            .unwrap_or(default),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_tmux_conf_and_appends_the_binding_once() {
+        let xdg = |path: &Path| path == Path::new("/xdg/tmux/tmux.conf");
+        assert_eq!(
+            tmux_conf_path_from_env(Some("/xdg"), Some("/home/u"), xdg),
+            Some(PathBuf::from("/xdg/tmux/tmux.conf"))
+        );
+        assert_eq!(
+            tmux_conf_path_from_env(None, Some("/home/u"), |_| false),
+            Some(PathBuf::from("/home/u/.tmux.conf"))
+        );
+        assert_eq!(tmux_conf_path_from_env(None, None, |_| true), None);
+
+        let text = "set -g mouse on";
+        let installed = format!("{}{}", text, appended(text));
+        assert!(installed.starts_with("set -g mouse on\n\n# trex"));
+        assert!(!is_installed(text));
+        assert!(is_installed(&installed));
+        assert!(!is_installed(
+            "# bind-key T display-popup -E \"trex --popup\""
+        ));
+    }
+}
@@ -0,0 +1,344 @@
+use crate::process::AiProcessInfo;
+use crate::tmux::{ActivityLevel, TmuxSession};
+
+/// A parsed session filter: structured predicates plus the remaining text,
+/// which is fuzzy-matched against the session name and path.
+///
+/// Operators (prefix any with `!` to negate):
+/// - `@agent` any AI agent in the session, `@claude` a specific agent
+/// - `#work` sessions tagged `work`
+/// - `is:attached`, `is:active`, `is:idle`, `is:dormant` (`!attached` works too)
+/// - `cpu>100`, `mem>2GB`, `panes>4`, `win<2` numeric comparisons
+/// - `git:dirty`, `git:clean`, `git:ahead`, `git:behind`, `git:<branch>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionQuery {
+    pub fuzzy: String,
+    pub predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Predicate {
+    pub negated: bool,
+    pub condition: Condition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Agent(Option<String>), // None matches any agent
+    Tag(String),
+    Attached,
+    Activity(ActivityLevel),
+    Compare(Field, Ordering, f64),
+    Git(GitCondition),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Cpu,   // percent
+    Mem,   // MB
+    Panes, // count
+    Windows,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ordering {
+    Greater,
+    Less,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GitCondition {
+    Dirty,
+    Clean,
+    Ahead,
+    Behind,
+    Branch(String),
+}
+
+impl SessionQuery {
+    // Splits filter input into operators and fuzzy text. Tokens that are not
+    // valid operators stay in the fuzzy text, so plain names keep working.
+    pub fn parse(input: &str) -> Self {
+        let mut fuzzy = Vec::new();
+        let mut predicates = Vec::new();
+
+        for token in input.split_whitespace() {
+            match parse_predicate(token) {
+                Some(predicate) => predicates.push(predicate),
+                None => fuzzy.push(token),
+            }
+        }
+
+        Self {
+            fuzzy: fuzzy.join(" "),
+            predicates,
+        }
+    }
+
+    // Returns true when the query selects sessions by a (non-negated) tag.
+    pub fn has_tag(&self) -> bool {
+        self.predicates
+            .iter()
+            .any(|predicate| !predicate.negated && matches!(predicate.condition, Condition::Tag(_)))
+    }
+
+    // Returns true when the session satisfies every predicate.
+    pub fn matches(&self, session: &TmuxSession, agents: &[AiProcessInfo]) -> bool {
+        self.predicates
+            .iter()
+            .all(|predicate| predicate.matches(session, agents))
+    }
+}
+
+impl Predicate {
+    pub fn matches(&self, session: &TmuxSession, agents: &[AiProcessInfo]) -> bool {
+        self.condition.matches(session, agents) != self.negated
+    }
+}
+
+impl Condition {
+    fn matches(&self, session: &TmuxSession, agents: &[AiProcessInfo]) -> bool {
+        match self {
+            Condition::Agent(name) => agents.iter().any(|agent| {
+                agent.tmux_session.as_deref() == Some(session.name.as_str())
+                    && name
+                        .as_ref()
+                        .is_none_or(|name| agent.process_name.eq_ignore_ascii_case(name))
+            }),
+            Condition::Tag(tag) => session.tags.contains(tag),
+            Condition::Attached => session.attached,
+            Condition::Activity(level) => session.activity_level() == Some(*level),
+            Condition::Compare(field, ordering, threshold) => {
+                let Some(value) = field.value(session) else {
+                    return false;
+                };
+                match ordering {
+                    Ordering::Greater => value > *threshold,
+                    Ordering::Less => value < *threshold,
+                }
+            }
+            Condition::Git(condition) => {
+                let Some(git) = session.git_status.as_ref().filter(|git| git.is_repo) else {
+                    return false;
+                };
+                match condition {
+                    GitCondition::Dirty => git.dirty_count > 0,
+                    GitCondition::Clean => git.dirty_count == 0,
+                    GitCondition::Ahead => git.ahead > 0,
+                    GitCondition::Behind => git.behind > 0,
+                    GitCondition::Branch(branch) => git.branch.as_deref() == Some(branch.as_str()),
+                }
+            }
+        }
+    }
+}
+
+impl Field {
+    fn value(&self, session: &TmuxSession) -> Option<f64> {
+        match self {
+            Field::Cpu => session.stats.as_ref().map(|stats| stats.cpu_percent),
+            Field::Mem => session.stats.as_ref().map(|stats| stats.mem_mb as f64),
+            Field::Panes => Some(session.panes as f64),
+            Field::Windows => Some(session.windows as f64),
+        }
+    }
+}
+
+fn parse_predicate(token: &str) -> Option<Predicate> {
+    let (negated, body) = match token.strip_prefix('!') {
+        Some(body) => (true, body),
+        None => (false, token),
+    };
+    let condition = parse_condition(body, negated)?;
+    Some(Predicate { negated, condition })
+}
+
+// Keywords, tags, and agent names match in any case; a branch keeps its own.
+fn parse_condition(text: &str, negated: bool) -> Option<Condition> {
+    let body = &text.to_lowercase();
+    if let Some(agent) = body.strip_prefix('@') {
+        return Some(match agent {
+            "" | "agent" | "agents" => Condition::Agent(None),
+            name => Condition::Agent(Some(name.to_string())),
+        });
+    }
+
+    if let Some(tag) = body.strip_prefix('#') {
+        return (!tag.is_empty()).then(|| Condition::Tag(tag.to_string()));
+    }
+
+    // `!attached` reads naturally, so negated flags may drop the `is:` prefix
+    let flag = body.strip_prefix("is:").or_else(|| negated.then_some(body));
+    if let Some(flag) = flag {
+        match flag {
+            "attached" => return Some(Condition::Attached),
+            "active" => return Some(Condition::Activity(ActivityLevel::Active)),
+            "idle" => return Some(Condition::Activity(ActivityLevel::Idle)),
+            "dormant" => return Some(Condition::Activity(ActivityLevel::Dormant)),
+            _ => {}
+        }
+    }
+
+    if let Some(git) = text
+        .get(.."git:".len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case("git:"))
+        .map(|prefix| &text[prefix.len()..])
+    {
+        return Some(Condition::Git(match git.to_lowercase().as_str() {
+            "" => return None,
+            "dirty" => GitCondition::Dirty,
+            "clean" => GitCondition::Clean,
+            "ahead" => GitCondition::Ahead,
+            "behind" => GitCondition::Behind,
+            _ => GitCondition::Branch(git.to_string()),
+        }));
+    }
+
+    parse_comparison(body)
+}
+
+fn parse_comparison(body: &str) -> Option<Condition> {
+    let (field, ordering, value) = if let Some((field, value)) = body.split_once('>') {
+        (field, Ordering::Greater, value)
+    } else {
+        let (field, value) = body.split_once('<')?;
+        (field, Ordering::Less, value)
+    };
+
+    let (field, value) = match field {
+        "cpu" => (Field::Cpu, value.trim_end_matches('%').parse().ok()?),
+        "mem" => (Field::Mem, crate::alert::parse_mem_mb(value)?),
+        "panes" => (Field::Panes, value.parse().ok()?),
+        "win" | "windows" => (Field::Windows, value.parse().ok()?),
+        _ => return None,
+    };
+
+    Some(Condition::Compare(field, ordering, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::GitStatus;
+    use crate::process::ProcessState;
+    use crate::sysinfo::SessionStats;
+
+    fn session(name: &str, cpu: f64, attached: bool, dirty: u32) -> TmuxSession {
+        TmuxSession {
+            id: String::new(),
+            name: name.to_string(),
+            attached,
+            clients: attached as u32,
+            windows: 2,
+            panes: 3,
+            size_constrained: false,
+            path: None,
+            created: None,
+            last_activity: None,
+            tags: Vec::new(),
+            note: None,
+            alerts: Default::default(),
+            git_status: Some(GitStatus {
+                is_repo: true,
+                branch: Some("main".to_string()),
+                dirty_count: dirty,
+                ahead: 0,
+                behind: 0,
+            }),
+            stats: Some(SessionStats {
+                cpu_percent: cpu,
+                mem_mb: 512,
+                ..Default::default()
+            }),
+            cpu_history: Vec::new(),
+            mem_history: Vec::new(),
+        }
+    }
+
+    fn agent(name: &str, session: &str) -> AiProcessInfo {
+        AiProcessInfo {
+            process_name: name.to_string(),
+            project_name: String::new(),
+            tmux_session: Some(session.to_string()),
+            tmux_pane: None,
+            activity_state: ProcessState::Running,
+            pid: 1,
+            child_ai_names: Vec::new(),
+            container: None,
+        }
+    }
+
+    #[test]
+    fn separates_operators_from_fuzzy_text() {
+        let query = SessionQuery::parse("api cpu>100 !attached git:dirty @agent");
+        assert_eq!(query.fuzzy, "api");
+        assert_eq!(query.predicates.len(), 4);
+        assert!(query.predicates[1].negated);
+    }
+
+    #[test]
+    fn keeps_unknown_operators_as_fuzzy_text() {
+        let query = SessionQuery::parse("foo:bar disk>10 git:");
+        assert_eq!(query.fuzzy, "foo:bar disk>10 git:");
+        assert!(query.predicates.is_empty());
+    }
+
+    #[test]
+    fn matches_numeric_and_flag_predicates() {
+        let busy = session("busy", 150.0, false, 0);
+        let quiet = session("quiet", 2.0, true, 0);
+
+        let query = SessionQuery::parse("cpu>100 !attached");
+        assert!(query.matches(&busy, &[]));
+        assert!(!query.matches(&quiet, &[]));
+
+        let query = SessionQuery::parse("is:attached mem<1GB");
+        assert!(query.matches(&quiet, &[]));
+        assert!(!query.matches(&busy, &[]));
+    }
+
+    #[test]
+    fn matches_git_predicates() {
+        let dirty = session("dirty", 0.0, false, 3);
+        let clean = session("clean", 0.0, false, 0);
+
+        assert!(SessionQuery::parse("git:dirty").matches(&dirty, &[]));
+        assert!(!SessionQuery::parse("git:dirty").matches(&clean, &[]));
+        assert!(SessionQuery::parse("!git:dirty").matches(&clean, &[]));
+        assert!(SessionQuery::parse("git:main").matches(&clean, &[]));
+
+        // Branches compare with their own case; keywords in any case
+        let mut feature = session("feature", 0.0, false, 1);
+        feature.git_status.as_mut().unwrap().branch = Some("Feature-X".to_string());
+        assert!(SessionQuery::parse("git:Feature-X").matches(&feature, &[]));
+        assert!(!SessionQuery::parse("git:feature-x").matches(&feature, &[]));
+        assert!(SessionQuery::parse("GIT:Dirty").matches(&feature, &[]));
+    }
+
+    #[test]
+    fn matches_tag_predicates() {
+        let mut work = session("work", 0.0, false, 0);
+        work.tags = vec!["work".to_string(), "ai".to_string()];
+        let scratch = session("scratch", 0.0, false, 0);
+
+        let query = SessionQuery::parse("#Work");
+        assert!(query.has_tag());
+        assert!(query.matches(&work, &[]));
+        assert!(!query.matches(&scratch, &[]));
+        assert!(SessionQuery::parse("!#ai").matches(&scratch, &[]));
+        assert!(!SessionQuery::parse("!#ai").has_tag());
+    }
+
+    #[test]
+    fn matches_agent_predicates() {
+        let api = session("api", 0.0, false, 0);
+        let web = session("web", 0.0, false, 0);
+        let agents = [agent("claude", "api")];
+
+        assert!(SessionQuery::parse("@agent").matches(&api, &agents));
+        assert!(!SessionQuery::parse("@agent").matches(&web, &agents));
+        assert!(SessionQuery::parse("@claude").matches(&api, &agents));
+        assert!(!SessionQuery::parse("@codex").matches(&api, &agents));
+        assert!(SessionQuery::parse("!@agent").matches(&web, &agents));
+    }
+}
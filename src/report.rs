@@ -0,0 +1,213 @@
+use crate::attach_log::AttachLog;
+use crate::timeline::{ActivityTimeline, unix_now};
+use crate::tmux::format_elapsed;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+// The periods `trex report` covers: the last day, or the week the timeline keeps.
+pub const DAY_SECS: u64 = 86400;
+pub const WEEK_SECS: u64 = crate::timeline::RETENTION_SECS;
+
+/* Time spent per project over a period, from the attach history and the
+ * activity timeline trex already keeps. A project is a session name, the
+ * way trex names sessions after their directories. */
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+    pub from: u64,
+    pub to: u64,
+    pub projects: Vec<ProjectUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectUsage {
+    pub project: String,
+    pub attached_secs: u64, // estimated for attaches whose detach trex didn't see
+    pub attaches: u32,
+    pub agent_secs: u64, // only counted while the TUI was running to sample it
+    pub cpu_hours: f64,
+}
+
+impl ProjectUsage {
+    fn new(project: &str) -> Self {
+        Self {
+            project: project.to_string(),
+            attached_secs: 0,
+            attaches: 0,
+            agent_secs: 0,
+            cpu_hours: 0.0,
+        }
+    }
+}
+
+// Builds the report for the last `period_secs` from the saved history.
+pub fn collect_report(period_secs: u64) -> UsageReport {
+    let to = unix_now();
+    UsageReport::from_history(
+        &ActivityTimeline::load(),
+        &AttachLog::load(),
+        to.saturating_sub(period_secs),
+        to,
+    )
+}
+
+impl UsageReport {
+    /* Sums attaches overlapping `from..to` (clipped to it) and timeline
+     * buckets in it, per project, most attached first. */
+    pub fn from_history(
+        timeline: &ActivityTimeline,
+        attaches: &AttachLog,
+        from: u64,
+        to: u64,
+    ) -> Self {
+        let mut usage: BTreeMap<String, ProjectUsage> = BTreeMap::new();
+
+        for attach in attaches.resolved(to) {
+            let end = attach.end.unwrap_or(attach.start).min(to);
+            if end < from || attach.start > to {
+                continue;
+            }
+            let entry = usage
+                .entry(attach.session.clone())
+                .or_insert_with(|| ProjectUsage::new(&attach.session));
+            entry.attached_secs += end - attach.start.max(from);
+            entry.attaches += 1;
+        }
+
+        for (name, bucket) in timeline.totals(from, to) {
+            if bucket.agent_secs == 0 && bucket.cpu_secs <= 0.0 && !usage.contains_key(&name) {
+                continue;
+            }
+            let entry = usage
+                .entry(name.clone())
+                .or_insert_with(|| ProjectUsage::new(&name));
+            entry.agent_secs += bucket.agent_secs;
+            entry.cpu_hours += bucket.cpu_secs / 3600.0;
+        }
+
+        let mut projects: Vec<ProjectUsage> = usage.into_values().collect();
+        projects.sort_by(|a, b| {
+            b.attached_secs
+                .cmp(&a.attached_secs)
+                .then(b.agent_secs.cmp(&a.agent_secs))
+                .then(a.project.cmp(&b.project))
+        });
+        Self { from, to, projects }
+    }
+
+    // A summary line, then one row per project.
+    pub fn render_table(&self) -> String {
+        let name_width = self
+            .projects
+            .iter()
+            .map(|usage| usage.project.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max("PROJECT".len());
+
+        let attached: u64 = self.projects.iter().map(|usage| usage.attached_secs).sum();
+        let agents: u64 = self.projects.iter().map(|usage| usage.agent_secs).sum();
+        // Folded from 0.0: an empty f64 sum is -0.0, which prints as `-0.00`
+        let cpu_hours = self
+            .projects
+            .iter()
+            .fold(0.0, |total, usage| total + usage.cpu_hours);
+        let mut out = format!(
+            "trex report: last {}, {} projects, {} attached, {} agent time, {:.2} CPU-hours\n\n",
+            format_elapsed(self.to - self.from),
+            self.projects.len(),
+            format_hours(attached),
+            format_hours(agents),
+            cpu_hours
+        );
+        if self.projects.is_empty() {
+            out.push_str("No attaches or agent time recorded\n");
+            return out;
+        }
+
+        out.push_str(&format!(
+            "{:<name_width$}  {:>8}  {:>8}  {:>10}  {:>7}\n",
+            "PROJECT", "ATTACHED", "ATTACHES", "AGENT TIME", "CPU-H"
+        ));
+        for usage in &self.projects {
+            out.push_str(&format!(
+                "{:<name_width$}  {:>8}  {:>8}  {:>10}  {:>7.2}\n",
+                usage.project,
+                format_hours(usage.attached_secs),
+                usage.attaches,
+                format_hours(usage.agent_secs),
+                usage.cpu_hours
+            ));
+        }
+        out
+    }
+}
+
+// Hours and minutes, as `3h05m`, or minutes alone under an hour.
+fn format_hours(secs: u64) -> String {
+    let minutes = secs / 60;
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attach_log::Attach;
+    use crate::tmux::{MockTmux, TmuxBackend};
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn sums_attached_agent_and_cpu_time_per_project() {
+        let mut attaches = AttachLog::default();
+        for (session, start, end) in [
+            ("api", NOW - 2 * DAY_SECS, Some(NOW - 2 * DAY_SECS + 600)),
+            ("api", NOW - 7200, Some(NOW - 3600)),
+            ("web", NOW - 1800, None),
+        ] {
+            let attach = Attach {
+                session: session.to_string(),
+                start,
+                end,
+            };
+            attaches.record(attach, NOW);
+        }
+
+        let mut timeline = ActivityTimeline::default();
+        let mut sessions = MockTmux::with_sessions(&["api", "idle", "web"])
+            .list_sessions()
+            .unwrap();
+        sessions[0].stats = Some(crate::sysinfo::SessionStats {
+            cpu_percent: 100.0,
+            ..Default::default()
+        });
+        timeline.record(&sessions, &["api"], 1800, NOW - 60);
+
+        let report = UsageReport::from_history(&timeline, &attaches, NOW - DAY_SECS, NOW);
+        let projects: Vec<&str> = report.projects.iter().map(|u| u.project.as_str()).collect();
+        assert_eq!(projects, ["api", "web"]);
+        assert_eq!(report.projects[0].attached_secs, 3600);
+        assert_eq!(report.projects[0].attaches, 1);
+        assert_eq!(report.projects[0].agent_secs, 1800);
+        assert_eq!(report.projects[0].cpu_hours, 0.5);
+        // The open attach runs until now
+        assert_eq!(report.projects[1].attached_secs, 1800);
+
+        let week = UsageReport::from_history(&timeline, &attaches, NOW - WEEK_SECS, NOW);
+        assert_eq!(week.projects[0].attaches, 2);
+
+        let table = report.render_table();
+        assert!(table.starts_with("trex report: last 1d, 2 projects, 1h30m attached"));
+        assert!(table.contains("api         1h00m"));
+        assert!(
+            UsageReport::from_history(&timeline, &attaches, NOW - 6 * DAY_SECS, NOW - 5 * DAY_SECS)
+                .render_table()
+                .contains("No attaches")
+        );
+    }
+}
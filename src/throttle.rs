@@ -0,0 +1,225 @@
+use crate::config::ThrottleConfig;
+use crate::process::set_nice;
+use crate::sysinfo::get_session_processes;
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::process::Command;
+
+/// Outcome of renicing every process in a session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReniceReport {
+    pub nice: i32,
+    pub reniced: usize,
+    pub failed: usize,
+}
+
+// Sets every process in the session's pane trees to the configured nice value.
+pub fn renice_session(session_name: &str, config: &ThrottleConfig) -> Result<ReniceReport> {
+    let (_, processes) = get_session_processes(session_name)?;
+    if processes.is_empty() {
+        bail!("No processes found in session {}", session_name);
+    }
+
+    let mut report = ReniceReport {
+        nice: config.nice,
+        reniced: 0,
+        failed: 0,
+    };
+    for process in &processes {
+        match set_nice(process.pid, config.nice) {
+            Ok(_) => report.reniced += 1,
+            Err(_) => report.failed += 1,
+        }
+    }
+
+    Ok(report)
+}
+
+// Returns true when a systemd user manager is available to create scopes.
+pub fn cgroup_limits_available() -> bool {
+    Path::new("/run/systemd/system").exists()
+        && which::which("busctl").is_ok()
+        && which::which("systemctl").is_ok()
+}
+
+/* Moves a session's processes into a transient systemd user scope with the
+ * configured CPU quota and memory ceiling, and returns the scope name.
+ *
+ * `systemd-run --scope` can only start new commands, so the scope is created
+ * over D-Bus with the existing PIDs. Children forked later inherit the cgroup.
+ * If the scope already exists, its limits are updated instead. */
+pub fn limit_session(session_name: &str, config: &ThrottleConfig) -> Result<String> {
+    if !cgroup_limits_available() {
+        bail!("cgroup limits need systemd with busctl and systemctl");
+    }
+    if config.cpu_percent.is_none() && config.mem_mb.is_none() {
+        bail!("No cpu or memory limit set in the [throttle] section of config.toml");
+    }
+
+    let unit = scope_unit_name(session_name);
+
+    if scope_is_active(&unit) {
+        let mut args = vec![
+            "--user".to_string(),
+            "set-property".to_string(),
+            "--runtime".to_string(),
+            unit.clone(),
+        ];
+        args.extend(systemctl_properties(config));
+        run(Command::new("systemctl").args(&args), "update scope limits")?;
+        return Ok(unit);
+    }
+
+    let (_, processes) = get_session_processes(session_name)?;
+    if processes.is_empty() {
+        bail!("No processes found in session {}", session_name);
+    }
+    let pids: Vec<u32> = processes.iter().map(|process| process.pid).collect();
+
+    let args = start_transient_unit_args(&unit, &pids, config);
+    run(Command::new("busctl").args(&args), "create scope")?;
+    Ok(unit)
+}
+
+fn scope_is_active(unit: &str) -> bool {
+    Command::new("systemctl")
+        .args(["--user", "--quiet", "is-active", unit])
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn run(command: &mut Command, action: &str) -> Result<()> {
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to {}", action))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to {}: {}", action, stderr.trim());
+    }
+    Ok(())
+}
+
+/* Builds a valid unit name, e.g. `trex-my\x20session.scope`. Like
+ * `systemd-escape`, bytes other than ASCII alphanumerics, `_`, `:`, and a
+ * non-leading `.` become `\xNN`, so distinct sessions never share a scope. */
+fn scope_unit_name(session_name: &str) -> String {
+    let mut escaped = String::with_capacity(session_name.len());
+    for (i, byte) in session_name.bytes().enumerate() {
+        if byte.is_ascii_alphanumeric() || byte == b'_' || byte == b':' || (byte == b'.' && i > 0) {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("\\x{:02x}", byte));
+        }
+    }
+    format!("trex-{}.scope", escaped)
+}
+
+fn systemctl_properties(config: &ThrottleConfig) -> Vec<String> {
+    let mut properties = Vec::new();
+    if let Some(percent) = config.cpu_percent {
+        properties.push(format!("CPUQuota={}%", percent));
+    }
+    if let Some(mb) = config.mem_mb {
+        properties.push(format!("MemoryMax={}M", mb));
+    }
+    properties
+}
+
+// Arguments for `busctl --user call ... StartTransientUnit` adopting `pids`.
+fn start_transient_unit_args(unit: &str, pids: &[u32], config: &ThrottleConfig) -> Vec<String> {
+    let mut properties: Vec<String> = vec!["PIDs".into(), "au".into(), pids.len().to_string()];
+    properties.extend(pids.iter().map(|pid| pid.to_string()));
+    let mut count = 1;
+
+    if let Some(percent) = config.cpu_percent {
+        // CPUQuota=100% is one second of CPU time per second
+        let usec = (percent * 10_000.0) as u64;
+        properties.extend(["CPUQuotaPerSecUSec".into(), "t".into(), usec.to_string()]);
+        count += 1;
+    }
+    if let Some(mb) = config.mem_mb {
+        let bytes = mb * 1024 * 1024;
+        properties.extend(["MemoryMax".into(), "t".into(), bytes.to_string()]);
+        count += 1;
+    }
+
+    let mut args: Vec<String> = [
+        "--user",
+        "call",
+        "org.freedesktop.systemd1",
+        "/org/freedesktop/systemd1",
+        "org.freedesktop.systemd1.Manager",
+        "StartTransientUnit",
+        "ssa(sv)a(sa(sv))",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+    args.extend([unit.to_string(), "fail".to_string(), count.to_string()]);
+    args.extend(properties);
+    args.push("0".to_string()); // no auxiliary units
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_session_names_into_unit_names() {
+        assert_eq!(scope_unit_name("api"), "trex-api.scope");
+        assert_eq!(scope_unit_name("my proj.v2"), r"trex-my\x20proj.v2.scope");
+        assert_eq!(
+            scope_unit_name(".dot-files"),
+            r"trex-\x2edot\x2dfiles.scope"
+        );
+        assert_eq!(scope_unit_name("caf\u{e9}"), r"trex-caf\xc3\xa9.scope");
+        // Names that differ only in punctuation keep separate scopes
+        assert_ne!(scope_unit_name("a.b"), scope_unit_name("a_b"));
+        assert_ne!(scope_unit_name("a b"), scope_unit_name("a_b"));
+        assert_ne!(scope_unit_name(r"a\x20b"), scope_unit_name("a b"));
+    }
+
+    #[test]
+    fn builds_transient_unit_call() {
+        let config = ThrottleConfig {
+            nice: 10,
+            cpu_percent: Some(50.0),
+            mem_mb: Some(2048),
+        };
+        let args = start_transient_unit_args("trex-api.scope", &[10, 20], &config);
+
+        let tail: Vec<&str> = args[7..].iter().map(String::as_str).collect();
+        assert_eq!(
+            tail,
+            [
+                "trex-api.scope",
+                "fail",
+                "3",
+                "PIDs",
+                "au",
+                "2",
+                "10",
+                "20",
+                "CPUQuotaPerSecUSec",
+                "t",
+                "500000",
+                "MemoryMax",
+                "t",
+                "2147483648",
+                "0",
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_systemctl_properties() {
+        let config = ThrottleConfig {
+            nice: 10,
+            cpu_percent: Some(150.0),
+            mem_mb: None,
+        };
+        assert_eq!(systemctl_properties(&config), vec!["CPUQuota=150%"]);
+    }
+}
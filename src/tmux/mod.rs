@@ -1,8 +0,0 @@
-pub mod commands;
-pub mod parser;
-pub mod session;
-pub mod window;
-
-pub use commands::TmuxClient;
-pub use session::{ActivityLevel, TmuxSession, find_matching_session_index};
-pub use window::TmuxWindow;
@@ -0,0 +1,163 @@
+use crate::config::TrexConfig;
+use crate::health::{HealthScore, HealthWeights};
+use crate::sysinfo::get_session_stats;
+use crate::tmux::{TmuxClient, TmuxSession};
+use anyhow::Result;
+use std::io::Write;
+use std::time::Duration;
+
+// Gap between the priming sample and the first table, so CPU has a delta
+const PRIME_INTERVAL: Duration = Duration::from_secs(1);
+
+// Clears the terminal and homes the cursor before each refresh
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+
+/* Prints a table of sessions with CPU, memory, and health every `interval`,
+ * like `docker stats`. On a terminal each table replaces the last; when
+ * stdout is piped or logged the tables are appended, separated by a blank
+ * line. With `once`, prints a single table and returns. */
+pub fn run_top(interval: Duration, once: bool) -> Result<()> {
+    TmuxClient::check_installed()?;
+    let weights = TrexConfig::load().health;
+    let interactive = unsafe { libc::isatty(1) } == 1;
+
+    for session in TmuxClient::list_sessions()? {
+        let _ = get_session_stats(&session.name);
+    }
+    std::thread::sleep(PRIME_INTERVAL);
+
+    let mut stdout = std::io::stdout();
+    loop {
+        let mut sessions = TmuxClient::list_sessions()?;
+        for session in &mut sessions {
+            session.stats = get_session_stats(&session.name).ok();
+        }
+
+        let table = format_table(&sessions, &weights);
+        if interactive {
+            write!(stdout, "{}{}", CLEAR_SCREEN, table)?;
+        } else {
+            writeln!(stdout, "{}", table)?;
+        }
+        stdout.flush()?;
+
+        if once {
+            return Ok(());
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+// Renders one refresh: a summary line, then one row per session, busiest first.
+pub fn format_table(sessions: &[TmuxSession], weights: &HealthWeights) -> String {
+    let mut sorted: Vec<&TmuxSession> = sessions.iter().collect();
+    sorted.sort_by(|a, b| cpu_of(b).total_cmp(&cpu_of(a)));
+
+    let name_width = sorted
+        .iter()
+        .map(|session| session.name.chars().count())
+        .max()
+        .unwrap_or_default()
+        .max("SESSION".len());
+
+    let total_cpu: f64 = sessions.iter().map(cpu_of).sum();
+    let total_mem: u64 = sessions
+        .iter()
+        .filter_map(|session| session.stats.as_ref())
+        .map(|stats| stats.mem_mb)
+        .sum();
+
+    let mut out = format!(
+        "trex top: {} sessions, {:.1}% CPU, {} MB\n\n",
+        sessions.len(),
+        total_cpu,
+        total_mem
+    );
+    out.push_str(&format!(
+        "{:<name_width$}  {:>7}  {:>9}  {:>6}  {:<12}  {:>3}  {:>7}  {:>6}\n",
+        "SESSION", "CPU %", "MEM", "MEM %", "HEALTH", "WIN", "CLIENTS", "IDLE"
+    ));
+
+    for session in sorted {
+        let health = HealthScore::calculate_with(session, weights);
+        let (cpu, mem, mem_percent) = match &session.stats {
+            Some(stats) => (
+                format!("{:.1}", stats.cpu_percent),
+                format!("{} MB", stats.mem_mb),
+                format!("{:.1}", stats.mem_percent),
+            ),
+            None => ("-".to_string(), "-".to_string(), "-".to_string()),
+        };
+        out.push_str(&format!(
+            "{:<name_width$}  {:>7}  {:>9}  {:>6}  {:<12}  {:>3}  {:>7}  {:>6}\n",
+            session.name,
+            cpu,
+            mem,
+            mem_percent,
+            format!("{:>3} {}", health.score, health.level().name()),
+            session.windows,
+            session.clients,
+            session
+                .activity_ago_string()
+                .unwrap_or_else(|| "-".to_string()),
+        ));
+    }
+
+    out
+}
+
+fn cpu_of(session: &TmuxSession) -> f64 {
+    session
+        .stats
+        .as_ref()
+        .map(|stats| stats.cpu_percent)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysinfo::SessionStats;
+
+    fn session(name: &str, cpu_percent: f64, mem_mb: u64) -> TmuxSession {
+        TmuxSession {
+            id: String::new(),
+            name: name.to_string(),
+            attached: false,
+            clients: 0,
+            windows: 2,
+            panes: 3,
+            size_constrained: false,
+            path: None,
+            created: None,
+            last_activity: None,
+            tags: Vec::new(),
+            note: None,
+            alerts: Default::default(),
+            git_status: None,
+            stats: Some(SessionStats {
+                cpu_percent,
+                mem_mb,
+                mem_percent: 1.5,
+                ..Default::default()
+            }),
+            cpu_history: Vec::new(),
+            mem_history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn table_lists_busiest_sessions_first() {
+        let sessions = vec![session("idle", 0.5, 100), session("build", 180.0, 2048)];
+        let table = format_table(&sessions, &HealthWeights::default());
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[0], "trex top: 2 sessions, 180.5% CPU, 2148 MB");
+        assert!(lines[2].starts_with("SESSION"));
+        assert!(lines[3].starts_with("build "));
+        assert!(lines[3].contains("180.0"));
+        assert!(lines[3].contains("2048 MB"));
+        assert!(lines[4].starts_with("idle "));
+        assert!(lines[4].contains("healthy"));
+    }
+}
@@ -1,96 +1,293 @@
-use crate::process::{AiProcessInfo, find_ai_processes, process_exists, read_process_state};
+use crate::process::{AiProcessInfo, ProcessScanner, ProcessState, read_process_usage};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
 
 use super::{App, AppMode, SessionAction};
 
+// A running agent scan: sends back the scanner, with its cache, and the agents found.
+pub type AgentScan = Receiver<(ProcessScanner, anyhow::Result<Vec<AiProcessInfo>>)>;
+
+// Clock ticks per second of /proc CPU times (USER_HZ).
+const USER_HZ: f64 = 100.0;
+
+/* CPU use and waiting time of one agent, sampled with its state by
+ * refresh_ai_process_states, for the stats overlay's agent leaderboard.
+ * Waiting time counts from when trex first saw the agent waiting. */
+#[derive(Debug, Clone, Copy)]
+pub struct AgentUsage {
+    pub cpu_percent: f64,
+    pub waiting_since: Option<Instant>,
+    ticks: u64,
+    sampled_at: Instant,
+}
+
+impl AgentUsage {
+    fn new(ticks: u64, now: Instant) -> Self {
+        Self {
+            cpu_percent: 0.0,
+            waiting_since: None,
+            ticks,
+            sampled_at: now,
+        }
+    }
+
+    fn sample(&mut self, state: ProcessState, ticks: u64, now: Instant) {
+        let elapsed = now.duration_since(self.sampled_at).as_secs_f64();
+        if elapsed > 0.0 {
+            let used = ticks.saturating_sub(self.ticks) as f64 / USER_HZ;
+            self.cpu_percent = used / elapsed * 100.0;
+            self.ticks = ticks;
+            self.sampled_at = now;
+        }
+        if state == ProcessState::Waiting {
+            self.waiting_since.get_or_insert(now);
+        } else {
+            self.waiting_since = None;
+        }
+    }
+}
+
 impl App {
-    // Returns the list of visible agents based on current mode.
-    // In ExpandedSession mode or with preview, filters to agents in the selected/expanded session.
+    // Returns the visible agents, as narrowed by sync_visible_agents.
     pub fn visible_agents(&self) -> Vec<&AiProcessInfo> {
+        self.agents.visible().collect()
+    }
+
+    /* Narrows the agent list to the expanded session in ExpandedSession
+     * mode, or to the selected session with preview on; otherwise shows
+     * every agent. Runs before each frame and key, since mode, preview,
+     * and session selection all change what is visible. */
+    pub fn sync_visible_agents(&mut self) {
         let filter_session = match &self.mode {
-            AppMode::ExpandedSession => self.expanded_session.as_ref(),
-            _ if self.show_preview => self.selected_session().map(|s| &s.name),
+            AppMode::ExpandedSession => self.expanded_session.clone(),
+            _ if self.show_preview => self.selected_session().map(|s| s.name.clone()),
             _ => None,
         };
 
-        match filter_session {
-            Some(session_name) => self
-                .ai_processes
-                .iter()
-                .filter(|p| p.tmux_session.as_ref() == Some(session_name))
-                .collect(),
-            None => self.ai_processes.iter().collect(),
-        }
+        let filtered = self
+            .agents
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, agent)| filter_session.is_none() || agent.tmux_session == filter_session)
+            .map(|(idx, _)| idx)
+            .collect();
+        self.agents.set_filtered(filtered);
     }
 
     // Refreshes the activity state of all known AI processes (fast operation).
     pub fn refresh_ai_process_states(&mut self) {
-        for proc in &mut self.ai_processes {
-            if process_exists(proc.pid) {
-                proc.activity_state = read_process_state(proc.pid);
+        let now = Instant::now();
+        for proc in &mut self.agents.items {
+            if let Some((state, ticks)) = read_process_usage(proc.pid) {
+                self.redraw |= state != proc.activity_state;
+                proc.activity_state = state;
+                self.agent_usage
+                    .entry(proc.pid)
+                    .or_insert_with(|| AgentUsage::new(ticks, now))
+                    .sample(state, ticks, now);
             }
         }
     }
 
-    // Rescans for AI processes (detects new/exited processes).
+    // Agents by CPU use over the last state refresh, busiest first.
+    pub fn agents_by_cpu(&self) -> Vec<(&AiProcessInfo, f64)> {
+        let mut ranked: Vec<_> = self
+            .agents
+            .items
+            .iter()
+            .filter_map(|agent| Some((agent, self.agent_usage.get(&agent.pid)?.cpu_percent)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked
+    }
+
+    // Waiting agents by how long they have been waiting, longest first.
+    pub fn agents_by_waiting(&self, now: Instant) -> Vec<(&AiProcessInfo, Duration)> {
+        let mut ranked: Vec<_> = self
+            .agents
+            .items
+            .iter()
+            .filter_map(|agent| {
+                let since = self.agent_usage.get(&agent.pid)?.waiting_since?;
+                Some((agent, now.saturating_duration_since(since)))
+            })
+            .collect();
+        ranked.sort_by_key(|item| std::cmp::Reverse(item.1));
+        ranked
+    }
+
+    /* Rescans for AI processes (detects new/exited processes) on a
+     * background thread, unless a scan is already running. The result
+     * arrives in poll_agent_scan. */
     pub fn rescan_ai_processes(&mut self) {
-        if let Ok(new_processes) = find_ai_processes() {
-            self.ai_processes = new_processes;
-            // Ensure agent selection is still valid
-            let visible_count = self.visible_agents().len();
-            if self.agent_selected_index >= visible_count && visible_count > 0 {
-                self.agent_selected_index = visible_count - 1;
+        let Some(mut scanner) = self.agent_scanner.take() else {
+            return;
+        };
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _timer = crate::logging::timed(|| "agent scan (/proc)".to_string());
+            let result = scanner.scan();
+            let _ = sender.send((scanner, result));
+        });
+        self.agent_scan = Some(receiver);
+    }
+
+    // Takes a finished background scan; returns true when the agent list was replaced.
+    pub fn poll_agent_scan(&mut self) -> bool {
+        let Some(receiver) = &self.agent_scan else {
+            return false;
+        };
+        let (scanner, result) = match receiver.try_recv() {
+            Ok(finished) => finished,
+            Err(TryRecvError::Empty) => return false,
+            // The scan thread died with the scanner; start over with an empty cache
+            Err(TryRecvError::Disconnected) => {
+                self.agent_scan = None;
+                self.agent_scanner = Some(ProcessScanner::new(
+                    crate::config::TrexConfig::load().agents,
+                ));
+                return false;
+            }
+        };
+        self.agent_scan = None;
+        self.agent_scanner = Some(scanner);
+        match result {
+            Ok(processes) => {
+                self.set_agents(processes);
+                true
+            }
+            Err(error) => {
+                log::warn!("agent scan: {}", error);
+                false
             }
         }
     }
 
+    fn set_agents(&mut self, processes: Vec<AiProcessInfo>) {
+        self.agents.items = processes;
+        // Forget marks on agents that exited
+        let pids: Vec<u32> = self.agents.items.iter().map(|p| p.pid).collect();
+        self.marked_agents.retain(|pid| pids.contains(pid));
+        self.agent_usage.retain(|pid, _| pids.contains(pid));
+        // Keeps the selection within the new list
+        self.sync_visible_agents();
+    }
+
     // Moves agent selection to the next agent.
     pub fn select_agent_next(&mut self) {
-        let len = self.visible_agents().len();
-        if len > 0 {
-            self.agent_selected_index = (self.agent_selected_index + 1).min(len - 1);
-        }
+        self.agents.next();
     }
 
     // Moves agent selection to the previous agent.
     pub fn select_agent_previous(&mut self) {
-        self.agent_selected_index = self.agent_selected_index.saturating_sub(1);
+        self.agents.previous();
     }
 
     // Moves agent selection to the first agent.
     pub fn select_agent_first(&mut self) {
-        self.agent_selected_index = 0;
+        self.agents.first();
     }
 
     // Moves agent selection to the last agent.
     pub fn select_agent_last(&mut self) {
-        let len = self.visible_agents().len();
-        if len > 0 {
-            self.agent_selected_index = len - 1;
-        }
+        self.agents.last();
     }
 
     // Returns the currently selected agent, if any.
     pub fn selected_agent(&self) -> Option<&AiProcessInfo> {
-        self.visible_agents()
-            .get(self.agent_selected_index)
-            .copied()
+        self.agents.selected_item()
     }
 
     // Attaches to the tmux session of the selected agent.
     pub fn attach_selected_agent(&mut self) {
-        if let Some(agent) = self.selected_agent()
-            && let Some(session_name) = &agent.tmux_session
-            // Don't attach to placeholder "(tmux)" session
-            && session_name != "(tmux)"
-        {
-            self.action = Some(SessionAction::Attach(session_name.clone()));
+        if let Some(session_name) = self.selected_agent_session() {
+            self.action = Some(SessionAction::Attach(self.session_ref(&session_name)));
             self.should_quit = true;
         }
     }
 
+    // Attaches read-only to the tmux session of the selected agent.
+    pub fn attach_selected_agent_read_only(&mut self) {
+        if let Some(session_name) = self.selected_agent_session() {
+            self.action = Some(SessionAction::AttachReadOnly(
+                self.session_ref(&session_name),
+            ));
+            self.should_quit = true;
+        }
+    }
+
+    // Returns the tmux session of the selected agent, if it is attachable.
+    fn selected_agent_session(&self) -> Option<String> {
+        self.selected_agent()
+            .and_then(|agent| agent.tmux_session.clone())
+            // Don't attach to placeholder "(tmux)" session
+            .filter(|session_name| session_name != "(tmux)")
+    }
+
     // Checks if we're at the bottom of the agent list (for navigation to sessions).
     pub fn at_bottom_of_agents(&self) -> bool {
-        let len = self.visible_agents().len();
-        len == 0 || self.agent_selected_index >= len.saturating_sub(1)
+        self.agents.at_last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    fn agent(pid: u32, session: &str) -> AiProcessInfo {
+        AiProcessInfo {
+            process_name: "claude".to_string(),
+            project_name: session.to_string(),
+            tmux_session: Some(session.to_string()),
+            tmux_pane: Some(format!("%{}", pid)),
+            activity_state: ProcessState::Unknown,
+            pid,
+            child_ai_names: Vec::new(),
+            container: None,
+        }
+    }
+
+    #[test]
+    fn leaderboard_ranks_agents_by_cpu_and_waiting_time() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(2);
+        let mut app = App::for_test(&MockTmux::default());
+        app.agents
+            .set_items(vec![agent(1, "api"), agent(2, "web"), agent(3, "docs")]);
+
+        // api: 1s of CPU over 2s, waiting since the first sample
+        let mut api = AgentUsage::new(0, start);
+        api.sample(ProcessState::Waiting, 0, start);
+        api.sample(ProcessState::Waiting, 100, later);
+        // web: busy for 2s of 2s, never waiting
+        let mut web = AgentUsage::new(0, start);
+        web.sample(ProcessState::Running, 200, later);
+        // docs: just started waiting
+        let mut docs = AgentUsage::new(0, start);
+        docs.sample(ProcessState::Waiting, 0, later);
+        app.agent_usage.extend([(1, api), (2, web), (3, docs)]);
+
+        let by_cpu: Vec<_> = app
+            .agents_by_cpu()
+            .iter()
+            .map(|(agent, cpu)| (agent.pid, cpu.round() as u32))
+            .collect();
+        assert_eq!(by_cpu, vec![(2, 100), (1, 50), (3, 0)]);
+
+        let by_waiting: Vec<_> = app
+            .agents_by_waiting(later)
+            .iter()
+            .map(|(agent, waited)| (agent.pid, waited.as_secs()))
+            .collect();
+        assert_eq!(by_waiting, vec![(1, 2), (3, 0)]);
+
+        // Leaving Waiting resets the clock
+        app.agent_usage
+            .get_mut(&1)
+            .unwrap()
+            .sample(ProcessState::Running, 100, later);
+        assert_eq!(app.agents_by_waiting(later).len(), 1);
     }
 }
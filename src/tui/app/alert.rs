@@ -0,0 +1,45 @@
+use crate::alert::{evaluate, notify_desktop};
+
+use super::App;
+
+impl App {
+    // Re-evaluates alert rules against the latest stats, notifying on new violations.
+    pub fn update_alerts(&mut self) {
+        if self.alert_rules.is_empty() {
+            return;
+        }
+
+        let mut alerts = Vec::new();
+        for session in &self.sessions.items {
+            if let Some(stats) = &session.stats {
+                alerts.extend(evaluate(&self.alert_rules, &session.name, stats));
+            }
+        }
+
+        if self.notify_alerts {
+            for alert in &alerts {
+                let already_alerting = self
+                    .alerts
+                    .iter()
+                    .any(|prev| prev.session == alert.session && prev.rule == alert.rule);
+                if !already_alerting {
+                    notify_desktop(alert);
+                }
+            }
+        }
+
+        self.alerts = alerts;
+    }
+
+    // Alternates every few ticks so alert indicators flash.
+    pub fn alert_flash_on(&self) -> bool {
+        (self.tick / 5).is_multiple_of(2)
+    }
+
+    // Returns true when the named session currently violates an alert rule.
+    pub fn session_has_alert(&self, session_name: &str) -> bool {
+        self.alerts
+            .iter()
+            .any(|alert| alert.session == session_name)
+    }
+}
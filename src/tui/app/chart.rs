@@ -0,0 +1,225 @@
+use crate::tmux::TmuxSession;
+
+use super::{App, SessionAction, SessionRef};
+
+// Bars drawn per chart in the bar chart view.
+pub const MAX_BARS: usize = 10;
+
+// What a chart in the bar chart view ranks sessions by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartMetric {
+    Cpu,
+    Memory,
+    Panes,
+    Health,
+    Io,
+}
+
+impl ChartMetric {
+    // Every metric, in the order of its toggle key (1-5) and of the charts.
+    pub const ALL: [ChartMetric; 5] = [
+        ChartMetric::Cpu,
+        ChartMetric::Memory,
+        ChartMetric::Panes,
+        ChartMetric::Health,
+        ChartMetric::Io,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ChartMetric::Cpu => "CPU Usage by Session (%)",
+            ChartMetric::Memory => "Memory Usage by Session (MB)",
+            ChartMetric::Panes => "Panes by Session",
+            ChartMetric::Health => "Health Score by Session (worst first)",
+            ChartMetric::Io => "Disk I/O by Session (KB/s)",
+        }
+    }
+
+    // Health ranks its lowest scores first; the rest rank their highest values.
+    pub fn lowest_first(&self) -> bool {
+        *self == ChartMetric::Health
+    }
+}
+
+impl App {
+    /* A session's value for a chart: CPU and memory averaged over the chart
+     * window, the live disk read + write rate, the health score, or the
+     * pane count. None for stats-based metrics before the first sample. */
+    pub fn chart_metric_value(&self, session: &TmuxSession, metric: ChartMetric) -> Option<u64> {
+        match metric {
+            ChartMetric::Cpu => self.chart_values(session).map(|(cpu, _)| cpu),
+            ChartMetric::Memory => self.chart_values(session).map(|(_, mem)| mem),
+            ChartMetric::Panes => Some(session.panes as u64),
+            ChartMetric::Health => Some(self.session_health(session).score as u64),
+            ChartMetric::Io => session
+                .stats
+                .as_ref()
+                .map(|stats| (stats.io_read_bps + stats.io_write_bps) / 1024),
+        }
+    }
+
+    // The bars of one chart, ranked and cut to MAX_BARS.
+    pub fn chart_bars(&self, metric: ChartMetric) -> Vec<(&TmuxSession, u64)> {
+        let mut bars: Vec<_> = self
+            .sessions
+            .items
+            .iter()
+            .filter_map(|s| Some((s, self.chart_metric_value(s, metric)?)))
+            .collect();
+        if metric.lowest_first() {
+            bars.sort_by_key(|bar| bar.1);
+        } else {
+            bars.sort_by_key(|bar| std::cmp::Reverse(bar.1));
+        }
+        bars.truncate(MAX_BARS);
+        bars
+    }
+
+    /* Shows or hides the chart for a metric, keeping charts in ALL order.
+     * The last chart stays, so the view is never empty. */
+    pub fn toggle_chart_metric(&mut self, metric: ChartMetric) {
+        if self.chart_metrics.contains(&metric) {
+            if self.chart_metrics.len() > 1 {
+                self.chart_metrics.retain(|shown| *shown != metric);
+            }
+        } else {
+            self.chart_metrics.push(metric);
+            self.chart_metrics
+                .sort_by_key(|shown| ChartMetric::ALL.iter().position(|m| m == shown));
+        }
+        self.select_bar(0);
+    }
+
+    // The session of the highlighted bar; h/l move along the top chart's ranking.
+    pub fn selected_bar_session(&self) -> Option<&TmuxSession> {
+        let metric = *self.chart_metrics.first()?;
+        self.chart_bars(metric)
+            .get(self.chart_selected)
+            .map(|(session, _)| *session)
+    }
+
+    fn select_bar(&mut self, index: usize) {
+        let len = self
+            .chart_metrics
+            .first()
+            .map_or(0, |metric| self.chart_bars(*metric).len());
+        self.chart_selected = index.min(len.saturating_sub(1));
+    }
+
+    pub fn select_bar_next(&mut self) {
+        self.select_bar(self.chart_selected + 1);
+    }
+
+    pub fn select_bar_previous(&mut self) {
+        self.select_bar(self.chart_selected.saturating_sub(1));
+    }
+
+    // Attaches to the session of the highlighted bar.
+    pub fn attach_selected_bar(&mut self) {
+        if let Some(session) = self.selected_bar_session().map(SessionRef::of) {
+            self.action = Some(SessionAction::Attach(session));
+            self.should_quit = true;
+        }
+    }
+
+    // Kills the session of the highlighted bar after the undo grace period.
+    pub fn kill_selected_bar(&mut self) {
+        if let Some(name) = self.selected_bar_session().map(|s| s.name.clone()) {
+            self.kill_named(&name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysinfo::SessionStats;
+    use crate::tmux::{MockTmux, TmuxBackend};
+    use crate::tui::app::AppMode;
+
+    fn chart_app(mock: &MockTmux) -> App {
+        let mut sessions = mock.list_sessions().unwrap();
+        for (session, (cpu, panes)) in sessions.iter_mut().zip([(10.0, 4), (80.0, 1), (40.0, 2)]) {
+            session.panes = panes;
+            session.stats = Some(SessionStats {
+                cpu_percent: cpu,
+                io_read_bps: 2048,
+                ..Default::default()
+            });
+        }
+        App::for_test_with(mock, sessions, 0)
+    }
+
+    fn names(bars: Vec<(&TmuxSession, u64)>) -> Vec<(String, u64)> {
+        bars.into_iter()
+            .map(|(session, value)| (session.name.clone(), value))
+            .collect()
+    }
+
+    #[test]
+    fn charts_rank_sessions_by_each_metric() {
+        let mock = MockTmux::with_sessions(&["api", "web", "docs"]);
+        let app = chart_app(&mock);
+
+        assert_eq!(
+            names(app.chart_bars(ChartMetric::Cpu)),
+            vec![
+                ("web".to_string(), 80),
+                ("docs".to_string(), 40),
+                ("api".to_string(), 10)
+            ]
+        );
+        assert_eq!(
+            names(app.chart_bars(ChartMetric::Panes))[0],
+            ("api".to_string(), 4)
+        );
+        assert!(
+            app.chart_bars(ChartMetric::Io)
+                .iter()
+                .all(|(_, kbps)| *kbps == 2)
+        );
+        let health = app.chart_bars(ChartMetric::Health);
+        assert!(health.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn charts_toggle_and_bars_select_for_attach() {
+        let mock = MockTmux::with_sessions(&["api", "web", "docs"]);
+        let mut app = chart_app(&mock);
+
+        app.toggle_chart_metric(ChartMetric::Health);
+        app.toggle_chart_metric(ChartMetric::Panes);
+        assert_eq!(
+            app.chart_metrics,
+            vec![
+                ChartMetric::Cpu,
+                ChartMetric::Memory,
+                ChartMetric::Panes,
+                ChartMetric::Health
+            ]
+        );
+        for metric in ChartMetric::ALL {
+            if app.chart_metrics.contains(&metric) {
+                app.toggle_chart_metric(metric);
+            }
+        }
+        // The last chart can't be hidden
+        assert_eq!(app.chart_metrics, vec![ChartMetric::Health]);
+
+        app.toggle_chart_metric(ChartMetric::Cpu);
+        for _ in 0..5 {
+            app.select_bar_next();
+        }
+        assert_eq!(app.selected_bar_session().unwrap().name, "api");
+        app.select_bar_previous();
+        assert_eq!(app.selected_bar_session().unwrap().name, "docs");
+
+        app.mode = AppMode::BarChartView;
+        crate::tui::render_to_buffer(&app, 100, 40).unwrap();
+
+        app.attach_selected_bar();
+        assert!(
+            matches!(&app.action, Some(SessionAction::Attach(session)) if session.name == "docs")
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::path::PathBuf;
+
+use crate::git::{GitStatus, commit_all, stash_all};
+
+use super::{App, AppMode};
+
+// How a session's uncommitted work is checkpointed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointKind {
+    Commit, // `git add -A` then commit with the typed message
+    Stash,  // `git stash push --include-untracked`
+}
+
+// A commit or stash waiting on its message prompt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    pub kind: CheckpointKind,
+    pub session: String,
+    pub path: PathBuf,
+    pub files: u32, // changed files when the prompt opened
+}
+
+impl App {
+    /* Opens the message prompt to commit or stash everything in the
+     * selected session's repo, or from the diff overlay's session while it
+     * is open. The repo's status is read fresh, so nothing is offered for a
+     * repo that is already clean; nothing runs until Enter. */
+    pub fn start_checkpoint(&mut self, kind: CheckpointKind) {
+        let Some(session) = (match self.diff_session.as_deref() {
+            Some(name) => self.sessions.items.iter().find(|s| s.name == name),
+            None => self.selected_session(),
+        }) else {
+            return;
+        };
+        let name = session.name.clone();
+        let Some(path) = session.path.clone() else {
+            self.warn(format!("{} has no working directory", name));
+            return;
+        };
+        let status = GitStatus::for_path(&path);
+        if !status.is_repo {
+            self.warn(format!("{} is not in a git repository", name));
+            return;
+        }
+        if status.dirty_count == 0 {
+            self.info(format!("{} has no uncommitted changes", name));
+            return;
+        }
+        self.checkpoint_input.clear();
+        self.checkpoint = Some(Checkpoint {
+            kind,
+            session: name,
+            path,
+            files: status.dirty_count,
+        });
+        self.mode = AppMode::Checkpointing;
+    }
+
+    /* Runs the commit or stash with the typed message. A commit needs a
+     * message; a stash without one keeps git's default. On success the
+     * session's git badge is refreshed and the diff overlay, now stale,
+     * closes. */
+    pub fn confirm_checkpoint(&mut self) {
+        let Some(checkpoint) = self.checkpoint.clone() else {
+            return self.cancel_checkpoint();
+        };
+        let message = self.checkpoint_input.trim().to_string();
+        let result = match checkpoint.kind {
+            CheckpointKind::Commit if message.is_empty() => return,
+            CheckpointKind::Commit => commit_all(&checkpoint.path, &message).map(|hash| {
+                format!(
+                    "Committed {} file(s) in {} as {}",
+                    checkpoint.files, checkpoint.session, hash
+                )
+            }),
+            CheckpointKind::Stash => stash_all(&checkpoint.path, &message).map(|()| {
+                format!(
+                    "Stashed {} file(s) in {}",
+                    checkpoint.files, checkpoint.session
+                )
+            }),
+        };
+        match result {
+            Ok(done) => {
+                self.info(done);
+                if let Some(session) = self
+                    .sessions
+                    .items
+                    .iter_mut()
+                    .find(|session| session.name == checkpoint.session)
+                {
+                    session.git_status = Some(GitStatus::for_path(&checkpoint.path));
+                }
+                self.checkpoint = None;
+                self.checkpoint_input.clear();
+                self.close_diff();
+            }
+            Err(error) => {
+                self.error(format!("{:#}", error));
+                self.cancel_checkpoint();
+            }
+        }
+    }
+
+    // Drops the prompt, going back to the diff overlay if it was opened there.
+    pub fn cancel_checkpoint(&mut self) {
+        self.checkpoint = None;
+        self.checkpoint_input.clear();
+        self.mode = if self.diff_session.is_some() {
+            AppMode::Diff
+        } else {
+            AppMode::Normal
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::{MockTmux, TmuxBackend};
+    use std::path::Path;
+    use std::process::Command;
+
+    fn git(root: &Path, args: &[&str]) {
+        Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(args)
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn checkpoint_commits_or_stashes_only_a_dirty_repo() {
+        let root = std::env::temp_dir().join(format!("trex-checkpoint-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        git(&root, &["init", "-q"]);
+        git(&root, &["config", "user.name", "trex"]);
+        git(&root, &["config", "user.email", "trex@localhost"]);
+
+        let mock = MockTmux::with_sessions(&["api", "docs"]);
+        let mut sessions = mock.list_sessions().unwrap();
+        sessions[0].path = Some(root.clone());
+        let mut app = App::for_test_with(&mock, sessions, 0);
+
+        // A clean repo has nothing to checkpoint
+        app.start_checkpoint(CheckpointKind::Commit);
+        assert_eq!(app.mode, AppMode::Normal);
+
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+        app.start_checkpoint(CheckpointKind::Commit);
+        assert_eq!(app.mode, AppMode::Checkpointing);
+        crate::tui::render_to_buffer(&app, 100, 40).unwrap();
+        // A commit waits for a message
+        app.confirm_checkpoint();
+        assert_eq!(app.mode, AppMode::Checkpointing);
+        app.checkpoint_input = "checkpoint".to_string();
+        app.confirm_checkpoint();
+        assert_eq!(app.mode, AppMode::Normal);
+        let git_status = app.sessions.items[0].git_status.as_ref().unwrap();
+        assert_eq!(git_status.dirty_count, 0);
+
+        // From the diff overlay, cancelling goes back to it
+        std::fs::write(root.join("notes.md"), "todo\n").unwrap();
+        app.open_diff();
+        app.start_checkpoint(CheckpointKind::Stash);
+        app.cancel_checkpoint();
+        assert_eq!(app.mode, AppMode::Diff);
+        app.start_checkpoint(CheckpointKind::Stash);
+        app.confirm_checkpoint();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(!root.join("notes.md").exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+use crate::ci::{CiStatus, RunState};
+use crate::tmux::TmuxSession;
+
+use super::App;
+
+// A running gh query: sends back the status of every repo it asked about, by path.
+pub type CiFetch = Receiver<HashMap<PathBuf, CiStatus>>;
+
+impl App {
+    /* Queries PR and CI state for every session's repo on a background
+     * thread, one repo per path, unless a query is already running. The
+     * result arrives in poll_ci_fetch; until then the last results stay
+     * on screen. */
+    pub fn refresh_ci_status(&mut self) {
+        if !self.ci_enabled || self.ci_fetch.is_some() {
+            return;
+        }
+        let mut repos: Vec<(PathBuf, String)> = self
+            .sessions
+            .items
+            .iter()
+            .filter_map(|session| {
+                let git = session.git_status.as_ref().filter(|git| git.is_repo)?;
+                Some((session.path.clone()?, git.branch.clone()?))
+            })
+            .collect();
+        repos.sort();
+        repos.dedup_by(|a, b| a.0 == b.0);
+        if repos.is_empty() {
+            return;
+        }
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _timer = crate::logging::timed(|| format!("ci status (gh, {} repos)", repos.len()));
+            let statuses = repos
+                .into_iter()
+                .map(|(path, branch)| {
+                    let status = CiStatus::for_branch(&path, &branch);
+                    (path, status)
+                })
+                .collect();
+            let _ = sender.send(statuses);
+        });
+        self.ci_fetch = Some(receiver);
+    }
+
+    // Takes a finished gh query; returns true when the badges changed.
+    pub fn poll_ci_fetch(&mut self) -> bool {
+        let Some(receiver) = &self.ci_fetch else {
+            return false;
+        };
+        let statuses = match receiver.try_recv() {
+            Ok(statuses) => statuses,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                self.ci_fetch = None;
+                return false;
+            }
+        };
+        self.ci_fetch = None;
+        let changed = statuses != self.ci_status;
+        self.ci_status = statuses;
+        changed
+    }
+
+    // The cached PR/CI state of the session's repo, when there is any.
+    pub fn session_ci(&self, session: &TmuxSession) -> Option<CiStatus> {
+        let status = self.ci_status.get(session.path.as_ref()?)?;
+        (!status.is_empty()).then_some(*status)
+    }
+
+    // The badge color: the latest run's outcome, or the git color without one.
+    pub fn ci_color(&self, status: &CiStatus) -> ratatui::style::Color {
+        match status.run {
+            Some(RunState::Passed) => self.theme.success,
+            Some(RunState::Failed) => self.theme.error,
+            Some(RunState::Pending) => self.theme.warning,
+            Some(RunState::Cancelled) | None => self.theme.secondary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ci::{PrState, PullRequest};
+    use crate::git::GitStatus;
+    use crate::tmux::{MockTmux, TmuxBackend};
+
+    #[test]
+    fn ci_results_are_cached_per_repo_path() {
+        let mock = MockTmux::with_sessions(&["api", "web"]);
+        let mut sessions = mock.list_sessions().unwrap();
+        for session in &mut sessions {
+            session.path = Some(PathBuf::from("/src/api"));
+            session.git_status = Some(GitStatus {
+                is_repo: true,
+                branch: Some("main".to_string()),
+                ..Default::default()
+            });
+        }
+        let mut app = App::for_test_with(&mock, sessions, 0);
+        app.ci_enabled = true;
+
+        let status = CiStatus {
+            pr: Some(PullRequest {
+                number: 42,
+                state: PrState::Open,
+            }),
+            run: Some(RunState::Failed),
+        };
+        let (sender, receiver) = std::sync::mpsc::channel();
+        app.ci_fetch = Some(receiver);
+        // A query in flight isn't started twice
+        app.refresh_ci_status();
+        assert!(!app.poll_ci_fetch());
+        sender
+            .send(HashMap::from([(PathBuf::from("/src/api"), status)]))
+            .unwrap();
+        assert!(app.poll_ci_fetch());
+        assert!(app.ci_fetch.is_none());
+
+        // Both sessions share the repo, so both show its badge
+        for session in &app.sessions.items {
+            assert_eq!(app.session_ci(session), Some(status));
+        }
+        let buffer = crate::tui::render_to_buffer(&app, 120, 30).unwrap();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("#42 ✗"));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::tmux::client::size_mismatch;
+
+use super::{App, AppMode, SessionAction, SessionRef};
+
+impl App {
+    // Opens the client list popup for the selected session.
+    pub fn open_client_list(&mut self) {
+        self.client_list_status = None;
+        self.reload_clients();
+        if self.selected_session().is_some() {
+            self.mode = AppMode::ClientList;
+        }
+    }
+
+    pub fn close_client_list(&mut self) {
+        self.clients.clear();
+        self.client_list_status = None;
+        self.mode = AppMode::Normal;
+    }
+
+    // Sets action to attach to the selected session, detaching all other clients, and quits.
+    pub fn attach_selected_takeover(&mut self) {
+        if let Some(session) = self.selected_session() {
+            self.action = Some(SessionAction::AttachTakeover(SessionRef::of(session)));
+            self.should_quit = true;
+        }
+    }
+
+    // Resizes the selected session's windows to its largest client.
+    pub fn fit_selected_to_largest_client(&mut self) {
+        let Some(name) = self.selected_session().map(|s| s.name.clone()) else {
+            return;
+        };
+        self.client_list_status = Some(match self.tmux.fit_windows_to_largest_client(&name) {
+            Ok(()) => format!("Resized windows in {} to the largest client", name),
+            Err(err) => format!("{:#}", err),
+        });
+        self.reload_clients();
+    }
+
+    // Detaches the smallest client that is constraining the selected session.
+    pub fn detach_smallest_client(&mut self) {
+        let Some(tty) = size_mismatch(&self.clients).map(|m| m.smallest.tty.clone()) else {
+            self.client_list_status = Some("No smaller client to detach".to_string());
+            return;
+        };
+        self.client_list_status = Some(match self.tmux.detach_client(&tty) {
+            Ok(()) => format!("Detached {}", tty),
+            Err(err) => format!("{:#}", err),
+        });
+        self.reload_clients();
+    }
+
+    // Re-reads the selected session's clients and updates its size-mismatch flag.
+    fn reload_clients(&mut self) {
+        let Some(name) = self.selected_session().map(|s| s.name.clone()) else {
+            return;
+        };
+        self.clients = match self.tmux.list_clients(&name) {
+            Ok(clients) => clients,
+            Err(error) => {
+                self.client_list_status = Some(format!("tmux error: {}", error));
+                Vec::new()
+            }
+        };
+        let constrained = size_mismatch(&self.clients).is_some();
+        if let Some(session) = self.sessions.items.iter_mut().find(|s| s.name == name) {
+            session.clients = self.clients.len() as u32;
+            session.attached = !self.clients.is_empty();
+            session.size_constrained = constrained;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::attach_log::{Attach, AttachLog};
+use crate::process::AiProcessInfo;
+use crate::timeline::unix_now;
+use crate::tmux::TmuxSession;
+
+use super::{App, AppMode, SessionAction};
+
+// Most recent attaches listed on the detail page.
+pub const DETAIL_ATTACHES: usize = 8;
+
+impl App {
+    /* Opens the full-screen detail page for the selected session, loading
+     * what the session list doesn't keep: its windows and the attaches
+     * made to it through trex, newest first. */
+    pub fn open_session_detail(&mut self) {
+        let Some(name) = self.selected_session().map(|s| s.name.clone()) else {
+            return;
+        };
+        self.detail_windows = match self.tmux.list_windows(&name) {
+            Ok(windows) => windows,
+            Err(error) => {
+                self.error(format!("tmux error: {}", error));
+                Vec::new()
+            }
+        };
+        let mut attaches: Vec<Attach> = AttachLog::load()
+            .resolved(unix_now())
+            .into_iter()
+            .filter(|attach| attach.session == name)
+            .collect();
+        attaches.reverse();
+        attaches.truncate(DETAIL_ATTACHES);
+        self.detail_attaches = attaches;
+        self.detail_session = Some(name);
+        self.mode = AppMode::SessionDetail;
+    }
+
+    pub fn close_session_detail(&mut self) {
+        self.detail_session = None;
+        self.detail_windows.clear();
+        self.detail_attaches.clear();
+        self.mode = AppMode::Normal;
+    }
+
+    // The session the detail page shows, looked up each frame so stats stay live.
+    pub fn detail_target(&self) -> Option<&TmuxSession> {
+        let name = self.detail_session.as_deref()?;
+        self.sessions
+            .items
+            .iter()
+            .find(|session| session.name == name)
+    }
+
+    // Agents running in the detail page's session.
+    pub fn detail_agents(&self) -> Vec<&AiProcessInfo> {
+        self.agents
+            .items
+            .iter()
+            .filter(|agent| {
+                agent.tmux_session.is_some() && agent.tmux_session == self.detail_session
+            })
+            .collect()
+    }
+
+    pub fn attach_detail_session(&mut self) {
+        if let Some(name) = self.detail_session.clone() {
+            self.action = Some(SessionAction::Attach(self.session_ref(&name)));
+            self.should_quit = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::{MockTmux, TmuxBackend};
+
+    #[test]
+    fn detail_page_follows_its_session() {
+        let mock = MockTmux::with_sessions(&["api", "web"]);
+        let mut app = App::for_test_with(&mock, mock.list_sessions().unwrap(), 1);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        app.open_session_detail();
+        assert_eq!(app.mode, AppMode::SessionDetail);
+        assert_eq!(app.detail_target().unwrap().name, "web");
+        assert!(mock.calls().contains(&"list-windows web".to_string()));
+        app.wm.workspaces.push(crate::wm::WorkspaceRule {
+            session_glob: "w*".to_string(),
+            workspace: "3".to_string(),
+        });
+        let buffer = crate::tui::render_to_buffer(&app, 120, 40).unwrap();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("workspace 3"));
+
+        // A session killed elsewhere closes the page
+        mock.state()
+            .sessions
+            .retain(|session| session.name != "web");
+        app.reload_sessions(&mut matcher);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.detail_session, None);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::git::GitDiff;
+
+use super::{App, AppMode};
+
+impl App {
+    /* Opens the diff overlay on the selected session's repo, starting on
+     * the `git diff --stat` summary. The diff is read once on open, so it
+     * holds still while it is reviewed; reopening it picks up new work. */
+    pub fn open_diff(&mut self) {
+        let Some(session) = self.selected_session() else {
+            return;
+        };
+        let name = session.name.clone();
+        let Some(diff) = session.path.as_deref().and_then(GitDiff::for_path) else {
+            self.warn(format!("{} is not in a git repository", name));
+            return;
+        };
+        if diff.is_empty() {
+            self.info(format!("{} has no uncommitted changes", name));
+            return;
+        }
+        self.diff = diff;
+        self.diff_session = Some(name);
+        self.diff_patch = false;
+        self.diff_scroll = 0;
+        self.mode = AppMode::Diff;
+    }
+
+    pub fn close_diff(&mut self) {
+        self.diff = GitDiff::default();
+        self.diff_session = None;
+        self.mode = AppMode::Normal;
+    }
+
+    // Switches between the stat summary and the full unified diff.
+    pub fn toggle_diff_patch(&mut self) {
+        self.diff_patch = !self.diff_patch;
+        self.diff_scroll = 0;
+    }
+
+    // Lines of the current view: the full diff, or the stat summary.
+    pub fn diff_lines(&self) -> &[String] {
+        if self.diff_patch {
+            &self.diff.patch
+        } else {
+            &self.diff.stat
+        }
+    }
+
+    // Scrolls the current view by `delta` lines, stopping at the ends.
+    pub fn scroll_diff(&mut self, delta: isize) {
+        let last = self.diff_lines().len().saturating_sub(1);
+        self.diff_scroll = self.diff_scroll.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn scroll_diff_to_end(&mut self, end: bool) {
+        self.diff_scroll = if end {
+            self.diff_lines().len().saturating_sub(1)
+        } else {
+            0
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::{MockTmux, TmuxBackend};
+    use std::process::Command;
+
+    #[test]
+    fn diff_overlay_shows_the_stat_then_the_patch() {
+        let root = std::env::temp_dir().join(format!("trex-diff-overlay-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        Command::new("git")
+            .arg("-C")
+            .arg(&root)
+            .args(["init", "-q"])
+            .status()
+            .unwrap();
+
+        let mock = MockTmux::with_sessions(&["api", "docs"]);
+        let mut sessions = mock.list_sessions().unwrap();
+        sessions[0].path = Some(root.clone());
+        let mut app = App::for_test_with(&mock, sessions, 0);
+
+        app.open_diff();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.current_toast().is_some());
+
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+        Command::new("git")
+            .arg("-C")
+            .arg(&root)
+            .args(["add", "main.rs"])
+            .status()
+            .unwrap();
+        app.open_diff();
+        assert_eq!(app.mode, AppMode::Diff);
+        assert_eq!(app.diff_session.as_deref(), Some("api"));
+        assert!(app.diff_lines()[0].contains("main.rs"));
+        crate::tui::render_to_buffer(&app, 100, 40).unwrap();
+
+        app.toggle_diff_patch();
+        assert!(app.diff_lines().contains(&"+fn main() {}".to_string()));
+        app.scroll_diff(100);
+        assert_eq!(app.diff_scroll, app.diff_lines().len() - 1);
+        crate::tui::render_to_buffer(&app, 100, 40).unwrap();
+
+        app.close_diff();
+        assert_eq!(app.mode, AppMode::Normal);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
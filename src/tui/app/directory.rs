@@ -1,83 +1,82 @@
 use super::{App, AppMode};
-use crate::directory::Directory;
+use crate::directory::{Directory, DirectoryPreview, SourceKind, cached_from, discover_from};
+use crate::project::ProjectConfig;
 
 impl App {
     // Moves selection to the next directory (wraps around).
     pub fn select_dir_next(&mut self) {
-        if !self.dir_filtered_indices.is_empty() {
-            self.dir_selected_index =
-                (self.dir_selected_index + 1) % self.dir_filtered_indices.len();
-        }
+        self.directories.next();
     }
 
     // Moves selection to the previous directory (wraps around).
     pub fn select_dir_previous(&mut self) {
-        if !self.dir_filtered_indices.is_empty() {
-            self.dir_selected_index = if self.dir_selected_index == 0 {
-                self.dir_filtered_indices.len() - 1
-            } else {
-                self.dir_selected_index - 1
-            };
-        }
+        self.directories.previous();
     }
 
     // Moves selection to the first directory.
     pub fn select_dir_first(&mut self) {
-        self.dir_selected_index = 0;
+        self.directories.first();
     }
 
     // Moves selection to the last directory.
     pub fn select_dir_last(&mut self) {
-        if !self.dir_filtered_indices.is_empty() {
-            self.dir_selected_index = self.dir_filtered_indices.len() - 1;
-        }
+        self.directories.last();
     }
 
     // Returns the currently selected directory, if any.
     pub fn selected_directory(&self) -> Option<&Directory> {
-        self.dir_filtered_indices
-            .get(self.dir_selected_index)
-            .and_then(|&idx| self.directories.get(idx))
+        self.directories.selected_item()
     }
 
-    // Applies fuzzy filtering to the directory list based on current input.
+    /* Applies fuzzy filtering to the directory list based on current input.
+     * A leading source prefix such as `git:` or `bookmark:` keeps only that
+     * source's directories and fuzzy-matches the rest of the input. */
     pub fn apply_dir_filter(&mut self, matcher: &mut nucleo::Matcher) {
-        if self.dir_filter_input.is_empty() {
-            self.dir_filtered_indices = (0..self.directories.len()).collect();
-        } else {
-            use nucleo::pattern::{CaseMatching, Normalization, Pattern};
+        let (source, query) = match SourceKind::split_prefix(&self.dir_filter_input) {
+            Some((kind, query)) => (Some(kind), query),
+            None => (None, self.dir_filter_input.as_str()),
+        };
+        let from_source = |dir: &Directory| source.is_none_or(|kind| dir.source == Some(kind));
 
-            let pattern = Pattern::parse(
-                &self.dir_filter_input,
-                CaseMatching::Smart,
-                Normalization::Smart,
-            );
-
-            let mut results: Vec<(usize, u32)> = self
+        if source.is_none() && query.is_empty() {
+            self.directories.show_all();
+            self.dir_highlights.clear();
+        } else if query.is_empty() {
+            let filtered = self
                 .directories
+                .items
                 .iter()
                 .enumerate()
-                .filter_map(|(idx, dir)| {
-                    let haystack = dir.match_string();
-                    let mut buf = Vec::new();
-                    let haystack_utf32 = nucleo::Utf32Str::new(&haystack, &mut buf);
-                    pattern
-                        .score(haystack_utf32, matcher)
-                        .map(|score| (idx, score))
-                })
+                .filter(|(_, dir)| from_source(dir))
+                .map(|(idx, _)| idx)
+                .collect();
+            self.dir_highlights.clear();
+            self.directories.set_filtered(filtered);
+        } else {
+            let mut results: Vec<(usize, u32, Vec<u32>)> = self
+                .dir_fuzzy
+                .matches(&self.directories.items, query, matcher)
+                .into_iter()
+                .filter(|(idx, _, _)| from_source(&self.directories.items[*idx]))
                 .collect();
 
             results.sort_by_key(|item| std::cmp::Reverse(item.1));
-            self.dir_filtered_indices = results.into_iter().map(|(idx, _)| idx).collect();
+            let filtered;
+            (filtered, self.dir_highlights) = results
+                .into_iter()
+                .map(|(idx, _, highlights)| (idx, highlights))
+                .unzip();
+            self.directories.set_filtered(filtered);
         }
 
-        self.dir_selected_index = 0;
+        self.directories.first();
     }
 
     // Clears the directory filter and returns to normal mode.
     pub fn clear_dir_filter(&mut self, matcher: &mut nucleo::Matcher) {
         self.dir_filter_input.clear();
         self.apply_dir_filter(matcher);
+        self.launching_agent = false;
         self.mode = AppMode::Normal;
     }
 
@@ -97,16 +96,154 @@ impl App {
         }
     }
 
-    // Refreshes the directory list with the current scan depth.
-    fn refresh_directories(&mut self, matcher: &mut nucleo::Matcher) {
-        self.directories = crate::directory::discover_directories_with_depth(self.dir_scan_depth);
-        self.dir_filtered_indices = (0..self.directories.len()).collect();
-        self.dir_selected_index = 0;
-        if !self.dir_filter_input.is_empty() {
-            self.apply_dir_filter(matcher);
+    /* Switches the list to the current scan depth: the cached scan shows
+     * right away (empty if there is none) while a background rescan
+     * catches up. */
+    pub(super) fn refresh_directories(&mut self, matcher: &mut nucleo::Matcher) {
+        let cached = cached_from(&self.dir_sources, self.dir_scan_depth);
+        self.set_directories(cached, matcher);
+        self.start_directory_scan();
+    }
+
+    /* Runs the enabled directory sources on a background thread, unless a
+     * scan at this depth is already running. The result arrives in
+     * poll_directory_scan. */
+    pub fn start_directory_scan(&mut self) {
+        let depth = self.dir_scan_depth;
+        if self
+            .dir_scan
+            .as_ref()
+            .is_some_and(|(running, _)| *running == depth)
+        {
+            return;
+        }
+        let sources = self.dir_sources.clone();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(discover_from(&sources, depth));
+        });
+        self.dir_scan = Some((depth, receiver));
+    }
+
+    /* Turns a directory source on or off and relists from the sources left
+     * on; a scan still running for the old set is dropped. The last source
+     * can't be turned off. */
+    pub fn toggle_dir_source(&mut self, kind: SourceKind, matcher: &mut nucleo::Matcher) {
+        if let Some(idx) = self.dir_sources.iter().position(|enabled| *enabled == kind) {
+            if self.dir_sources.len() == 1 {
+                self.warn(format!("{} is the only directory source left", kind.name()));
+                return;
+            }
+            self.dir_sources.remove(idx);
+        } else {
+            self.dir_sources.push(kind);
+            self.dir_sources.sort();
+        }
+        self.dir_scan = None;
+        self.refresh_directories(matcher);
+    }
+
+    // Takes a finished background scan, if it is for the depth still shown.
+    pub fn poll_directory_scan(&mut self, matcher: &mut nucleo::Matcher) {
+        let Some((depth, receiver)) = &self.dir_scan else {
+            return;
+        };
+        let depth = *depth;
+        let directories = match receiver.try_recv() {
+            Ok(directories) => directories,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.dir_scan = None;
+                return;
+            }
+        };
+        self.dir_scan = None;
+        if depth == self.dir_scan_depth {
+            self.set_directories(directories, matcher);
+        }
+    }
+
+    // Replaces the directory list, keeping the filter and highlighted directory.
+    fn set_directories(&mut self, directories: Vec<Directory>, matcher: &mut nucleo::Matcher) {
+        let selected = self.selected_directory().map(|dir| dir.path.clone());
+        self.directories.items = crate::directory::with_current_dir(directories);
+        self.dir_lines.clear();
+        self.apply_dir_filter(matcher);
+        if let Some(path) = selected {
+            self.directories.select_where(|dir| dir.path == path);
+        }
+        if self.mode == AppMode::SelectingDirectory {
+            self.refresh_dir_preview();
+        }
+    }
+
+    /* Enter in the picker: names a session in the selected directory (or
+     * picks an agent for it when launching one), or, when the filter
+     * matches nothing, asks to create the typed path. A source-prefixed
+     * filter is a search, never a path. */
+    pub fn choose_directory(&mut self) {
+        if let Some(dir) = self.selected_directory().cloned() {
+            self.continue_in(&dir);
+            return;
+        }
+        if SourceKind::split_prefix(&self.dir_filter_input).is_some() {
+            return;
+        }
+        let Ok(cwd) = std::env::current_dir() else {
+            return;
+        };
+        let home = std::env::var_os("HOME").map(std::path::PathBuf::from);
+        self.dir_create =
+            crate::directory::resolve_new_directory(&self.dir_filter_input, &cwd, home.as_deref());
+    }
+
+    fn continue_in(&mut self, dir: &Directory) {
+        if self.launching_agent {
+            self.choose_agent_in(dir);
+        } else if ProjectConfig::exists(&dir.path) {
+            self.create_from_project(dir);
+        } else {
+            self.name_session_in(dir);
         }
     }
 
+    pub fn cancel_dir_create(&mut self) {
+        self.dir_create = None;
+    }
+
+    /* Creates the confirmed directory, adds it to the picker list, and
+     * moves on to naming the session there. */
+    pub fn confirm_dir_create(&mut self) {
+        let Some(path) = self.dir_create.take() else {
+            return;
+        };
+        match crate::directory::create_directory(&path) {
+            Ok(dir) => {
+                self.info(format!("Created {}", dir.path.display()));
+                self.directories.items.push(dir.clone());
+                self.continue_in(&dir);
+            }
+            Err(error) => self.error(format!("Failed to create {}: {}", path.display(), error)),
+        }
+    }
+
+    // Loads the preview for the highlighted directory when the highlight moves.
+    pub fn refresh_dir_preview(&mut self) {
+        let Some(path) = self.selected_directory().map(|dir| dir.path.clone()) else {
+            self.dir_preview = None;
+            return;
+        };
+        if self
+            .dir_preview
+            .as_ref()
+            .is_some_and(|(previewed, _)| *previewed == path)
+        {
+            return;
+        }
+        let preview = DirectoryPreview::load(&path);
+        self.dir_preview = Some((path, preview));
+    }
+
     // Sets the directory filter input to the selected directory's path.
     pub fn tab_complete_directory(&mut self) {
         if let Some(dir) = self.selected_directory() {
@@ -114,3 +251,121 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::directory::{Directory, SourceKind};
+    use crate::tmux::MockTmux;
+    use crate::tui::app::{App, AppMode};
+    use std::path::PathBuf;
+
+    #[test]
+    fn unmatched_filter_offers_to_create_the_directory() {
+        let root = std::env::temp_dir().join(format!("trex-dir-create-{}", std::process::id()));
+        let target = root.join("new-project");
+        let mut app = App::for_test(&MockTmux::default());
+        app.mode = AppMode::SelectingDirectory;
+        app.directories.set_filtered(Vec::new());
+        app.dir_filter_input = target.display().to_string();
+
+        app.choose_directory();
+        assert_eq!(app.dir_create.as_deref(), Some(target.as_path()));
+        app.cancel_dir_create();
+        assert!(!target.exists());
+
+        app.choose_directory();
+        app.confirm_dir_create();
+        assert!(target.is_dir());
+        assert_eq!(app.mode, AppMode::NamingSession);
+        assert_eq!(app.session_name_input, "new-project");
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn directory_sources_toggle_but_one_stays_on() {
+        let mut app = App::for_test(&MockTmux::default());
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.dir_sources = vec![SourceKind::Zoxide, SourceKind::Tmuxinator];
+
+        app.toggle_dir_source(SourceKind::Bookmarks, &mut matcher);
+        assert_eq!(
+            app.dir_sources,
+            [
+                SourceKind::Bookmarks,
+                SourceKind::Zoxide,
+                SourceKind::Tmuxinator
+            ]
+        );
+        assert!(app.dir_scan.is_some());
+
+        app.toggle_dir_source(SourceKind::Zoxide, &mut matcher);
+        app.toggle_dir_source(SourceKind::Tmuxinator, &mut matcher);
+        app.toggle_dir_source(SourceKind::Bookmarks, &mut matcher);
+        assert_eq!(app.dir_sources, [SourceKind::Bookmarks]);
+        assert!(app.current_toast().is_some());
+    }
+
+    #[test]
+    fn source_prefix_filters_to_one_source() {
+        let mut app = App::for_test(&MockTmux::default());
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        let dir = |path: &str, source| Directory {
+            source: Some(source),
+            ..Directory::new(PathBuf::from(path))
+        };
+        app.dir_sources = vec![SourceKind::Bookmarks, SourceKind::GitRepos];
+        app.directories.items = vec![
+            dir("/src/api", SourceKind::Bookmarks),
+            dir("/src/web", SourceKind::GitRepos),
+            dir("/src/api-docs", SourceKind::GitRepos),
+        ];
+
+        app.dir_filter_input = "git:".to_string();
+        app.apply_dir_filter(&mut matcher);
+        assert_eq!(app.directories.filtered(), [1, 2]);
+        app.dir_filter_input = "git:api".to_string();
+        app.apply_dir_filter(&mut matcher);
+        assert_eq!(app.directories.filtered(), [2]);
+        app.dir_filter_input = "bookmark:web".to_string();
+        app.apply_dir_filter(&mut matcher);
+        assert!(app.directories.is_empty());
+        // A search that finds nothing doesn't offer to create `bookmark:web`
+        app.choose_directory();
+        assert_eq!(app.dir_create, None);
+
+        app.dir_filter_input.clear();
+        app.apply_dir_filter(&mut matcher);
+        app.mode = AppMode::SelectingDirectory;
+        let buffer = crate::tui::render_to_buffer(&app, 120, 30).unwrap();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("[/src/api] bookmark"));
+    }
+
+    #[test]
+    fn large_directory_lists_only_format_the_rows_on_screen() {
+        let mut app = App::for_test(&MockTmux::default());
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.directories.items = (0..20_000)
+            .map(|idx| Directory::new(PathBuf::from(format!("/src/project-{}", idx))))
+            .collect();
+        app.apply_dir_filter(&mut matcher);
+        app.mode = AppMode::SelectingDirectory;
+
+        crate::tui::render_to_buffer(&app, 120, 30).unwrap();
+        let rows = app.dir_lines.builds();
+        assert!(rows > 0 && rows < 30);
+
+        // Moving down reformats the two rows whose highlight changed
+        app.directories.next();
+        crate::tui::render_to_buffer(&app, 120, 30).unwrap();
+        assert_eq!(app.dir_lines.builds(), rows + 2);
+
+        app.dir_filter_input = "project-1999".to_string();
+        app.apply_dir_filter(&mut matcher);
+        crate::tui::render_to_buffer(&app, 120, 30).unwrap();
+        let buffer = crate::tui::render_to_buffer(&app, 120, 30).unwrap();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("[/src/project-1999]"));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::config::EnvRefresh;
+
+use super::App;
+
+// Pane commands that are an interactive shell, where an `export` can be typed.
+const SHELLS: [&str; 6] = ["bash", "zsh", "sh", "dash", "ksh", "fish"];
+
+/* The line typed into a shell pane to set or unset a variable. It starts
+ * with a space so shells ignoring space-prefixed commands (bash with
+ * HISTCONTROL=ignorespace, zsh with HIST_IGNORE_SPACE) keep it out of
+ * history. */
+fn export_command(shell: &str, name: &str, value: Option<&str>) -> String {
+    match (shell, value) {
+        ("fish", Some(value)) => format!(" set -gx {} '{}'", name, value.replace('\'', "\\'")),
+        ("fish", None) => format!(" set -e {}", name),
+        (_, Some(value)) => format!(" export {}='{}'", name, value.replace('\'', "'\\''")),
+        (_, None) => format!(" unset {}", name),
+    }
+}
+
+impl App {
+    /* Copies the `[environment]` variables from trex's own environment (as
+     * `lookup` reads it) into the selected session, the fix for a session
+     * still pointing at the SSH agent socket or X display of an earlier
+     * connection. Variables
+     * trex doesn't have are removed from the session. `shells` variables
+     * are also exported in every pane sitting at a shell prompt; panes
+     * running anything else (editors, agents) are left alone. */
+    pub fn refresh_selected_environment(&mut self, lookup: impl Fn(&str) -> Option<String>) {
+        let Some(name) = self.selected_session().map(|s| s.name.clone()) else {
+            return;
+        };
+        let variables: Vec<(String, EnvRefresh, Option<String>)> = self
+            .environment
+            .variables
+            .iter()
+            .map(|(variable, refresh)| (variable.clone(), *refresh, lookup(variable)))
+            .collect();
+        if variables.is_empty() {
+            self.warn("No variables to refresh: every [environment] entry is off");
+            return;
+        }
+
+        let current = match self.tmux.session_environment(&name) {
+            Ok(current) => current,
+            Err(err) => return self.error(format!("{:#}", err)),
+        };
+        let mut changed = Vec::new();
+        for (variable, _, value) in &variables {
+            if current.get(variable) == value.as_ref() {
+                continue;
+            }
+            if let Err(err) = self.tmux.set_environment(&name, variable, value.as_deref()) {
+                return self.error(format!("{:#}", err));
+            }
+            changed.push(variable.as_str());
+        }
+
+        let exported: Vec<&(String, EnvRefresh, Option<String>)> = variables
+            .iter()
+            .filter(|(_, refresh, _)| *refresh == EnvRefresh::Shells)
+            .collect();
+        let mut shells = 0;
+        if !exported.is_empty() {
+            let panes = match self.tmux.list_panes(&name) {
+                Ok(panes) => panes,
+                Err(err) => return self.error(format!("{:#}", err)),
+            };
+            for (pane, command) in panes {
+                if !SHELLS.contains(&command.as_str()) {
+                    continue;
+                }
+                for (variable, _, value) in &exported {
+                    let line = export_command(&command, variable, value.as_deref());
+                    if let Err(err) = self.tmux.send_to_pane(&pane, &line) {
+                        return self.error(format!("{:#}", err));
+                    }
+                }
+                shells += 1;
+            }
+        }
+
+        let names: Vec<&str> = variables.iter().map(|(name, _, _)| name.as_str()).collect();
+        let mut message = if changed.is_empty() {
+            format!("{} already had the current {}", name, names.join(", "))
+        } else {
+            format!("Updated {} in {}", changed.join(", "), name)
+        };
+        if !exported.is_empty() {
+            message.push_str(&format!(
+                ", exported to {} shell{}",
+                shells,
+                if shells == 1 { "" } else { "s" }
+            ));
+        }
+        self.info(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+    use std::collections::HashMap;
+
+    #[test]
+    fn refreshes_stale_variables_and_shell_panes() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        {
+            let mut state = mock.state();
+            let stale = [("TREX_TEST_SOCK".to_string(), "/tmp/old".to_string())];
+            state
+                .environment
+                .insert("api".to_string(), stale.into_iter().collect());
+            state.pane_commands.insert(
+                "api".to_string(),
+                vec![
+                    ("%1".to_string(), "zsh".to_string()),
+                    ("%2".to_string(), "claude".to_string()),
+                    ("%3".to_string(), "fish".to_string()),
+                ],
+            );
+        }
+        let mut app = App::for_test(&mock);
+        app.environment.variables = vec![
+            ("TREX_TEST_SOCK".to_string(), EnvRefresh::Shells),
+            ("TREX_TEST_UNSET".to_string(), EnvRefresh::Session),
+        ];
+
+        let env = HashMap::from([("TREX_TEST_SOCK", "/tmp/new's")]);
+        app.refresh_selected_environment(|name| env.get(name).map(|value| value.to_string()));
+        let calls = mock.calls();
+        assert!(calls.contains(&"set-environment api TREX_TEST_SOCK /tmp/new's".to_string()));
+        assert!(
+            calls.contains(&"send-keys %1  export TREX_TEST_SOCK='/tmp/new'\\''s'".to_string())
+        );
+        assert!(calls.contains(&"send-keys %3  set -gx TREX_TEST_SOCK '/tmp/new\\'s'".to_string()));
+        assert!(!calls.iter().any(|call| call.starts_with("send-keys %2")));
+        // Already absent from the session, so nothing to unset
+        assert!(!calls.iter().any(|call| call.contains("TREX_TEST_UNSET")));
+        assert_eq!(
+            app.current_toast().unwrap().message,
+            "Updated TREX_TEST_SOCK in api, exported to 2 shells"
+        );
+
+        assert_eq!(export_command("bash", "DISPLAY", None), " unset DISPLAY");
+    }
+}
@@ -0,0 +1,22 @@
+use crate::export::{ExportFormat, StatsExport};
+
+use super::App;
+
+impl App {
+    // Writes current stats and sparkline history to the cache dir for offline analysis.
+    pub fn export_stats(&mut self, format: ExportFormat) {
+        let Some(dir) = crate::logging::cache_dir() else {
+            self.error("Cannot export stats: no cache directory (HOME unset)");
+            return;
+        };
+        let export = StatsExport::from_sessions(
+            &self.sessions.items,
+            &self.health_weights,
+            self.schedule.session_stats.interval(),
+        );
+        match export.write_to_dir(&dir, format) {
+            Ok(path) => self.info(format!("Exported stats to {}", path.display())),
+            Err(error) => self.error(format!("Failed to export stats: {:#}", error)),
+        }
+    }
+}
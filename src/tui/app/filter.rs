@@ -1,37 +1,38 @@
-use super::{App, AppMode};
+use super::fuzzy::FuzzyCache;
+use super::{App, AppMode, PendingKind};
+use crate::process::AiProcessInfo;
+use crate::query::SessionQuery;
+use crate::tmux::TmuxSession;
 
 impl App {
+    /* Filters sessions by the operators in the filter input (`@agent`,
+     * `!attached`, `cpu>100`, `git:dirty`, ...) and fuzzy-matches whatever
+     * text is left. Without fuzzy text the list keeps its normal order. */
     pub fn apply_filter(&mut self, matcher: &mut nucleo::Matcher) {
-        if self.filter_input.is_empty() {
-            self.filtered_indices = (0..self.sessions.len()).collect();
-        } else {
-            use nucleo::pattern::{CaseMatching, Normalization, Pattern};
-
-            let pattern = Pattern::parse(
-                &self.filter_input,
-                CaseMatching::Smart,
-                Normalization::Smart,
-            );
-
-            let mut results: Vec<(usize, u32)> = self
-                .sessions
-                .iter()
-                .enumerate()
-                .filter_map(|(idx, session)| {
-                    let haystack = session.match_string();
-                    let mut buf = Vec::new();
-                    let haystack_utf32 = nucleo::Utf32Str::new(&haystack, &mut buf);
-                    pattern
-                        .score(haystack_utf32, matcher)
-                        .map(|score| (idx, score))
-                })
-                .collect();
-
-            results.sort_by_key(|item| std::cmp::Reverse(item.1));
-            self.filtered_indices = results.into_iter().map(|(idx, _)| idx).collect();
+        let matches = filter_sessions(
+            &self.sessions.items,
+            &self.agents.items,
+            &self.filter_input,
+            matcher,
+            &mut self.session_fuzzy,
+        );
+        let filtered;
+        (filtered, self.filter_highlights) = matches.into_iter().unzip();
+        if SessionQuery::parse(&self.filter_input).fuzzy.is_empty() {
+            self.filter_highlights.clear();
         }
 
-        self.selected_index = 0;
+        self.sessions.set_filtered(filtered);
+        self.sessions.first();
+    }
+
+    /* Batch kill or detach for a tag: when the filter selects a tag
+     * (`#work`), queues the action for every filtered session with the
+     * usual undo grace period. Does nothing for other filters. */
+    pub fn queue_pending_for_tag(&mut self, kind: PendingKind) {
+        if SessionQuery::parse(&self.filter_input).has_tag() {
+            self.queue_pending_filtered(kind);
+        }
     }
 
     /// Clears the session filter and returns to normal mode.
@@ -41,3 +42,92 @@ impl App {
         self.mode = AppMode::Normal;
     }
 }
+
+/* The sessions a filter input selects, as indices into `sessions` with the
+ * matched name chars, best fuzzy match first. Shared by the filter and
+ * `trex <query>`, which attaches straight away to a single match. */
+pub fn filter_sessions(
+    sessions: &[TmuxSession],
+    agents: &[AiProcessInfo],
+    input: &str,
+    matcher: &mut nucleo::Matcher,
+    fuzzy: &mut FuzzyCache,
+) -> Vec<(usize, Vec<u32>)> {
+    let query = SessionQuery::parse(input);
+    if query.fuzzy.is_empty() {
+        return sessions
+            .iter()
+            .enumerate()
+            .filter(|(_, session)| query.matches(session, agents))
+            .map(|(idx, _)| (idx, Vec::new()))
+            .collect();
+    }
+
+    // Operators are checked on every pass, since stats and agents change
+    let mut results: Vec<(usize, u32, Vec<u32>)> = fuzzy
+        .matches(sessions, &query.fuzzy, matcher)
+        .into_iter()
+        .filter(|(idx, _, _)| query.matches(&sessions[*idx], agents))
+        .collect();
+
+    results.sort_by_key(|item| std::cmp::Reverse(item.1));
+    results
+        .into_iter()
+        .map(|(idx, _, highlights)| (idx, highlights))
+        .collect()
+}
+
+/* The one session `trex <query>` should attach to without opening the
+ * TUI: the only session the query matches, or the session named exactly
+ * the query when others match too. */
+pub fn single_match<'a>(
+    sessions: &'a [TmuxSession],
+    query: &str,
+    matcher: &mut nucleo::Matcher,
+) -> Option<&'a TmuxSession> {
+    if let Some(exact) = sessions.iter().find(|session| session.name == query) {
+        return Some(exact);
+    }
+    match filter_sessions(sessions, &[], query, matcher, &mut FuzzyCache::default())[..] {
+        [(idx, _)] => sessions.get(idx),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::single_match;
+    use crate::tmux::{MockTmux, TmuxBackend};
+    use crate::tui::app::App;
+
+    #[test]
+    fn fuzzy_filter_records_matched_name_chars() {
+        let mock = MockTmux::with_sessions(&["backend", "docs"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        app.filter_input = "bkd".to_string();
+        app.apply_filter(&mut matcher);
+        assert_eq!(app.sessions.filtered(), [0]);
+        assert_eq!(app.filter_highlights, vec![vec![0, 3, 6]]);
+
+        app.filter_input = "!attached".to_string();
+        app.apply_filter(&mut matcher);
+        assert!(app.filter_highlights.is_empty());
+    }
+
+    #[test]
+    fn single_match_prefers_an_exact_name() {
+        let mock = MockTmux::with_sessions(&["api", "api-v2", "docs"]);
+        let sessions = mock.list_sessions().unwrap();
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        let name = |query: &str, matcher: &mut nucleo::Matcher| {
+            single_match(&sessions, query, matcher).map(|session| session.name.clone())
+        };
+        assert_eq!(name("dcs", &mut matcher).as_deref(), Some("docs"));
+        assert_eq!(name("api", &mut matcher).as_deref(), Some("api"));
+        assert_eq!(name("ap", &mut matcher), None);
+        assert_eq!(name("zzz", &mut matcher), None);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::process::{AiProcessInfo, ProcessState, read_process_state, signal_process};
+
+use super::{App, AppMode, SessionAction};
+
+impl App {
+    // Marks or unmarks the selected agent for fleet actions.
+    pub fn toggle_agent_mark(&mut self) {
+        let Some(pid) = self.selected_agent().map(|agent| agent.pid) else {
+            return;
+        };
+        if !self.marked_agents.remove(&pid) {
+            self.marked_agents.insert(pid);
+        }
+    }
+
+    // The agents fleet actions apply to: the marked ones, else every visible agent.
+    pub fn fleet_agents(&self) -> Vec<&AiProcessInfo> {
+        let visible = self.visible_agents();
+        if visible
+            .iter()
+            .any(|agent| self.marked_agents.contains(&agent.pid))
+        {
+            visible
+                .into_iter()
+                .filter(|agent| self.marked_agents.contains(&agent.pid))
+                .collect()
+        } else {
+            visible
+        }
+    }
+
+    /* Sends a signal to every fleet agent: SIGSTOP pauses them, SIGCONT
+     * resumes them. Only the agent process itself is signalled. */
+    pub fn signal_fleet(&mut self, signal: i32, verb: &str) {
+        let pids: Vec<u32> = self.fleet_agents().iter().map(|agent| agent.pid).collect();
+        if pids.is_empty() {
+            return;
+        }
+        let failures: Vec<String> = pids
+            .iter()
+            .filter_map(|&pid| signal_process(pid, signal).err())
+            .map(|error| format!("{:#}", error))
+            .collect();
+        for agent in &mut self.agents.items {
+            if pids.contains(&agent.pid) {
+                agent.activity_state = read_process_state(agent.pid);
+            }
+        }
+
+        let done = pids.len() - failures.len();
+        match failures.first() {
+            None => self.info(format!("{} {} agent(s)", verb, done)),
+            Some(error) => self.error(format!(
+                "{} {} of {} agent(s): {}",
+                verb,
+                done,
+                pids.len(),
+                error
+            )),
+        }
+    }
+
+    // Opens the prompt for a message to every fleet agent.
+    pub fn start_agent_message(&mut self) {
+        if self.fleet_agents().is_empty() {
+            return;
+        }
+        self.agent_message_input.clear();
+        self.mode = AppMode::MessagingAgents;
+    }
+
+    /* Types the message into each fleet agent's pane and presses Enter.
+     * Agents whose pane could not be resolved are skipped. */
+    pub fn confirm_agent_message(&mut self) {
+        let message = self.agent_message_input.trim().to_string();
+        let panes: Vec<Option<String>> = self
+            .fleet_agents()
+            .iter()
+            .map(|agent| agent.tmux_pane.clone())
+            .collect();
+        self.cancel_agent_message();
+        if message.is_empty() {
+            return;
+        }
+
+        let mut sent = 0;
+        let mut errors = Vec::new();
+        for pane in panes.iter().flatten() {
+            match self.tmux.send_to_pane(pane, &message) {
+                Ok(()) => sent += 1,
+                Err(error) => errors.push(format!("{:#}", error)),
+            }
+        }
+        let skipped = panes.len() - sent - errors.len();
+        match errors.first() {
+            Some(error) => self.error(format!("Sent to {} agent(s); {}", sent, error)),
+            None if skipped > 0 => self.warn(format!(
+                "Sent to {} agent(s); {} not in a known pane",
+                sent, skipped
+            )),
+            None => self.info(format!("Sent to {} agent(s)", sent)),
+        }
+    }
+
+    pub fn cancel_agent_message(&mut self) {
+        self.agent_message_input.clear();
+        self.mode = AppMode::Normal;
+    }
+
+    /* Attaches to the next waiting agent after the selected one, wrapping
+     * around, so repeated use in hub mode walks every agent needing input. */
+    pub fn attach_next_waiting_agent(&mut self) {
+        let agents = self.visible_agents();
+        let len = agents.len();
+        let next = (1..=len)
+            .map(|offset| (self.agents.selected() + offset) % len)
+            .find(|&idx| {
+                let agent = agents[idx];
+                agent.activity_state == ProcessState::Waiting
+                    && agent
+                        .tmux_session
+                        .as_ref()
+                        .is_some_and(|session| session != "(tmux)")
+            });
+        let Some(idx) = next else {
+            self.info("No agent is waiting");
+            return;
+        };
+        let session = agents[idx].tmux_session.clone().unwrap_or_default();
+        self.agents.select(idx);
+        self.action = Some(SessionAction::Attach(self.session_ref(&session)));
+        self.should_quit = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::process::{AiProcessInfo, ProcessState};
+    use crate::tmux::MockTmux;
+    use crate::tui::app::{App, AppMode, SessionAction};
+
+    fn agent(pid: u32, session: &str, state: ProcessState) -> AiProcessInfo {
+        AiProcessInfo {
+            process_name: "claude".to_string(),
+            project_name: session.to_string(),
+            tmux_session: Some(session.to_string()),
+            tmux_pane: Some(format!("%{}", pid)),
+            activity_state: state,
+            pid,
+            child_ai_names: Vec::new(),
+            container: None,
+        }
+    }
+
+    fn fleet_app(mock: &MockTmux) -> App {
+        let mut app = App::for_test(mock);
+        app.agents.set_items(vec![
+            agent(1, "api", ProcessState::Waiting),
+            agent(2, "web", ProcessState::Running),
+            agent(3, "docs", ProcessState::Waiting),
+        ]);
+        app
+    }
+
+    #[test]
+    fn messages_go_to_marked_agents_or_all() {
+        let mock = MockTmux::default();
+        let mut app = fleet_app(&mock);
+
+        app.agents.select(1);
+        app.toggle_agent_mark();
+        app.start_agent_message();
+        assert_eq!(app.mode, AppMode::MessagingAgents);
+        app.agent_message_input = "rebase on main".to_string();
+        app.confirm_agent_message();
+        assert_eq!(
+            mock.calls(),
+            vec!["list-sessions", "send-keys %2 rebase on main"]
+        );
+
+        app.toggle_agent_mark();
+        assert_eq!(app.fleet_agents().len(), 3);
+    }
+
+    #[test]
+    fn next_waiting_agent_wraps_around() {
+        let mock = MockTmux::default();
+        let mut app = fleet_app(&mock);
+
+        app.agents.select(0);
+        app.attach_next_waiting_agent();
+        assert!(
+            matches!(&app.action, Some(SessionAction::Attach(session)) if session.name == "docs")
+        );
+
+        app.attach_next_waiting_agent();
+        assert!(
+            matches!(&app.action, Some(SessionAction::Attach(session)) if session.name == "api")
+        );
+    }
+}
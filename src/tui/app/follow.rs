@@ -0,0 +1,118 @@
+use super::{App, AppMode};
+
+// Lines of the followed pane captured on each refresh.
+const FOLLOW_LINES: usize = 100;
+
+// The agent pane the preview is pinned to, with what its title shows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FollowTarget {
+    pub pane: String,  // tmux pane id, `%3`
+    pub label: String, // the agent's breadcrumb and session, `claude in api`
+}
+
+impl App {
+    /* Pins the preview to the selected agent's pane, or unpins it when
+     * that agent is already followed. While pinned, the preview shows the
+     * pane whatever session is selected and refreshes on its own cadence,
+     * scrolled to the newest output like `tail -f`. */
+    pub fn toggle_follow_selected_agent(&mut self) {
+        let Some(agent) = self.selected_agent() else {
+            return;
+        };
+        let (Some(pane), Some(session)) = (agent.tmux_pane.clone(), agent.tmux_session.clone())
+        else {
+            self.warn(format!("{} is not running in tmux", agent.breadcrumb()));
+            return;
+        };
+        if self
+            .follow
+            .as_ref()
+            .is_some_and(|follow| follow.pane == pane)
+        {
+            self.stop_following();
+            return;
+        }
+        let label = format!("{} in {}", agent.breadcrumb(), session);
+        self.info(format!("Following {}", label));
+        self.follow = Some(FollowTarget { pane, label });
+        if self.show_preview {
+            self.refresh_preview();
+        } else {
+            self.toggle_preview();
+        }
+    }
+
+    // Unpins the preview, going back to the selected session's pane.
+    pub fn stop_following(&mut self) {
+        if let Some(follow) = self.follow.take() {
+            self.info(format!("Stopped following {}", follow.label));
+            self.refresh_preview();
+        }
+    }
+
+    // Whether the followed pane is due live refreshes (copy mode freezes it).
+    pub fn following(&self) -> bool {
+        self.follow.is_some() && self.mode != AppMode::PreviewCopy
+    }
+
+    /* Captures the followed pane into the preview. A pane that is gone
+     * (its agent exited and the pane closed) ends follow mode. */
+    pub(super) fn refresh_follow(&mut self) {
+        let Some(follow) = self.follow.clone() else {
+            return;
+        };
+        match self.tmux.capture_pane_id(&follow.pane, FOLLOW_LINES) {
+            Ok(lines) => self.preview_lines = lines,
+            Err(error) => {
+                self.follow = None;
+                self.warn(format!("Stopped following {}: {}", follow.label, error));
+                self.refresh_preview();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::{AiProcessInfo, ProcessState};
+    use crate::tmux::MockTmux;
+
+    #[test]
+    fn follow_pins_the_preview_to_the_agent_pane() {
+        let mock = MockTmux::with_sessions(&["api", "web"]);
+        mock.state()
+            .panes
+            .insert("%7".to_string(), vec!["thinking...".to_string()]);
+        let mut app = App::for_test(&mock);
+        app.agents.set_items(vec![AiProcessInfo {
+            process_name: "claude".to_string(),
+            project_name: "web".to_string(),
+            tmux_session: Some("web".to_string()),
+            tmux_pane: Some("%7".to_string()),
+            activity_state: ProcessState::Running,
+            pid: 7,
+            child_ai_names: Vec::new(),
+            container: None,
+        }]);
+
+        app.toggle_follow_selected_agent();
+        assert!(app.show_preview);
+        assert_eq!(app.follow.as_ref().unwrap().label, "claude in web");
+        assert_eq!(app.preview_lines, ["thinking..."]);
+
+        // Navigating the sessions keeps the preview on the agent
+        app.select_next();
+        mock.state()
+            .panes
+            .insert("%7".to_string(), vec!["done".to_string()]);
+        app.refresh_preview();
+        assert_eq!(app.preview_lines, ["done"]);
+        crate::tui::render_to_buffer(&app, 100, 30).unwrap();
+
+        // A closed pane ends follow mode
+        mock.state().panes.remove("%7");
+        app.refresh_preview();
+        assert_eq!(app.follow, None);
+    }
+}
@@ -0,0 +1,184 @@
+use nucleo::Utf32String;
+use nucleo::pattern::{CaseMatching, Normalization, Pattern};
+
+use super::line_key;
+use crate::directory::Directory;
+use crate::tmux::TmuxSession;
+
+// An item of a fuzzy-filtered list: what the filter matches against.
+pub trait FuzzyItem {
+    // Hash of the fields the haystack is built from
+    fn haystack_key(&self) -> u64;
+    // Text the filter matches; starts with the name lists highlight
+    fn haystack(&self) -> String;
+    fn name_chars(&self) -> u32;
+}
+
+impl FuzzyItem for TmuxSession {
+    fn haystack_key(&self) -> u64 {
+        line_key((&self.name, &self.path, &self.note))
+    }
+
+    fn haystack(&self) -> String {
+        self.match_string()
+    }
+
+    fn name_chars(&self) -> u32 {
+        self.name.chars().count() as u32
+    }
+}
+
+impl FuzzyItem for Directory {
+    fn haystack_key(&self) -> u64 {
+        line_key(&self.path)
+    }
+
+    fn haystack(&self) -> String {
+        self.match_string()
+    }
+
+    fn name_chars(&self) -> u32 {
+        self.display_name().chars().count() as u32
+    }
+}
+
+/* Haystacks of a fuzzy-filtered list kept between filter passes, so a
+ * keystroke doesn't rebuild every item's match string and its chars. Each
+ * is rebuilt when its item's key changes. While no item changed, a query
+ * that appends to the last one (the usual keystroke) only scores what the
+ * last query matched, since it can only match fewer. */
+#[derive(Debug, Default)]
+pub struct FuzzyCache {
+    haystacks: Vec<Haystack>,
+    query: String,       // fuzzy text of the last pass
+    matched: Vec<usize>, // items the last pass matched, in list order
+}
+
+#[derive(Debug)]
+struct Haystack {
+    key: u64,
+    text: Utf32String,
+    name_chars: u32,
+}
+
+impl Haystack {
+    fn new(item: &impl FuzzyItem, key: u64) -> Self {
+        Self {
+            key,
+            text: Utf32String::from(item.haystack()),
+            name_chars: item.name_chars(),
+        }
+    }
+}
+
+impl FuzzyCache {
+    /* Scores every item against `fuzzy`. Returns the matching items as
+     * (index, score, matched char positions inside the name), in list
+     * order. */
+    pub fn matches<T: FuzzyItem>(
+        &mut self,
+        items: &[T],
+        fuzzy: &str,
+        matcher: &mut nucleo::Matcher,
+    ) -> Vec<(usize, u32, Vec<u32>)> {
+        let mut changed = self.haystacks.len() != items.len();
+        self.haystacks.truncate(items.len());
+        for (idx, item) in items.iter().enumerate() {
+            let key = item.haystack_key();
+            match self.haystacks.get_mut(idx) {
+                Some(haystack) if haystack.key == key => {}
+                Some(haystack) => {
+                    *haystack = Haystack::new(item, key);
+                    changed = true;
+                }
+                None => self.haystacks.push(Haystack::new(item, key)),
+            }
+        }
+
+        let candidates = if !changed && narrows(&self.query, fuzzy) {
+            std::mem::take(&mut self.matched)
+        } else {
+            (0..items.len()).collect()
+        };
+        let pattern = Pattern::parse(fuzzy, CaseMatching::Smart, Normalization::Smart);
+        let mut indices = Vec::new();
+        let results: Vec<(usize, u32, Vec<u32>)> = candidates
+            .into_iter()
+            .filter_map(|idx| {
+                let haystack = &self.haystacks[idx];
+                indices.clear();
+                let score = pattern.indices(haystack.text.slice(..), matcher, &mut indices)?;
+                indices.sort_unstable();
+                indices.dedup();
+                let highlights = indices
+                    .iter()
+                    .copied()
+                    .filter(|&index| index < haystack.name_chars)
+                    .collect();
+                Some((idx, score, highlights))
+            })
+            .collect();
+
+        self.matched = results.iter().map(|(idx, _, _)| *idx).collect();
+        self.query.clear();
+        self.query.push_str(fuzzy);
+        results
+    }
+}
+
+/* Whether everything `new` matches was matched by `old`: `new` appends to
+ * `old`, and appending doesn't change what old's last atom means, as it
+ * would for a negation (`!foo`), an end anchor (`foo$`), or an escape. */
+fn narrows(old: &str, new: &str) -> bool {
+    !old.is_empty()
+        && new.starts_with(old)
+        && !old.ends_with(['$', '\\'])
+        && Pattern::parse(old, CaseMatching::Smart, Normalization::Smart)
+            .atoms
+            .last()
+            .is_none_or(|atom| !atom.negative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::{MockTmux, TmuxBackend};
+
+    #[test]
+    fn typing_narrows_until_the_items_change() {
+        assert!(narrows("ap", "api"));
+        assert!(narrows("api", "api web"));
+        assert!(!narrows("", "a"));
+        assert!(!narrows("api", "ap"));
+        assert!(!narrows("!ap", "!api"));
+        assert!(!narrows("api$", "api$x"));
+
+        let mut sessions = MockTmux::with_sessions(&["api", "apps", "docs"])
+            .list_sessions()
+            .unwrap();
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        let mut cache = FuzzyCache::default();
+        let names = |results: Vec<(usize, u32, Vec<u32>)>, sessions: &[TmuxSession]| {
+            results
+                .into_iter()
+                .map(|(idx, _, _)| sessions[idx].name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let results = cache.matches(&sessions, "ap", &mut matcher);
+        assert_eq!(results[0].2, [0, 1]);
+        assert_eq!(names(results, &sessions), ["api", "apps"]);
+        assert_eq!(cache.matched, [0, 1]);
+        assert_eq!(
+            names(cache.matches(&sessions, "aps", &mut matcher), &sessions),
+            ["apps"]
+        );
+
+        // A note that now matches rescans the list despite the appended text
+        sessions[2].note = Some("aps2".to_string());
+        assert_eq!(
+            names(cache.matches(&sessions, "aps2", &mut matcher), &sessions),
+            ["docs"]
+        );
+    }
+}
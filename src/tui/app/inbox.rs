@@ -0,0 +1,144 @@
+use crate::health::HealthLevel;
+use crate::process::ProcessState;
+
+use super::App;
+
+// Why a session is in the needs-attention inbox, most urgent first.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttentionReason {
+    Waiting(Vec<String>), // agents waiting for input, by breadcrumb
+    Critical(u8),         // health score
+    Alert(String),        // the violated alert, as `cpu 312.0% (* cpu>250%)`
+}
+
+// A session that needs attention, with every reason it does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InboxEntry {
+    pub session: String,
+    pub reasons: Vec<AttentionReason>,
+}
+
+impl App {
+    /* Sessions with an agent waiting for input, critical health, or an
+     * alert violation, taken from every session so the sort order and the
+     * filter never hide them. Sessions with a waiting agent come first. */
+    pub fn inbox(&self) -> Vec<InboxEntry> {
+        let mut entries: Vec<InboxEntry> = self
+            .sessions
+            .items
+            .iter()
+            .filter_map(|session| {
+                let mut reasons = Vec::new();
+                let waiting: Vec<String> = self
+                    .agents
+                    .items
+                    .iter()
+                    .filter(|agent| {
+                        agent.activity_state == ProcessState::Waiting
+                            && agent.tmux_session.as_deref() == Some(session.name.as_str())
+                    })
+                    .map(|agent| agent.breadcrumb())
+                    .collect();
+                if !waiting.is_empty() {
+                    reasons.push(AttentionReason::Waiting(waiting));
+                }
+                let health = self.session_health(session);
+                if session.stats.is_some() && health.level() == HealthLevel::Critical {
+                    reasons.push(AttentionReason::Critical(health.score));
+                }
+                reasons.extend(
+                    self.alerts
+                        .iter()
+                        .filter(|alert| alert.session == session.name)
+                        .map(|alert| {
+                            let message = alert.message();
+                            let detail = message
+                                .strip_prefix(&format!("{}: ", session.name))
+                                .unwrap_or(&message);
+                            AttentionReason::Alert(detail.to_string())
+                        }),
+                );
+                (!reasons.is_empty()).then(|| InboxEntry {
+                    session: session.name.clone(),
+                    reasons,
+                })
+            })
+            .collect();
+        entries.sort_by_key(|entry| {
+            !matches!(entry.reasons.first(), Some(AttentionReason::Waiting(_)))
+        });
+        entries
+    }
+
+    /* Moves the session selection to the next inbox session after the
+     * selected one, clearing the filter first if it hides that session. */
+    pub fn select_next_inbox(&mut self, matcher: &mut nucleo::Matcher) {
+        let entries = self.inbox();
+        if entries.is_empty() {
+            self.info("Nothing needs attention");
+            return;
+        }
+        let current = self.selected_session().map(|session| session.name.clone());
+        let next = entries
+            .iter()
+            .position(|entry| Some(&entry.session) == current.as_ref())
+            .map_or(0, |idx| (idx + 1) % entries.len());
+        let name = entries[next].session.clone();
+
+        if !self.sessions.select_where(|session| session.name == name) {
+            self.filter_input.clear();
+            self.apply_filter(matcher);
+            self.sessions.select_where(|session| session.name == name);
+        }
+        self.refresh_preview();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::AiProcessInfo;
+    use crate::tmux::{MockTmux, TmuxBackend};
+
+    #[test]
+    fn inbox_lists_waiting_agents_first_and_ignores_the_filter() {
+        let mock = MockTmux::with_sessions(&["api", "docs", "web"]);
+        let mut sessions = mock.list_sessions().unwrap();
+        sessions[0].stats = Some(crate::sysinfo::SessionStats {
+            cpu_percent: 400.0,
+            mem_mb: 16384,
+            ..Default::default()
+        });
+        sessions[0].last_activity = Some(0);
+        let mut app = App::for_test_with(&mock, sessions, 0);
+        app.agents.set_items(vec![AiProcessInfo {
+            process_name: "claude".to_string(),
+            project_name: "web".to_string(),
+            tmux_session: Some("web".to_string()),
+            tmux_pane: Some("%3".to_string()),
+            activity_state: ProcessState::Waiting,
+            pid: 7,
+            child_ai_names: Vec::new(),
+            container: None,
+        }]);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.filter_input = "docs".to_string();
+        app.apply_filter(&mut matcher);
+
+        let inbox = app.inbox();
+        let names: Vec<&str> = inbox.iter().map(|entry| entry.session.as_str()).collect();
+        assert_eq!(names, ["web", "api"]);
+        assert_eq!(
+            inbox[0].reasons,
+            [AttentionReason::Waiting(vec!["claude".to_string()])]
+        );
+        assert!(matches!(inbox[1].reasons[0], AttentionReason::Critical(_)));
+
+        app.select_next_inbox(&mut matcher);
+        assert_eq!(app.selected_session().unwrap().name, "web");
+        assert!(app.filter_input.is_empty());
+        app.select_next_inbox(&mut matcher);
+        assert_eq!(app.selected_session().unwrap().name, "api");
+        crate::tui::render_to_buffer(&app, 100, 40).unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+use super::{App, AppMode, SessionAction};
+use crate::directory::Directory;
+use crate::project::ProjectConfig;
+
+impl App {
+    // `a`: picks a directory, then an agent to launch in a new session there.
+    pub fn open_agent_launcher(&mut self, matcher: &mut nucleo::Matcher) {
+        self.open_directory_selection(matcher);
+        self.launching_agent = true;
+    }
+
+    // The agents offered by the launcher, from the configured AI list.
+    pub fn agent_choices(&self) -> &'static [&'static str] {
+        crate::process::AI_PROCESSES
+    }
+
+    // Highlights the first installed agent for the chosen directory.
+    pub(super) fn choose_agent_in(&mut self, dir: &Directory) {
+        self.agent_launch_dir = Some(dir.clone());
+        self.agent_choice_index = self
+            .agent_choices()
+            .iter()
+            .position(|agent| which::which(agent).is_ok())
+            .unwrap_or(0);
+        self.detect_dev_env(dir);
+        match ProjectConfig::load(&dir.path) {
+            Ok(Some(ProjectConfig {
+                agent: Some(agent),
+                name,
+                ..
+            })) => return self.launch_project_agent(dir, &agent, name),
+            Ok(_) => {}
+            Err(error) => self.warn(format!("Ignored {}", error)),
+        }
+        self.mode = AppMode::SelectingAgent;
+    }
+
+    /* Launches the agent command a `.trex.toml` names, skipping the agent
+     * list, in a new session under the config's name or a free variant. */
+    fn launch_project_agent(&mut self, dir: &Directory, agent: &str, name: Option<String>) {
+        let program = agent.split_whitespace().next().unwrap_or_default();
+        if which::which(program).is_err() {
+            self.agent_launch_dir = None;
+            self.error(format!("{} not found in PATH", program));
+            return;
+        }
+        let name = name.map_or_else(
+            || dir.session_name(),
+            |name| crate::directory::sanitize_session_name(&name),
+        );
+        let taken: Vec<&str> = self
+            .sessions
+            .items
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        let name = crate::directory::suggest_session_name(&name, &dir.path, &taken);
+        self.agent_launch_dir = None;
+        self.action = Some(SessionAction::LaunchAgent {
+            name,
+            path: dir.path.clone(),
+            agent: self.wrap_in_dev_env(agent.to_string()),
+            editor: self
+                .agent_launch_editor
+                .then(|| self.wrap_in_dev_env(editor_command())),
+        });
+        self.should_quit = true;
+    }
+
+    // The command run inside the directory's dev environment when that is on.
+    fn wrap_in_dev_env(&self, command: String) -> String {
+        match self.active_dev_env() {
+            Some(env) => env.wrap(Some(&command)),
+            None => command,
+        }
+    }
+
+    pub fn select_launch_agent_next(&mut self) {
+        let len = self.agent_choices().len();
+        self.agent_choice_index = (self.agent_choice_index + 1).min(len.saturating_sub(1));
+    }
+
+    pub fn select_launch_agent_previous(&mut self) {
+        self.agent_choice_index = self.agent_choice_index.saturating_sub(1);
+    }
+
+    pub fn toggle_agent_editor(&mut self) {
+        self.agent_launch_editor = !self.agent_launch_editor;
+    }
+
+    // Back to the directory picker, still launching an agent.
+    pub fn cancel_agent_choice(&mut self) {
+        self.agent_launch_dir = None;
+        self.mode = AppMode::SelectingDirectory;
+    }
+
+    /* The session the launch will create: the project's name, or a free
+     * variant of it, since the launcher always starts a new session. */
+    pub fn agent_session_name(&self) -> Option<String> {
+        let dir = self.agent_launch_dir.as_ref()?;
+        let taken: Vec<&str> = self
+            .sessions
+            .items
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        Some(crate::directory::suggest_session_name(
+            &dir.session_name(),
+            &dir.path,
+            &taken,
+        ))
+    }
+
+    /* Launches the highlighted agent in the first window of a new session, with the
+     * editor in a second window when toggled on. Refuses agents not in PATH. */
+    pub fn confirm_agent_launch(&mut self) {
+        let Some(agent) = self.agent_choices().get(self.agent_choice_index) else {
+            return;
+        };
+        if which::which(agent).is_err() {
+            self.error(format!("{} not found in PATH", agent));
+            return;
+        }
+        let (Some(name), Some(dir)) = (self.agent_session_name(), self.agent_launch_dir.take())
+        else {
+            return;
+        };
+        // Both the agent and the editor start inside the project's dev environment
+        self.action = Some(SessionAction::LaunchAgent {
+            name,
+            path: dir.path,
+            agent: self.wrap_in_dev_env(agent.to_string()),
+            editor: self
+                .agent_launch_editor
+                .then(|| self.wrap_in_dev_env(editor_command())),
+        });
+        self.should_quit = true;
+    }
+}
+
+// The user's editor: $VISUAL, then $EDITOR, else vi.
+pub fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::directory::Directory;
+    use crate::tmux::MockTmux;
+    use crate::tui::app::{App, AppMode};
+    use std::path::PathBuf;
+
+    #[test]
+    fn launcher_names_the_session_after_the_project() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        app.open_agent_launcher(&mut matcher);
+        assert!(app.launching_agent);
+        app.directories
+            .set_items(vec![Directory::new(PathBuf::from("/work/api"))]);
+        app.apply_dir_filter(&mut matcher);
+        app.choose_directory();
+        assert_eq!(app.mode, AppMode::SelectingAgent);
+        assert_eq!(app.agent_session_name().as_deref(), Some("work-api"));
+
+        app.cancel_agent_choice();
+        assert_eq!(app.mode, AppMode::SelectingDirectory);
+        app.clear_dir_filter(&mut matcher);
+        assert!(!app.launching_agent);
+    }
+}
@@ -0,0 +1,87 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use ratatui::text::Line;
+
+/* Lines a renderer formats once and draws again on later frames while what
+ * they show is unchanged, so an idle screen redrawn at 10 FPS doesn't
+ * rebuild the same strings and spans every frame. Each entry (the agent
+ * box, one per session header) is stored with a key hashed from
+ * everything its lines are built from; a frame with a different key
+ * rebuilds it. The theme is fixed for the run, so keys leave it out.
+ * Rendering borrows App immutably, hence the RefCell. */
+#[derive(Debug, Default)]
+pub struct LineCache {
+    entries: RefCell<HashMap<String, (u64, Vec<Line<'static>>)>>,
+    builds: Cell<u64>, // entries built since startup
+}
+
+impl LineCache {
+    /* Calls `draw` with the lines cached under `id`, building them first
+     * when there are none yet or they were built for another key. */
+    pub fn with_lines<R>(
+        &self,
+        id: &str,
+        key: u64,
+        build: impl FnOnce() -> Vec<Line<'static>>,
+        draw: impl FnOnce(&[Line<'static>]) -> R,
+    ) -> R {
+        let mut entries = self.entries.borrow_mut();
+        if entries
+            .get(id)
+            .is_none_or(|(cached_key, _)| *cached_key != key)
+        {
+            self.builds.set(self.builds.get() + 1);
+            let lines = build();
+            match entries.get_mut(id) {
+                Some(entry) => *entry = (key, lines),
+                None => {
+                    entries.insert(id.to_string(), (key, lines));
+                }
+            }
+        }
+        draw(&entries[id].1)
+    }
+
+    // Drops every entry, e.g. after a reload so killed sessions' headers go.
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    pub fn builds(&self) -> u64 {
+        self.builds.get()
+    }
+}
+
+// Hashes the inputs of a cached entry into its key.
+pub fn line_key(inputs: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    inputs.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tmux::MockTmux;
+    use crate::tui::app::App;
+
+    #[test]
+    fn idle_frames_reuse_the_formatted_lines() {
+        let mock = MockTmux::with_sessions(&["api", "docs"]);
+        let mut app = App::for_test(&mock);
+
+        crate::tui::render_to_buffer(&app, 120, 40).unwrap();
+        let built = app.line_cache.builds();
+        assert!(built >= 2, "each session header is built once");
+        crate::tui::render_to_buffer(&app, 120, 40).unwrap();
+        assert_eq!(app.line_cache.builds(), built);
+
+        // A changed session rebuilds only its own header
+        app.sessions.items[1].note = Some("release".to_string());
+        let buffer = crate::tui::render_to_buffer(&app, 120, 40).unwrap();
+        assert_eq!(app.line_cache.builds(), built + 1);
+        let text: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("— release"));
+    }
+}
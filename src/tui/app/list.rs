@@ -0,0 +1,335 @@
+use std::cell::Cell;
+use std::ops::Range;
+
+use super::{App, AppMode, FocusArea};
+
+/* Scroll window of a rendered list: the first visible row and how many
+ * rows fit. The renderer records the window each frame (hence the Cells,
+ * since rendering borrows App immutably), so paging moves by what is
+ * actually on screen. */
+#[derive(Debug, Default)]
+pub struct ListState {
+    offset: Cell<usize>,
+    rows: Cell<usize>,
+}
+
+impl ListState {
+    /* Returns the indices to draw in `rows` rows. The window only scrolls
+     * when the selection would leave it, so moving back up doesn't jump. */
+    pub fn window(&self, selected: usize, len: usize, rows: usize) -> Range<usize> {
+        let mut offset = self.offset.get().min(len.saturating_sub(rows));
+        if selected < offset {
+            offset = selected;
+        } else if selected >= offset + rows {
+            offset = selected + 1 - rows;
+        }
+        self.offset.set(offset);
+        self.rows.set(rows);
+        offset..(offset + rows).min(len)
+    }
+
+    // Rows shown at the last draw; at least one so paging always moves.
+    pub fn page(&self) -> usize {
+        self.rows.get().max(1)
+    }
+}
+
+/// A paging key: Ctrl-d/Ctrl-u move half a page, PgDn/PgUp a full page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageStep {
+    HalfDown,
+    HalfUp,
+    Down,
+    Up,
+}
+
+impl PageStep {
+    /* The selection after paging a list of `len` items showing `page`
+     * rows. Unlike j/k, paging stops at the ends instead of wrapping, so
+     * holding the key can't cycle through the list. */
+    pub fn apply(self, selected: usize, len: usize, page: usize) -> usize {
+        let half = (page / 2).max(1);
+        let target = match self {
+            PageStep::HalfDown => selected + half,
+            PageStep::HalfUp => selected.saturating_sub(half),
+            PageStep::Down => selected + page,
+            PageStep::Up => selected.saturating_sub(page),
+        };
+        target.min(len.saturating_sub(1))
+    }
+}
+
+/* Items shown as a filtered, selectable, scrollable list: the sessions,
+ * directories, expanded windows, and agents all use this, so selection
+ * rules (wrapping, clamping after a filter or reload, paging) live in
+ * one place.
+ *
+ * `filtered` holds indices into `items` in display order, and the
+ * selection is a position in `filtered`; both stay private so the
+ * selection can't point past the visible items. */
+#[derive(Debug)]
+pub struct SelectableList<T> {
+    pub items: Vec<T>,
+    filtered: Vec<usize>,
+    selected: usize,
+    wrap: bool, // whether next/previous wrap around at the ends
+    pub view: ListState,
+}
+
+impl<T> Default for SelectableList<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> SelectableList<T> {
+    // A wrapping list showing every item, with the first selected.
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            filtered: (0..items.len()).collect(),
+            items,
+            selected: 0,
+            wrap: true,
+            view: ListState::default(),
+        }
+    }
+
+    // Makes next/previous stop at the ends instead of wrapping.
+    pub fn without_wrap(mut self) -> Self {
+        self.wrap = false;
+        self
+    }
+
+    // Replaces the items and shows all of them, keeping the position if it still fits.
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        self.show_all();
+    }
+
+    pub fn show_all(&mut self) {
+        self.set_filtered((0..self.items.len()).collect());
+    }
+
+    // Shows only `filtered` (indices into items, in display order).
+    pub fn set_filtered(&mut self, filtered: Vec<usize>) {
+        self.filtered = filtered;
+        self.select(self.selected);
+    }
+
+    // Indices into items of the visible entries, in display order.
+    pub fn filtered(&self) -> &[usize] {
+        &self.filtered
+    }
+
+    // Number of visible entries.
+    pub fn len(&self) -> usize {
+        self.filtered.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filtered.is_empty()
+    }
+
+    // The visible entries, in display order.
+    pub fn visible(&self) -> impl Iterator<Item = &T> {
+        self.filtered.iter().filter_map(|&idx| self.items.get(idx))
+    }
+
+    /* The visible entries at the display positions in `range`, with their
+     * positions. Slices the filter, so drawing a window of a long list
+     * only touches the rows drawn. */
+    pub fn visible_range(&self, range: Range<usize>) -> impl Iterator<Item = (usize, &T)> {
+        let start = range.start.min(self.filtered.len());
+        let end = range.end.clamp(start, self.filtered.len());
+        self.filtered[start..end]
+            .iter()
+            .zip(start..)
+            .filter_map(|(&idx, position)| Some((position, self.items.get(idx)?)))
+    }
+
+    // The visible entry at a display position.
+    pub fn get(&self, position: usize) -> Option<&T> {
+        self.filtered
+            .get(position)
+            .and_then(|&idx| self.items.get(idx))
+    }
+
+    // Display position of the selection.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_item(&self) -> Option<&T> {
+        self.get(self.selected)
+    }
+
+    pub fn selected_item_mut(&mut self) -> Option<&mut T> {
+        let idx = *self.filtered.get(self.selected)?;
+        self.items.get_mut(idx)
+    }
+
+    // Selects a display position, clamped to the visible entries.
+    pub fn select(&mut self, position: usize) {
+        self.selected = position.min(self.filtered.len().saturating_sub(1));
+    }
+
+    // Selects the first visible entry matching `predicate`, if any.
+    pub fn select_where(&mut self, predicate: impl Fn(&T) -> bool) -> bool {
+        let position = self.visible().position(predicate);
+        if let Some(position) = position {
+            self.selected = position;
+        }
+        position.is_some()
+    }
+
+    pub fn next(&mut self) {
+        let len = self.filtered.len();
+        if len == 0 {
+            return;
+        }
+        self.selected = if self.wrap {
+            (self.selected + 1) % len
+        } else {
+            (self.selected + 1).min(len - 1)
+        };
+    }
+
+    pub fn previous(&mut self) {
+        let len = self.filtered.len();
+        if len == 0 {
+            return;
+        }
+        self.selected = match self.selected {
+            0 if self.wrap => len - 1,
+            selected => selected.saturating_sub(1),
+        };
+    }
+
+    pub fn first(&mut self) {
+        self.selected = 0;
+    }
+
+    pub fn last(&mut self) {
+        self.selected = self.filtered.len().saturating_sub(1);
+    }
+
+    pub fn at_last(&self) -> bool {
+        self.selected + 1 >= self.filtered.len()
+    }
+
+    // Moves by a page of the rows drawn last frame.
+    pub fn page(&mut self, step: PageStep) {
+        self.selected = step.apply(self.selected, self.filtered.len(), self.view.page());
+    }
+
+    // The display positions to draw in `rows` rows, keeping the selection in view.
+    pub fn window(&self, rows: usize) -> Range<usize> {
+        self.view.window(self.selected, self.filtered.len(), rows)
+    }
+}
+
+impl App {
+    /* Pages whichever list the current mode shows: sessions (normal and
+     * filter mode, and the timeline), directories, or the expanded session's windows.
+     * Returns false when the mode has no pageable list, so the key falls
+     * through to the mode's own handling. */
+    pub fn page_list(&mut self, step: PageStep) -> bool {
+        match self.mode {
+            AppMode::Normal | AppMode::Filtering | AppMode::Timeline => {
+                self.focus = FocusArea::Sessions;
+                self.sessions.page(step);
+                self.refresh_preview();
+            }
+            AppMode::SelectingDirectory if self.dir_create.is_none() => {
+                self.directories.page(step);
+                self.refresh_dir_preview();
+            }
+            AppMode::ExpandedSession if self.window_kill.is_none() => {
+                self.expanded_windows.page(step);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    #[test]
+    fn window_scrolls_only_to_keep_selection_visible() {
+        let list = ListState::default();
+        assert_eq!(list.window(0, 20, 5), 0..5);
+        assert_eq!(list.window(7, 20, 5), 3..8);
+        // Moving back up inside the window keeps it where it is
+        assert_eq!(list.window(4, 20, 5), 3..8);
+        assert_eq!(list.window(1, 20, 5), 1..6);
+        // A shrunken list pulls the window back
+        assert_eq!(list.window(2, 4, 5), 0..4);
+        assert_eq!(list.page(), 5);
+    }
+
+    #[test]
+    fn paging_clamps_at_the_ends() {
+        assert_eq!(PageStep::HalfDown.apply(0, 20, 6), 3);
+        assert_eq!(PageStep::Down.apply(10, 20, 6), 16);
+        assert_eq!(PageStep::Down.apply(16, 20, 6), 19);
+        assert_eq!(PageStep::HalfUp.apply(2, 20, 6), 0);
+        assert_eq!(PageStep::Up.apply(19, 20, 6), 13);
+        assert_eq!(PageStep::HalfDown.apply(0, 0, 6), 0);
+        assert_eq!(PageStep::HalfDown.apply(0, 20, 1), 1);
+    }
+
+    #[test]
+    fn selection_wraps_and_follows_the_filter() {
+        let mut list = SelectableList::new(vec!["a", "b", "c", "d"]);
+        list.previous();
+        assert_eq!(list.selected_item(), Some(&"d"));
+        list.next();
+        assert_eq!(list.selected_item(), Some(&"a"));
+
+        list.last();
+        list.set_filtered(vec![2, 0]);
+        assert_eq!(list.selected(), 1);
+        assert_eq!(list.selected_item(), Some(&"a"));
+        assert_eq!(list.visible().collect::<Vec<_>>(), vec![&"c", &"a"]);
+
+        list.set_filtered(Vec::new());
+        assert_eq!(list.selected_item(), None);
+        list.next();
+        assert_eq!(list.selected(), 0);
+    }
+
+    #[test]
+    fn unwrapped_selection_stops_at_the_ends() {
+        let mut list = SelectableList::new(vec![1, 2, 3]).without_wrap();
+        list.previous();
+        assert_eq!(list.selected(), 0);
+        list.last();
+        list.next();
+        assert_eq!(list.selected(), 2);
+        assert!(list.at_last());
+    }
+
+    #[test]
+    fn session_list_pages_by_the_rows_drawn() {
+        let names: Vec<String> = (0..30).map(|i| format!("s{:02}", i)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mock = MockTmux::with_sessions(&names);
+        let mut app = App::for_test(&mock);
+
+        crate::tui::render_to_buffer(&app, 100, 40).unwrap();
+        let page = app.sessions.view.page();
+        assert!(page > 1 && page < 30);
+
+        assert!(app.page_list(PageStep::Down));
+        assert_eq!(app.sessions.selected(), page);
+        assert!(app.page_list(PageStep::HalfUp));
+        assert_eq!(app.sessions.selected(), page - page / 2);
+
+        app.mode = AppMode::StatsOverlay;
+        assert!(!app.page_list(PageStep::Down));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::tmux::TmuxSession;
+
+use super::{App, AppMode, SessionAction, SessionRef};
+
+impl App {
+    // Starts choosing a session to mirror the selected session's active window into.
+    // Preselects an attached session, since that is usually where the user is working.
+    pub fn start_mirror(&mut self) {
+        let Some(source) = self.selected_session().map(|s| s.name.clone()) else {
+            return;
+        };
+        self.mirror_source = Some(source);
+        self.mirror_target_index = self
+            .mirror_targets()
+            .iter()
+            .position(|session| session.attached)
+            .unwrap_or(0);
+        self.mode = AppMode::SelectingMirrorTarget;
+    }
+
+    pub fn cancel_mirror(&mut self) {
+        self.mirror_source = None;
+        self.mode = AppMode::Normal;
+    }
+
+    // Returns every session except the mirror source.
+    pub fn mirror_targets(&self) -> Vec<&TmuxSession> {
+        self.sessions
+            .items
+            .iter()
+            .filter(|session| Some(&session.name) != self.mirror_source.as_ref())
+            .collect()
+    }
+
+    pub fn select_mirror_target_next(&mut self) {
+        let len = self.mirror_targets().len();
+        if len > 0 {
+            self.mirror_target_index = (self.mirror_target_index + 1).min(len - 1);
+        }
+    }
+
+    pub fn select_mirror_target_previous(&mut self) {
+        self.mirror_target_index = self.mirror_target_index.saturating_sub(1);
+    }
+
+    // Sets action to link the source's active window into the highlighted target and quits.
+    pub fn confirm_mirror(&mut self) {
+        let target = self
+            .mirror_targets()
+            .get(self.mirror_target_index)
+            .map(|session| SessionRef::of(session));
+        if let (Some(source), Some(target)) = (self.mirror_source.as_ref(), target) {
+            let source = self.session_ref(source);
+            self.action = Some(SessionAction::Mirror { source, target });
+            self.should_quit = true;
+        }
+    }
+}
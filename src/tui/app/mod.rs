@@ -1,17 +1,90 @@
-use crate::directory::Directory;
-use crate::process::{AiProcessInfo, find_ai_processes};
-use crate::template::SessionTemplate;
+use crate::alert::{AlertRule, AlertViolation};
+use crate::attach_log::Attach;
+use crate::ci::CiStatus;
+use crate::config::{
+    EnvironmentConfig, PreviewOrientation, SparklineScale, ThrottleConfig, TrexConfig,
+};
+use crate::devenv::DevEnv;
+use crate::directory::{Directory, DirectoryPreview, SourceKind};
+use crate::git::GitDiff;
+use crate::health::HealthWeights;
+use crate::layouts::SavedLayout;
+use crate::policy::{IdlePolicy, PolicyMatch};
+use crate::process::{AiProcessInfo, ProcessScanner};
+use crate::service::ServiceUnit;
+use crate::sysinfo::{ProcessStats, SystemStats, WindowStats};
+use crate::template::{SessionTemplate, TemplateCatalog};
 use crate::theme::ThemeColors;
-use crate::tmux::{TmuxSession, TmuxWindow};
+use crate::tmux::{AttachedClient, TmuxBackend, TmuxSession, TmuxWindow};
 
 // Submodules
 mod agent;
+mod alert;
+mod chart;
+mod checkpoint;
+mod ci;
+mod clients;
+mod detail;
+mod diff;
 mod directory;
+mod environment;
+mod export;
 mod filter;
+mod fleet;
+mod follow;
+mod fuzzy;
+mod inbox;
+mod launcher;
+mod layouts;
+mod line_cache;
+mod list;
+mod mirror;
 mod naming;
+mod note;
+mod pending;
+mod perf;
+mod policy;
+mod power;
 mod preview;
+mod preview_copy;
+mod process_tree;
+mod queue;
+mod schedule;
+mod screen;
+mod services;
 mod session;
+mod startup;
+mod stats;
+mod tags;
+mod timeline;
+mod toast;
 mod window;
+mod window_search;
+
+pub use agent::{AgentScan, AgentUsage};
+pub use chart::{ChartMetric, MAX_BARS};
+pub use checkpoint::{Checkpoint, CheckpointKind};
+pub use ci::CiFetch;
+pub use filter::single_match;
+pub use follow::FollowTarget;
+pub use fuzzy::FuzzyCache;
+pub use inbox::{AttentionReason, InboxEntry};
+pub use launcher::editor_command;
+pub use line_cache::{LineCache, line_key};
+pub use list::{ListState, PageStep, SelectableList};
+pub use pending::{PendingAction, PendingKind};
+pub use perf::{PerfSample, PerfStats};
+use schedule::Schedule;
+pub use screen::MIN_TERMINAL_SIZE;
+use session::sort_flagged_first;
+pub use startup::{StartupScreen, StartupView};
+pub use stats::{StatsPanel, StatsRow};
+pub use timeline::TIMELINE_SPANS;
+pub use toast::{Toast, ToastLevel};
+pub use window::KillTarget;
+
+// Number of sessions reachable with the 1-9 quick-switch keys.
+pub const QUICK_SWITCH_KEYS: usize = 9;
 
 // The current mode of the application.
 #[derive(Debug, Clone, PartialEq)]
@@ -20,9 +93,28 @@ pub enum AppMode {
     Filtering,
     SelectingDirectory,
     NamingSession,
+    SelectingAgent,
+    MessagingAgents,
+    PolicyReview,
     ExpandedSession,
     BarChartView,
     StatsOverlay,
+    Timeline,
+    HealthDetail,
+    ProcessTree,
+    Services,
+    Layouts,
+    NamingLayout,
+    SelectingMirrorTarget,
+    ClientList,
+    EditingNote,
+    EditingTags,
+    WindowSearch,
+    Legend,
+    SessionDetail,
+    PreviewCopy,
+    Diff,
+    Checkpointing,
 }
 
 // Which UI area has keyboard focus.
@@ -33,37 +125,81 @@ pub enum FocusArea {
     Sessions,
 }
 
+/* A session an action works on: its tmux target (the `$id`), so a rename
+ * between choosing the action and running it can't lose the session or
+ * swap in another of that name, and its name for messages and the attach
+ * history. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionRef {
+    pub target: String,
+    pub name: String,
+}
+
+impl SessionRef {
+    pub fn of(session: &TmuxSession) -> Self {
+        Self {
+            target: session.target().to_string(),
+            name: session.name.clone(),
+        }
+    }
+}
+
 // An action to perform after exiting the TUI.
 #[derive(Debug, Clone)]
 pub enum SessionAction {
-    Attach(String),
-    AttachWindow(String, u32),
+    Attach(SessionRef),
+    AttachReadOnly(SessionRef),
+    AttachTakeover(SessionRef),
+    AttachWindow(SessionRef, TmuxWindow), // the window as listed, targeted by its id
+    Mirror {
+        source: SessionRef,
+        target: SessionRef,
+    },
     Create {
         name: String,
         path: std::path::PathBuf,
         template: SessionTemplate,
+        command: Option<String>, // Runs in the first window in place of the shell
+    },
+    Clone {
+        source: SessionRef,
+        name: String, // the new session's
+    },
+    LaunchAgent {
+        name: String,
+        path: std::path::PathBuf,
+        agent: String,
+        editor: Option<String>, // Opened in a second window when set
     },
-    Delete(String),
-    DeleteAll,
-    Detach(String),
-    DetachAll,
 }
 
 // Application state for the TUI.
 pub struct App {
-    pub sessions: Vec<TmuxSession>,
-    pub filtered_indices: Vec<usize>,
-    pub selected_index: usize,
+    // tmux operations; the CLI in production, MockTmux in tests
+    pub tmux: Box<dyn TmuxBackend>,
+
+    pub sessions: SelectableList<TmuxSession>,
     pub filter_input: String,
+    pub filter_highlights: Vec<Vec<u32>>, // matched name chars, parallel to sessions.filtered()
+    pub session_fuzzy: FuzzyCache,        // haystacks kept between filter keystrokes
     pub mode: AppMode,
     pub should_quit: bool,
     pub action: Option<SessionAction>,
 
-    pub directories: Vec<Directory>,
-    pub dir_filtered_indices: Vec<usize>,
-    pub dir_selected_index: usize,
+    pub directories: SelectableList<Directory>,
     pub dir_filter_input: String,
+    pub dir_highlights: Vec<Vec<u32>>, // matched name chars, parallel to directories.filtered()
+    pub dir_fuzzy: FuzzyCache,
+    pub dir_lines: LineCache, // formatted rows of the directories scrolled past
     pub dir_scan_depth: u32,
+    // Background rescan in flight: its depth and the channel its result arrives on
+    pub dir_scan: Option<(u32, std::sync::mpsc::Receiver<Vec<Directory>>)>,
+    // Directory sources merged into the picker, in SourceKind::ALL order (`[directories] sources`)
+    pub dir_sources: Vec<SourceKind>,
+    // Directory typed into a filter with no matches, waiting for y/n to create it
+    pub dir_create: Option<std::path::PathBuf>,
+    // README, branch, and entries of the highlighted directory, keyed by its path
+    pub dir_preview: Option<(std::path::PathBuf, DirectoryPreview)>,
 
     // Session naming state
     pub session_name_input: String,
@@ -71,76 +207,477 @@ pub struct App {
     pub templates: Vec<SessionTemplate>,
     pub template_warnings: Vec<String>,
     pub selected_template_index: usize,
+    // Optional start command for the first window, and whether it has focus
+    pub session_command_input: String,
+    pub naming_command_focus: bool,
+    // Start commands used before, most recent first; Some(i) while one is recalled
+    pub recent_commands: Vec<String>,
+    pub recent_command_index: Option<usize>,
+
+    // Agent launcher: set while the directory picker chooses where to launch,
+    // then the chosen directory, highlighted agent, and editor window toggle
+    pub launching_agent: bool,
+    pub agent_launch_dir: Option<Directory>,
+    pub agent_choice_index: usize,
+    pub agent_launch_editor: bool,
+
+    // The dev environment (.envrc or flake) found in the directory a session
+    // is being created in, and whether its commands start inside it
+    pub dev_env: Option<DevEnv>,
+    pub use_dev_env: bool,
+
+    // Note prompt: the session being annotated and the text typed so far
+    pub note_session: Option<String>,
+    pub note_input: String,
+
+    // Tag prompt: the session being tagged and the tags typed so far
+    pub tags_session: Option<String>,
+    pub tags_input: String,
 
     // Window expansion state
     pub expanded_session: Option<String>,
-    pub expanded_windows: Vec<TmuxWindow>,
-    pub selected_window_index: usize,
+    pub expanded_windows: SelectableList<TmuxWindow>,
+    // Window or pane kill waiting for y/n confirmation in the expanded view
+    pub window_kill: Option<KillTarget>,
+    // `/` filter over the expanded session's windows (name and command)
+    pub window_filtering: bool,
+    pub window_filter_input: String,
+    // CPU and memory per window index of the expanded session, from the last stats sample
+    pub window_stats: std::collections::HashMap<u32, WindowStats>,
 
-    // Preview state
+    // Preview state; the size is the preview's percent of the session area
     pub show_preview: bool,
     pub preview_lines: Vec<String>,
+    pub preview_size: u16,
+    pub preview_orientation: PreviewOrientation,
+    // Copy mode over the preview: cursor line and selection start, into preview_lines
+    pub copy_cursor: usize,
+    pub copy_anchor: Option<usize>,
+    // Agent pane the preview is pinned to, whatever session is selected
+    pub follow: Option<FollowTarget>,
+    // Whether the preview state is restored on startup (`[preview] remember`)
+    pub remember_preview: bool,
 
-    // AI process detection
-    pub ai_processes: Vec<AiProcessInfo>,
+    // AI process detection; the visible agents are narrowed to the expanded
+    // or previewed session (see sync_visible_agents)
+    pub agents: SelectableList<AiProcessInfo>,
+    /* Agent scanner (with `[agents]` options from config.toml) and the
+     * background scan using it; the scanner is handed to the scan thread
+     * and comes back with the result (see app/agent.rs) */
+    pub agent_scanner: Option<ProcessScanner>,
+    pub agent_scan: Option<AgentScan>,
+    // CPU and waiting time per agent pid, for the stats overlay's leaderboard
+    pub agent_usage: std::collections::HashMap<u32, AgentUsage>,
 
     // Focus tracking for agent/session navigation
     pub focus: FocusArea,
-    pub agent_selected_index: usize,
+    // Agent pids marked for fleet actions; none marked means every visible agent
+    pub marked_agents: std::collections::HashSet<u32>,
+    // Message typed for the fleet, sent to each agent's pane
+    pub agent_message_input: String,
+    // Session targets to visit in turn with `>`, mirrored in tmux's `@trex_queue`
+    pub review_queue: Vec<String>,
+
+    // Idle policies from config.toml, the sessions they currently match, and
+    // kill candidates dismissed from the review overlay (by tmux target)
+    pub policies: Vec<IdlePolicy>,
+    pub policy_matches: Vec<PolicyMatch>,
+    pub policy_dismissed: std::collections::HashSet<String>,
+
+    // Alert thresholds from config.toml and their current violations
+    pub alert_rules: Vec<AlertRule>,
+    pub notify_alerts: bool,
+    pub alerts: Vec<AlertViolation>,
+
+    // Health scoring weights from config.toml
+    pub health_weights: HealthWeights,
+
+    // Machine-wide load and memory, and whether CPU is shown per machine
+    pub system_stats: SystemStats,
+    pub normalize_cpu: bool,
+
+    // Set while stats sampling is paused; history gaps are filled on resume
+    pub sampling_paused_at: Option<std::time::Instant>,
+
+    // Sparkline history length and scaling; chart_window is the number of
+    // recent samples averaged in the bar chart view
+    pub history_length: usize,
+    pub sparkline_scale: SparklineScale,
+    pub chart_window: usize,
+    // Charts shown in the bar chart view, and the highlighted bar of the top one
+    pub chart_metrics: Vec<ChartMetric>,
+    pub chart_selected: usize,
+    // Per-session activity samples for the timeline view, and its span (index into TIMELINE_SPANS)
+    pub timeline: crate::timeline::ActivityTimeline,
+    pub timeline_span: usize,
+
+    // Highlighted row of the stats overlay, an index into stats_rows()
+    pub stats_selected: usize,
+
+    // Session detail page: its session, and the windows and attaches loaded when it opened
+    pub detail_session: Option<String>,
+    pub detail_windows: Vec<TmuxWindow>,
+    pub detail_attaches: Vec<Attach>,
+
+    // Diff overlay: its session, the diff read when it opened, and the view and scroll
+    pub diff_session: Option<String>,
+    pub diff: GitDiff,
+    pub diff_patch: bool, // the full unified diff rather than the stat summary
+    pub diff_scroll: usize,
+    // Commit or stash of a session's repo waiting on its message prompt
+    pub checkpoint: Option<Checkpoint>,
+    pub checkpoint_input: String,
+
+    // Process tree overlay: session, its processes, selection, and last action result
+    pub process_tree_session: Option<String>,
+    pub process_tree: Vec<ProcessStats>,
+    pub process_tree_selected: usize,
+    pub process_tree_status: Option<String>,
+
+    /* Agents run as systemd user services: the `[services] units` patterns,
+     * the matching units, and the popup's selection, status report, and
+     * last action result */
+    pub service_patterns: Vec<String>,
+    pub services: Vec<ServiceUnit>,
+    pub services_selected: usize,
+    pub service_status: Vec<String>,
+    pub services_message: Option<String>,
+
+    /* Layouts picker: the saved layouts, the session it applies them to,
+     * that session's current layout, and the name typed when saving it */
+    pub layouts: Vec<SavedLayout>,
+    pub layouts_selected: usize,
+    pub layout_session: Option<String>,
+    pub layout_current: Option<String>,
+    pub layout_name_input: String,
+
+    // Variables `E` copies into the selected session from trex's environment
+    pub environment: EnvironmentConfig,
+
+    // `[wm]` settings; the detail page shows the session's workspace
+    pub wm: crate::config::WmConfig,
+
+    // `[power]` settings, and whether the battery saver is slowing refreshes
+    pub power: crate::config::PowerConfig,
+    pub power_saving: bool,
+
+    // `[ui] sort_flagged`: sessions with a tmux bell/activity/silence flag list first
+    pub sort_flagged: bool,
+
+    // `[ui] legend`: the icon legend in the session list title; `?` shows it in full
+    pub title_legend: bool,
+
+    // Clients attached to the selected session, loaded when the client list opens
+    pub clients: Vec<AttachedClient>,
+    pub client_list_status: Option<String>,
+
+    // Session whose active window is being mirrored, and the highlighted
+    // target among the other sessions
+    pub mirror_source: Option<String>,
+    pub mirror_target_index: usize,
+
+    // Global window search: every session's windows, the search text, and
+    // the matching entries in display order
+    pub window_search: Vec<(String, TmuxWindow)>,
+    pub window_search_input: String,
+    pub window_search_indices: Vec<usize>,
+    pub window_search_selected: usize,
+
+    // Nice value and cgroup limits used to throttle a session
+    pub throttle: ThrottleConfig,
+
+    // Kills and detaches waiting out their undo grace period
+    pub pending_actions: Vec<PendingAction>,
+
+    // Queued action results and errors, shown one at a time on the help line
+    pub toasts: std::collections::VecDeque<Toast>,
+
+    // Command for the git UI launcher (`[git] ui`, default lazygit)
+    pub git_ui: String,
+    // PR/CI badges (`[git] ci`): the last gh results per repo path, and the query in flight
+    pub ci_enabled: bool,
+    pub ci_status: std::collections::HashMap<std::path::PathBuf, CiStatus>,
+    pub ci_fetch: Option<CiFetch>,
+
+    // Background refresh cadences
+    pub schedule: Schedule,
+
+    // Frame and refresh timings for the F12 performance HUD
+    pub perf: PerfStats,
+    // Agent box and session header lines kept between frames
+    pub line_cache: LineCache,
 
     // Theme colors
     pub theme: ThemeColors,
 
-    // Tick counter for animations (incremented each render cycle)
+    // Tick counter for animations (advanced by schedule.animation)
     pub tick: u64,
+    // Set when the screen is out of date; the event loop draws and clears it
+    pub redraw: bool,
+    // Last key press, for slowing animations down when idle
+    pub last_input: std::time::Instant,
+    // Terminal size from the last resize event (columns, rows)
+    pub terminal_size: (u16, u16),
+    // Running in a tmux popup, where normal mode fits itself to a small size
+    pub popup: bool,
+}
+
+/* What the app starts from besides tmux. `load` reads it from the user's
+ * machine; tests build it in memory so they never see the real config,
+ * caches, or processes. */
+struct Startup {
+    config: TrexConfig,
+    theme: ThemeColors,
+    templates: TemplateCatalog,
+    recent_commands: Vec<String>,
+    directories: Vec<Directory>,
+    agent_scanner: ProcessScanner,
+    agents: Vec<AiProcessInfo>,
+    system_stats: SystemStats,
+}
+
+impl Startup {
+    fn load() -> Self {
+        let config = TrexConfig::load();
+        // The last scan from disk and the quick sources; start_directory_scan refreshes them
+        let directories = crate::directory::with_current_dir(crate::directory::cached_from(
+            &config.directories.sources,
+            crate::directory::DEFAULT_DEPTH,
+        ));
+        // The first scan runs here so agents show in the first frame
+        let mut agent_scanner = ProcessScanner::new(config.agents);
+        let agents = agent_scanner.scan().unwrap_or_default();
+        Self {
+            theme: crate::theme::load_theme(),
+            templates: TemplateCatalog::load(),
+            recent_commands: crate::recent_commands::load_recent_commands(),
+            directories,
+            agent_scanner,
+            agents,
+            system_stats: crate::sysinfo::get_system_stats().unwrap_or_default(),
+            config,
+        }
+    }
 }
 
 impl App {
-    // Creates a new app with a preselected session index.
-    pub fn with_preselection(sessions: Vec<TmuxSession>, preselect_index: usize) -> Self {
-        let filtered_indices: Vec<usize> = (0..sessions.len()).collect();
-        let selected_index = if preselect_index < sessions.len() {
-            preselect_index
-        } else {
-            0
-        };
+    // Creates a new app over a tmux backend with a preselected session index.
+    pub fn with_preselection(
+        tmux: Box<dyn TmuxBackend>,
+        sessions: Vec<TmuxSession>,
+        preselect_index: usize,
+    ) -> Self {
+        let startup = Startup::load();
+        let git_ci = startup.config.git_ci;
+        let mut app = Self::from_startup(tmux, sessions, preselect_index, startup);
+        app.ci_enabled = git_ci && crate::ci::gh_available();
+        app.refresh_services();
+        if git_ci && !app.ci_enabled {
+            app.warn("gh not found in PATH, PR/CI badges are off");
+        }
+        app.refresh_ci_status();
+        app
+    }
 
-        let dir_scan_depth = crate::directory::DEFAULT_DEPTH;
-        let directories = crate::directory::discover_directories_with_depth(dir_scan_depth);
-        let dir_filtered_indices: Vec<usize> = (0..directories.len()).collect();
+    // Builds the app without running any external command.
+    fn from_startup(
+        tmux: Box<dyn TmuxBackend>,
+        sessions: Vec<TmuxSession>,
+        preselect_index: usize,
+        startup: Startup,
+    ) -> Self {
+        let mut sessions = SelectableList::new(sessions);
+        if preselect_index < sessions.len() {
+            sessions.select(preselect_index);
+        }
 
-        let ai_processes = find_ai_processes().unwrap_or_default();
-        let theme = crate::theme::load_theme();
-        let template_catalog = crate::template::TemplateCatalog::load();
+        let Startup {
+            config,
+            theme,
+            templates: template_catalog,
+            recent_commands,
+            directories,
+            agent_scanner,
+            agents,
+            system_stats,
+        } = startup;
+        let dir_scan_depth = crate::directory::DEFAULT_DEPTH;
+        // Agents stop at the ends, since moving past them changes focus
+        let agents = SelectableList::new(agents).without_wrap();
+        let git_ui = config.git_ui_command().to_string();
+        let review_queue = tmux.review_queue().unwrap_or_default();
 
-        Self {
+        let mut app = Self {
+            tmux,
             sessions,
-            filtered_indices,
-            selected_index,
             filter_input: String::new(),
+            filter_highlights: Vec::new(),
+            session_fuzzy: FuzzyCache::default(),
             mode: AppMode::Normal,
             should_quit: false,
             action: None,
-            directories,
-            dir_filtered_indices,
-            dir_selected_index: 0,
+            directories: SelectableList::new(directories),
             dir_filter_input: String::new(),
+            dir_highlights: Vec::new(),
+            dir_fuzzy: FuzzyCache::default(),
+            dir_lines: LineCache::default(),
             dir_scan_depth,
+            dir_scan: None,
+            dir_sources: config.directories.sources.clone(),
+            dir_create: None,
+            dir_preview: None,
             session_name_input: String::new(),
             selected_dir_path: None,
             templates: template_catalog.templates,
             template_warnings: template_catalog.warnings,
             selected_template_index: 0,
+            session_command_input: String::new(),
+            naming_command_focus: false,
+            recent_commands,
+            recent_command_index: None,
+            launching_agent: false,
+            agent_launch_dir: None,
+            agent_choice_index: 0,
+            agent_launch_editor: false,
+            dev_env: None,
+            use_dev_env: true,
+            note_session: None,
+            note_input: String::new(),
+            tags_session: None,
+            tags_input: String::new(),
             expanded_session: None,
-            expanded_windows: Vec::new(),
-            selected_window_index: 0,
+            expanded_windows: SelectableList::default(),
+            window_kill: None,
+            window_filtering: false,
+            window_filter_input: String::new(),
+            window_stats: Default::default(),
             show_preview: false,
             preview_lines: Vec::new(),
-            ai_processes,
+            preview_size: config.preview.size,
+            preview_orientation: config.preview.orientation,
+            copy_cursor: 0,
+            copy_anchor: None,
+            follow: None,
+            remember_preview: config.preview.remember,
+            agents,
+            agent_scanner: Some(agent_scanner),
+            agent_scan: None,
+            agent_usage: Default::default(),
             focus: FocusArea::default(),
-            agent_selected_index: 0,
+            marked_agents: Default::default(),
+            agent_message_input: String::new(),
+            review_queue,
+            policies: config.policies,
+            policy_matches: Vec::new(),
+            policy_dismissed: Default::default(),
+            alert_rules: config.alerts.rules,
+            notify_alerts: config.alerts.notify,
+            alerts: Vec::new(),
+            health_weights: config.health,
+            system_stats,
+            normalize_cpu: false,
+            sampling_paused_at: None,
+            history_length: config.history.length,
+            sparkline_scale: config.history.scale,
+            chart_window: 1,
+            chart_metrics: vec![ChartMetric::Cpu, ChartMetric::Memory],
+            chart_selected: 0,
+            timeline: Default::default(),
+            timeline_span: TIMELINE_SPANS.len() - 1,
+            stats_selected: 0,
+            detail_session: None,
+            detail_windows: Vec::new(),
+            detail_attaches: Vec::new(),
+            diff_session: None,
+            diff: GitDiff::default(),
+            diff_patch: false,
+            diff_scroll: 0,
+            checkpoint: None,
+            checkpoint_input: String::new(),
+            process_tree_session: None,
+            process_tree: Vec::new(),
+            process_tree_selected: 0,
+            process_tree_status: None,
+            service_patterns: config.service_units,
+            services: Vec::new(),
+            services_selected: 0,
+            service_status: Vec::new(),
+            services_message: None,
+            layouts: Vec::new(),
+            layouts_selected: 0,
+            layout_session: None,
+            layout_current: None,
+            layout_name_input: String::new(),
+            environment: config.environment,
+            wm: config.wm,
+            power: config.power,
+            power_saving: false,
+            sort_flagged: config.sort_flagged,
+            title_legend: !config.hide_legend,
+            clients: Vec::new(),
+            client_list_status: None,
+            mirror_source: None,
+            mirror_target_index: 0,
+            window_search: Vec::new(),
+            window_search_input: String::new(),
+            window_search_indices: Vec::new(),
+            window_search_selected: 0,
+            throttle: config.throttle,
+            git_ui,
+            ci_enabled: false,
+            ci_status: Default::default(),
+            ci_fetch: None,
+            pending_actions: Vec::new(),
+            toasts: Default::default(),
+            schedule: Schedule::default(),
+            perf: PerfStats::default(),
+            line_cache: LineCache::default(),
             theme,
             tick: 0,
+            redraw: true,
+            last_input: std::time::Instant::now(),
+            // Until the event loop reports the real size
+            terminal_size: MIN_TERMINAL_SIZE,
+            popup: false,
+        };
+        if app.sort_flagged {
+            let selected = app.selected_session().map(|session| session.name.clone());
+            sort_flagged_first(&mut app.sessions.items);
+            if let Some(name) = selected {
+                app.sessions.select_where(|session| session.name == name);
+            }
         }
+        app.update_policies();
+        app
+    }
+}
+
+#[cfg(test)]
+impl App {
+    // An app over the mock's sessions with the first selected, where most tests start.
+    pub fn for_test(mock: &crate::tmux::MockTmux) -> Self {
+        let sessions = mock.list_sessions().unwrap();
+        App::for_test_with(mock, sessions, 0)
+    }
+
+    /* An app over the given sessions with default settings: no user config,
+     * theme, templates, or caches, and no process scan or external command. */
+    pub fn for_test_with(
+        mock: &crate::tmux::MockTmux,
+        sessions: Vec<TmuxSession>,
+        preselect_index: usize,
+    ) -> Self {
+        let config = TrexConfig::default();
+        let startup = Startup {
+            theme: ThemeColors::default(),
+            templates: TemplateCatalog::builtins(),
+            recent_commands: Vec::new(),
+            directories: Vec::new(),
+            agent_scanner: ProcessScanner::new(config.agents),
+            agents: Vec::new(),
+            system_stats: SystemStats::default(),
+            config,
+        };
+        App::from_startup(Box::new(mock.clone()), sessions, preselect_index, startup)
     }
 }
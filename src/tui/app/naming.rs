@@ -1,19 +1,177 @@
 use super::{App, AppMode, SessionAction};
+use crate::compose::ContainerProject;
+use crate::devenv::DevEnv;
+use crate::directory::Directory;
+use crate::project::ProjectConfig;
 use crate::template::SessionTemplate;
+use crate::tmux::TmuxSession;
 
 impl App {
-    // Pre-fills the session name with the sanitized directory name.
-    pub fn enter_naming_mode(&mut self) {
-        if let Some(dir) = self.selected_directory() {
-            let name = dir.session_name();
-            let path = dir.path.clone();
-            self.session_name_input = name;
-            self.selected_dir_path = Some(path);
-            self.selected_template_index = 0;
-            self.mode = AppMode::NamingSession;
+    /* Names the session after the directory. When another directory already
+     * has a session by that name, starts from the suggested free name
+     * instead; a session in this same directory keeps the name, so Enter
+     * attaches to it. */
+    pub(super) fn name_session_in(&mut self, dir: &Directory) {
+        self.name_session_as(dir, dir.session_name());
+    }
+
+    fn name_session_as(&mut self, dir: &Directory, name: String) {
+        self.session_name_input = name;
+        self.selected_dir_path = Some(dir.path.clone());
+        if let Some(existing) = self.existing_session_for_name()
+            && existing.path.as_ref() != Some(&dir.path)
+            && let Some(suggestion) = self.suggested_session_name()
+        {
+            self.session_name_input = suggestion;
+        }
+        self.selected_template_index = 0;
+        self.session_command_input.clear();
+        self.naming_command_focus = false;
+        self.recent_command_index = None;
+        self.detect_dev_env(dir);
+        self.offer_project_template(dir);
+        self.mode = AppMode::NamingSession;
+    }
+
+    /* Enter on a directory with a `.trex.toml`: creates the session it
+     * describes right away, under its name (or a free variant when another
+     * directory's session has it). A config that doesn't parse is reported
+     * and the naming screen opens as usual. */
+    pub(super) fn create_from_project(&mut self, dir: &Directory) {
+        let project = match ProjectConfig::load(&dir.path) {
+            Ok(Some(project)) => project,
+            Ok(None) => return self.name_session_in(dir),
+            Err(error) => {
+                self.warn(format!("Ignored {}", error));
+                return self.name_session_in(dir);
+            }
+        };
+        let name = project.name.clone().unwrap_or_else(|| dir.session_name());
+        self.name_session_as(dir, name);
+        self.templates.insert(0, project.template());
+        self.selected_template_index = 0;
+        self.confirm_session_name();
+    }
+
+    /* Offers a template for the directory's compose or devcontainer
+     * setup, one Tab after Terminal, replacing the one offered for the
+     * last directory. */
+    fn offer_project_template(&mut self, dir: &Directory) {
+        self.templates.retain(|template| !template.is_project());
+        if let Some(project) = ContainerProject::detect(&dir.path) {
+            let at = self.templates.len().min(1);
+            self.templates.insert(at, project.template());
+        }
+    }
+
+    // The container template offered for the directory being named, if any.
+    pub fn project_template(&self) -> Option<&SessionTemplate> {
+        self.templates
+            .iter()
+            .find(|template| template.id == SessionTemplate::PROJECT_ID)
+    }
+
+    /* Looks for an `.envrc` or flake in the session's directory; when one
+     * is found, the session starts inside it unless toggled off. */
+    pub(super) fn detect_dev_env(&mut self, dir: &Directory) {
+        self.dev_env = DevEnv::detect(&dir.path);
+        self.use_dev_env = true;
+    }
+
+    pub fn toggle_dev_env(&mut self) {
+        if self.dev_env.is_some() {
+            self.use_dev_env = !self.use_dev_env;
+        }
+    }
+
+    // The environment the new session's commands will run in, if any.
+    pub fn active_dev_env(&self) -> Option<DevEnv> {
+        self.dev_env.filter(|_| self.use_dev_env)
+    }
+
+    // The name the session will get: the sanitized input, or `session`.
+    pub fn pending_session_name(&self) -> String {
+        if self.session_name_input.is_empty() {
+            "session".to_string()
+        } else {
+            crate::directory::sanitize_session_name(&self.session_name_input)
+        }
+    }
+
+    // The running session Enter would attach to instead of creating one.
+    pub fn existing_session_for_name(&self) -> Option<&TmuxSession> {
+        let name = self.pending_session_name();
+        self.sessions
+            .items
+            .iter()
+            .find(|session| session.name == name)
+    }
+
+    // A free name for the new session while the typed one is taken.
+    pub fn suggested_session_name(&self) -> Option<String> {
+        self.existing_session_for_name()?;
+        let path = self.selected_dir_path.as_deref()?;
+        let taken: Vec<&str> = self
+            .sessions
+            .items
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        Some(crate::directory::suggest_session_name(
+            &self.pending_session_name(),
+            path,
+            &taken,
+        ))
+    }
+
+    pub fn use_suggested_session_name(&mut self) {
+        if let Some(suggestion) = self.suggested_session_name() {
+            self.session_name_input = suggestion;
+        }
+    }
+
+    // Switches typing between the session name and the start command.
+    pub fn toggle_naming_command_focus(&mut self) {
+        self.naming_command_focus = !self.naming_command_focus;
+    }
+
+    pub fn push_naming_char(&mut self, c: char) {
+        if self.naming_command_focus {
+            self.session_command_input.push(c);
+            self.recent_command_index = None;
+        } else {
+            self.session_name_input.push(c);
+        }
+    }
+
+    pub fn pop_naming_char(&mut self) {
+        if self.naming_command_focus {
+            self.session_command_input.pop();
+            self.recent_command_index = None;
+        } else {
+            self.session_name_input.pop();
         }
     }
 
+    /* Steps through the recent start commands, shell-history style: older
+     * moves back from the newest, newer past the newest clears the field. */
+    pub fn recall_recent_command(&mut self, older: bool) {
+        if self.recent_commands.is_empty() {
+            return;
+        }
+        let last = self.recent_commands.len() - 1;
+        self.recent_command_index = match (self.recent_command_index, older) {
+            (None, true) => Some(0),
+            (Some(idx), true) => Some((idx + 1).min(last)),
+            (Some(0), false) | (None, false) => None,
+            (Some(idx), false) => Some(idx - 1),
+        };
+        self.session_command_input = self
+            .recent_command_index
+            .map(|idx| self.recent_commands[idx].clone())
+            .unwrap_or_default();
+    }
+
     pub fn selected_template(&self) -> Option<&SessionTemplate> {
         self.templates
             .get(self.selected_template_index)
@@ -38,19 +196,39 @@ impl App {
     }
 
     pub fn confirm_session_name(&mut self) {
+        let name = self.pending_session_name();
         if let (Some(template), Some(path)) = (
             self.selected_template().cloned(),
             self.selected_dir_path.take(),
         ) {
-            let name = if self.session_name_input.is_empty() {
-                "session".to_string()
-            } else {
-                crate::directory::sanitize_session_name(&self.session_name_input)
+            let command = self.session_command_input.trim().to_string();
+            let command = (!command.is_empty()).then_some(command);
+            if let Some(command) = &command {
+                crate::recent_commands::push_recent_command(&mut self.recent_commands, command);
+                if let Err(error) =
+                    crate::recent_commands::save_recent_commands(&self.recent_commands)
+                {
+                    log::warn!("recent commands: {}", error);
+                }
+            }
+            /* Inside a dev environment the first pane always gets a
+             * command: the typed one, the template's own, or the shell. */
+            let command = match self.active_dev_env() {
+                Some(env) => Some(
+                    env.wrap(
+                        command
+                            .as_deref()
+                            .or(template.panes.first().map(|pane| pane.command.as_str()))
+                            .filter(|command| !command.trim().is_empty()),
+                    ),
+                ),
+                None => command,
             };
             self.action = Some(SessionAction::Create {
                 name,
                 path,
                 template,
+                command,
             });
             self.should_quit = true;
         }
@@ -58,7 +236,161 @@ impl App {
 
     pub fn cancel_naming(&mut self) {
         self.session_name_input.clear();
+        self.session_command_input.clear();
         self.selected_dir_path = None;
         self.mode = AppMode::SelectingDirectory;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::devenv::DevEnv;
+    use crate::directory::Directory;
+    use crate::tmux::MockTmux;
+    use crate::tui::app::{App, AppMode, SessionAction};
+    use std::path::PathBuf;
+
+    #[test]
+    fn taken_names_suggest_a_free_one() {
+        let mock = MockTmux::with_sessions(&["api", "client-api"]);
+        let mut app = App::for_test(&mock);
+        let api = app
+            .sessions
+            .items
+            .iter_mut()
+            .find(|s| s.name == "api")
+            .unwrap();
+        api.path = Some(PathBuf::from("/work/client/api"));
+
+        // Same directory as the running session: keep the name and attach
+        app.name_session_in(&Directory::new(PathBuf::from("/work/client/api")));
+        assert_eq!(app.session_name_input, "api");
+        assert!(app.existing_session_for_name().is_some());
+        assert_eq!(app.suggested_session_name().as_deref(), Some("api-2"));
+
+        // Another directory: start from the suggestion
+        app.name_session_in(&Directory::new(PathBuf::from("/work/server/api")));
+        assert_eq!(app.session_name_input, "server-api");
+        assert!(app.existing_session_for_name().is_none());
+
+        app.session_name_input = "client-api".to_string();
+        app.use_suggested_session_name();
+        assert_eq!(app.session_name_input, "server-client-api");
+    }
+
+    #[test]
+    fn command_field_recalls_recent_commands() {
+        let mut app = App::for_test(&MockTmux::default());
+        app.recent_commands = vec!["claude".to_string(), "nvim".to_string()];
+        app.name_session_in(&Directory::new(PathBuf::from("/work/api")));
+
+        app.toggle_naming_command_focus();
+        app.recall_recent_command(true);
+        assert_eq!(app.session_command_input, "claude");
+        app.recall_recent_command(true);
+        app.recall_recent_command(true);
+        assert_eq!(app.session_command_input, "nvim");
+        app.recall_recent_command(false);
+        app.recall_recent_command(false);
+        assert_eq!(app.session_command_input, "");
+
+        app.push_naming_char('x');
+        app.toggle_naming_command_focus();
+        app.pop_naming_char();
+        assert_eq!(app.session_command_input, "x");
+        assert_eq!(app.session_name_input, "ap");
+    }
+
+    #[test]
+    fn dev_env_wraps_the_first_command_unless_toggled_off() {
+        let mut app = App::for_test(&MockTmux::default());
+        let dir = Directory::new(PathBuf::from("/work/api"));
+        app.name_session_in(&dir);
+        assert_eq!(app.active_dev_env(), None);
+
+        app.dev_env = Some(DevEnv::Direnv);
+        crate::tui::render_to_buffer(&app, 100, 30).unwrap();
+        app.confirm_session_name();
+        let Some(SessionAction::Create { command, .. }) = app.action.take() else {
+            panic!("expected a create action");
+        };
+        assert_eq!(command.as_deref(), Some("direnv exec . \"$SHELL\""));
+
+        app.name_session_in(&dir);
+        app.dev_env = Some(DevEnv::Flake);
+        app.toggle_dev_env();
+        app.confirm_session_name();
+        let Some(SessionAction::Create { command, .. }) = app.action.take() else {
+            panic!("expected a create action");
+        };
+        assert_eq!(command, None);
+    }
+
+    #[test]
+    fn compose_projects_offer_a_container_template() {
+        let root = std::env::temp_dir().join(format!("trex-naming-compose-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join("docker-compose.yml"),
+            "services:\n  web:\n    build: .\n",
+        )
+        .unwrap();
+        let mut app = App::for_test(&MockTmux::default());
+        let templates = app.templates.len();
+
+        app.name_session_in(&Directory::new(root.clone()));
+        assert!(app.selected_template().unwrap().is_terminal());
+        let text = |app: &App| {
+            let buffer = crate::tui::render_to_buffer(app, 100, 30).unwrap();
+            buffer
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        };
+        assert!(text(&app).contains("Compose (web) template available"));
+        app.select_next_template();
+        assert!(app.selected_template().unwrap().is_project());
+        assert!(text(&app).contains("shell: docker compose up --wait web"));
+
+        // Another directory drops the offer
+        app.name_session_in(&Directory::new(PathBuf::from("/work/api")));
+        assert_eq!(app.templates.len(), templates);
+        assert!(app.project_template().is_none());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn project_config_creates_its_session_on_enter() {
+        let root = std::env::temp_dir().join(format!("trex-project-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join(".trex.toml"),
+            "name = \"backend\"\n[[windows]]\ncommand = \"nvim\"\n[[windows]]\nname = \"logs\"\ncommand = \"tail -f log\"\n",
+        )
+        .unwrap();
+        let mut app = App::for_test(&MockTmux::default());
+        app.mode = AppMode::SelectingDirectory;
+        app.directories.items = vec![Directory::new(root.clone())];
+        app.directories.show_all();
+        let buffer = crate::tui::render_to_buffer(&app, 120, 30).unwrap();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("⚙"));
+
+        app.choose_directory();
+        let Some(SessionAction::Create { name, template, .. }) = app.action.take() else {
+            panic!("expected a create action");
+        };
+        assert_eq!(name, "backend");
+        assert_eq!(template.panes[0].command, "nvim");
+        assert_eq!(template.windows[0].name, "logs");
+
+        // A broken config falls back to the naming screen
+        std::fs::write(root.join(".trex.toml"), "name = \n").unwrap();
+        app.should_quit = false;
+        app.choose_directory();
+        assert_eq!(app.mode, AppMode::NamingSession);
+        assert!(app.current_toast().is_some());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
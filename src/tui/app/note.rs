@@ -0,0 +1,38 @@
+use super::{App, AppMode};
+
+impl App {
+    // Opens the note prompt for the selected session, prefilled with its current note.
+    pub fn start_note_edit(&mut self) {
+        let Some((name, note)) = self
+            .selected_session()
+            .map(|session| (session.name.clone(), session.note.clone()))
+        else {
+            return;
+        };
+        self.note_input = note.unwrap_or_default();
+        self.note_session = Some(name);
+        self.mode = AppMode::EditingNote;
+    }
+
+    // Saves the note to tmux; an empty note clears it.
+    pub fn confirm_note(&mut self) {
+        if let Some(name) = self.note_session.take() {
+            let note = self.note_input.trim().to_string();
+            match self.tmux.set_session_note(&name, &note) {
+                Ok(()) => {
+                    if let Some(session) = self.sessions.items.iter_mut().find(|s| s.name == name) {
+                        session.note = (!note.is_empty()).then_some(note);
+                    }
+                }
+                Err(error) => self.error(format!("{:#}", error)),
+            }
+        }
+        self.cancel_note();
+    }
+
+    pub fn cancel_note(&mut self) {
+        self.note_session = None;
+        self.note_input.clear();
+        self.mode = AppMode::Normal;
+    }
+}
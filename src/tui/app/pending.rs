@@ -0,0 +1,218 @@
+use std::time::{Duration, Instant};
+
+use super::App;
+
+// How long a kill or detach waits before running, so `u` can undo it.
+pub const UNDO_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingKind {
+    Kill,
+    Detach,
+}
+
+impl PendingKind {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            PendingKind::Kill => "Killing",
+            PendingKind::Detach => "Detaching",
+        }
+    }
+}
+
+// A kill or detach waiting out its grace period.
+#[derive(Debug, Clone)]
+pub struct PendingAction {
+    pub kind: PendingKind,
+    pub session: String, // display name, updated when the session is renamed
+    pub target: String,  // session id (or name) the tmux command runs against
+    pub due: Instant,
+}
+
+impl PendingAction {
+    // Whole seconds left before the action runs, rounded up.
+    pub fn seconds_left(&self, now: Instant) -> u64 {
+        let left = self.due.saturating_duration_since(now);
+        left.as_secs() + u64::from(left.subsec_nanos() > 0)
+    }
+}
+
+impl App {
+    /* Queues a kill or detach of the selected session. Nothing happens in
+     * tmux until the grace period ends, so undo only has to drop the entry. */
+    pub fn queue_pending(&mut self, kind: PendingKind) {
+        if let Some(session) = self
+            .selected_session()
+            .map(|session| (session.name.clone(), session.target().to_string()))
+        {
+            self.queue_pending_batch(kind, vec![session]);
+        }
+    }
+
+    // Queues a kill or detach of a session by name, whether or not it is selected.
+    pub fn queue_pending_named(&mut self, kind: PendingKind, name: &str) {
+        if let Some(session) = self
+            .sessions
+            .items
+            .iter()
+            .find(|session| session.name == name)
+            .map(|session| (session.name.clone(), session.target().to_string()))
+        {
+            self.queue_pending_batch(kind, vec![session]);
+        }
+    }
+
+    /* Kills a session by name after the grace period, with a toast saying
+     * how to undo it, for views that hide the session list's countdown. */
+    pub fn kill_named(&mut self, name: &str) {
+        self.queue_pending_named(PendingKind::Kill, name);
+        self.info(format!(
+            "Killing {} in {}s, u to undo",
+            name,
+            UNDO_GRACE_PERIOD.as_secs()
+        ));
+    }
+
+    // Queues a kill or detach of every session in the filtered list.
+    pub fn queue_pending_filtered(&mut self, kind: PendingKind) {
+        let sessions = self
+            .sessions
+            .visible()
+            .map(|session| (session.name.clone(), session.target().to_string()))
+            .collect();
+        self.queue_pending_batch(kind, sessions);
+    }
+
+    /* Queued actions in one batch share a deadline, which undo uses to
+     * group them. Sessions come as (name, target) pairs. */
+    fn queue_pending_batch(&mut self, kind: PendingKind, sessions: Vec<(String, String)>) {
+        let due = Instant::now() + UNDO_GRACE_PERIOD;
+        self.pending_actions
+            .retain(|pending| !sessions.iter().any(|(_, target)| *target == pending.target));
+        self.pending_actions
+            .extend(sessions.into_iter().map(|(session, target)| PendingAction {
+                kind,
+                session,
+                target,
+                due,
+            }));
+    }
+
+    // Cancels the most recently queued kill or detach batch.
+    pub fn undo_pending(&mut self) {
+        let Some(due) = self.pending_actions.last().map(|pending| pending.due) else {
+            return;
+        };
+        let count = self
+            .pending_actions
+            .iter()
+            .filter(|pending| pending.due == due)
+            .count();
+        let undone = self
+            .pending_actions
+            .split_off(self.pending_actions.len() - count);
+        self.info(match undone.as_slice() {
+            [pending] => format!("Undone: {} stays", pending.session),
+            _ => format!("Undone: {} sessions stay", undone.len()),
+        });
+    }
+
+    // Returns the pending action for a session, if one is queued.
+    pub fn pending_for(&self, session_name: &str) -> Option<&PendingAction> {
+        self.pending_actions
+            .iter()
+            .find(|pending| pending.session == session_name)
+    }
+
+    // Runs every pending action whose grace period has ended.
+    pub fn run_due_pending(&mut self, now: Instant) {
+        let (due, waiting) = std::mem::take(&mut self.pending_actions)
+            .into_iter()
+            .partition(|pending| pending.due <= now);
+        self.pending_actions = waiting;
+        self.run_pending(due);
+    }
+
+    // Runs all pending actions immediately; called when the TUI exits.
+    pub fn flush_pending(&mut self) {
+        let pending = std::mem::take(&mut self.pending_actions);
+        self.run_pending(pending);
+    }
+
+    fn run_pending(&mut self, pending: Vec<PendingAction>) {
+        if pending.is_empty() {
+            return;
+        }
+        for action in pending {
+            let result = match action.kind {
+                PendingKind::Kill => self.tmux.delete_session(&action.target),
+                PendingKind::Detach => self.tmux.detach_session(&action.target),
+            };
+            match (result, action.kind) {
+                (Ok(()), PendingKind::Kill) => self.info(format!("Killed {}", action.session)),
+                (Ok(()), PendingKind::Detach) => self.info(format!("Detached {}", action.session)),
+                (Err(error), _) => self.error(format!("{:#}", error)),
+            }
+        }
+        self.schedule.session_list.force();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    #[test]
+    fn kill_runs_after_grace_period() {
+        let mock = MockTmux::with_sessions(&["dev", "api"]);
+        let mut app = App::for_test(&mock);
+
+        app.delete_selected();
+        app.run_due_pending(Instant::now());
+        assert_eq!(mock.session_names(), vec!["dev", "api"]);
+
+        app.run_due_pending(Instant::now() + UNDO_GRACE_PERIOD);
+        assert_eq!(mock.session_names(), vec!["api"]);
+        assert!(app.pending_actions.is_empty());
+    }
+
+    #[test]
+    fn undo_cancels_pending_kill() {
+        let mock = MockTmux::with_sessions(&["dev"]);
+        let mut app = App::for_test(&mock);
+
+        app.delete_selected();
+        app.undo_pending();
+        app.flush_pending();
+        assert_eq!(mock.session_names(), vec!["dev"]);
+        assert!(!mock.calls().iter().any(|call| call.starts_with("kill")));
+    }
+
+    #[test]
+    fn pending_kill_follows_a_rename() {
+        let mock = MockTmux::with_sessions(&["dev", "api"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        app.delete_selected();
+        mock.rename_session("dev", "web");
+        app.reload_sessions(&mut matcher);
+        assert!(app.pending_for("web").is_some());
+
+        app.flush_pending();
+        assert_eq!(mock.session_names(), vec!["api"]);
+    }
+
+    #[test]
+    fn failed_kill_raises_error_toast() {
+        let mock = MockTmux::with_sessions(&["dev"]);
+        let mut app = App::for_test(&mock);
+
+        app.delete_selected();
+        mock.state().sessions.clear();
+        app.flush_pending();
+        let toast = app.current_toast().unwrap();
+        assert_eq!(toast.level, crate::tui::app::ToastLevel::Error);
+    }
+}
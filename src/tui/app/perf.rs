@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use crate::perf;
+
+use super::App;
+
+// Timing of one kind of work: the last run and the slowest run seen.
+#[derive(Debug, Clone, Default)]
+pub struct PerfSample {
+    pub last: Duration,
+    pub peak: Duration,
+    pub allocations: u64, // allocations made during the last run
+    pub runs: u64,
+}
+
+impl PerfSample {
+    fn record(&mut self, elapsed: Duration, allocations: u64) {
+        self.last = elapsed;
+        self.peak = self.peak.max(elapsed);
+        self.allocations = allocations;
+        self.runs += 1;
+    }
+}
+
+/* Numbers behind the hidden F12 performance HUD: frame render time and
+ * each background refresh task, with allocation counts, to track down
+ * stutter with many sessions. Always recorded, since it is a few clock
+ * reads per frame; only drawn when visible. */
+#[derive(Debug, Clone, Default)]
+pub struct PerfStats {
+    pub visible: bool,
+    pub frame: PerfSample,
+    pub tasks: Vec<(&'static str, PerfSample)>, // in first-run order
+}
+
+impl App {
+    pub fn toggle_perf_hud(&mut self) {
+        self.perf.visible = !self.perf.visible;
+    }
+
+    pub fn record_frame(&mut self, elapsed: Duration, allocations: u64) {
+        self.perf.frame.record(elapsed, allocations);
+    }
+
+    /* Runs a background refresh, recording its time and allocations for the
+     * HUD and logging the time at debug level. */
+    pub(super) fn run_task(&mut self, name: &'static str, task: impl FnOnce(&mut Self)) {
+        let start = Instant::now();
+        let allocations = perf::allocations();
+        task(self);
+        let elapsed = start.elapsed();
+        let allocations = perf::allocations().saturating_sub(allocations);
+        log::debug!(
+            "refresh {} took {:.1}ms ({} allocations)",
+            name,
+            elapsed.as_secs_f64() * 1000.0,
+            allocations
+        );
+
+        let tasks = &mut self.perf.tasks;
+        let index = match tasks.iter().position(|(task, _)| *task == name) {
+            Some(index) => index,
+            None => {
+                tasks.push((name, PerfSample::default()));
+                tasks.len() - 1
+            }
+        };
+        tasks[index].1.record(elapsed, allocations);
+    }
+}
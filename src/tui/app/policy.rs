@@ -0,0 +1,143 @@
+use crate::policy::{PolicyAction, PolicyMatch, evaluate};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{App, AppMode};
+
+impl App {
+    /* Re-evaluates idle policies against the session list. New kill
+     * candidates raise a toast pointing at the review overlay; nothing is
+     * killed without confirmation there. */
+    pub fn update_policies(&mut self) {
+        if self.policies.is_empty() {
+            return;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let previous: Vec<String> = self
+            .kill_candidates()
+            .iter()
+            .map(|candidate| candidate.target.clone())
+            .collect();
+        self.policy_matches = evaluate(&self.policies, &self.sessions.items, now);
+
+        let new = self
+            .kill_candidates()
+            .iter()
+            .filter(|candidate| !previous.contains(&candidate.target))
+            .count();
+        if new > 0 {
+            self.warn(format!(
+                "{} idle session(s) match kill policies. I: review",
+                new
+            ));
+        }
+    }
+
+    // The policy match for a session, if it is flagged or up for killing.
+    pub fn policy_match(&self, session_name: &str) -> Option<&PolicyMatch> {
+        self.policy_matches
+            .iter()
+            .find(|policy_match| policy_match.session == session_name)
+    }
+
+    // Sessions a kill policy matches that were not dismissed this run.
+    pub fn kill_candidates(&self) -> Vec<&PolicyMatch> {
+        self.policy_matches
+            .iter()
+            .filter(|policy_match| policy_match.policy.action == PolicyAction::Kill)
+            .filter(|policy_match| !self.policy_dismissed.contains(&policy_match.target))
+            .collect()
+    }
+
+    pub fn open_policy_review(&mut self) {
+        if self.kill_candidates().is_empty() {
+            self.info("No idle sessions match kill policies");
+            return;
+        }
+        self.mode = AppMode::PolicyReview;
+    }
+
+    // Kills every candidate listed in the review overlay.
+    pub fn confirm_policy_kills(&mut self) {
+        let targets: Vec<(String, String)> = self
+            .kill_candidates()
+            .iter()
+            .map(|candidate| (candidate.session.clone(), candidate.target.clone()))
+            .collect();
+        self.mode = AppMode::Normal;
+
+        let mut killed = 0;
+        for (name, target) in &targets {
+            match self.tmux.delete_session(target) {
+                Ok(()) => killed += 1,
+                Err(error) => self.error(format!("Failed to kill {}: {:#}", name, error)),
+            }
+        }
+        if killed > 0 {
+            self.info(format!("Killed {} idle session(s)", killed));
+        }
+        self.policy_matches
+            .retain(|policy_match| !targets.iter().any(|(_, t)| *t == policy_match.target));
+        self.schedule.session_list.force();
+    }
+
+    // Keeps the listed sessions until trex restarts; they stay flagged in the list.
+    pub fn dismiss_policy_review(&mut self) {
+        let targets: Vec<String> = self
+            .kill_candidates()
+            .iter()
+            .map(|candidate| candidate.target.clone())
+            .collect();
+        self.policy_dismissed.extend(targets);
+        self.mode = AppMode::Normal;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::IdlePolicy;
+    use crate::tmux::{MockTmux, TmuxBackend};
+    use crate::tui::app::{App, AppMode};
+
+    fn idle_app(mock: &MockTmux) -> App {
+        let mut sessions = mock.list_sessions().unwrap();
+        for session in &mut sessions {
+            session.last_activity = Some(0);
+        }
+        let mut app = App::for_test_with(mock, sessions, 0);
+        app.policies = vec![
+            IdlePolicy::parse("tmp-*", "24h", Some("kill")).unwrap(),
+            IdlePolicy::parse("*", "24h", None).unwrap(),
+        ];
+        app.update_policies();
+        app
+    }
+
+    #[test]
+    fn review_kills_only_after_confirmation() {
+        let mock = MockTmux::with_sessions(&["tmp-scratch", "main"]);
+        let mut app = idle_app(&mock);
+        assert!(app.policy_match("main").is_some());
+        assert_eq!(app.kill_candidates().len(), 1);
+
+        app.open_policy_review();
+        assert_eq!(app.mode, AppMode::PolicyReview);
+        app.confirm_policy_kills();
+        assert_eq!(mock.session_names(), vec!["main"]);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn dismissed_candidates_stay_flagged() {
+        let mock = MockTmux::with_sessions(&["tmp-scratch"]);
+        let mut app = idle_app(&mock);
+
+        app.open_policy_review();
+        app.dismiss_policy_review();
+        assert!(app.kill_candidates().is_empty());
+        assert!(app.policy_match("tmp-scratch").is_some());
+        assert_eq!(mock.session_names(), vec!["tmp-scratch"]);
+    }
+}
@@ -0,0 +1,61 @@
+use super::App;
+use crate::config::PowerSaver;
+use crate::power::{PowerSource, power_source};
+
+impl App {
+    /* Applies the `[power]` saver: by default it follows the power supply,
+     * saving on battery; `on` and `off` skip reading it. */
+    pub fn refresh_power(&mut self) {
+        let saving = match self.power.saver {
+            PowerSaver::Auto => power_source() == PowerSource::Battery,
+            PowerSaver::On => true,
+            PowerSaver::Off => false,
+        };
+        self.set_power_saving(saving);
+    }
+
+    /* Slows background refreshes by the configured factor and stops the
+     * animations while saving; restores both when back on AC. */
+    pub fn set_power_saving(&mut self, saving: bool) {
+        if saving == self.power_saving {
+            return;
+        }
+        self.power_saving = saving;
+        let factor = if saving { self.power.slowdown } else { 1 };
+        self.schedule.set_slowdown(factor);
+        self.redraw = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn battery_saver_slows_refreshes_and_stops_animations() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.power.saver = PowerSaver::On;
+        app.refresh_power();
+        assert!(app.power_saving);
+        assert_eq!(
+            app.schedule.session_stats.interval(),
+            Duration::from_secs(6)
+        );
+
+        let now = Instant::now() + Duration::from_millis(100);
+        app.run_scheduled_work(now, &mut matcher);
+        assert_eq!(app.tick, 0);
+        assert!(app.time_until_work(now) > Duration::from_secs(1));
+
+        app.power.saver = PowerSaver::Off;
+        app.refresh_power();
+        assert_eq!(
+            app.schedule.session_stats.interval(),
+            Duration::from_secs(2)
+        );
+    }
+}
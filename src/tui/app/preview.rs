@@ -1,6 +1,57 @@
-use crate::tmux::TmuxClient;
-
 use super::App;
+use crate::config::{MAX_PREVIEW_SIZE, MIN_PREVIEW_SIZE, PreviewOrientation};
+use std::path::PathBuf;
+
+// Percent of the session area `<` and `>` move the preview split by.
+pub const PREVIEW_SIZE_STEP: u16 = 5;
+
+/* The preview layout as of the last exit, kept in `preview` under the trex
+ * cache directory so the next run opens the same way. One `key=value` per
+ * line: `shown`, `size`, and `orientation`; unknown or invalid lines are
+ * ignored, leaving the configured value. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewState {
+    pub shown: bool,
+    pub size: u16,
+    pub orientation: PreviewOrientation,
+}
+
+impl PreviewState {
+    fn parse(text: &str, mut state: Self) -> Self {
+        for (key, value) in text.lines().filter_map(|line| line.trim().split_once('=')) {
+            match key.trim() {
+                "shown" => state.shown = value.trim() == "true",
+                "size" => {
+                    if let Ok(size) = value.trim().parse::<u16>()
+                        && (MIN_PREVIEW_SIZE..=MAX_PREVIEW_SIZE).contains(&size)
+                    {
+                        state.size = size;
+                    }
+                }
+                "orientation" => {
+                    if let Some(orientation) = PreviewOrientation::parse(value.trim()) {
+                        state.orientation = orientation;
+                    }
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "shown={}\nsize={}\norientation={}\n",
+            self.shown,
+            self.size,
+            self.orientation.as_str()
+        )
+    }
+}
+
+fn preview_state_path() -> Option<PathBuf> {
+    crate::logging::cache_dir().map(|dir| dir.join("preview"))
+}
 
 impl App {
     pub fn toggle_preview(&mut self) {
@@ -9,6 +60,7 @@ impl App {
             self.refresh_preview();
         } else {
             self.preview_lines.clear();
+            self.follow = None;
         }
     }
 
@@ -16,14 +68,120 @@ impl App {
         if !self.show_preview {
             return;
         }
-        if let Some(session) = self.selected_session() {
-            if let Ok(lines) = TmuxClient::capture_pane(&session.name, 30) {
-                self.preview_lines = lines;
-            } else {
-                self.preview_lines.clear();
-            }
+        if self.follow.is_some() {
+            self.refresh_follow();
+        } else if let Some(session) = self.selected_session() {
+            // A failed capture shows the error in place of the pane content
+            self.preview_lines = match self.tmux.capture_pane(&session.name, 30) {
+                Ok(lines) => lines,
+                Err(error) => vec![format!("tmux error: {}", error)],
+            };
         } else {
             self.preview_lines.clear();
         }
     }
+
+    // Grows or shrinks the preview by a step, within the configurable range.
+    pub fn resize_preview(&mut self, grow: bool) {
+        self.preview_size = if grow {
+            self.preview_size + PREVIEW_SIZE_STEP
+        } else {
+            self.preview_size.saturating_sub(PREVIEW_SIZE_STEP)
+        }
+        .clamp(MIN_PREVIEW_SIZE, MAX_PREVIEW_SIZE);
+    }
+
+    // Switches the preview between the right of the session list and below it.
+    pub fn flip_preview(&mut self) {
+        self.preview_orientation = self.preview_orientation.flipped();
+    }
+
+    fn preview_state(&self) -> PreviewState {
+        PreviewState {
+            shown: self.show_preview,
+            size: self.preview_size,
+            orientation: self.preview_orientation,
+        }
+    }
+
+    // Reopens the preview as it was at the last exit, unless `[preview] remember = false`.
+    pub fn restore_preview_state(&mut self) {
+        if !self.remember_preview {
+            return;
+        }
+        let Some(text) = preview_state_path().and_then(|path| std::fs::read_to_string(path).ok())
+        else {
+            return;
+        };
+        let state = PreviewState::parse(&text, self.preview_state());
+        self.preview_size = state.size;
+        self.preview_orientation = state.orientation;
+        if state.shown != self.show_preview {
+            self.toggle_preview();
+        }
+    }
+
+    pub fn save_preview_state(&self) {
+        if !self.remember_preview {
+            return;
+        }
+        let Some(path) = preview_state_path() else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, self.preview_state().render()));
+        if let Err(error) = result {
+            log::warn!("preview state: {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    #[test]
+    fn preview_state_round_trips_and_skips_bad_values() {
+        let state = PreviewState {
+            shown: true,
+            size: 35,
+            orientation: PreviewOrientation::Vertical,
+        };
+        let defaults = PreviewState {
+            shown: false,
+            size: 50,
+            orientation: PreviewOrientation::Horizontal,
+        };
+        assert_eq!(PreviewState::parse(&state.render(), defaults), state);
+
+        let parsed = PreviewState::parse("size=5\norientation=sideways\nshown=true\n", defaults);
+        assert_eq!(parsed.size, 50);
+        assert_eq!(parsed.orientation, PreviewOrientation::Horizontal);
+        assert!(parsed.shown);
+    }
+
+    #[test]
+    fn preview_resizes_within_limits() {
+        let mock = MockTmux::with_sessions(&["dev"]);
+        let mut app = App::for_test(&mock);
+        app.preview_size = 50;
+
+        app.resize_preview(true);
+        assert_eq!(app.preview_size, 55);
+        for _ in 0..20 {
+            app.resize_preview(true);
+        }
+        assert_eq!(app.preview_size, MAX_PREVIEW_SIZE);
+        for _ in 0..20 {
+            app.resize_preview(false);
+        }
+        assert_eq!(app.preview_size, MIN_PREVIEW_SIZE);
+
+        let orientation = app.preview_orientation;
+        app.flip_preview();
+        assert_ne!(app.preview_orientation, orientation);
+    }
 }
@@ -0,0 +1,132 @@
+use crate::clipboard;
+
+use super::{App, AppMode};
+
+// Lines of scrollback captured for copy mode, more than the preview keeps.
+pub const COPY_HISTORY_LINES: usize = 200;
+
+impl App {
+    /* Enters copy mode on the selected session's pane, opening the preview
+     * if it is hidden. The capture reaches further back than the preview
+     * and stays frozen until copy mode ends, so lines don't move under the
+     * cursor. The cursor starts on the last non-blank line. */
+    pub fn start_preview_copy(&mut self) {
+        let Some(name) = self.selected_session().map(|s| s.name.clone()) else {
+            return;
+        };
+        if !self.show_preview {
+            self.toggle_preview();
+        }
+        match self.tmux.capture_pane(&name, COPY_HISTORY_LINES) {
+            Ok(lines) => self.preview_lines = lines,
+            Err(error) => {
+                self.error(format!("tmux error: {}", error));
+                return;
+            }
+        }
+        self.copy_cursor = self
+            .preview_lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .unwrap_or(0);
+        self.copy_anchor = None;
+        self.mode = AppMode::PreviewCopy;
+    }
+
+    // Leaves copy mode, going back to the live preview.
+    pub fn cancel_preview_copy(&mut self) {
+        self.copy_anchor = None;
+        self.mode = AppMode::Normal;
+        self.refresh_preview();
+    }
+
+    // Moves the cursor by `delta` lines, stopping at the ends.
+    pub fn move_copy_cursor(&mut self, delta: isize) {
+        let last = self.preview_lines.len().saturating_sub(1);
+        self.copy_cursor = self.copy_cursor.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn copy_cursor_to_end(&mut self, end: bool) {
+        self.copy_cursor = if end {
+            self.preview_lines.len().saturating_sub(1)
+        } else {
+            0
+        };
+    }
+
+    // Starts a line selection at the cursor, or drops the current one.
+    pub fn toggle_copy_selection(&mut self) {
+        self.copy_anchor = match self.copy_anchor {
+            Some(_) => None,
+            None => Some(self.copy_cursor),
+        };
+    }
+
+    // Lines the yank takes: the selection, or just the cursor line without one.
+    pub fn copy_range(&self) -> std::ops::RangeInclusive<usize> {
+        let anchor = self.copy_anchor.unwrap_or(self.copy_cursor);
+        anchor.min(self.copy_cursor)..=anchor.max(self.copy_cursor)
+    }
+
+    /* Copies the selected lines to the clipboard, each with its trailing
+     * padding trimmed, and leaves copy mode. */
+    pub fn yank_preview_selection(&mut self) {
+        let range = self.copy_range();
+        let count = range.end() - range.start() + 1;
+        let text = self
+            .preview_lines
+            .get(range)
+            .unwrap_or_default()
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n");
+        match clipboard::copy(&text) {
+            Ok(method) => self.info(format!("Copied {} line(s) ({})", count, method)),
+            Err(error) => self.error(format!("Copy failed: {}", error)),
+        }
+        self.cancel_preview_copy();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    #[test]
+    fn copy_mode_selects_lines_around_the_cursor() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        mock.state().panes.insert(
+            "api".to_string(),
+            [
+                "$ cargo test",
+                "error[E0308]: mismatched types",
+                "  --> src/lib.rs:4",
+                "",
+            ]
+            .map(String::from)
+            .to_vec(),
+        );
+        let mut app = App::for_test(&mock);
+
+        app.start_preview_copy();
+        assert_eq!(app.mode, AppMode::PreviewCopy);
+        assert!(app.show_preview);
+        assert_eq!(app.copy_cursor, 2);
+        assert_eq!(app.copy_range(), 2..=2);
+
+        app.toggle_copy_selection();
+        app.move_copy_cursor(-1);
+        assert_eq!(app.copy_range(), 1..=2);
+        app.move_copy_cursor(-5);
+        assert_eq!(app.copy_cursor, 0);
+        app.copy_cursor_to_end(true);
+        assert_eq!(app.copy_range(), 2..=3);
+        crate::tui::render_to_buffer(&app, 100, 30).unwrap();
+
+        app.cancel_preview_copy();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.copy_anchor, None);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::process::{renice_process, signal_process};
+use crate::sysinfo::ProcessStats;
+use crate::throttle::{limit_session, renice_session};
+
+use super::{App, AppMode};
+
+impl App {
+    // Opens the process tree overlay for the selected session.
+    pub fn open_process_tree(&mut self) {
+        let Some(name) = self.selected_session().map(|s| s.name.clone()) else {
+            return;
+        };
+        self.process_tree = crate::sysinfo::get_session_processes(&name)
+            .map(|(_, processes)| processes)
+            .unwrap_or_default();
+        self.process_tree_session = Some(name);
+        self.process_tree_selected = 0;
+        self.process_tree_status = None;
+        self.mode = AppMode::ProcessTree;
+    }
+
+    pub fn close_process_tree(&mut self) {
+        self.process_tree_session = None;
+        self.process_tree.clear();
+        self.process_tree_status = None;
+        self.mode = AppMode::Normal;
+    }
+
+    pub fn select_process_next(&mut self) {
+        if !self.process_tree.is_empty() {
+            self.process_tree_selected =
+                (self.process_tree_selected + 1).min(self.process_tree.len() - 1);
+        }
+    }
+
+    pub fn select_process_previous(&mut self) {
+        self.process_tree_selected = self.process_tree_selected.saturating_sub(1);
+    }
+
+    // Returns the highlighted process, if any.
+    pub fn selected_process(&self) -> Option<&ProcessStats> {
+        self.process_tree.get(self.process_tree_selected)
+    }
+
+    // Sends a signal to the highlighted process and records the outcome.
+    pub fn signal_selected_process(&mut self, signal: i32, label: &str) {
+        let Some(process) = self.selected_process() else {
+            return;
+        };
+        let (pid, name) = (process.pid, process.name.clone());
+        self.process_tree_status = Some(match signal_process(pid, signal) {
+            Ok(()) => format!("Sent {} to {} ({})", label, name, pid),
+            Err(err) => format!("{:#}", err),
+        });
+    }
+
+    // Changes the highlighted process's nice value and records the outcome.
+    pub fn renice_selected_process(&mut self, delta: i32) {
+        let Some(process) = self.selected_process() else {
+            return;
+        };
+        let (pid, name) = (process.pid, process.name.clone());
+        self.process_tree_status = Some(match renice_process(pid, delta) {
+            Ok(nice) => format!("{} ({}) is now nice {}", name, pid, nice),
+            Err(err) => format!("{:#}", err),
+        });
+    }
+
+    // Renices every process in the tree's session to the configured value.
+    pub fn renice_tree_session(&mut self) {
+        let Some(name) = self.process_tree_session.clone() else {
+            return;
+        };
+        self.process_tree_status = Some(match renice_session(&name, &self.throttle) {
+            Ok(report) if report.failed == 0 => {
+                format!("Set {} processes to nice {}", report.reniced, report.nice)
+            }
+            Ok(report) => format!(
+                "Set {} processes to nice {}, {} failed (lowering nice needs root)",
+                report.reniced, report.nice, report.failed
+            ),
+            Err(err) => format!("{:#}", err),
+        });
+    }
+
+    // Places the tree's session in a systemd scope with the configured limits.
+    pub fn limit_tree_session(&mut self) {
+        let Some(name) = self.process_tree_session.clone() else {
+            return;
+        };
+        self.process_tree_status = Some(match limit_session(&name, &self.throttle) {
+            Ok(unit) => format!("Limited {} via {}", name, unit),
+            Err(err) => format!("{:#}", err),
+        });
+    }
+}
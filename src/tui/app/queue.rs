@@ -0,0 +1,122 @@
+use crate::tmux::TmuxSession;
+
+use super::{App, FocusArea, SessionAction, SessionRef};
+
+impl App {
+    // The session `Q` acts on: the selected session, or the selected agent's.
+    fn queue_candidate(&self) -> Option<&TmuxSession> {
+        match self.focus {
+            FocusArea::Sessions => self.selected_session(),
+            FocusArea::Agents => {
+                let name = self.selected_agent()?.tmux_session.as_ref()?;
+                self.sessions
+                    .items
+                    .iter()
+                    .find(|session| &session.name == name)
+            }
+        }
+    }
+
+    // 1-based place of a session in the review queue.
+    pub fn queue_position(&self, session: &TmuxSession) -> Option<usize> {
+        self.review_queue
+            .iter()
+            .position(|target| *target == session.target())
+            .map(|idx| idx + 1)
+    }
+
+    // Adds the session to the end of the review queue, or takes it out.
+    pub fn toggle_queued(&mut self) {
+        let Some((name, target)) = self
+            .queue_candidate()
+            .map(|session| (session.name.clone(), session.target().to_string()))
+        else {
+            return;
+        };
+        let message = match self.review_queue.iter().position(|t| *t == target) {
+            Some(idx) => {
+                self.review_queue.remove(idx);
+                format!("Removed {} from the review queue", name)
+            }
+            None => {
+                self.review_queue.push(target);
+                format!("Queued {} ({} to review)", name, self.review_queue.len())
+            }
+        };
+        if self.save_review_queue() {
+            self.info(message);
+        }
+    }
+
+    /* Attaches to the session at the front of the queue and moves it to the
+     * back, so repeated use visits each queued session in turn. Sessions
+     * that no longer exist are dropped. */
+    pub fn attach_next_in_queue(&mut self) {
+        let sessions = &self.sessions.items;
+        self.review_queue
+            .retain(|target| sessions.iter().any(|s| s.target() == target));
+        if self.review_queue.is_empty() {
+            self.save_review_queue();
+            self.info("Review queue is empty");
+            return;
+        }
+        self.review_queue.rotate_left(1);
+        let Some(session) = self.review_queue.last().and_then(|target| {
+            self.sessions
+                .items
+                .iter()
+                .find(|s| s.target() == target)
+                .map(SessionRef::of)
+        }) else {
+            return;
+        };
+        if self.save_review_queue() {
+            self.action = Some(SessionAction::Attach(session));
+            self.should_quit = true;
+        }
+    }
+
+    fn save_review_queue(&mut self) -> bool {
+        match self.tmux.set_review_queue(&self.review_queue) {
+            Ok(()) => true,
+            Err(error) => {
+                self.error(format!("{:#}", error));
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tmux::{MockTmux, TmuxBackend};
+    use crate::tui::app::{App, SessionAction};
+
+    #[test]
+    fn queue_visits_sessions_in_turn() {
+        let mock = MockTmux::with_sessions(&["api", "web", "docs"]);
+        let sessions = mock.list_sessions().unwrap();
+        let mut app = App::for_test_with(&mock, sessions, 2);
+
+        app.toggle_queued();
+        app.sessions.select(0);
+        app.toggle_queued();
+        assert_eq!(mock.state().review_queue, vec!["$2", "$0"]);
+        assert_eq!(app.queue_position(&app.sessions.items[0]), Some(2));
+
+        app.attach_next_in_queue();
+        assert!(
+            matches!(&app.action, Some(SessionAction::Attach(session)) if session.name == "docs")
+        );
+        app.attach_next_in_queue();
+        assert!(
+            matches!(&app.action, Some(SessionAction::Attach(session)) if session.name == "api")
+        );
+        assert_eq!(mock.state().review_queue, vec!["$2", "$0"]);
+
+        app.toggle_queued();
+        app.sessions.items.retain(|session| session.name != "docs");
+        app.attach_next_in_queue();
+        assert!(app.review_queue.is_empty());
+    }
+}
@@ -0,0 +1,343 @@
+use std::time::{Duration, Instant};
+
+use super::{App, AppMode};
+
+// Animation frame rate while in use, and the slower rate once idle
+const ANIMATION_INTERVAL: Duration = Duration::from_millis(100);
+const IDLE_ANIMATION_INTERVAL: Duration = Duration::from_secs(1);
+// Time without a key press before the TUI counts as idle
+pub const IDLE_AFTER: Duration = Duration::from_secs(30);
+// How often an in-flight directory, agent, or gh scan is checked for its result
+const SCAN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// A repeating background task that becomes due once its interval has elapsed.
+#[derive(Debug, Clone)]
+pub struct Cadence {
+    interval: Duration,
+    base: Duration, // the interval before any power-saving slowdown
+    last_run: Option<Instant>,
+}
+
+impl Cadence {
+    // Creates a cadence whose first run is one interval from now.
+    fn every(interval: Duration) -> Self {
+        Self {
+            interval,
+            base: interval,
+            last_run: Some(Instant::now()),
+        }
+    }
+
+    // Returns true (and restarts the interval) when the task should run.
+    pub fn due(&mut self, now: Instant) -> bool {
+        let due = self
+            .last_run
+            .is_none_or(|last_run| now.duration_since(last_run) >= self.interval);
+        if due {
+            self.last_run = Some(now);
+        }
+        due
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    // Runs the task `factor` times less often than it was created with.
+    fn slow_down(&mut self, factor: u32) {
+        self.interval = self.base * factor.max(1);
+    }
+
+    // Time left until the task is due; zero when it already is.
+    pub fn until_due(&self, now: Instant) -> Duration {
+        self.last_run.map_or(Duration::ZERO, |last_run| {
+            self.interval.saturating_sub(now.duration_since(last_run))
+        })
+    }
+
+    // Makes the task run on the next scheduler pass.
+    pub fn force(&mut self) {
+        self.last_run = None;
+    }
+}
+
+/* Background work grouped by cost, so cheap checks stay responsive while
+ * expensive scans (git, /proc walks, directory discovery) run rarely.
+ * The event loop sleeps until the next of these is due (or a key arrives)
+ * and only redraws when something changed. */
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    pub animation: Cadence,     // spinner and blink frames; slows down when idle
+    pub process_state: Cadence, // cheap: read /proc/<pid>/stat of known agents
+    pub session_list: Cadence,  // cheap: tmux list-sessions, keeps the filter applied
+    pub session_stats: Cadence, // medium: per-session CPU, memory, IO, expanded windows
+    pub process_scan: Cadence,  // expensive: walk /proc for new agents, list agent services
+    pub git_status: Cadence,    // expensive: one git call per session
+    pub ci_status: Cadence,     // expensive: gh network calls per repo, when `[git] ci` is on
+    pub directories: Cadence,   // expensive: rescanned in the background when the picker opens
+    pub follow: Cadence,        // cheap: capture the followed agent pane while following
+    pub power: Cadence,         // cheap: read /sys/class/power_supply for the battery saver
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            animation: Cadence::every(ANIMATION_INTERVAL),
+            process_state: Cadence::every(Duration::from_millis(500)),
+            session_list: Cadence::every(Duration::from_secs(5)),
+            session_stats: Cadence::every(Duration::from_secs(2)),
+            process_scan: Cadence::every(Duration::from_secs(15)),
+            git_status: Cadence::every(Duration::from_secs(30)),
+            ci_status: Cadence::every(Duration::from_secs(120)),
+            directories: Cadence::every(Duration::from_secs(30)),
+            follow: Cadence::every(Duration::from_secs(1)),
+            power: Cadence::every(Duration::from_secs(60)),
+        }
+    }
+}
+
+impl Schedule {
+    /* Stretches the background refreshes by `factor` (1 restores them).
+     * The followed pane keeps its rate, since the user is watching it. */
+    pub fn set_slowdown(&mut self, factor: u32) {
+        for cadence in [
+            &mut self.process_state,
+            &mut self.session_list,
+            &mut self.session_stats,
+            &mut self.process_scan,
+            &mut self.git_status,
+            &mut self.ci_status,
+            &mut self.directories,
+        ] {
+            cadence.slow_down(factor);
+        }
+    }
+}
+
+impl App {
+    /* Runs whichever background refreshes are due, flagging a redraw when
+     * one may have changed what is on screen. Agent states are polled
+     * often, so they only flag one when a state actually changed. */
+    pub fn run_scheduled_work(&mut self, now: Instant, matcher: &mut nucleo::Matcher) {
+        self.advance_animation(now);
+        if self.schedule.process_state.due(now) {
+            self.run_task("agent state (/proc)", Self::refresh_ai_process_states);
+        }
+        let queued = (self.pending_actions.len(), self.toasts.len());
+        self.run_due_pending(now);
+        self.advance_toasts(now);
+        if queued != (self.pending_actions.len(), self.toasts.len()) {
+            self.redraw = true;
+        }
+        if self.schedule.session_list.due(now) {
+            self.run_task("session list (tmux)", |app| app.reload_sessions(matcher));
+            self.redraw = true;
+        }
+        if !self.sampling_paused() && self.schedule.session_stats.due(now) {
+            self.run_task("session stats (/proc)", Self::refresh_session_stats);
+            // New, renamed, and busy windows show up while the session is expanded
+            if self.mode == AppMode::ExpandedSession {
+                self.run_task("windows (tmux)", |app| {
+                    app.refresh_expanded_windows(matcher)
+                });
+            }
+            self.redraw = true;
+        }
+        // Copy mode freezes the capture, so the followed pane waits until it ends
+        if self.following() && self.schedule.follow.due(now) {
+            self.run_task("followed pane (tmux)", Self::refresh_follow);
+            self.redraw = true;
+        }
+        // The /proc walk runs on its own thread; the list updates when it finishes
+        if self.schedule.process_scan.due(now) {
+            self.rescan_ai_processes();
+            if !self.service_patterns.is_empty() {
+                self.run_task("agent services (systemctl)", Self::refresh_services);
+                self.redraw = true;
+            }
+        }
+        if self.agent_scan.is_some() {
+            self.redraw |= self.poll_agent_scan();
+        }
+        if self.schedule.git_status.due(now) {
+            self.run_task("git status (git)", Self::refresh_git_status);
+            self.redraw = true;
+        }
+        // gh runs on its own thread; the badges update when it finishes
+        if self.ci_enabled && self.schedule.ci_status.due(now) {
+            self.refresh_ci_status();
+        }
+        if self.ci_fetch.is_some() {
+            self.redraw |= self.poll_ci_fetch();
+        }
+        if self.dir_scan.is_some() {
+            self.poll_directory_scan(matcher);
+            self.redraw |= self.dir_scan.is_none();
+        }
+        if self.schedule.power.due(now) {
+            self.refresh_power();
+        }
+    }
+
+    /* Steps the animation tick, at a slower rate once no key has been
+     * pressed for IDLE_AFTER, so an unattended TUI barely wakes up. The
+     * battery saver stops it altogether. */
+    fn advance_animation(&mut self, now: Instant) {
+        if self.power_saving {
+            return;
+        }
+        let interval = if self.is_idle(now) {
+            IDLE_ANIMATION_INTERVAL
+        } else {
+            ANIMATION_INTERVAL
+        };
+        self.schedule.animation.set_interval(interval);
+        if self.schedule.animation.due(now) {
+            self.tick = self.tick.wrapping_add(1);
+            self.redraw = true;
+        }
+    }
+
+    pub fn is_idle(&self, now: Instant) -> bool {
+        now.duration_since(self.last_input) >= IDLE_AFTER
+    }
+
+    // Records a key press: leaves idle mode and redraws.
+    pub fn note_input(&mut self, now: Instant) {
+        if self.is_idle(now) {
+            self.schedule.animation.force();
+        }
+        self.last_input = now;
+        self.redraw = true;
+    }
+
+    /* How long the event loop can wait for a key before background work
+     * is due: the nearest cadence or pending action, or a short poll while
+     * a directory, agent, or gh scan runs in the background. */
+    pub fn time_until_work(&self, now: Instant) -> Duration {
+        let schedule = &self.schedule;
+        let mut cadences = vec![
+            &schedule.process_state,
+            &schedule.session_list,
+            &schedule.process_scan,
+            &schedule.git_status,
+            &schedule.power,
+        ];
+        if !self.power_saving {
+            cadences.push(&schedule.animation);
+        }
+        if !self.sampling_paused() {
+            cadences.push(&schedule.session_stats);
+        }
+        if self.following() {
+            cadences.push(&schedule.follow);
+        }
+        if self.ci_enabled {
+            cadences.push(&schedule.ci_status);
+        }
+        let mut wait = cadences
+            .iter()
+            .map(|cadence| cadence.until_due(now))
+            .min()
+            .unwrap_or(ANIMATION_INTERVAL);
+        if let Some(due) = self.pending_actions.iter().map(|pending| pending.due).min() {
+            wait = wait.min(due.saturating_duration_since(now));
+        }
+        if self.dir_scan.is_some() || self.agent_scan.is_some() || self.ci_fetch.is_some() {
+            wait = wait.min(SCAN_POLL_INTERVAL);
+        }
+        wait
+    }
+
+    // Forces every background refresh to run on the next pass.
+    pub fn refresh_now(&mut self) {
+        self.schedule.process_state.force();
+        self.schedule.session_list.force();
+        self.schedule.session_stats.force();
+        self.schedule.process_scan.force();
+        self.schedule.git_status.force();
+        self.schedule.ci_status.force();
+        self.schedule.directories.force();
+    }
+
+    // Opens the directory picker, rescanning directories in the background if stale.
+    pub fn open_directory_selection(&mut self, matcher: &mut nucleo::Matcher) {
+        self.poll_directory_scan(matcher);
+        if self.schedule.directories.due(Instant::now()) {
+            self.start_directory_scan();
+        }
+        self.refresh_dir_preview();
+        self.launching_agent = false;
+        self.mode = AppMode::SelectingDirectory;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    fn app() -> App {
+        let mock = MockTmux::with_sessions(&["api"]);
+        App::for_test(&mock)
+    }
+
+    #[test]
+    fn redraws_only_when_something_changed() {
+        let mut app = app();
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        let now = Instant::now();
+        app.redraw = false;
+
+        // Nothing is due right after startup
+        app.run_scheduled_work(now, &mut matcher);
+        assert!(!app.redraw);
+        assert!(app.time_until_work(now) <= ANIMATION_INTERVAL);
+
+        app.run_scheduled_work(now + ANIMATION_INTERVAL, &mut matcher);
+        assert!(app.redraw);
+        assert_eq!(app.tick, 1);
+    }
+
+    #[test]
+    fn agent_scan_runs_in_the_background() {
+        let mut app = app();
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        let now = Instant::now();
+
+        app.schedule.process_scan.force();
+        app.run_scheduled_work(now, &mut matcher);
+        assert!(app.agent_scan.is_some());
+        assert!(app.agent_scanner.is_none());
+        assert!(app.time_until_work(now) <= SCAN_POLL_INTERVAL);
+
+        // A second scan doesn't start while one is running
+        app.rescan_ai_processes();
+        while app.agent_scan.is_some() {
+            std::thread::sleep(Duration::from_millis(10));
+            app.poll_agent_scan();
+        }
+        assert!(app.agent_scanner.is_some());
+    }
+
+    #[test]
+    fn idle_slows_the_animation_until_a_key() {
+        let mut app = app();
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        let idle = Instant::now() + IDLE_AFTER;
+
+        app.run_scheduled_work(idle, &mut matcher);
+        assert!(app.is_idle(idle));
+        assert_eq!(app.schedule.animation.interval(), IDLE_ANIMATION_INTERVAL);
+
+        app.note_input(idle);
+        assert!(!app.is_idle(idle));
+        assert_eq!(app.time_until_work(idle), Duration::ZERO);
+        app.run_scheduled_work(idle, &mut matcher);
+        assert_eq!(app.schedule.animation.interval(), ANIMATION_INTERVAL);
+    }
+}
@@ -0,0 +1,100 @@
+use super::App;
+
+// Smallest terminal the layouts fit in without clipping (columns, rows)
+pub const MIN_TERMINAL_SIZE: (u16, u16) = (80, 25);
+
+/* Below this size a popup (`--popup`) fits normal mode to its geometry:
+ * fewer rows drop the system overview and cap the agent box, fewer columns
+ * stack the preview under the session list instead of beside it. */
+pub const POPUP_COMPACT_SIZE: (u16, u16) = (120, 35);
+
+impl App {
+    // Records the terminal size reported by a resize event and redraws.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.terminal_size = (width, height);
+        self.redraw = true;
+    }
+
+    /* True when the terminal is below MIN_TERMINAL_SIZE in either
+     * direction. The views aren't drawn then, so keys other than quit
+     * are ignored rather than acting on a list the user can't see. */
+    pub fn is_too_small(&self) -> bool {
+        let (width, height) = self.terminal_size;
+        width < MIN_TERMINAL_SIZE.0 || height < MIN_TERMINAL_SIZE.1
+    }
+
+    // A popup narrower than POPUP_COMPACT_SIZE.
+    pub fn popup_narrow(&self) -> bool {
+        self.popup && self.terminal_size.0 < POPUP_COMPACT_SIZE.0
+    }
+
+    // A popup shorter than POPUP_COMPACT_SIZE.
+    pub fn popup_short(&self) -> bool {
+        self.popup && self.terminal_size.1 < POPUP_COMPACT_SIZE.1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+    use crate::tui::events::handle_key;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn screen_text(app: &App, width: u16, height: u16) -> String {
+        let buffer = crate::tui::render_to_buffer(app, width, height).unwrap();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn small_terminal_shows_required_size() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        let app = App::for_test(&mock);
+
+        let text = screen_text(&app, 60, 20);
+        assert!(text.contains("Terminal too small"));
+        assert!(text.contains("60x20"));
+        assert!(text.contains("80x25"));
+
+        assert!(!screen_text(&app, 80, 25).contains("Terminal too small"));
+    }
+
+    #[test]
+    fn small_terminal_ignores_keys_but_quit() {
+        let mock = MockTmux::with_sessions(&["api", "web"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.resize(70, 30);
+        assert!(app.is_too_small());
+
+        handle_key(
+            &mut app,
+            KeyCode::Char('d'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert!(app.pending_actions.is_empty());
+        handle_key(
+            &mut app,
+            KeyCode::Char('q'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn small_popup_gives_the_session_list_the_room() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        let mut app = App::for_test(&mock);
+        app.show_preview = true;
+        app.resize(90, 26);
+        assert!(screen_text(&app, 90, 26).contains("clients)"));
+
+        app.popup = true;
+        assert!(app.popup_narrow() && app.popup_short());
+        let text = screen_text(&app, 90, 26);
+        assert!(!text.contains("clients)"));
+        assert!(text.contains("Preview: api"));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::service::{
+    ServiceAction, ServiceUnit, control_service, list_service_units, service_status,
+};
+
+use super::{App, AppMode};
+
+// Journal lines shown under a unit's status in the services popup.
+pub const SERVICE_LOG_LINES: usize = 10;
+
+impl App {
+    // Opens the services popup, or explains how to configure it.
+    pub fn open_services(&mut self) {
+        if self.service_patterns.is_empty() {
+            self.warn("No agent services configured: set [services] units in config.toml");
+            return;
+        }
+        self.services_selected = 0;
+        self.service_status.clear();
+        self.services_message = None;
+        self.refresh_services();
+        self.mode = AppMode::Services;
+    }
+
+    pub fn close_services(&mut self) {
+        self.service_status.clear();
+        self.services_message = None;
+        self.mode = AppMode::Normal;
+    }
+
+    /* Re-lists the configured units; runs with the agent scan so the agent
+     * box count stays current. A failed listing keeps the last units. */
+    pub fn refresh_services(&mut self) {
+        if self.service_patterns.is_empty() {
+            return;
+        }
+        match list_service_units(&self.service_patterns) {
+            Ok(units) => {
+                self.services = units;
+                self.services_selected = self
+                    .services_selected
+                    .min(self.services.len().saturating_sub(1));
+            }
+            Err(err) => self.services_message = Some(format!("{:#}", err)),
+        }
+    }
+
+    pub fn select_service_next(&mut self) {
+        if !self.services.is_empty() {
+            self.services_selected = (self.services_selected + 1).min(self.services.len() - 1);
+        }
+        self.service_status.clear();
+    }
+
+    pub fn select_service_previous(&mut self) {
+        self.services_selected = self.services_selected.saturating_sub(1);
+        self.service_status.clear();
+    }
+
+    pub fn selected_service(&self) -> Option<&ServiceUnit> {
+        self.services.get(self.services_selected)
+    }
+
+    // Starts, stops, or restarts the highlighted unit and records the outcome.
+    pub fn control_selected_service(&mut self, action: ServiceAction) {
+        let Some(name) = self.selected_service().map(|unit| unit.name.clone()) else {
+            return;
+        };
+        self.services_message = Some(match control_service(&name, action) {
+            Ok(()) => format!("Ran {} on {}", action.verb(), name),
+            Err(err) => format!("{:#}", err),
+        });
+        self.refresh_services();
+        if !self.service_status.is_empty() {
+            self.show_selected_service_status();
+        }
+    }
+
+    // Loads `systemctl status` for the highlighted unit into the popup.
+    pub fn show_selected_service_status(&mut self) {
+        let Some(name) = self.selected_service().map(|unit| unit.name.clone()) else {
+            return;
+        };
+        match service_status(&name, SERVICE_LOG_LINES) {
+            Ok(lines) => self.service_status = lines,
+            Err(err) => self.services_message = Some(format!("{:#}", err)),
+        }
+    }
+
+    // Running and total configured units, for the agent box title; None when none are configured.
+    pub fn service_counts(&self) -> Option<(usize, usize)> {
+        if self.service_patterns.is_empty() {
+            return None;
+        }
+        let running = self
+            .services
+            .iter()
+            .filter(|unit| unit.is_running())
+            .count();
+        Some((running, self.services.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    fn unit(name: &str, active: &str) -> ServiceUnit {
+        ServiceUnit {
+            name: name.to_string(),
+            active: active.to_string(),
+            sub: "running".to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn services_popup_needs_configured_patterns() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        let mut app = App::for_test(&mock);
+        app.service_patterns.clear();
+
+        app.open_services();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.current_toast().is_some());
+        assert_eq!(app.service_counts(), None);
+
+        app.service_patterns = vec!["agent-*.service".to_string()];
+        app.services = vec![
+            unit("agent-a.service", "active"),
+            unit("agent-b.service", "inactive"),
+        ];
+        assert_eq!(app.service_counts(), Some((1, 2)));
+        app.mode = AppMode::Services;
+        app.select_service_next();
+        app.select_service_next();
+        assert_eq!(app.selected_service().unwrap().name, "agent-b.service");
+        crate::tui::render_to_buffer(&app, 100, 30).unwrap();
+
+        app.close_services();
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+}
@@ -1,109 +1,469 @@
+use crate::clipboard;
+use crate::config::SparklineScale;
+use crate::git::GitStatus;
+use crate::health::HealthScore;
 use crate::tmux::TmuxSession;
 
-use super::{App, SessionAction};
+use std::time::Instant;
+
+use super::{App, AppMode, PendingKind, QUICK_SWITCH_KEYS, SessionAction, SessionRef};
 
 impl App {
     // Moves selection to the next session (wraps around).
     pub fn select_next(&mut self) {
-        if !self.filtered_indices.is_empty() {
-            self.selected_index = (self.selected_index + 1) % self.filtered_indices.len();
-        }
+        self.sessions.next();
     }
 
     // Moves selection to the previous session (wraps around).
     pub fn select_previous(&mut self) {
-        if !self.filtered_indices.is_empty() {
-            self.selected_index = if self.selected_index == 0 {
-                self.filtered_indices.len() - 1
-            } else {
-                self.selected_index - 1
-            };
-        }
+        self.sessions.previous();
     }
 
     // Moves selection to the first session.
     pub fn select_first(&mut self) {
-        self.selected_index = 0;
+        self.sessions.first();
     }
 
     // Moves selection to the last session.
     pub fn select_last(&mut self) {
-        if !self.filtered_indices.is_empty() {
-            self.selected_index = self.filtered_indices.len() - 1;
-        }
+        self.sessions.last();
     }
 
     // Returns the currently selected session, if any.
     pub fn selected_session(&self) -> Option<&TmuxSession> {
-        self.filtered_indices
-            .get(self.selected_index)
-            .and_then(|&idx| self.sessions.get(idx))
+        self.sessions.selected_item()
+    }
+
+    /* The session named `name` for an action, by id when it is listed;
+     * otherwise by name, as tmux would resolve it. */
+    pub fn session_ref(&self, name: &str) -> SessionRef {
+        match self.sessions.items.iter().find(|s| s.name == name) {
+            Some(session) => SessionRef::of(session),
+            None => SessionRef {
+                target: name.to_string(),
+                name: name.to_string(),
+            },
+        }
     }
 
     // Sets action to attach to the selected session and quits.
     pub fn attach_selected(&mut self) {
         if let Some(session) = self.selected_session() {
-            self.action = Some(SessionAction::Attach(session.name.clone()));
+            self.action = Some(SessionAction::Attach(SessionRef::of(session)));
             self.should_quit = true;
         }
     }
 
-    // Sets action to delete the selected session and quits.
-    pub fn delete_selected(&mut self) {
+    // Attaches to the session at a 1-based position in the current list order.
+    pub fn attach_by_number(&mut self, number: usize) {
+        if (1..=QUICK_SWITCH_KEYS).contains(&number) && number <= self.sessions.len() {
+            self.sessions.select(number - 1);
+            self.attach_selected();
+        }
+    }
+
+    // Sets action to attach read-only to the selected session and quits.
+    pub fn attach_selected_read_only(&mut self) {
         if let Some(session) = self.selected_session() {
-            self.action = Some(SessionAction::Delete(session.name.clone()));
+            self.action = Some(SessionAction::AttachReadOnly(SessionRef::of(session)));
             self.should_quit = true;
         }
     }
 
-    // Sets action to delete all sessions and quits.
-    pub fn delete_all(&mut self) {
-        self.action = Some(SessionAction::DeleteAll);
+    /* Sets action to clone the selected session and quits. The clone is
+     * named after the source with the first free `-N` suffix. */
+    pub fn clone_selected(&mut self) {
+        let Some(source) = self.selected_session().map(SessionRef::of) else {
+            return;
+        };
+        let name = (2..)
+            .map(|n| format!("{}-{}", source.name, n))
+            .find(|name| !self.sessions.items.iter().any(|s| &s.name == name))
+            .expect("some suffix is free");
+        self.action = Some(SessionAction::Clone { source, name });
         self.should_quit = true;
     }
 
-    // Sets action to detach the selected session and quits.
-    pub fn detach_selected(&mut self) {
+    // Kills the selected session after the undo grace period.
+    pub fn delete_selected(&mut self) {
+        self.queue_pending(PendingKind::Kill);
+    }
+
+    // Copies the selected session's working directory to the clipboard.
+    pub fn yank_selected_path(&mut self) {
+        let Some(session) = self.selected_session() else {
+            return;
+        };
+        match session.path.as_ref() {
+            Some(path) => self.yank(path.display().to_string()),
+            None => self.warn(format!("{} has no path", session.name)),
+        }
+    }
+
+    // Copies a `tmux attach -t <name>` command for the selected session.
+    pub fn yank_attach_command(&mut self) {
         if let Some(session) = self.selected_session() {
-            self.action = Some(SessionAction::Detach(session.name.clone()));
-            self.should_quit = true;
+            let command = format!("tmux attach -t {}", clipboard::shell_quote(&session.name));
+            self.yank(command);
+        }
+    }
+
+    fn yank(&mut self, text: String) {
+        match clipboard::copy(&text) {
+            Ok(method) => self.info(format!("Copied {} ({})", text, method)),
+            Err(error) => self.error(format!("Copy failed: {}", error)),
         }
     }
 
-    // Sets action to detach all sessions and quits.
+    /* Opens the configured git UI (lazygit by default) in a new window of the
+     * selected session and attaches to it. Only offered for dirty repos. */
+    pub fn open_git_ui(&mut self) {
+        let Some(session) = self.selected_session() else {
+            return;
+        };
+        let (name, path) = match (&session.path, &session.git_status) {
+            (Some(path), Some(git)) if git.is_repo && git.dirty_count > 0 => {
+                (session.name.clone(), path.clone())
+            }
+            _ => {
+                self.warn(format!("{} has no uncommitted changes", session.name));
+                return;
+            }
+        };
+
+        let command = self.git_ui.clone();
+        let program = command.split_whitespace().next().unwrap_or_default();
+        if which::which(program).is_err() {
+            self.error(format!("{} not found in PATH", program));
+            return;
+        }
+
+        match self
+            .tmux
+            .new_window_with_command(&name, program, &path, &command)
+        {
+            Ok(()) => {
+                self.action = Some(SessionAction::Attach(self.session_ref(&name)));
+                self.should_quit = true;
+            }
+            Err(error) => self.error(format!("{:#}", error)),
+        }
+    }
+
+    // Deletes all sessions by killing the tmux server.
+    pub fn delete_all(&mut self) {
+        match self.tmux.delete_all_sessions() {
+            Ok(()) => self.info("Deleted all sessions"),
+            Err(error) => self.error(format!("{:#}", error)),
+        }
+        self.schedule.session_list.force();
+    }
+
+    // Detaches the selected session's clients after the undo grace period.
+    pub fn detach_selected(&mut self) {
+        self.queue_pending(PendingKind::Detach);
+    }
+
+    // Detaches every client from every session.
     pub fn detach_all(&mut self) {
-        self.action = Some(SessionAction::DetachAll);
-        self.should_quit = true;
+        match self.tmux.detach_all_sessions() {
+            Ok(()) => self.info("Detached all clients"),
+            Err(error) => self.error(format!("{:#}", error)),
+        }
+        self.schedule.session_list.force();
+    }
+
+    // Calculates a session's health score with the configured weights.
+    pub fn session_health(&self, session: &TmuxSession) -> HealthScore {
+        HealthScore::calculate_with(session, &self.health_weights)
     }
 
     // Checks if we're at the top of the session list (for navigation to agents).
     pub fn at_top_of_sessions(&self) -> bool {
-        self.selected_index == 0
+        self.sessions.selected() == 0
+    }
+
+    // Converts a session CPU percentage for display, normalizing by core count when enabled.
+    pub fn display_cpu(&self, cpu_percent: f64) -> f64 {
+        if self.normalize_cpu {
+            crate::sysinfo::normalize_cpu_percent(cpu_percent, self.system_stats.num_cpus)
+        } else {
+            cpu_percent
+        }
+    }
+
+    // Toggles between per-core CPU percentages and share of the whole machine.
+    pub fn toggle_normalize_cpu(&mut self) {
+        self.normalize_cpu = !self.normalize_cpu;
+    }
+
+    // Re-reads git status for every session with a known path.
+    pub fn refresh_git_status(&mut self) {
+        for session in &mut self.sessions.items {
+            if let Some(ref path) = session.path {
+                session.git_status = Some(GitStatus::for_path(path));
+            }
+        }
+    }
+
+    /* Freezes or resumes stats sampling. On resume, each sampling interval
+     * missed while paused is recorded as a gap in the sparkline history. */
+    pub fn toggle_sampling_pause(&mut self) {
+        match self.sampling_paused_at.take() {
+            Some(paused_at) => {
+                let interval = self.schedule.session_stats.interval().as_secs_f64();
+                let missed = (paused_at.elapsed().as_secs_f64() / interval) as usize;
+                let limit = self.history_length;
+                for session in &mut self.sessions.items {
+                    for _ in 0..missed.min(limit) {
+                        push_history(&mut session.cpu_history, None, limit);
+                        push_history(&mut session.mem_history, None, limit);
+                    }
+                }
+                self.schedule.session_stats.force();
+            }
+            None => self.sampling_paused_at = Some(Instant::now()),
+        }
+    }
+
+    pub fn sampling_paused(&self) -> bool {
+        self.sampling_paused_at.is_some()
+    }
+
+    // Switches sparklines between per-session and shared (global) scaling.
+    pub fn toggle_sparkline_scale(&mut self) {
+        self.sparkline_scale = match self.sparkline_scale {
+            SparklineScale::Session => SparklineScale::Global,
+            SparklineScale::Global => SparklineScale::Session,
+        };
+    }
+
+    /* Returns the fixed CPU and memory maxima for sparklines, or None when
+     * each sparkline scales to its own data. */
+    pub fn sparkline_max(&self) -> Option<(u64, u64)> {
+        if self.sparkline_scale == SparklineScale::Session {
+            return None;
+        }
+        let peak = |history: fn(&TmuxSession) -> &Vec<Option<u64>>| {
+            self.sessions
+                .items
+                .iter()
+                .flat_map(|session| history(session).iter().flatten())
+                .copied()
+                .max()
+                .unwrap_or(0)
+                .max(1)
+        };
+        Some((peak(|s| &s.cpu_history), peak(|s| &s.mem_history)))
+    }
+
+    // Widens the chart averaging window (zoom out), up to the full history.
+    pub fn zoom_chart_out(&mut self) {
+        self.chart_window = (self.chart_window * 2).min(self.history_length);
+    }
+
+    // Narrows the chart averaging window (zoom in), down to the latest sample.
+    pub fn zoom_chart_in(&mut self) {
+        self.chart_window = (self.chart_window / 2).max(1);
+    }
+
+    /* Returns a session's CPU and memory averaged over the chart window.
+     * A window of one sample uses the live stats. */
+    pub fn chart_values(&self, session: &TmuxSession) -> Option<(u64, u64)> {
+        let stats = session.stats.as_ref()?;
+        if self.chart_window <= 1 {
+            return Some((stats.cpu_percent as u64, stats.mem_mb));
+        }
+        let average = |history: &[Option<u64>]| {
+            let start = history.len().saturating_sub(self.chart_window);
+            let samples: Vec<u64> = history[start..].iter().flatten().copied().collect();
+            (!samples.is_empty()).then(|| samples.iter().sum::<u64>() / samples.len() as u64)
+        };
+        Some((
+            average(&session.cpu_history).unwrap_or(stats.cpu_percent as u64),
+            average(&session.mem_history).unwrap_or(stats.mem_mb),
+        ))
+    }
+
+    /* Reloads the session list from tmux so sessions created or killed
+     * elsewhere show up. Stats, history, and git status carry over by
+     * session id, so a rename done outside trex keeps them; new sessions get
+     * their git status right away. */
+    pub fn reload_sessions(&mut self, matcher: &mut nucleo::Matcher) {
+        let mut sessions = match self.tmux.list_sessions() {
+            Ok(sessions) => sessions,
+            Err(error) => {
+                self.error(format!("tmux error: {}", error));
+                return;
+            }
+        };
+
+        let mut renames = Vec::new();
+        for session in &mut sessions {
+            match self
+                .sessions
+                .items
+                .iter_mut()
+                .find(|old| old.is_same(session))
+            {
+                Some(old) => {
+                    if old.name != session.name {
+                        renames.push((old.name.clone(), session.name.clone()));
+                    }
+                    session.git_status = old.git_status.take();
+                    session.stats = old.stats.take();
+                    session.cpu_history = std::mem::take(&mut old.cpu_history);
+                    session.mem_history = std::mem::take(&mut old.mem_history);
+                }
+                None => {
+                    if let Some(ref path) = session.path {
+                        session.git_status = Some(GitStatus::for_path(path));
+                    }
+                }
+            }
+        }
+
+        if self.sort_flagged {
+            sort_flagged_first(&mut sessions);
+        }
+
+        let selected_target = self
+            .selected_session()
+            .map(|session| session.target().to_string());
+        let previous_index = self.sessions.selected();
+        self.sessions.items = sessions;
+        self.line_cache.clear();
+        self.follow_renames(&renames);
+        self.apply_filter(matcher);
+        self.restore_selection(selected_target.as_deref(), previous_index);
+        self.update_policies();
+
+        if self.mode == AppMode::ExpandedSession {
+            self.refresh_expanded_windows(matcher);
+        }
+        if self.mode == AppMode::SessionDetail && self.detail_target().is_none() {
+            self.close_session_detail();
+        }
+    }
+
+    /* Points state that names a session (expanded view, prompts, mirror
+     * source, pending kills) at its new name after a rename elsewhere. */
+    fn follow_renames(&mut self, renames: &[(String, String)]) {
+        for (old_name, new_name) in renames {
+            for name in [
+                &mut self.expanded_session,
+                &mut self.note_session,
+                &mut self.tags_session,
+                &mut self.process_tree_session,
+                &mut self.detail_session,
+                &mut self.mirror_source,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                if name == old_name {
+                    name.clone_from(new_name);
+                }
+            }
+            for pending in &mut self.pending_actions {
+                if &pending.session == old_name {
+                    pending.session.clone_from(new_name);
+                }
+            }
+        }
+    }
+
+    /* Keeps the cursor on the same session after the list changes, matched
+     * by tmux target so a renamed session stays selected. If that session
+     * is gone, the cursor stays at its old position instead of jumping back
+     * to the top. */
+    fn restore_selection(&mut self, selected_target: Option<&str>, previous_index: usize) {
+        let found = selected_target.is_some_and(|target| {
+            self.sessions
+                .select_where(|session| session.target() == target)
+        });
+        if !found {
+            self.sessions.select(previous_index);
+        }
     }
 
     // Refreshes system stats for all sessions.
     pub fn refresh_session_stats(&mut self) {
-        const MAX_HISTORY: usize = 20;
+        if let Ok(system_stats) = crate::sysinfo::get_system_stats() {
+            self.system_stats = system_stats;
+        }
+
+        let limit = self.history_length;
+        for session in &mut self.sessions.items {
+            if let Ok((stats, processes)) = crate::sysinfo::get_session_processes(&session.name) {
+                if self.expanded_session.as_ref() == Some(&session.name) {
+                    self.window_stats = crate::sysinfo::window_totals(&processes);
+                }
+
+                // The process tree overlay reuses this scan for its session
+                if self.process_tree_session.as_ref() == Some(&session.name) {
+                    self.process_tree = processes;
+                }
 
-        for session in &mut self.sessions {
-            if let Ok(stats) = crate::sysinfo::get_session_stats(&session.name) {
                 let cpu_percent = stats.cpu_percent;
                 let mem_mb = stats.mem_mb;
 
                 session.stats = Some(stats);
 
                 // Update CPU history (convert f64 to u64 for sparkline)
-                session.cpu_history.push(cpu_percent as u64);
-                if session.cpu_history.len() > MAX_HISTORY {
-                    session.cpu_history.remove(0);
-                }
+                push_history(&mut session.cpu_history, Some(cpu_percent as u64), limit);
 
                 // Update memory history
-                session.mem_history.push(mem_mb);
-                if session.mem_history.len() > MAX_HISTORY {
-                    session.mem_history.remove(0);
-                }
+                push_history(&mut session.mem_history, Some(mem_mb), limit);
             }
         }
+
+        self.process_tree_selected = self
+            .process_tree_selected
+            .min(self.process_tree.len().saturating_sub(1));
+        self.record_timeline();
+        self.update_alerts();
+    }
+}
+
+// Appends a sample to a sparkline history, dropping the oldest past `limit`.
+fn push_history(history: &mut Vec<Option<u64>>, sample: Option<u64>, limit: usize) {
+    history.push(sample);
+    if history.len() > limit {
+        history.remove(0);
+    }
+}
+
+/* Moves sessions with a tmux bell, activity, or silence flag ahead of the
+ * rest for `[ui] sort_flagged`, keeping tmux's order within each group. */
+pub(super) fn sort_flagged_first(sessions: &mut [TmuxSession]) {
+    sessions.sort_by_key(|session| !session.alerts.any());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::{MockTmux, TmuxBackend, WindowAlerts};
+
+    #[test]
+    fn reload_lists_flagged_sessions_first_when_asked() {
+        let mock = MockTmux::with_sessions(&["api", "docs", "web"]);
+        let mut app = App::for_test_with(&mock, mock.list_sessions().unwrap(), 1);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        mock.state().sessions[2].alerts = WindowAlerts {
+            bell: true,
+            ..Default::default()
+        };
+
+        app.reload_sessions(&mut matcher);
+        let names: Vec<&str> = app.sessions.visible().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["api", "docs", "web"]);
+
+        app.sort_flagged = true;
+        app.reload_sessions(&mut matcher);
+        let names: Vec<&str> = app.sessions.visible().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["web", "api", "docs"]);
+        assert_eq!(app.selected_session().unwrap().name, "docs");
+        crate::tui::render_to_buffer(&app, 100, 30).unwrap();
     }
 }
@@ -0,0 +1,171 @@
+use super::{App, AppMode};
+
+// The screen trex opens on instead of the session list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StartupScreen {
+    #[default]
+    Sessions,
+    Stats,
+    Charts,
+    Timeline,
+    Expanded(String), // a session name
+}
+
+/* Where the TUI starts, from the `--stats`, `--charts`, `--timeline`,
+ * `--expanded <session>`, and `--filter <query>` flags, so a keybinding
+ * can open trex straight into a view. */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StartupView {
+    pub screen: StartupScreen,
+    pub filter: Option<String>,
+    pub selected: Option<String>, // session to highlight, when still listed
+    pub error: Option<String>,    // why the last selected action didn't run
+    pub popup: bool,              // running in a tmux popup (`--popup`)
+}
+
+impl App {
+    /* Applies the startup flags once the app is loaded. A filter is typed
+     * into the session filter, left open for more typing unless another
+     * screen was asked for; an expanded session is looked up among the
+     * sessions the filter leaves. */
+    pub fn apply_startup_view(&mut self, view: &StartupView, matcher: &mut nucleo::Matcher) {
+        self.popup = view.popup;
+        if let Some(query) = view.filter.as_deref().filter(|query| !query.is_empty()) {
+            self.filter_input = query.to_string();
+            self.apply_filter(matcher);
+            self.mode = AppMode::Filtering;
+        }
+        if let Some(name) = &view.selected {
+            self.sessions.select_where(|session| &session.name == name);
+        }
+
+        match &view.screen {
+            StartupScreen::Sessions => {}
+            StartupScreen::Stats => {
+                self.stats_selected = 0;
+                self.mode = AppMode::StatsOverlay;
+            }
+            StartupScreen::Charts => {
+                self.chart_selected = 0;
+                self.mode = AppMode::BarChartView;
+            }
+            StartupScreen::Timeline => self.open_timeline(),
+            StartupScreen::Expanded(name) => {
+                if self.sessions.select_where(|session| &session.name == name) {
+                    self.expand_selected();
+                } else {
+                    self.warn(format!("No session named {} to expand", name));
+                }
+            }
+        }
+
+        if let Some(error) = &view.error {
+            self.error(error.clone());
+        }
+    }
+
+    /* The view to reopen on when the selected action fails: the screen it
+     * was chosen from, the session filter, and the highlighted session, so
+     * the user can pick again where they left off. */
+    pub fn resume_view(&self) -> StartupView {
+        let screen = match self.mode {
+            AppMode::StatsOverlay => StartupScreen::Stats,
+            AppMode::BarChartView => StartupScreen::Charts,
+            AppMode::Timeline => StartupScreen::Timeline,
+            AppMode::ExpandedSession => match &self.expanded_session {
+                Some(name) => StartupScreen::Expanded(name.clone()),
+                None => StartupScreen::Sessions,
+            },
+            _ => StartupScreen::Sessions,
+        };
+        StartupView {
+            screen,
+            filter: Some(self.filter_input.clone()).filter(|filter| !filter.is_empty()),
+            selected: self.selected_session().map(|session| session.name.clone()),
+            error: None,
+            popup: self.popup,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    #[test]
+    fn startup_view_filters_and_opens_the_requested_screen() {
+        let mock = MockTmux::with_sessions(&["api", "docs", "web"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        let view = StartupView {
+            screen: StartupScreen::Sessions,
+            filter: Some("doc".to_string()),
+            selected: None,
+            error: None,
+            popup: false,
+        };
+        app.apply_startup_view(&view, &mut matcher);
+        assert_eq!(app.mode, AppMode::Filtering);
+        assert_eq!(app.sessions.filtered().len(), 1);
+
+        let view = StartupView {
+            screen: StartupScreen::Expanded("docs".to_string()),
+            ..StartupView::default()
+        };
+        app.apply_startup_view(&view, &mut matcher);
+        assert_eq!(app.mode, AppMode::ExpandedSession);
+        assert_eq!(app.expanded_session.as_deref(), Some("docs"));
+
+        app.mode = AppMode::Normal;
+        let view = StartupView {
+            screen: StartupScreen::Expanded("gone".to_string()),
+            ..StartupView::default()
+        };
+        app.apply_startup_view(&view, &mut matcher);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.current_toast().is_some());
+
+        let view = StartupView {
+            screen: StartupScreen::Stats,
+            ..StartupView::default()
+        };
+        app.apply_startup_view(&view, &mut matcher);
+        assert_eq!(app.mode, AppMode::StatsOverlay);
+
+        let view = StartupView {
+            error: Some("Session gone no longer exists".to_string()),
+            ..StartupView::default()
+        };
+        app.apply_startup_view(&view, &mut matcher);
+        assert!(
+            app.toasts
+                .iter()
+                .any(|toast| toast.message == "Session gone no longer exists")
+        );
+    }
+
+    #[test]
+    fn resume_view_reopens_where_the_action_was_chosen() {
+        let mock = MockTmux::with_sessions(&["api", "docs", "web"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.sessions.select_where(|session| session.name == "web");
+        app.mode = AppMode::BarChartView;
+        let view = app.resume_view();
+        assert_eq!(view.screen, StartupScreen::Charts);
+        assert_eq!(view.selected.as_deref(), Some("web"));
+        assert_eq!(view.filter, None);
+
+        let mut app = App::for_test(&mock);
+        app.apply_startup_view(
+            &StartupView {
+                screen: StartupScreen::Sessions,
+                ..view
+            },
+            &mut matcher,
+        );
+        assert_eq!(app.selected_session().unwrap().name, "web");
+    }
+}
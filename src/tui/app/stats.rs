@@ -0,0 +1,247 @@
+use crate::health::HealthLevel;
+use crate::process::AiProcessInfo;
+use crate::tmux::TmuxSession;
+use std::time::Instant;
+
+use super::{App, SessionAction};
+
+// Rows listed in each ranking panel of the stats overlay.
+const TOP_SESSIONS: usize = 5;
+const CRITICAL_SESSIONS: usize = 3;
+const RECENT_SESSIONS: usize = 10;
+
+// The stats overlay panels that list sessions, in the order they are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsPanel {
+    Cpu,
+    Memory,
+    Health,
+    AgentCpu,
+    AgentWaiting,
+    Activity,
+}
+
+/* One listed row of the stats overlay: its panel and the session it names.
+ * Agents outside tmux list with no session. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatsRow {
+    pub panel: StatsPanel,
+    pub session: Option<String>,
+}
+
+impl App {
+    // Sessions with stats by CPU use, busiest first.
+    pub fn top_sessions_by_cpu(&self) -> Vec<&TmuxSession> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .items
+            .iter()
+            .filter(|s| s.stats.is_some())
+            .collect();
+        sessions.sort_by(|a, b| cpu_of(b).total_cmp(&cpu_of(a)));
+        sessions.truncate(TOP_SESSIONS);
+        sessions
+    }
+
+    // Sessions with stats by memory use, largest first.
+    pub fn top_sessions_by_memory(&self) -> Vec<&TmuxSession> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .items
+            .iter()
+            .filter(|s| s.stats.is_some())
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.stats.as_ref().map_or(0, |s| s.mem_mb)));
+        sessions.truncate(TOP_SESSIONS);
+        sessions
+    }
+
+    // The first few sessions in critical health, with their scores.
+    pub fn critical_sessions(&self) -> Vec<(&TmuxSession, u8)> {
+        self.sessions
+            .items
+            .iter()
+            .map(|s| (s, self.session_health(s)))
+            .filter(|(_, health)| health.level() == HealthLevel::Critical)
+            .map(|(s, health)| (s, health.score))
+            .take(CRITICAL_SESSIONS)
+            .collect()
+    }
+
+    // Sessions by last activity, most recent first.
+    pub fn recently_active_sessions(&self) -> Vec<&TmuxSession> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .items
+            .iter()
+            .filter(|s| s.last_activity.is_some())
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
+        sessions.truncate(RECENT_SESSIONS);
+        sessions
+    }
+
+    // Every row the stats overlay lists, in the order arrow keys walk them.
+    pub fn stats_rows(&self) -> Vec<StatsRow> {
+        let named = |panel, session: &TmuxSession| StatsRow {
+            panel,
+            session: Some(session.name.clone()),
+        };
+        let agent = |panel, agent: &AiProcessInfo| StatsRow {
+            panel,
+            session: agent.tmux_session.clone().filter(|s| s != "(tmux)"),
+        };
+
+        let mut rows = Vec::new();
+        for session in self.top_sessions_by_cpu() {
+            rows.push(named(StatsPanel::Cpu, session));
+        }
+        for session in self.top_sessions_by_memory() {
+            rows.push(named(StatsPanel::Memory, session));
+        }
+        for (session, _) in self.critical_sessions() {
+            rows.push(named(StatsPanel::Health, session));
+        }
+        for (a, _) in self.agents_by_cpu().into_iter().take(TOP_SESSIONS) {
+            rows.push(agent(StatsPanel::AgentCpu, a));
+        }
+        for (a, _) in self
+            .agents_by_waiting(Instant::now())
+            .into_iter()
+            .take(TOP_SESSIONS)
+        {
+            rows.push(agent(StatsPanel::AgentWaiting, a));
+        }
+        for session in self.recently_active_sessions() {
+            rows.push(named(StatsPanel::Activity, session));
+        }
+        rows
+    }
+
+    // The highlighted row's panel and its position within the panel.
+    pub fn stats_selection(&self) -> Option<(StatsPanel, usize)> {
+        let rows = self.stats_rows();
+        let row = rows.get(self.stats_selected)?;
+        let position = rows[..self.stats_selected]
+            .iter()
+            .filter(|earlier| earlier.panel == row.panel)
+            .count();
+        Some((row.panel, position))
+    }
+
+    pub fn select_stats_next(&mut self) {
+        let len = self.stats_rows().len();
+        if len > 0 {
+            self.stats_selected = (self.stats_selected + 1).min(len - 1);
+        }
+    }
+
+    pub fn select_stats_previous(&mut self) {
+        self.stats_selected = self.stats_selected.saturating_sub(1);
+    }
+
+    // The session named by the highlighted row; warns when the row has none.
+    fn selected_stats_session(&mut self) -> Option<String> {
+        let row = self.stats_rows().into_iter().nth(self.stats_selected)?;
+        if row.session.is_none() {
+            self.warn("That agent is not running in a tmux session");
+        }
+        row.session
+    }
+
+    // Attaches to the session of the highlighted row.
+    pub fn attach_stats_selected(&mut self) {
+        if let Some(name) = self.selected_stats_session() {
+            self.action = Some(SessionAction::Attach(self.session_ref(&name)));
+            self.should_quit = true;
+        }
+    }
+
+    // Kills the session of the highlighted row after the undo grace period.
+    pub fn kill_stats_selected(&mut self) {
+        if let Some(name) = self.selected_stats_session() {
+            self.kill_named(&name);
+        }
+    }
+}
+
+fn cpu_of(session: &TmuxSession) -> f64 {
+    session
+        .stats
+        .as_ref()
+        .map_or(0.0, |stats| stats.cpu_percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysinfo::SessionStats;
+    use crate::tmux::{MockTmux, TmuxBackend};
+    use crate::tui::app::{AppMode, PendingKind};
+
+    fn stats_app(mock: &MockTmux) -> App {
+        let mut sessions = mock.list_sessions().unwrap();
+        for (session, (cpu, mem)) in sessions.iter_mut().zip([(10.0, 900), (80.0, 100)]) {
+            session.last_activity = None;
+            session.stats = Some(SessionStats {
+                cpu_percent: cpu,
+                mem_mb: mem,
+                ..Default::default()
+            });
+        }
+        let mut app = App::for_test_with(mock, sessions, 0);
+        app.agents.set_items(Vec::new());
+        app.mode = AppMode::StatsOverlay;
+        app
+    }
+
+    #[test]
+    fn overlay_rows_follow_the_panels() {
+        let mock = MockTmux::with_sessions(&["api", "web"]);
+        let mut app = stats_app(&mock);
+
+        let rows: Vec<_> = app
+            .stats_rows()
+            .into_iter()
+            .map(|row| (row.panel, row.session.unwrap()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (StatsPanel::Cpu, "web".to_string()),
+                (StatsPanel::Cpu, "api".to_string()),
+                (StatsPanel::Memory, "api".to_string()),
+                (StatsPanel::Memory, "web".to_string()),
+            ]
+        );
+
+        assert_eq!(app.stats_selection(), Some((StatsPanel::Cpu, 0)));
+        for _ in 0..5 {
+            app.select_stats_next();
+        }
+        assert_eq!(app.stats_selection(), Some((StatsPanel::Memory, 1)));
+        app.select_stats_previous();
+        assert_eq!(app.stats_selection(), Some((StatsPanel::Memory, 0)));
+    }
+
+    #[test]
+    fn overlay_attaches_and_kills_the_highlighted_session() {
+        let mock = MockTmux::with_sessions(&["api", "web"]);
+        let mut app = stats_app(&mock);
+
+        app.select_stats_next();
+        app.kill_stats_selected();
+        assert_eq!(
+            app.pending_for("api").map(|p| p.kind),
+            Some(PendingKind::Kill)
+        );
+        app.undo_pending();
+        assert!(app.pending_for("api").is_none());
+
+        app.attach_stats_selected();
+        assert!(
+            matches!(&app.action, Some(SessionAction::Attach(session)) if session.name == "api")
+        );
+        assert!(app.should_quit);
+    }
+}
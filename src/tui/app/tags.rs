@@ -0,0 +1,40 @@
+use crate::tmux::parser::parse_tags;
+
+use super::{App, AppMode};
+
+impl App {
+    // Opens the tag prompt for the selected session, prefilled with its tags.
+    pub fn start_tags_edit(&mut self) {
+        let Some((name, tags)) = self
+            .selected_session()
+            .map(|session| (session.name.clone(), session.tags.join(", ")))
+        else {
+            return;
+        };
+        self.tags_input = tags;
+        self.tags_session = Some(name);
+        self.mode = AppMode::EditingTags;
+    }
+
+    // Saves the normalized tags to tmux; no tags clears the option.
+    pub fn confirm_tags(&mut self) {
+        if let Some(name) = self.tags_session.take() {
+            let tags = parse_tags(&self.tags_input);
+            match self.tmux.set_session_tags(&name, &tags) {
+                Ok(()) => {
+                    if let Some(session) = self.sessions.items.iter_mut().find(|s| s.name == name) {
+                        session.tags = tags;
+                    }
+                }
+                Err(error) => self.error(format!("{:#}", error)),
+            }
+        }
+        self.cancel_tags();
+    }
+
+    pub fn cancel_tags(&mut self) {
+        self.tags_session = None;
+        self.tags_input.clear();
+        self.mode = AppMode::Normal;
+    }
+}
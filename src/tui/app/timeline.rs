@@ -0,0 +1,101 @@
+use crate::timeline::{ActivityTimeline, unix_now};
+
+use super::{App, AppMode};
+
+// Spans the timeline view can cover, narrowest first: 6 hours to a week.
+pub const TIMELINE_SPANS: [u64; 4] = [6 * 3600, 86400, 3 * 86400, 7 * 86400];
+
+impl App {
+    // Opens the timeline view on the week, keeping the session selection.
+    pub fn open_timeline(&mut self) {
+        self.timeline_span = TIMELINE_SPANS.len() - 1;
+        self.mode = AppMode::Timeline;
+    }
+
+    // Seconds of history the timeline view shows.
+    pub fn timeline_span_secs(&self) -> u64 {
+        TIMELINE_SPANS[self.timeline_span.min(TIMELINE_SPANS.len() - 1)]
+    }
+
+    pub fn zoom_timeline_in(&mut self) {
+        self.timeline_span = self.timeline_span.saturating_sub(1);
+    }
+
+    pub fn zoom_timeline_out(&mut self) {
+        self.timeline_span = (self.timeline_span + 1).min(TIMELINE_SPANS.len() - 1);
+    }
+
+    /* Adds a sample of every session and its agents to the activity
+     * timeline; runs with each stats refresh, so a sample stands for one
+     * stats interval. */
+    pub fn record_timeline(&mut self) {
+        let agent_sessions: Vec<&str> = self
+            .agents
+            .items
+            .iter()
+            .filter_map(|agent| agent.tmux_session.as_deref())
+            .collect();
+        self.timeline.record(
+            &self.sessions.items,
+            &agent_sessions,
+            self.schedule.session_stats.interval().as_secs(),
+            unix_now(),
+        );
+    }
+
+    // Loads the timeline saved at the last exit; called when the TUI starts.
+    pub fn load_timeline(&mut self) {
+        self.timeline = ActivityTimeline::load();
+    }
+
+    pub fn save_timeline(&self) {
+        if let Err(error) = self.timeline.save() {
+            log::warn!("timeline: {}", error);
+        }
+    }
+
+    /* The unix time a session's bar starts: when tmux created it, or when
+     * the timeline first saw it if tmux didn't say. */
+    pub fn timeline_start(&self, session: &crate::tmux::TmuxSession) -> Option<u64> {
+        session
+            .created
+            .or_else(|| self.timeline.first_seen(&session.name))
+    }
+
+    // The current unix time, for drawing the timeline up to now.
+    pub fn timeline_now(&self) -> u64 {
+        unix_now()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    #[test]
+    fn timeline_zooms_between_spans_and_records_sessions() {
+        let mock = MockTmux::with_sessions(&["api", "web"]);
+        let mut app = App::for_test(&mock);
+
+        app.open_timeline();
+        assert_eq!(app.mode, AppMode::Timeline);
+        assert_eq!(app.timeline_span_secs(), 7 * 86400);
+        app.zoom_timeline_out();
+        assert_eq!(app.timeline_span_secs(), 7 * 86400);
+        for _ in 0..5 {
+            app.zoom_timeline_in();
+        }
+        assert_eq!(app.timeline_span_secs(), 6 * 3600);
+
+        app.sessions.items[0].last_activity = Some(unix_now());
+        app.record_timeline();
+        let now = unix_now();
+        assert_eq!(app.timeline.intensity("api", now, now + 1), Some(1.0));
+        assert_eq!(app.timeline.intensity("web", now, now + 1), Some(0.0));
+        // Mock sessions have no creation time, so bars start at the first sample
+        assert!(app.timeline_start(&app.sessions.items[1]).is_some());
+
+        crate::tui::render_to_buffer(&app, 100, 30).unwrap();
+    }
+}
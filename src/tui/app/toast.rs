@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use super::App;
+
+// How long each toast stays on the bottom line.
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+// A transient one-line message reporting the result of an action.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub message: String,
+    shown_at: Option<Instant>, // set when the toast reaches the front of the queue
+}
+
+impl App {
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push_toast(ToastLevel::Info, message.into());
+    }
+
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.push_toast(ToastLevel::Warn, message.into());
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push_toast(ToastLevel::Error, message.into());
+    }
+
+    // Queues a toast; repeats of the last queued message are dropped so a
+    // failing background refresh does not flood the queue.
+    fn push_toast(&mut self, level: ToastLevel, message: String) {
+        if self
+            .toasts
+            .back()
+            .is_some_and(|last| last.level == level && last.message == message)
+        {
+            return;
+        }
+        self.toasts.push_back(Toast {
+            level,
+            message,
+            shown_at: None,
+        });
+    }
+
+    // Returns the toast currently on screen, if any.
+    pub fn current_toast(&self) -> Option<&Toast> {
+        self.toasts.front()
+    }
+
+    /* Starts the front toast's timer the first time it is shown and drops
+     * it once TOAST_DURATION has passed, so queued toasts each get their
+     * full time on screen. */
+    pub fn advance_toasts(&mut self, now: Instant) {
+        while let Some(toast) = self.toasts.front_mut() {
+            let shown_at = *toast.shown_at.get_or_insert(now);
+            if now.duration_since(shown_at) < TOAST_DURATION {
+                break;
+            }
+            self.toasts.pop_front();
+        }
+    }
+}
@@ -1,51 +1,301 @@
-use crate::tmux::{TmuxClient, TmuxWindow};
+use crate::tmux::TmuxWindow;
 
 use super::{App, AppMode, SessionAction};
 
+// Seconds of quiet before `monitor-silence` flags a window, when `s` turns it on.
+pub const MONITOR_SILENCE_SECS: u32 = 30;
+
+// What `x`/`X` kill in the expanded view, once confirmed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KillTarget {
+    Window {
+        session: String,
+        index: u32,
+        name: String,
+    },
+    Pane {
+        session: String,
+        index: u32,
+        name: String,
+    },
+}
+
+impl KillTarget {
+    // The confirmation question shown on the help line.
+    pub fn prompt(&self) -> String {
+        match self {
+            KillTarget::Window {
+                session,
+                index,
+                name,
+            } => format!("Kill window {}:{} {}?", session, index, name),
+            KillTarget::Pane {
+                session,
+                index,
+                name,
+            } => format!("Kill active pane of {}:{} {}?", session, index, name),
+        }
+    }
+}
+
 impl App {
     // Expands the selected session to show its windows.
     pub fn expand_selected(&mut self) {
         if let Some(session) = self.selected_session() {
             let session_name = session.name.clone();
-            if let Ok(windows) = TmuxClient::list_windows(&session_name) {
-                self.expanded_session = Some(session_name);
-                self.expanded_windows = windows;
-                self.selected_window_index = 0;
-                self.mode = AppMode::ExpandedSession;
+            match self.tmux.list_windows(&session_name) {
+                Ok(windows) => {
+                    self.expanded_session = Some(session_name);
+                    self.expanded_windows.set_items(windows);
+                    self.expanded_windows.first();
+                    self.window_filtering = false;
+                    self.window_filter_input.clear();
+                    self.mode = AppMode::ExpandedSession;
+                    // Sample now so per-window stats appear right away
+                    self.window_stats.clear();
+                    self.schedule.session_stats.force();
+                }
+                Err(error) => self.error(format!("tmux error: {}", error)),
             }
         }
     }
 
-    // Collapses the expanded session view.
+    // Collapses the expanded session view, returning to an active filter if any.
     pub fn collapse_session(&mut self) {
         self.expanded_session = None;
-        self.expanded_windows.clear();
-        self.selected_window_index = 0;
-        self.mode = AppMode::Normal;
+        self.expanded_windows.set_items(Vec::new());
+        self.window_stats.clear();
+        self.window_filtering = false;
+        self.window_filter_input.clear();
+        self.mode = if self.filter_input.is_empty() {
+            AppMode::Normal
+        } else {
+            AppMode::Filtering
+        };
+    }
+
+    /* Reloads the expanded session's windows, keeping the selected window
+     * by its tmux index and the window filter applied. Collapses if the
+     * session no longer exists. */
+    pub fn refresh_expanded_windows(&mut self, matcher: &mut nucleo::Matcher) {
+        let Some(session_name) = self.expanded_session.clone() else {
+            return;
+        };
+        if !self
+            .sessions
+            .items
+            .iter()
+            .any(|session| session.name == session_name)
+        {
+            self.collapse_session();
+            return;
+        }
+        let windows = match self.tmux.list_windows(&session_name) {
+            Ok(windows) => windows,
+            Err(error) => {
+                self.error(format!("tmux error: {}", error));
+                return;
+            }
+        };
+
+        let selected = self.selected_window().map(|window| window.index);
+        let previous_index = self.expanded_windows.selected();
+        self.expanded_windows.items = windows;
+        self.apply_window_filter(matcher);
+        let found = selected.is_some_and(|index| {
+            self.expanded_windows
+                .select_where(|window| window.index == index)
+        });
+        if !found {
+            self.expanded_windows.select(previous_index);
+        }
+    }
+
+    /* Fuzzy-matches the window filter against each window's name and
+     * current command, best match first. An empty filter shows every
+     * window in tmux order. */
+    pub fn apply_window_filter(&mut self, matcher: &mut nucleo::Matcher) {
+        if self.window_filter_input.is_empty() {
+            self.expanded_windows.show_all();
+        } else {
+            use nucleo::pattern::{CaseMatching, Normalization, Pattern};
+            use std::fmt::Write;
+
+            let pattern = Pattern::parse(
+                &self.window_filter_input,
+                CaseMatching::Smart,
+                Normalization::Smart,
+            );
+
+            // One haystack and char buffer, reused for every window
+            let (mut haystack, mut buf) = (String::new(), Vec::new());
+            let mut results: Vec<(usize, u32)> = self
+                .expanded_windows
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, window)| {
+                    haystack.clear();
+                    let _ = write!(haystack, "{} {}", window.name, window.current_command);
+                    let haystack_utf32 = nucleo::Utf32Str::new(&haystack, &mut buf);
+                    pattern
+                        .score(haystack_utf32, matcher)
+                        .map(|score| (idx, score))
+                })
+                .collect();
+
+            results.sort_by_key(|item| std::cmp::Reverse(item.1));
+            self.expanded_windows
+                .set_filtered(results.into_iter().map(|(idx, _)| idx).collect());
+        }
+
+        self.expanded_windows.first();
+    }
+
+    pub fn start_window_filter(&mut self) {
+        self.window_filtering = true;
+    }
+
+    // Clears the window filter and shows every window again.
+    pub fn clear_window_filter(&mut self, matcher: &mut nucleo::Matcher) {
+        self.window_filtering = false;
+        self.window_filter_input.clear();
+        self.apply_window_filter(matcher);
+    }
+
+    // Windows that pass the filter, in display order.
+    pub fn visible_windows(&self) -> impl Iterator<Item = &TmuxWindow> {
+        self.expanded_windows.visible()
     }
 
     // Moves selection to the next window (wraps around).
     pub fn select_next_window(&mut self) {
-        if !self.expanded_windows.is_empty() {
-            self.selected_window_index =
-                (self.selected_window_index + 1) % self.expanded_windows.len();
-        }
+        self.expanded_windows.next();
     }
 
     // Moves selection to the previous window (wraps around).
     pub fn select_previous_window(&mut self) {
-        if !self.expanded_windows.is_empty() {
-            self.selected_window_index = if self.selected_window_index == 0 {
-                self.expanded_windows.len() - 1
-            } else {
-                self.selected_window_index - 1
-            };
-        }
+        self.expanded_windows.previous();
     }
 
     // Returns the currently selected window, if any.
     pub fn selected_window(&self) -> Option<&TmuxWindow> {
-        self.expanded_windows.get(self.selected_window_index)
+        self.expanded_windows.selected_item()
+    }
+
+    /* Asks to kill the selected window (or its active pane with `pane`).
+     * Nothing runs until confirm_window_kill. */
+    pub fn request_window_kill(&mut self, pane: bool) {
+        let (Some(session), Some(window)) = (self.expanded_session.clone(), self.selected_window())
+        else {
+            return;
+        };
+        let (index, name) = (window.index, window.name.clone());
+        self.window_kill = Some(if pane {
+            KillTarget::Pane {
+                session,
+                index,
+                name,
+            }
+        } else {
+            KillTarget::Window {
+                session,
+                index,
+                name,
+            }
+        });
+    }
+
+    pub fn cancel_window_kill(&mut self) {
+        self.window_kill = None;
+    }
+
+    /* Runs the confirmed kill, then reloads sessions so the window list,
+     * window and pane counts, and a session ended by its last window all
+     * update at once. */
+    pub fn confirm_window_kill(&mut self, matcher: &mut nucleo::Matcher) {
+        let Some(target) = self.window_kill.take() else {
+            return;
+        };
+        let result = match &target {
+            KillTarget::Window { session, index, .. } => self.tmux.kill_window(session, *index),
+            KillTarget::Pane { session, index, .. } => self.tmux.kill_pane(session, *index),
+        };
+        match (result, &target) {
+            (Ok(()), KillTarget::Window { name, .. }) => {
+                self.info(format!("Killed window {}", name))
+            }
+            (Ok(()), KillTarget::Pane { name, .. }) => {
+                self.info(format!("Killed a pane in {}", name))
+            }
+            (Err(error), _) => self.error(format!("{:#}", error)),
+        }
+        self.reload_sessions(matcher);
+    }
+
+    /* Restarts the selected window's exited pane with its original
+     * command, then reloads the window list so the dead marker clears. */
+    pub fn respawn_selected_window(&mut self, matcher: &mut nucleo::Matcher) {
+        let (Some(session), Some(window)) = (self.expanded_session.clone(), self.selected_window())
+        else {
+            return;
+        };
+        let (index, name) = (window.index, window.name.clone());
+        if !window.dead {
+            self.warn(format!("Window {} is still running", name));
+            return;
+        }
+        match self.tmux.respawn_pane(&session, index) {
+            Ok(()) => self.info(format!("Respawned {}", name)),
+            Err(error) => self.error(format!("{:#}", error)),
+        }
+        self.refresh_expanded_windows(matcher);
+    }
+
+    /* Turns tmux's `monitor-activity` on or off for the selected window,
+     * so output in it raises the activity flag while nobody is viewing it,
+     * then reloads the window list to show the new setting. */
+    pub fn toggle_window_activity_monitor(&mut self, matcher: &mut nucleo::Matcher) {
+        let (Some(session), Some(window)) = (self.expanded_session.clone(), self.selected_window())
+        else {
+            return;
+        };
+        let (index, name, on) = (window.index, window.name.clone(), !window.monitor_activity);
+        let value = if on { "on" } else { "off" };
+        match self
+            .tmux
+            .set_window_option(&session, index, "monitor-activity", value)
+        {
+            Ok(()) if on => self.info(format!("Watching {} for activity", name)),
+            Ok(()) => self.info(format!("Stopped watching {} for activity", name)),
+            Err(error) => self.error(format!("{:#}", error)),
+        }
+        self.refresh_expanded_windows(matcher);
+    }
+
+    /* Turns tmux's `monitor-silence` on (MONITOR_SILENCE_SECS) or off for
+     * the selected window, so a long-running job that finishes or stalls
+     * raises the silence flag. */
+    pub fn toggle_window_silence_monitor(&mut self, matcher: &mut nucleo::Matcher) {
+        let (Some(session), Some(window)) = (self.expanded_session.clone(), self.selected_window())
+        else {
+            return;
+        };
+        let (index, name) = (window.index, window.name.clone());
+        let secs = if window.monitor_silence > 0 {
+            0
+        } else {
+            MONITOR_SILENCE_SECS
+        };
+        match self
+            .tmux
+            .set_window_option(&session, index, "monitor-silence", &secs.to_string())
+        {
+            Ok(()) if secs > 0 => self.info(format!("Watching {} for {}s of silence", name, secs)),
+            Ok(()) => self.info(format!("Stopped watching {} for silence", name)),
+            Err(error) => self.error(format!("{:#}", error)),
+        }
+        self.refresh_expanded_windows(matcher);
     }
 
     // Attaches to the selected window.
@@ -53,10 +303,143 @@ impl App {
         if let (Some(session_name), Some(window)) = (&self.expanded_session, self.selected_window())
         {
             self.action = Some(SessionAction::AttachWindow(
-                session_name.clone(),
-                window.index,
+                self.session_ref(session_name),
+                window.clone(),
             ));
             self.should_quit = true;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+
+    fn window(index: u32, name: &str, command: &str) -> TmuxWindow {
+        TmuxWindow {
+            index,
+            name: name.to_string(),
+            active: index == 0,
+            current_command: command.to_string(),
+            dead: false,
+            alerts: Default::default(),
+            monitor_activity: false,
+            monitor_silence: 0,
+            id: format!("@{}", index + 10),
+        }
+    }
+
+    #[test]
+    fn window_filter_matches_name_and_command() {
+        let mock = MockTmux::with_sessions(&["dev"]);
+        mock.state().windows.insert(
+            "dev".to_string(),
+            vec![
+                window(0, "editor", "nvim"),
+                window(1, "tests", "cargo"),
+                window(2, "shell", "zsh"),
+            ],
+        );
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        app.expand_selected();
+        app.window_filter_input = "cargo".to_string();
+        app.apply_window_filter(&mut matcher);
+        assert_eq!(app.selected_window().map(|w| w.index), Some(1));
+
+        // A reload keeps the filter and the selected window
+        mock.state()
+            .windows
+            .get_mut("dev")
+            .unwrap()
+            .insert(0, window(5, "cargo-watch", "cargo"));
+        app.refresh_expanded_windows(&mut matcher);
+        assert_eq!(app.expanded_windows.len(), 2);
+        assert_eq!(app.selected_window().map(|w| w.index), Some(1));
+
+        app.clear_window_filter(&mut matcher);
+        assert_eq!(app.visible_windows().count(), 4);
+    }
+
+    #[test]
+    fn window_kill_waits_for_confirmation() {
+        let mock = MockTmux::with_sessions(&["dev"]);
+        mock.state().windows.insert(
+            "dev".to_string(),
+            vec![window(0, "editor", "nvim"), window(1, "tests", "cargo")],
+        );
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        app.expand_selected();
+        app.select_next_window();
+        app.request_window_kill(false);
+        app.cancel_window_kill();
+        assert!(!mock.calls().iter().any(|call| call.starts_with("kill")));
+
+        app.request_window_kill(false);
+        app.confirm_window_kill(&mut matcher);
+        assert!(mock.calls().contains(&"kill-window dev:1".to_string()));
+        assert_eq!(app.visible_windows().count(), 1);
+
+        // Killing the last window ends the session and collapses the view
+        app.request_window_kill(false);
+        app.confirm_window_kill(&mut matcher);
+        assert!(mock.session_names().is_empty());
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn respawn_only_runs_on_dead_panes() {
+        let mock = MockTmux::with_sessions(&["dev"]);
+        let mut crashed = window(1, "server", "bash");
+        crashed.dead = true;
+        mock.state().windows.insert(
+            "dev".to_string(),
+            vec![window(0, "editor", "nvim"), crashed],
+        );
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        app.expand_selected();
+        app.respawn_selected_window(&mut matcher);
+        assert!(!mock.calls().iter().any(|call| call.starts_with("respawn")));
+
+        app.select_next_window();
+        app.respawn_selected_window(&mut matcher);
+        assert!(mock.calls().contains(&"respawn-pane dev:1".to_string()));
+        assert!(!app.selected_window().unwrap().dead);
+    }
+
+    #[test]
+    fn monitor_toggles_set_window_options() {
+        let mock = MockTmux::with_sessions(&["dev"]);
+        mock.state().windows.insert(
+            "dev".to_string(),
+            vec![window(0, "editor", "nvim"), window(1, "agent", "claude")],
+        );
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        app.expand_selected();
+        app.select_next_window();
+        app.toggle_window_silence_monitor(&mut matcher);
+        assert!(
+            mock.calls()
+                .contains(&"set-option -w dev:1 monitor-silence 30".to_string())
+        );
+        assert_eq!(app.selected_window().unwrap().monitor_silence, 30);
+        assert_eq!(
+            app.current_toast().unwrap().message,
+            "Watching agent for 30s of silence"
+        );
+        app.toggle_window_silence_monitor(&mut matcher);
+        assert_eq!(app.selected_window().unwrap().monitor_silence, 0);
+
+        app.toggle_window_activity_monitor(&mut matcher);
+        assert!(app.selected_window().unwrap().monitor_activity);
+        crate::tui::render_to_buffer(&app, 100, 30).unwrap();
+    }
+}
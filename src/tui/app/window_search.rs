@@ -0,0 +1,147 @@
+use super::{App, AppMode, SessionAction};
+
+impl App {
+    // Loads every window of every session and opens the window search.
+    pub fn open_window_search(&mut self) {
+        match self.tmux.list_all_windows() {
+            Ok(windows) => {
+                self.window_search_indices = (0..windows.len()).collect();
+                self.window_search = windows;
+                self.window_search_input.clear();
+                self.window_search_selected = 0;
+                self.mode = AppMode::WindowSearch;
+            }
+            Err(error) => self.error(format!("tmux error: {}", error)),
+        }
+    }
+
+    pub fn close_window_search(&mut self) {
+        self.window_search.clear();
+        self.window_search_indices.clear();
+        self.window_search_input.clear();
+        self.mode = AppMode::Normal;
+    }
+
+    /* Fuzzy-matches the search against `session:window command` for every
+     * window, best match first. An empty search lists windows in tmux order. */
+    pub fn apply_window_search(&mut self, matcher: &mut nucleo::Matcher) {
+        if self.window_search_input.is_empty() {
+            self.window_search_indices = (0..self.window_search.len()).collect();
+        } else {
+            use nucleo::pattern::{CaseMatching, Normalization, Pattern};
+            use std::fmt::Write;
+
+            let pattern = Pattern::parse(
+                &self.window_search_input,
+                CaseMatching::Smart,
+                Normalization::Smart,
+            );
+
+            // One haystack and char buffer, reused for every window
+            let (mut haystack, mut buf) = (String::new(), Vec::new());
+            let mut results: Vec<(usize, u32)> = self
+                .window_search
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, (session, window))| {
+                    haystack.clear();
+                    let _ = write!(
+                        haystack,
+                        "{}:{} {}",
+                        session, window.name, window.current_command
+                    );
+                    let haystack_utf32 = nucleo::Utf32Str::new(&haystack, &mut buf);
+                    pattern
+                        .score(haystack_utf32, matcher)
+                        .map(|score| (idx, score))
+                })
+                .collect();
+
+            results.sort_by_key(|item| std::cmp::Reverse(item.1));
+            self.window_search_indices = results.into_iter().map(|(idx, _)| idx).collect();
+        }
+
+        self.window_search_selected = 0;
+    }
+
+    pub fn select_window_search_next(&mut self) {
+        if !self.window_search_indices.is_empty() {
+            self.window_search_selected =
+                (self.window_search_selected + 1) % self.window_search_indices.len();
+        }
+    }
+
+    pub fn select_window_search_previous(&mut self) {
+        if !self.window_search_indices.is_empty() {
+            self.window_search_selected = if self.window_search_selected == 0 {
+                self.window_search_indices.len() - 1
+            } else {
+                self.window_search_selected - 1
+            };
+        }
+    }
+
+    // Sets action to attach to the highlighted window and quits.
+    pub fn confirm_window_search(&mut self) {
+        let selected = self
+            .window_search_indices
+            .get(self.window_search_selected)
+            .and_then(|&idx| self.window_search.get(idx));
+        if let Some((session, window)) = selected {
+            self.action = Some(SessionAction::AttachWindow(
+                self.session_ref(session),
+                window.clone(),
+            ));
+            self.should_quit = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::{MockTmux, TmuxWindow};
+
+    fn window(index: u32, name: &str, command: &str) -> TmuxWindow {
+        TmuxWindow {
+            index,
+            name: name.to_string(),
+            active: false,
+            current_command: command.to_string(),
+            dead: false,
+            alerts: Default::default(),
+            monitor_activity: false,
+            monitor_silence: 0,
+            id: String::new(),
+        }
+    }
+
+    #[test]
+    fn search_attaches_to_matching_window() {
+        let mock = MockTmux::with_sessions(&["dev", "api"]);
+        {
+            let mut state = mock.state();
+            state
+                .windows
+                .insert("dev".to_string(), vec![window(0, "editor", "nvim")]);
+            state.windows.insert(
+                "api".to_string(),
+                vec![window(0, "shell", "zsh"), window(3, "server", "cargo")],
+            );
+        }
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        app.open_window_search();
+        assert_eq!(app.window_search_indices.len(), 3);
+
+        app.window_search_input = "api cargo".to_string();
+        app.apply_window_search(&mut matcher);
+        app.confirm_window_search();
+        assert!(matches!(
+            app.action,
+            Some(SessionAction::AttachWindow(ref session, ref window))
+                if session.name == "api" && window.index == 3
+        ));
+    }
+}
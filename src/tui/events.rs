@@ -1,5 +1,34 @@
-use crate::tui::app::{App, AppMode, FocusArea};
-use crossterm::event::{KeyCode, KeyModifiers};
+use crate::directory::SourceKind;
+use crate::export::ExportFormat;
+use crate::service::ServiceAction;
+use crate::tui::app::{
+    App, AppMode, ChartMetric, CheckpointKind, FocusArea, PageStep, PendingKind,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+/* Turns a terminal key event into the code and modifiers the handlers
+ * match on, or None for events to ignore (key releases). Terminals on
+ * the enhanced (kitty) keyboard protocol report Shift-Tab as Tab with
+ * SHIFT and may report shifted letters as lowercase with SHIFT; both are
+ * folded into the legacy forms, so `BackTab` and `Char('G')` match the
+ * same way everywhere. SHIFT is then dropped from characters, since the
+ * character itself carries it. */
+pub fn normalize_key(key: KeyEvent) -> Option<(KeyCode, KeyModifiers)> {
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+    let code = match key.code {
+        KeyCode::Tab if shift => KeyCode::BackTab,
+        KeyCode::Char(c) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+        code => code,
+    };
+    let modifiers = match code {
+        KeyCode::Char(_) | KeyCode::BackTab => key.modifiers - KeyModifiers::SHIFT,
+        _ => key.modifiers,
+    };
+    Some((code, modifiers))
+}
 
 // Handles a key event and updates the app state accordingly.
 pub fn handle_key(
@@ -8,29 +37,128 @@ pub fn handle_key(
     modifiers: KeyModifiers,
     matcher: &mut nucleo::Matcher,
 ) {
+    // Agent navigation below works on the agents visible right now
+    app.sync_visible_agents();
+
+    // Nothing but the size warning is drawn, so only quitting works
+    if app.is_too_small() {
+        if code == KeyCode::Char('q')
+            || (modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('c'))
+        {
+            app.should_quit = true;
+        }
+        return;
+    }
+
+    // The performance HUD toggles from any mode
+    if code == KeyCode::F(12) {
+        app.toggle_perf_hud();
+        return;
+    }
+
+    // Ctrl-Enter and Alt-Enter only reach us on the enhanced keyboard
+    // protocol; they work in filter mode too, where A and R type
+    let attaching = matches!(app.mode, AppMode::Normal | AppMode::Filtering);
+    if attaching && code == KeyCode::Enter {
+        if modifiers.contains(KeyModifiers::CONTROL) && app.focus == FocusArea::Sessions {
+            app.attach_selected_takeover();
+            return;
+        }
+        if modifiers.contains(KeyModifiers::ALT) {
+            match app.focus {
+                FocusArea::Agents => app.attach_selected_agent_read_only(),
+                FocusArea::Sessions => app.attach_selected_read_only(),
+            }
+            return;
+        }
+    }
+
     if modifiers.contains(KeyModifiers::CONTROL) {
         match code {
             KeyCode::Char('c') | KeyCode::Char('t') => {
                 app.should_quit = true;
                 return;
             }
+            // Batch kill/detach of the sessions matching a `#tag` filter
+            KeyCode::Char('d') if app.mode == AppMode::Filtering => {
+                app.queue_pending_for_tag(PendingKind::Kill);
+                return;
+            }
+            KeyCode::Char('x') if app.mode == AppMode::Filtering => {
+                app.queue_pending_for_tag(PendingKind::Detach);
+                return;
+            }
+            KeyCode::Char('u') if app.mode == AppMode::Filtering => {
+                app.undo_pending();
+                return;
+            }
+            KeyCode::Char('n') if app.mode == AppMode::NamingSession => {
+                app.use_suggested_session_name();
+                return;
+            }
+            KeyCode::Char('e') if app.mode == AppMode::NamingSession => {
+                app.toggle_naming_command_focus();
+                return;
+            }
+            KeyCode::Char('d') if app.mode == AppMode::NamingSession => {
+                app.toggle_dev_env();
+                return;
+            }
+            // Half-page scroll where there's a list to page, and nothing
+            // elsewhere: the plain d/u of other screens must not fire
+            KeyCode::Char('d') => {
+                app.page_list(PageStep::HalfDown);
+                return;
+            }
+            KeyCode::Char('u') => {
+                app.page_list(PageStep::HalfUp);
+                return;
+            }
             _ => {}
         }
     }
 
+    let page_step = match code {
+        KeyCode::PageDown => Some(PageStep::Down),
+        KeyCode::PageUp => Some(PageStep::Up),
+        _ => None,
+    };
+    if page_step.is_some_and(|step| app.page_list(step)) {
+        return;
+    }
+
     match app.mode {
         AppMode::Normal => handle_normal_mode(app, code, matcher),
         AppMode::Filtering => handle_filter_mode(app, code, matcher),
         AppMode::SelectingDirectory => handle_dir_mode(app, code, matcher),
         AppMode::NamingSession => handle_naming_mode(app, code),
-        AppMode::ExpandedSession => handle_expanded_mode(app, code),
+        AppMode::SelectingAgent => handle_agent_launch_mode(app, code),
+        AppMode::ExpandedSession => handle_expanded_mode(app, code, matcher),
         AppMode::BarChartView => handle_barchart_mode(app, code),
         AppMode::StatsOverlay => handle_stats_overlay_mode(app, code),
+        AppMode::Timeline => handle_timeline_mode(app, code),
+        AppMode::HealthDetail => handle_health_detail_mode(app, code),
+        AppMode::ProcessTree => handle_process_tree_mode(app, code),
+        AppMode::Services => handle_services_mode(app, code),
+        AppMode::Layouts => handle_layouts_mode(app, code),
+        AppMode::NamingLayout => handle_layout_name_mode(app, code),
+        AppMode::SelectingMirrorTarget => handle_mirror_mode(app, code),
+        AppMode::ClientList => handle_client_list_mode(app, code),
+        AppMode::EditingNote => handle_note_mode(app, code),
+        AppMode::MessagingAgents => handle_agent_message_mode(app, code),
+        AppMode::PolicyReview => handle_policy_review_mode(app, code),
+        AppMode::EditingTags => handle_tags_mode(app, code),
+        AppMode::WindowSearch => handle_window_search_mode(app, code, matcher),
+        AppMode::Legend => handle_legend_mode(app, code),
+        AppMode::SessionDetail => handle_session_detail_mode(app, code),
+        AppMode::PreviewCopy => handle_preview_copy_mode(app, code),
+        AppMode::Diff => handle_diff_mode(app, code),
+        AppMode::Checkpointing => handle_checkpoint_mode(app, code),
     }
 }
 
 // Handles key events in normal mode (session list navigation and actions).
-fn handle_normal_mode(app: &mut App, code: KeyCode, _matcher: &mut nucleo::Matcher) {
+fn handle_normal_mode(app: &mut App, code: KeyCode, matcher: &mut nucleo::Matcher) {
     match code {
         KeyCode::Char('j') | KeyCode::Down => {
             match app.focus {
@@ -81,10 +209,43 @@ fn handle_normal_mode(app: &mut App, code: KeyCode, _matcher: &mut nucleo::Match
             app.refresh_preview();
         }
 
+        // Agent fleet: mark agents, then message, pause, or resume them
+        KeyCode::Char(' ') if app.focus == FocusArea::Agents => app.toggle_agent_mark(),
+        KeyCode::Char('m') if app.focus == FocusArea::Agents => app.start_agent_message(),
+        KeyCode::Char('z') if app.focus == FocusArea::Agents => {
+            app.signal_fleet(libc::SIGSTOP, "Paused");
+        }
+        KeyCode::Char('Z') if app.focus == FocusArea::Agents => {
+            app.signal_fleet(libc::SIGCONT, "Resumed");
+        }
+        KeyCode::Char('W') => app.attach_next_waiting_agent(),
+
+        // Needs-attention inbox: select its next session, even if filtered out
+        KeyCode::Char('!') => {
+            app.focus = FocusArea::Sessions;
+            app.select_next_inbox(matcher);
+        }
+
+        // Resize the preview while it is shown
+        KeyCode::Char('[') if app.show_preview => app.resize_preview(false),
+        KeyCode::Char(']') if app.show_preview => app.resize_preview(true),
+        KeyCode::Char('o') if app.show_preview => app.flip_preview(),
+
+        // Review queue: add/remove the session, then visit queued sessions in turn
+        KeyCode::Char('Q') => app.toggle_queued(),
+        KeyCode::Char('>') => app.attach_next_in_queue(),
+
+        // Idle policies: review the sessions a kill policy matches
+        KeyCode::Char('I') => app.open_policy_review(),
+
         KeyCode::Enter => match app.focus {
             FocusArea::Agents => app.attach_selected_agent(),
             FocusArea::Sessions => app.attach_selected(),
         },
+        KeyCode::Char('R') => match app.focus {
+            FocusArea::Agents => app.attach_selected_agent_read_only(),
+            FocusArea::Sessions => app.attach_selected_read_only(),
+        },
         KeyCode::Char('d') if app.focus == FocusArea::Sessions => {
             app.delete_selected();
         }
@@ -97,7 +258,20 @@ fn handle_normal_mode(app: &mut App, code: KeyCode, _matcher: &mut nucleo::Match
         KeyCode::Char('X') if app.focus == FocusArea::Sessions => {
             app.detach_all();
         }
-        KeyCode::Char('c') => app.mode = AppMode::SelectingDirectory,
+        KeyCode::Char('u') => app.undo_pending(),
+
+        // Open lazygit (or the configured git UI) for a dirty session
+        KeyCode::Char('L') if app.focus == FocusArea::Sessions => app.open_git_ui(),
+
+        // Copy the session path, or the command to attach to it
+        KeyCode::Char('y') if app.focus == FocusArea::Sessions => app.yank_selected_path(),
+        KeyCode::Char('Y') if app.focus == FocusArea::Sessions => app.yank_attach_command(),
+
+        // Edit the selected session's note
+        KeyCode::Char('e') if app.focus == FocusArea::Sessions => app.start_note_edit(),
+        KeyCode::Char('t') if app.focus == FocusArea::Sessions => app.start_tags_edit(),
+        KeyCode::Char('c') => app.open_directory_selection(matcher),
+        KeyCode::Char('a') => app.open_agent_launcher(matcher),
 
         // Window expansion (only from session focus)
         KeyCode::Char('l') | KeyCode::Right if app.focus == FocusArea::Sessions => {
@@ -109,11 +283,99 @@ fn handle_normal_mode(app: &mut App, code: KeyCode, _matcher: &mut nucleo::Match
 
         KeyCode::Char('/') => app.mode = AppMode::Filtering,
 
+        // Search windows across all sessions
+        KeyCode::Char('w') => app.open_window_search(),
+
         // Bar chart view toggle
-        KeyCode::Char('b') => app.mode = AppMode::BarChartView,
+        KeyCode::Char('b') => {
+            app.chart_selected = 0;
+            app.mode = AppMode::BarChartView;
+        }
+
+        // Session timeline over the last week
+        KeyCode::Char('v') => app.open_timeline(),
 
         // Stats overlay toggle
-        KeyCode::Char('s') => app.mode = AppMode::StatsOverlay,
+        KeyCode::Char('s') => {
+            app.stats_selected = 0;
+            app.mode = AppMode::StatsOverlay;
+        }
+
+        // Refresh stats, agents, and git status immediately
+        KeyCode::Char('r') => app.refresh_now(),
+
+        // Quick-switch: 1-9 attach to the session at that position
+        KeyCode::Char(c @ '1'..='9') => app.attach_by_number(c as usize - '0' as usize),
+
+        // Attach and detach other clients, or list attached clients
+        KeyCode::Char('A') if app.focus == FocusArea::Sessions => app.attach_selected_takeover(),
+        KeyCode::Char('C') if app.focus == FocusArea::Sessions => app.open_client_list(),
+
+        // Clone the selected session's directory and windows under a new name
+        KeyCode::Char('K') if app.focus == FocusArea::Sessions => app.clone_selected(),
+
+        // Mirror the selected session's active window into another session
+        KeyCode::Char('M') if app.focus == FocusArea::Sessions => app.start_mirror(),
+
+        // Process tree for the selected session
+        KeyCode::Char('T') if app.focus == FocusArea::Sessions => app.open_process_tree(),
+
+        // Refresh SSH_AUTH_SOCK, DISPLAY, ... in a session from a new connection
+        KeyCode::Char('E') if app.focus == FocusArea::Sessions => {
+            app.refresh_selected_environment(|name| std::env::var(name).ok())
+        }
+
+        // Save the session's window layout, or apply a saved one to it
+        KeyCode::Char('O') if app.focus == FocusArea::Sessions => app.open_layouts(),
+
+        // Agents run as systemd user services
+        KeyCode::Char('U') => app.open_services(),
+
+        // Pause/resume stats sampling
+        KeyCode::Char('P') => app.toggle_sampling_pause(),
+
+        // Sparkline scale toggle (per session vs. shared)
+        KeyCode::Char('S') => app.toggle_sparkline_scale(),
+
+        // CPU normalization toggle (per core vs. whole machine)
+        KeyCode::Char('n') => app.toggle_normalize_cpu(),
+
+        // Health breakdown for the selected session
+        KeyCode::Char('H')
+            if app.focus == FocusArea::Sessions && app.selected_session().is_some() =>
+        {
+            app.mode = AppMode::HealthDetail
+        }
+
+        // Pin the preview to the selected agent's pane, or unpin it
+        KeyCode::Char('F') => match app.focus {
+            FocusArea::Agents => app.toggle_follow_selected_agent(),
+            FocusArea::Sessions => app.stop_following(),
+        },
+
+        // Copy mode over the selected session's pane
+        KeyCode::Char('V') if app.focus == FocusArea::Sessions => app.start_preview_copy(),
+
+        // Uncommitted changes in the selected session's repo
+        KeyCode::Char('f') if app.focus == FocusArea::Sessions => app.open_diff(),
+
+        // Checkpoint the selected session's repo: commit everything, or stash it
+        KeyCode::Char('+') if app.focus == FocusArea::Sessions => {
+            app.start_checkpoint(CheckpointKind::Commit)
+        }
+        KeyCode::Char('-') if app.focus == FocusArea::Sessions => {
+            app.start_checkpoint(CheckpointKind::Stash)
+        }
+
+        // Full-screen detail page for the selected session
+        KeyCode::Char('i')
+            if app.focus == FocusArea::Sessions && app.selected_session().is_some() =>
+        {
+            app.open_session_detail()
+        }
+
+        // Icon legend with the activity and health thresholds
+        KeyCode::Char('?') => app.mode = AppMode::Legend,
 
         KeyCode::Esc | KeyCode::Char('q') => app.should_quit = true,
 
@@ -143,23 +405,38 @@ fn handle_filter_mode(app: &mut App, code: KeyCode, matcher: &mut nucleo::Matche
         }
         KeyCode::Down | KeyCode::Tab => app.select_next(),
         KeyCode::Up | KeyCode::BackTab => app.select_previous(),
+        // Expand keeps the filter; collapsing returns here
+        KeyCode::Right => app.expand_selected(),
         _ => {}
     }
 }
 
 // Handles key events in directory selection mode (creating new sessions).
 fn handle_dir_mode(app: &mut App, code: KeyCode, matcher: &mut nucleo::Matcher) {
+    if app.dir_create.is_some() {
+        match code {
+            KeyCode::Char('y') => app.confirm_dir_create(),
+            _ => app.cancel_dir_create(),
+        }
+        return;
+    }
+
     match code {
         KeyCode::Down => app.select_dir_next(),
         KeyCode::Up => app.select_dir_previous(),
         KeyCode::Home => app.select_dir_first(),
         KeyCode::End => app.select_dir_last(),
 
-        KeyCode::Enter => app.enter_naming_mode(),
+        KeyCode::Enter => app.choose_directory(),
 
         KeyCode::Char('+') | KeyCode::Char('=') => app.increase_depth(matcher),
         KeyCode::Char('-') | KeyCode::Char('_') => app.decrease_depth(matcher),
 
+        // F1-F6 toggle the directory sources, in the order the picker lists them
+        KeyCode::F(n) if (1..=SourceKind::ALL.len() as u8).contains(&n) => {
+            app.toggle_dir_source(SourceKind::ALL[n as usize - 1], matcher)
+        }
+
         KeyCode::Tab => {
             app.tab_complete_directory();
             app.apply_dir_filter(matcher);
@@ -180,6 +457,11 @@ fn handle_dir_mode(app: &mut App, code: KeyCode, matcher: &mut nucleo::Matcher)
 
         _ => {}
     }
+
+    // Navigation and filtering can both move the highlight
+    if app.mode == AppMode::SelectingDirectory {
+        app.refresh_dir_preview();
+    }
 }
 
 // Handles key events in session naming mode.
@@ -189,24 +471,61 @@ fn handle_naming_mode(app: &mut App, code: KeyCode) {
         KeyCode::Esc => app.cancel_naming(),
         KeyCode::Tab => app.select_next_template(),
         KeyCode::BackTab => app.select_previous_template(),
-        KeyCode::Backspace => {
-            app.session_name_input.pop();
-        }
-        KeyCode::Char(c) => {
-            app.session_name_input.push(c);
-        }
+        KeyCode::Up if app.naming_command_focus => app.recall_recent_command(true),
+        KeyCode::Down if app.naming_command_focus => app.recall_recent_command(false),
+        KeyCode::Backspace => app.pop_naming_char(),
+        KeyCode::Char(c) => app.push_naming_char(c),
+        _ => {}
+    }
+}
+
+// Handles key events in the agent launcher's agent picker.
+fn handle_agent_launch_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => app.select_launch_agent_next(),
+        KeyCode::Char('k') | KeyCode::Up => app.select_launch_agent_previous(),
+        KeyCode::Char('e') => app.toggle_agent_editor(),
+        KeyCode::Char('d') => app.toggle_dev_env(),
+        KeyCode::Enter => app.confirm_agent_launch(),
+        KeyCode::Esc => app.cancel_agent_choice(),
         _ => {}
     }
 }
 
 // Handles key events in expanded session mode (window list navigation).
-fn handle_expanded_mode(app: &mut App, code: KeyCode) {
+fn handle_expanded_mode(app: &mut App, code: KeyCode, matcher: &mut nucleo::Matcher) {
+    if app.window_kill.is_some() {
+        match code {
+            KeyCode::Char('y') => app.confirm_window_kill(matcher),
+            _ => app.cancel_window_kill(),
+        }
+        return;
+    }
+
+    if app.window_filtering {
+        handle_window_filter(app, code, matcher);
+        return;
+    }
+
     match code {
         KeyCode::Char('j') | KeyCode::Down => app.select_next_window(),
         KeyCode::Char('k') | KeyCode::Up => app.select_previous_window(),
 
         KeyCode::Enter => app.attach_selected_window(),
 
+        KeyCode::Char('/') => app.start_window_filter(),
+
+        // Kill the selected window or its active pane, after y/n confirmation
+        KeyCode::Char('x') => app.request_window_kill(false),
+        KeyCode::Char('X') => app.request_window_kill(true),
+
+        // Restart the selected window's exited pane
+        KeyCode::Char('r') => app.respawn_selected_window(matcher),
+
+        // Toggle tmux's activity and silence monitoring for the selected window
+        KeyCode::Char('a') => app.toggle_window_activity_monitor(matcher),
+        KeyCode::Char('s') => app.toggle_window_silence_monitor(matcher),
+
         // Collapse back to normal mode
         KeyCode::Char('h') | KeyCode::Left | KeyCode::Esc => app.collapse_session(),
 
@@ -216,12 +535,61 @@ fn handle_expanded_mode(app: &mut App, code: KeyCode) {
     }
 }
 
+// Handles typing in the expanded view's window filter.
+fn handle_window_filter(app: &mut App, code: KeyCode, matcher: &mut nucleo::Matcher) {
+    match code {
+        KeyCode::Esc => app.clear_window_filter(matcher),
+        KeyCode::Enter => app.attach_selected_window(),
+        KeyCode::Backspace => {
+            app.window_filter_input.pop();
+            app.apply_window_filter(matcher);
+            if app.window_filter_input.is_empty() {
+                app.window_filtering = false;
+            }
+        }
+        KeyCode::Char(c) => {
+            app.window_filter_input.push(c);
+            app.apply_window_filter(matcher);
+        }
+        KeyCode::Down | KeyCode::Tab => app.select_next_window(),
+        KeyCode::Up | KeyCode::BackTab => app.select_previous_window(),
+        _ => {}
+    }
+}
+
 // Handles key events in bar chart view mode.
 fn handle_barchart_mode(app: &mut App, code: KeyCode) {
     match code {
         KeyCode::Char('b') | KeyCode::Esc => {
             app.mode = AppMode::Normal;
         }
+        KeyCode::Char('+') | KeyCode::Char('=') => app.zoom_chart_out(),
+        KeyCode::Char('-') | KeyCode::Char('_') => app.zoom_chart_in(),
+        KeyCode::Char('l') | KeyCode::Right => app.select_bar_next(),
+        KeyCode::Char('h') | KeyCode::Left => app.select_bar_previous(),
+        KeyCode::Enter => app.attach_selected_bar(),
+        // Not `k`, which moves up everywhere else
+        KeyCode::Char('x') => app.kill_selected_bar(),
+        KeyCode::Char('u') => app.undo_pending(),
+        KeyCode::Char(c @ '1'..='5') => {
+            app.toggle_chart_metric(ChartMetric::ALL[c as usize - '1' as usize])
+        }
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the session timeline view.
+fn handle_timeline_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('v') | KeyCode::Esc => {
+            app.mode = AppMode::Normal;
+        }
+        KeyCode::Char('j') | KeyCode::Down => app.select_next(),
+        KeyCode::Char('k') | KeyCode::Up => app.select_previous(),
+        KeyCode::Enter => app.attach_selected(),
+        KeyCode::Char('+') | KeyCode::Char('=') => app.zoom_timeline_out(),
+        KeyCode::Char('-') | KeyCode::Char('_') => app.zoom_timeline_in(),
         KeyCode::Char('q') => app.should_quit = true,
         _ => {}
     }
@@ -233,7 +601,465 @@ fn handle_stats_overlay_mode(app: &mut App, code: KeyCode) {
         KeyCode::Char('s') | KeyCode::Esc => {
             app.mode = AppMode::Normal;
         }
+        KeyCode::Down => app.select_stats_next(),
+        KeyCode::Up => app.select_stats_previous(),
+        KeyCode::Enter => app.attach_stats_selected(),
+        // Not `k`, which moves up everywhere else
+        KeyCode::Char('x') => app.kill_stats_selected(),
+        KeyCode::Char('u') => app.undo_pending(),
+        KeyCode::Char('c') => app.export_stats(ExportFormat::Csv),
+        KeyCode::Char('J') => app.export_stats(ExportFormat::Json),
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the health detail popup.
+fn handle_health_detail_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('H') | KeyCode::Esc => {
+            app.mode = AppMode::Normal;
+        }
         KeyCode::Char('q') => app.should_quit = true,
         _ => {}
     }
 }
+
+// Handles key events in preview copy mode.
+fn handle_preview_copy_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => app.move_copy_cursor(1),
+        KeyCode::Char('k') | KeyCode::Up => app.move_copy_cursor(-1),
+        KeyCode::PageDown => app.move_copy_cursor(10),
+        KeyCode::PageUp => app.move_copy_cursor(-10),
+        KeyCode::Char('g') => app.copy_cursor_to_end(false),
+        KeyCode::Char('G') => app.copy_cursor_to_end(true),
+        KeyCode::Char('v') | KeyCode::Char(' ') => app.toggle_copy_selection(),
+        KeyCode::Char('y') | KeyCode::Enter => app.yank_preview_selection(),
+        KeyCode::Char('V') | KeyCode::Esc => app.cancel_preview_copy(),
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the diff overlay.
+fn handle_diff_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => app.scroll_diff(1),
+        KeyCode::Char('k') | KeyCode::Up => app.scroll_diff(-1),
+        KeyCode::PageDown | KeyCode::Char(' ') => app.scroll_diff(20),
+        KeyCode::PageUp => app.scroll_diff(-20),
+        KeyCode::Char('g') => app.scroll_diff_to_end(false),
+        KeyCode::Char('G') => app.scroll_diff_to_end(true),
+        KeyCode::Tab | KeyCode::Char('d') => app.toggle_diff_patch(),
+        KeyCode::Char('c') | KeyCode::Char('+') => app.start_checkpoint(CheckpointKind::Commit),
+        KeyCode::Char('s') | KeyCode::Char('-') => app.start_checkpoint(CheckpointKind::Stash),
+        KeyCode::Char('f') | KeyCode::Esc => app.close_diff(),
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events on the session detail page.
+fn handle_session_detail_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('i') | KeyCode::Esc => app.close_session_detail(),
+        KeyCode::Enter => app.attach_detail_session(),
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the icon legend overlay.
+fn handle_legend_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('?') | KeyCode::Esc => app.mode = AppMode::Normal,
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the process tree overlay.
+fn handle_process_tree_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => app.select_process_next(),
+        KeyCode::Char('k') | KeyCode::Up => app.select_process_previous(),
+        KeyCode::Char('x') => app.signal_selected_process(libc::SIGTERM, "SIGTERM"),
+        KeyCode::Char('X') => app.signal_selected_process(libc::SIGKILL, "SIGKILL"),
+        KeyCode::Char('+') | KeyCode::Char('=') => app.renice_selected_process(1),
+        KeyCode::Char('-') | KeyCode::Char('_') => app.renice_selected_process(-1),
+        KeyCode::Char('N') => app.renice_tree_session(),
+        KeyCode::Char('L') => app.limit_tree_session(),
+        KeyCode::Char('T') | KeyCode::Esc => app.close_process_tree(),
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the agent services popup.
+fn handle_services_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => app.select_service_next(),
+        KeyCode::Char('k') | KeyCode::Up => app.select_service_previous(),
+        KeyCode::Char('s') => app.control_selected_service(ServiceAction::Start),
+        KeyCode::Char('x') => app.control_selected_service(ServiceAction::Stop),
+        KeyCode::Char('r') => app.control_selected_service(ServiceAction::Restart),
+        KeyCode::Enter => app.show_selected_service_status(),
+        KeyCode::Char('U') | KeyCode::Esc => app.close_services(),
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the layouts picker.
+fn handle_layouts_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => app.select_layout_next(),
+        KeyCode::Char('k') | KeyCode::Up => app.select_layout_previous(),
+        KeyCode::Enter => app.apply_selected_layout(),
+        KeyCode::Char('s') => app.start_layout_save(),
+        KeyCode::Char('d') => app.delete_selected_layout(),
+        KeyCode::Char('O') | KeyCode::Esc => app.close_layouts(),
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the layout name prompt.
+fn handle_layout_name_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.confirm_layout_save(),
+        KeyCode::Esc => app.cancel_layout_save(),
+        KeyCode::Backspace => {
+            app.layout_name_input.pop();
+        }
+        KeyCode::Char(c) => app.layout_name_input.push(c),
+        _ => {}
+    }
+}
+
+// Handles key events in the mirror target picker.
+fn handle_mirror_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => app.select_mirror_target_next(),
+        KeyCode::Char('k') | KeyCode::Up => app.select_mirror_target_previous(),
+        KeyCode::Enter => app.confirm_mirror(),
+        KeyCode::Esc => app.cancel_mirror(),
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the attached clients popup.
+fn handle_client_list_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('A') => app.attach_selected_takeover(),
+        KeyCode::Char('F') => app.fit_selected_to_largest_client(),
+        KeyCode::Char('K') => app.detach_smallest_client(),
+        KeyCode::Char('C') | KeyCode::Esc => app.close_client_list(),
+        KeyCode::Char('q') => app.should_quit = true,
+        _ => {}
+    }
+}
+
+// Handles key events in the session note prompt.
+fn handle_note_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.confirm_note(),
+        KeyCode::Esc => app.cancel_note(),
+        KeyCode::Backspace => {
+            app.note_input.pop();
+        }
+        KeyCode::Char(c) => app.note_input.push(c),
+        _ => {}
+    }
+}
+
+// Handles key events in the commit or stash message prompt.
+fn handle_checkpoint_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.confirm_checkpoint(),
+        KeyCode::Esc => app.cancel_checkpoint(),
+        KeyCode::Backspace => {
+            app.checkpoint_input.pop();
+        }
+        KeyCode::Char(c) => app.checkpoint_input.push(c),
+        _ => {}
+    }
+}
+
+// Handles key events in the fleet message prompt.
+fn handle_agent_message_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.confirm_agent_message(),
+        KeyCode::Esc => app.cancel_agent_message(),
+        KeyCode::Backspace => {
+            app.agent_message_input.pop();
+        }
+        KeyCode::Char(c) => app.agent_message_input.push(c),
+        _ => {}
+    }
+}
+
+// Handles key events in the idle policy review; only `y` kills.
+fn handle_policy_review_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('y') => app.confirm_policy_kills(),
+        _ => app.dismiss_policy_review(),
+    }
+}
+
+// Handles key events in the session tag prompt.
+fn handle_tags_mode(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.confirm_tags(),
+        KeyCode::Esc => app.cancel_tags(),
+        KeyCode::Backspace => {
+            app.tags_input.pop();
+        }
+        KeyCode::Char(c) => app.tags_input.push(c),
+        _ => {}
+    }
+}
+
+// Handles key events in the global window search.
+fn handle_window_search_mode(app: &mut App, code: KeyCode, matcher: &mut nucleo::Matcher) {
+    match code {
+        KeyCode::Esc => app.close_window_search(),
+        KeyCode::Enter => app.confirm_window_search(),
+        KeyCode::Backspace => {
+            app.window_search_input.pop();
+            app.apply_window_search(matcher);
+        }
+        KeyCode::Char(c) => {
+            app.window_search_input.push(c);
+            app.apply_window_search(matcher);
+        }
+        KeyCode::Down | KeyCode::Tab => app.select_window_search_next(),
+        KeyCode::Up | KeyCode::BackTab => app.select_window_search_previous(),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::{MockTmux, TmuxBackend};
+    use crate::tui::app::SessionAction;
+
+    #[test]
+    fn enhanced_key_events_match_legacy_bindings() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        assert_eq!(
+            normalize_key(key(KeyCode::Tab, KeyModifiers::SHIFT)),
+            Some((KeyCode::BackTab, KeyModifiers::NONE))
+        );
+        assert_eq!(
+            normalize_key(key(KeyCode::Char('g'), KeyModifiers::SHIFT)),
+            Some((KeyCode::Char('G'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            normalize_key(key(KeyCode::Enter, KeyModifiers::CONTROL)),
+            Some((KeyCode::Enter, KeyModifiers::CONTROL))
+        );
+
+        let mut release = key(KeyCode::Char('q'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        assert_eq!(normalize_key(release), None);
+    }
+
+    #[test]
+    fn modified_enter_attaches_from_the_filter() {
+        let mock = MockTmux::with_sessions(&["api", "web"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+
+        handle_key(
+            &mut app,
+            KeyCode::Char('/'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        handle_key(
+            &mut app,
+            KeyCode::Char('w'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::ALT, &mut matcher);
+        assert!(matches!(
+            app.action,
+            Some(SessionAction::AttachReadOnly(ref session)) if session.name == "web"
+        ));
+
+        handle_key(
+            &mut app,
+            KeyCode::Enter,
+            KeyModifiers::CONTROL,
+            &mut matcher,
+        );
+        assert!(matches!(
+            app.action,
+            Some(SessionAction::AttachTakeover(ref session)) if session.name == "web"
+        ));
+    }
+
+    #[test]
+    fn ctrl_paging_keys_never_reach_plain_key_bindings() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        let layouts = vec![crate::layouts::SavedLayout {
+            name: "split".to_string(),
+            layout: "b25d,80x24,0,0,1".to_string(),
+        }];
+        app.mode = AppMode::Layouts;
+        app.layouts = layouts.clone();
+
+        // Plain d deletes the selected layout; Ctrl-d has no list to page here
+        handle_key(
+            &mut app,
+            KeyCode::Char('d'),
+            KeyModifiers::CONTROL,
+            &mut matcher,
+        );
+        assert_eq!(app.layouts, layouts);
+        assert_eq!(app.mode, AppMode::Layouts);
+    }
+
+    #[test]
+    fn question_mark_toggles_the_legend() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.title_legend = false;
+
+        handle_key(
+            &mut app,
+            KeyCode::Char('?'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert_eq!(app.mode, AppMode::Legend);
+        crate::tui::render_to_buffer(&app, 100, 40).unwrap();
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE, &mut matcher);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn stats_overlay_kills_on_x_not_k() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        let mut sessions = mock.list_sessions().unwrap();
+        sessions[0].stats = Some(Default::default());
+        let mut app = App::for_test_with(&mock, sessions, 0);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.mode = AppMode::StatsOverlay;
+
+        handle_key(
+            &mut app,
+            KeyCode::Char('k'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert!(app.pending_actions.is_empty());
+
+        handle_key(
+            &mut app,
+            KeyCode::Char('x'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert_eq!(
+            app.pending_actions
+                .iter()
+                .map(|pending| pending.kind)
+                .collect::<Vec<_>>(),
+            vec![PendingKind::Kill]
+        );
+        handle_key(
+            &mut app,
+            KeyCode::Char('u'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert!(app.pending_actions.is_empty());
+    }
+
+    #[test]
+    fn bar_chart_kills_on_x_not_k() {
+        let mock = MockTmux::with_sessions(&["api"]);
+        let mut sessions = mock.list_sessions().unwrap();
+        sessions[0].stats = Some(Default::default());
+        let mut app = App::for_test_with(&mock, sessions, 0);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.mode = AppMode::BarChartView;
+
+        handle_key(
+            &mut app,
+            KeyCode::Char('k'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert!(app.pending_actions.is_empty());
+
+        handle_key(
+            &mut app,
+            KeyCode::Char('x'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert_eq!(
+            app.pending_actions
+                .iter()
+                .map(|pending| pending.kind)
+                .collect::<Vec<_>>(),
+            vec![PendingKind::Kill]
+        );
+        handle_key(
+            &mut app,
+            KeyCode::Char('u'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert!(app.pending_actions.is_empty());
+    }
+
+    #[test]
+    fn brackets_resize_the_preview_and_gt_visits_the_queue() {
+        let mock = MockTmux::with_sessions(&["api", "web"]);
+        let mut app = App::for_test(&mock);
+        let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+        app.show_preview = true;
+        let size = app.preview_size;
+
+        handle_key(
+            &mut app,
+            KeyCode::Char(']'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert!(app.preview_size > size);
+        handle_key(
+            &mut app,
+            KeyCode::Char('['),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert_eq!(app.preview_size, size);
+
+        // `>` keeps visiting the review queue with the preview open
+        app.sessions.select(1);
+        app.toggle_queued();
+        handle_key(
+            &mut app,
+            KeyCode::Char('>'),
+            KeyModifiers::NONE,
+            &mut matcher,
+        );
+        assert_eq!(app.preview_size, size);
+        assert!(matches!(
+            app.action,
+            Some(SessionAction::Attach(ref session)) if session.name == "web"
+        ));
+    }
+}
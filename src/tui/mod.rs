@@ -2,45 +2,117 @@ pub mod app;
 pub mod events;
 pub mod ui;
 
-use crate::tmux::TmuxSession;
-use crate::tui::app::{App, SessionAction};
-use crate::tui::events::handle_key;
+use crate::logging;
+use crate::perf;
+use crate::tmux::{TmuxBackend, TmuxSession};
+use crate::tui::app::{App, SessionAction, StartupView};
+use crate::tui::events::{handle_key, normalize_key};
 use crate::tui::ui::render;
 
 use anyhow::Result;
 use crossterm::{
     ExecutableCommand,
-    event::{self, Event},
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    event::{
+        self, Event, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+        supports_keyboard_enhancement,
+    },
+};
+use ratatui::{
+    Terminal,
+    backend::{CrosstermBackend, TestBackend},
+    buffer::Buffer,
+    layout::Rect,
 };
-use ratatui::{Terminal, backend::CrosstermBackend};
 use std::io::{Stdout, Write, stdout};
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 // Runs the TUI with a specific session preselected.
 //
 // Sets up the terminal, runs the event loop, then restores the terminal.
 // Returns the action selected by the user, if any.
 pub fn run_tui_with_preselection(
+    tmux: Box<dyn TmuxBackend>,
+    sessions: Vec<TmuxSession>,
+    preselect_index: usize,
+) -> Result<Option<SessionAction>> {
+    run_tui_with_view(tmux, sessions, preselect_index, &StartupView::default())
+}
+
+// Like run_tui_with_preselection, but opens on the view the startup flags asked for.
+pub fn run_tui_with_view(
+    tmux: Box<dyn TmuxBackend>,
     sessions: Vec<TmuxSession>,
     preselect_index: usize,
+    view: &StartupView,
 ) -> Result<Option<SessionAction>> {
+    let selected = run_tui_resumable(tmux, sessions, preselect_index, view)?;
+    Ok(selected.map(|(action, _)| action))
+}
+
+/* Like run_tui_with_view, also returning the view the action was chosen
+ * from (see App::resume_view), to reopen on should the action fail. */
+pub fn run_tui_resumable(
+    tmux: Box<dyn TmuxBackend>,
+    sessions: Vec<TmuxSession>,
+    preselect_index: usize,
+    view: &StartupView,
+) -> Result<Option<(SessionAction, StartupView)>> {
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
+    /* The enhanced (kitty) keyboard protocol tells apart keys the legacy
+     * encoding merges, like Ctrl-Enter and Alt-Enter from Enter. Terminals
+     * without it keep the legacy encoding, where those keys aren't bound. */
+    let enhanced_keys = supports_keyboard_enhancement().unwrap_or(false);
+    if enhanced_keys {
+        stdout().execute(PushKeyboardEnhancementFlags(
+            KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS,
+        ))?;
+    }
     let backend = CrosstermBackend::new(stdout());
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::with_preselection(sessions, preselect_index);
+    let mut app = App::with_preselection(tmux, sessions, preselect_index);
+    let size = terminal.size()?;
+    app.resize(size.width, size.height);
+    app.restore_preview_state();
+    app.load_timeline();
+    app.refresh_power();
+    // The picker starts from the cached scan; check it for changes meanwhile
+    app.start_directory_scan();
     let mut matcher = nucleo::Matcher::new(nucleo::Config::DEFAULT);
+    app.apply_startup_view(view, &mut matcher);
     let result = run_app(&mut terminal, &mut app, &mut matcher);
 
+    // Kills and detaches still in their grace period run now
+    app.flush_pending();
+    app.save_preview_state();
+    app.save_timeline();
+
     drop(terminal);
+    if enhanced_keys {
+        stdout().execute(PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
     stdout().execute(LeaveAlternateScreen)?;
     stdout().flush()?;
 
     result?;
-    Ok(app.action)
+    let resume = app.resume_view();
+    Ok(app.action.map(|action| (action, resume)))
+}
+
+/* Renders the app's current view into an off-screen buffer of the given
+ * size, without touching the terminal. Used for layout snapshot tests and
+ * by tools that show trex views elsewhere (e.g. a web dashboard). */
+pub fn render_to_buffer(app: &App, width: u16, height: u16) -> Result<Buffer> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|frame| render(frame, app))?;
+    Ok(terminal.backend().buffer().clone())
 }
 
 // Main event loop that renders the UI and handles input.
@@ -49,37 +121,40 @@ fn run_app(
     app: &mut App,
     matcher: &mut nucleo::Matcher,
 ) -> Result<()> {
-    let mut last_state_refresh = Instant::now();
-    let mut last_full_rescan = Instant::now();
-    let mut last_stats_refresh = Instant::now();
-
+    let mut frame_log = logging::FrameLog::new(Instant::now());
     loop {
-        app.tick = app.tick.wrapping_add(1);
-        terminal.draw(|frame| render(frame, app))?;
-
-        if event::poll(Duration::from_millis(100))?
-            && let Event::Key(key) = event::read()?
-        {
-            handle_key(app, key.code, key.modifiers, matcher);
+        if app.redraw {
+            app.redraw = false;
+            app.sync_visible_agents();
+            let start = Instant::now();
+            let allocations = perf::allocations();
+            terminal.draw(|frame| render(frame, app))?;
+            let elapsed = start.elapsed();
+            frame_log.record(elapsed, Instant::now());
+            app.record_frame(elapsed, perf::allocations().saturating_sub(allocations));
         }
 
-        // Refresh process states every 100ms (real-time activity indicators)
-        if last_state_refresh.elapsed() >= Duration::from_millis(100) {
-            app.refresh_ai_process_states();
-            last_state_refresh = Instant::now();
+        // Sleep until a key arrives or background work is due
+        if event::poll(app.time_until_work(Instant::now()))? {
+            match event::read()? {
+                Event::Key(key) => {
+                    if let Some((code, modifiers)) = normalize_key(key) {
+                        handle_key(app, code, modifiers, matcher);
+                        app.note_input(Instant::now());
+                    }
+                }
+                // Layouts are recomputed from the new size on the next draw
+                Event::Resize(width, height) => {
+                    terminal.resize(Rect::new(0, 0, width, height))?;
+                    app.resize(width, height);
+                }
+                // Focus changes and pastes only need a fresh frame
+                _ => app.redraw = true,
+            }
         }
 
-        // Refresh session stats every second (CPU/memory usage)
-        if last_stats_refresh.elapsed() >= Duration::from_secs(1) {
-            app.refresh_session_stats();
-            last_stats_refresh = Instant::now();
-        }
-
-        // Full rescan for new/exited processes every 2 seconds
-        if last_full_rescan.elapsed() >= Duration::from_secs(2) {
-            app.rescan_ai_processes();
-            last_full_rescan = Instant::now();
-        }
+        // Background refreshes run on their own cadences (see app/schedule.rs)
+        app.run_scheduled_work(Instant::now(), matcher);
 
         if app.should_quit {
             break;
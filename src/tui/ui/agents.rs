@@ -1,4 +1,5 @@
-use crate::process::ProcessState;
+use super::text::{padding_to_width, truncate_to_width};
+use crate::process::{AiProcessInfo, ProcessState};
 use crate::tui::app::{App, AppMode, FocusArea};
 use ratatui::{
     Frame,
@@ -7,7 +8,16 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Paragraph},
 };
-use std::borrow::Cow;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use unicode_width::UnicodeWidthStr;
+
+const COL_WIDTH: usize = 38;
+const MAX_ROWS: usize = 5;
+// Columns of the project name, ellipsis included
+const PROJECT_NAME_WIDTH: usize = 15;
+
+// Line cache entry of the agent rows; tmux session names can't contain `:`
+const AGENT_BOX_ID: &str = ":agents";
 
 pub fn render_agent_box(frame: &mut Frame, app: &App, area: Rect) {
     let visible_agents = app.visible_agents();
@@ -27,6 +37,19 @@ pub fn render_agent_box(frame: &mut Frame, app: &App, area: Rect) {
         }
         _ => " RUNNING AGENTS ".to_string(),
     };
+    let title = match visible_agents
+        .iter()
+        .filter(|agent| app.marked_agents.contains(&agent.pid))
+        .count()
+    {
+        0 => title,
+        marked => format!("{}[{} marked] ", title, marked),
+    };
+    // Agents run as systemd services are listed in their own popup (`U`)
+    let title = match app.service_counts() {
+        Some((running, total)) => format!("{}[⚙ {}/{} services] ", title, running, total),
+        None => title,
+    };
 
     let border_color = if app.focus == FocusArea::Agents {
         app.theme.secondary
@@ -60,9 +83,6 @@ pub fn render_agent_box(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    const COL_WIDTH: usize = 38;
-    const MAX_ROWS: usize = 5;
-
     let process_count = visible_agents.len();
     let num_cols = (inner.width as usize / COL_WIDTH).max(1);
     let max_display = num_cols * MAX_ROWS;
@@ -70,7 +90,66 @@ pub fn render_agent_box(frame: &mut Frame, app: &App, area: Rect) {
     let num_rows = display_count.min(MAX_ROWS);
     let show_more = process_count > max_display;
 
-    // Build lines with dynamic columns (column-first: fill top-to-bottom, then left-to-right)
+    // Rows are cached between frames; the key covers what they show
+    let mut hasher = DefaultHasher::new();
+    (
+        inner.width,
+        app.focus == FocusArea::Agents,
+        app.agents.selected(),
+    )
+        .hash(&mut hasher);
+    for agent in &visible_agents[..display_count] {
+        (
+            agent.pid,
+            agent.activity_state,
+            agent.tmux_session.is_some(),
+            agent.container.is_some(),
+            &agent.project_name,
+            &agent.process_name,
+            &agent.child_ai_names,
+            app.marked_agents.contains(&agent.pid),
+        )
+            .hash(&mut hasher);
+    }
+
+    let buffer = frame.buffer_mut();
+    app.line_cache.with_lines(
+        AGENT_BOX_ID,
+        hasher.finish(),
+        || agent_lines(app, &visible_agents, num_cols, num_rows, display_count),
+        |lines| {
+            for (row, line) in lines.iter().enumerate().take(inner.height as usize) {
+                buffer.set_line(inner.x, inner.y + row as u16, line, inner.width);
+            }
+        },
+    );
+
+    // Render "+N more" at bottom-right only if processes don't fit
+    if show_more {
+        let extra = process_count - max_display;
+        let more_text = format!("+{} more ", extra);
+        let more_width = more_text.len() as u16;
+        let more_area = Rect {
+            x: inner.x + inner.width.saturating_sub(more_width),
+            y: inner.y + inner.height.saturating_sub(1),
+            width: more_width,
+            height: 1,
+        };
+        let more_paragraph =
+            Paragraph::new(more_text).style(Style::default().fg(app.theme.text_dim));
+        frame.render_widget(more_paragraph, more_area);
+    }
+}
+
+/* The agent rows, in columns filled top to bottom, then left to right.
+ * Each entry is padded to the column width. */
+fn agent_lines(
+    app: &App,
+    visible_agents: &[&AiProcessInfo],
+    num_cols: usize,
+    num_rows: usize,
+    display_count: usize,
+) -> Vec<Line<'static>> {
     let mut lines: Vec<Line> = Vec::new();
     for row in 0..num_rows {
         let mut spans = Vec::new();
@@ -79,12 +158,13 @@ pub fn render_agent_box(frame: &mut Frame, app: &App, area: Rect) {
             let idx = col * MAX_ROWS + row;
             if idx < display_count {
                 let proc = visible_agents[idx];
-                let is_selected = app.focus == FocusArea::Agents && idx == app.agent_selected_index;
+                let is_selected = app.focus == FocusArea::Agents && idx == app.agents.selected();
 
                 // Activity indicator based on process state
                 let (activity_icon, activity_color) = match proc.activity_state {
                     ProcessState::Running => ("▶", app.theme.success),
                     ProcessState::Waiting => ("⏸", app.theme.warning),
+                    ProcessState::Stopped => ("■", app.theme.error),
                     ProcessState::Unknown => ("◼", app.theme.text_dim),
                 };
 
@@ -96,11 +176,7 @@ pub fn render_agent_box(frame: &mut Frame, app: &App, area: Rect) {
                 };
 
                 // Project name (truncated)
-                let display_name = if proc.project_name.len() > 12 {
-                    Cow::Owned(format!("{}...", &proc.project_name[..12]))
-                } else {
-                    Cow::Borrowed(proc.project_name.as_str())
-                };
+                let display_name = truncate_to_width(&proc.project_name, PROJECT_NAME_WIDTH);
 
                 // Text color: theme primary when selected, otherwise dimmed
                 let text_color = if is_selected {
@@ -115,30 +191,39 @@ pub fn render_agent_box(frame: &mut Frame, app: &App, area: Rect) {
                     Style::default().fg(text_color)
                 };
 
-                // For activity icon, we need separate spans to color it
-                spans.push(Span::styled(" ", text_style));
+                // For activity icon, we need separate spans to color it;
+                // agents marked for fleet actions get a leading marker
+                if app.marked_agents.contains(&proc.pid) {
+                    spans.push(Span::styled(
+                        "*",
+                        Style::default()
+                            .fg(app.theme.secondary)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                } else {
+                    spans.push(Span::styled(" ", text_style));
+                }
                 spans.push(Span::styled(
                     activity_icon,
                     Style::default().fg(activity_color),
                 ));
 
-                // Build the main display text with optional child AI names
-                let main_text = if proc.child_ai_names.is_empty() {
-                    format!(" {}:{} {}", proc.process_name, display_name, tmux_icon)
-                } else {
-                    let child_names = proc.child_ai_names.join(", ");
-                    format!(
-                        " {}:{} {} ({})",
-                        proc.process_name, display_name, tmux_icon, child_names
-                    )
-                };
+                // Agents in a container carry a badge after the tmux indicator
+                let container_badge = if proc.container.is_some() { " ▣" } else { "" };
+
+                // The agent, with any agents it started as a breadcrumb (`opencode→claude`)
+                let main_text = format!(
+                    " {}:{} {}{}",
+                    proc.breadcrumb(),
+                    display_name,
+                    tmux_icon,
+                    container_badge
+                );
 
                 // Add padding to reach column width
-                let current_len = 1 + activity_icon.chars().count() + main_text.chars().count();
+                let padding = padding_to_width(&main_text, COL_WIDTH - 1 - activity_icon.width());
                 spans.push(Span::styled(main_text, text_style));
-                if current_len < COL_WIDTH {
-                    spans.push(Span::raw(" ".repeat(COL_WIDTH - current_len)));
-                }
+                spans.push(Span::raw(padding));
             }
         }
 
@@ -147,22 +232,5 @@ pub fn render_agent_box(frame: &mut Frame, app: &App, area: Rect) {
         }
     }
 
-    let ai_paragraph = Paragraph::new(lines);
-    frame.render_widget(ai_paragraph, inner);
-
-    // Render "+N more" at bottom-right only if processes don't fit
-    if show_more {
-        let extra = process_count - max_display;
-        let more_text = format!("+{} more ", extra);
-        let more_width = more_text.len() as u16;
-        let more_area = Rect {
-            x: inner.x + inner.width.saturating_sub(more_width),
-            y: inner.y + inner.height.saturating_sub(1),
-            width: more_width,
-            height: 1,
-        };
-        let more_paragraph =
-            Paragraph::new(more_text).style(Style::default().fg(app.theme.text_dim));
-        frame.render_widget(more_paragraph, more_area);
-    }
+    lines
 }
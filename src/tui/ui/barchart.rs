@@ -1,4 +1,5 @@
-use crate::tui::app::App;
+use super::text::truncate_to_width;
+use crate::tui::app::{App, ChartMetric};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -7,21 +8,30 @@ use ratatui::{
     widgets::{BarChart, BarGroup, Block, BorderType, Borders, Paragraph},
 };
 
+// Columns of a session name shown under its bar
+const BAR_LABEL_WIDTH: usize = 8;
+
 pub fn render_barchart_view(frame: &mut Frame, app: &App) {
+    // Title, one equal share per shown chart, help
+    let charts = app.chart_metrics.len() as u32;
+    let constraints: Vec<Constraint> = std::iter::once(Constraint::Length(3))
+        .chain(
+            app.chart_metrics
+                .iter()
+                .map(|_| Constraint::Ratio(1, charts)),
+        )
+        .chain(std::iter::once(Constraint::Length(2)))
+        .collect();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Min(10),   // CPU chart
-            Constraint::Min(10),   // Memory chart
-            Constraint::Length(2), // Help
-        ])
+        .constraints(constraints)
         .split(frame.area());
 
     render_barchart_title(frame, app, chunks[0]);
-    render_cpu_barchart(frame, app, chunks[1]);
-    render_memory_barchart(frame, app, chunks[2]);
-    render_barchart_help(frame, app, chunks[3]);
+    for (metric, area) in app.chart_metrics.iter().zip(&chunks[1..]) {
+        render_metric_barchart(frame, app, *area, *metric);
+    }
+    render_barchart_help(frame, app, chunks[chunks.len() - 1]);
 }
 
 fn render_barchart_title(frame: &mut Frame, app: &App, area: Rect) {
@@ -33,6 +43,10 @@ fn render_barchart_title(frame: &mut Frame, app: &App, area: Rect) {
                 .fg(app.theme.primary)
                 .add_modifier(Modifier::BOLD),
         ),
+        Span::styled(
+            format!("  {}", chart_window_label(app)),
+            Style::default().fg(app.theme.text_dim),
+        ),
     ]);
 
     let block = Block::default()
@@ -47,102 +61,33 @@ fn render_barchart_title(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(para, area);
 }
 
-fn render_cpu_barchart(frame: &mut Frame, app: &App, area: Rect) {
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(app.theme.success))
-        .title(" CPU Usage by Session (%) ");
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    // Collect CPU data from sessions
-    let mut data: Vec<(&str, u64)> = app
-        .sessions
-        .iter()
-        .filter_map(|s| {
-            s.stats.as_ref().map(|stats| {
-                let name = if s.name.len() > 8 {
-                    &s.name[..8]
-                } else {
-                    &s.name
-                };
-                (name, stats.cpu_percent as u64)
-            })
-        })
-        .collect();
-
-    // Sort by CPU descending and take top 10
-    data.sort_by_key(|item| std::cmp::Reverse(item.1));
-    data.truncate(10);
-
-    if data.is_empty() {
-        let empty = Paragraph::new("No session data available")
-            .style(Style::default().fg(app.theme.text_dim));
-        frame.render_widget(empty, inner);
-        return;
+// Describes the time window the bars cover, e.g. "last 10 samples (~20s avg)".
+fn chart_window_label(app: &App) -> String {
+    if app.chart_window <= 1 {
+        return "latest sample".to_string();
     }
-
-    // Each bar gets a gradient color based on its CPU percentage
-    let max_cpu = data.iter().map(|(_, v)| *v).max().unwrap_or(100).max(100);
-    let bar_data: Vec<_> = data
-        .iter()
-        .map(|(label, value)| {
-            let pct = (*value as f64 / max_cpu as f64) * 100.0;
-            ratatui::widgets::Bar::default()
-                .value(*value)
-                .label(Line::from(*label))
-                .style(Style::default().fg(app.theme.gradient_color(pct)))
-        })
-        .collect();
-
-    let group = BarGroup::default().bars(&bar_data);
-
-    let barchart = BarChart::default()
-        .data(group)
-        .bar_width(5)
-        .bar_gap(1)
-        .bar_style(Style::default().fg(app.theme.gradient_color(0.0)))
-        .value_style(
-            Style::default()
-                .fg(app.theme.text)
-                .add_modifier(Modifier::BOLD),
-        );
-
-    frame.render_widget(barchart, inner);
+    let seconds = app.chart_window as u64 * app.schedule.session_stats.interval().as_secs();
+    format!("last {} samples (~{}s avg)", app.chart_window, seconds)
 }
 
-fn render_memory_barchart(frame: &mut Frame, app: &App, area: Rect) {
+fn render_metric_barchart(frame: &mut Frame, app: &App, area: Rect, metric: ChartMetric) {
+    let border = match metric {
+        ChartMetric::Cpu => app.theme.success,
+        ChartMetric::Memory => app.theme.info,
+        ChartMetric::Panes => app.theme.secondary,
+        ChartMetric::Health => app.theme.warning,
+        ChartMetric::Io => app.theme.primary,
+    };
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(app.theme.info))
-        .title(" Memory Usage by Session (MB) ");
+        .border_style(Style::default().fg(border))
+        .title(format!(" {} ", metric.title()));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Collect memory data from sessions
-    let mut data: Vec<(&str, u64)> = app
-        .sessions
-        .iter()
-        .filter_map(|s| {
-            s.stats.as_ref().map(|stats| {
-                let name = if s.name.len() > 8 {
-                    &s.name[..8]
-                } else {
-                    &s.name
-                };
-                (name, stats.mem_mb)
-            })
-        })
-        .collect();
-
-    // Sort by memory descending and take top 10
-    data.sort_by_key(|item| std::cmp::Reverse(item.1));
-    data.truncate(10);
-
+    let data = app.chart_bars(metric);
     if data.is_empty() {
         let empty = Paragraph::new("No session data available")
             .style(Style::default().fg(app.theme.text_dim));
@@ -150,15 +95,35 @@ fn render_memory_barchart(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    // Each bar gets a gradient color normalized against the heaviest session
-    let max_mem = data.iter().map(|(_, v)| *v).max().unwrap_or(1).max(1);
+    /* Each bar gets a gradient color: CPU against 100% (or the busiest
+     * session past it), health by how far below 100 it scores, the rest
+     * against the largest bar. */
+    let max = data.iter().map(|(_, v)| *v).max().unwrap_or(1).max(1);
+    let scale = match metric {
+        ChartMetric::Cpu => max.max(100),
+        _ => max,
+    };
+    let selected = app.selected_bar_session().map(|s| s.name.as_str());
     let bar_data: Vec<_> = data
         .iter()
-        .map(|(label, value)| {
-            let pct = (*value as f64 / max_mem as f64) * 100.0;
+        .map(|(session, value)| {
+            let pct = if metric == ChartMetric::Health {
+                100.0 - *value as f64
+            } else {
+                (*value as f64 / scale as f64) * 100.0
+            };
+            let label = truncate_to_width(&session.name, BAR_LABEL_WIDTH);
+            // The highlighted session's bar is marked in every chart
+            let label_style = if selected == Some(session.name.as_str()) {
+                Style::default()
+                    .fg(app.theme.primary)
+                    .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
             ratatui::widgets::Bar::default()
                 .value(*value)
-                .label(Line::from(*label))
+                .label(Line::styled(label, label_style))
                 .style(Style::default().fg(app.theme.gradient_color(pct)))
         })
         .collect();
@@ -196,7 +161,48 @@ fn render_barchart_help(frame: &mut Frame, app: &App, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
-            " to return to normal view",
+            " to return to normal view, ",
+            Style::default().fg(app.theme.text_dim),
+        ),
+        Span::styled(
+            "+/-",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            " to zoom the time window, ",
+            Style::default().fg(app.theme.text_dim),
+        ),
+        Span::styled(
+            "h/l",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" select, ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" attach, ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(
+            "x",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" kill, ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(
+            "1-5",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            " toggle cpu/mem/panes/health/io",
             Style::default().fg(app.theme.text_dim),
         ),
     ]));
@@ -0,0 +1,108 @@
+use crate::tmux::client::size_mismatch;
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+
+/* Renders a popup listing the clients attached to the selected session.
+ *
+ * Shows each client's tty, terminal size, and last input time, so a
+ * forgotten client shrinking the session is easy to spot. */
+pub fn render_client_list(frame: &mut Frame, app: &App) {
+    let Some(session) = app.selected_session() else {
+        return;
+    };
+
+    let area = centered_rect(50, 40, frame.area());
+    frame.render_widget(Clear, area);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {:<14} {:>9}  {:<9} LAST INPUT", "TTY", "SIZE", "MODE"),
+            Style::default()
+                .fg(app.theme.text_dim)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ];
+
+    if app.clients.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No clients attached",
+            Style::default().fg(app.theme.text_dim),
+        )));
+    }
+
+    for client in &app.clients {
+        let last_input = client
+            .activity_ago_string()
+            .map(|ago| format!("{} ago", ago))
+            .unwrap_or_else(|| "unknown".to_string());
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<14} ", client.tty),
+                Style::default().fg(app.theme.text),
+            ),
+            Span::styled(
+                format!("{:>9}  ", format!("{}x{}", client.width, client.height)),
+                Style::default().fg(app.theme.info),
+            ),
+            Span::styled(
+                format!("{:<9} ", if client.readonly { "read-only" } else { "rw" }),
+                Style::default().fg(app.theme.text_dim),
+            ),
+            Span::styled(last_input, Style::default().fg(app.theme.text_dim)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    if let Some(mismatch) = size_mismatch(&app.clients) {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  ⇲ Windows limited to {}x{} by {} (largest client {}x{})",
+                mismatch.smallest.width,
+                mismatch.smallest.height,
+                mismatch.smallest.tty,
+                mismatch.largest.width,
+                mismatch.largest.height
+            ),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            "  F fits windows to the largest client, K detaches the smallest",
+            Style::default()
+                .fg(app.theme.text_dim)
+                .add_modifier(Modifier::ITALIC),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        "  A attaches here and detaches every other client",
+        Style::default()
+            .fg(app.theme.text_dim)
+            .add_modifier(Modifier::ITALIC),
+    )));
+    if let Some(status) = &app.client_list_status {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", status),
+            Style::default().fg(app.theme.warning),
+        )));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(format!(" 🖥 Clients: {} ", session.name))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,363 @@
+use crate::health::HealthLevel;
+use crate::process::ProcessState;
+use crate::timeline::unix_now;
+use crate::tmux::{TmuxSession, format_elapsed};
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Sparkline},
+};
+
+use super::normal::render_help;
+
+/* Renders the full-screen detail page for one session: a header with its
+ * path, clients, tags, and note, CPU and memory history charts, then its
+ * windows and agents, git state and health breakdown, and the attaches
+ * made to it through trex. */
+pub fn render_session_detail(frame: &mut Frame, app: &App) {
+    let Some(session) = app.detail_target() else {
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // Header
+            Constraint::Length(8), // CPU and memory history
+            Constraint::Min(6),    // Windows, agents, git, health, attaches
+            Constraint::Length(2), // Help
+        ])
+        .split(frame.area());
+
+    render_header(frame, app, session, chunks[0]);
+    render_history(frame, app, session, chunks[1]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+        ])
+        .split(chunks[2]);
+    let agents = app.detail_agents();
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(agents.len().max(1) as u16 + 2),
+        ])
+        .split(columns[0]);
+    let middle = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(6)])
+        .split(columns[1]);
+
+    let windows: Vec<Line> = app
+        .detail_windows
+        .iter()
+        .map(|window| {
+            let mut spans = vec![
+                Span::styled(
+                    if window.active { "⚡ " } else { "  " },
+                    Style::default().fg(app.theme.primary),
+                ),
+                Span::styled(
+                    format!("#{} ", window.index),
+                    Style::default().fg(app.theme.text_dim),
+                ),
+                Span::styled(window.name.clone(), Style::default().fg(app.theme.text)),
+                Span::styled(
+                    format!(" ⟨{}⟩", window.current_command),
+                    Style::default().fg(app.theme.info),
+                ),
+            ];
+            if window.dead {
+                spans.push(Span::styled(
+                    " exited",
+                    Style::default().fg(app.theme.error),
+                ));
+            }
+            if window.alerts.any() {
+                spans.push(Span::styled(
+                    format!(" {}", window.alerts.badge()),
+                    Style::default().fg(app.theme.warning),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    render_section(frame, app, left[0], " 🪟 Windows ", windows);
+
+    let agent_lines: Vec<Line> = agents
+        .iter()
+        .map(|agent| {
+            let (state, color) = match agent.activity_state {
+                ProcessState::Running => ("▶ working", app.theme.success),
+                ProcessState::Waiting => ("⏸ waiting", app.theme.warning),
+                ProcessState::Stopped => ("■ stopped", app.theme.error),
+                ProcessState::Unknown => ("◼ unknown", app.theme.text_dim),
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", state), Style::default().fg(color)),
+                Span::styled(agent.breadcrumb(), Style::default().fg(app.theme.text)),
+                Span::styled(
+                    format!(" (pid {})", agent.pid),
+                    Style::default().fg(app.theme.text_dim),
+                ),
+            ])
+        })
+        .collect();
+    render_section(frame, app, left[1], " 🤖 Agents ", agent_lines);
+
+    render_section(frame, app, middle[0], " 🌿 Git ", git_lines(app, session));
+    render_section(
+        frame,
+        app,
+        middle[1],
+        " 🏥 Health ",
+        health_lines(app, session),
+    );
+
+    let now = unix_now();
+    let attaches: Vec<Line> = app
+        .detail_attaches
+        .iter()
+        .map(|attach| {
+            let end = attach.end.unwrap_or(now);
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{:>4} ago",
+                        format_elapsed(now.saturating_sub(attach.start))
+                    ),
+                    Style::default().fg(app.theme.text),
+                ),
+                Span::styled(
+                    format!("  for {}", format_elapsed(end.saturating_sub(attach.start))),
+                    Style::default().fg(app.theme.text_dim),
+                ),
+            ])
+        })
+        .collect();
+    render_section(frame, app, columns[2], " ⏱️  Attaches ", attaches);
+
+    render_help(frame, app, chunks[3]);
+}
+
+fn render_header(frame: &mut Frame, app: &App, session: &TmuxSession, area: Rect) {
+    let now = unix_now();
+    let mut first = vec![
+        Span::styled(
+            session.name.clone(),
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  {} win, {} panes, {} client(s)",
+                session.windows, session.panes, session.clients
+            ),
+            Style::default().fg(app.theme.text_dim),
+        ),
+    ];
+    if let Some(created) = session.created {
+        first.push(Span::styled(
+            format!(
+                "  created {} ago",
+                format_elapsed(now.saturating_sub(created))
+            ),
+            Style::default().fg(app.theme.text_dim),
+        ));
+    }
+    if let Some(ago) = session.activity_ago_string() {
+        first.push(Span::styled(
+            format!("  active {} ago", ago),
+            Style::default().fg(app.theme.text_dim),
+        ));
+    }
+    if let Some(rule) = app.wm.workspace_for(&session.name) {
+        first.push(Span::styled(
+            format!("  workspace {}", rule.workspace),
+            Style::default().fg(app.theme.info),
+        ));
+    }
+
+    let mut second = vec![Span::styled(
+        session
+            .path
+            .as_ref()
+            .map_or("no path".to_string(), |path| path.display().to_string()),
+        Style::default().fg(app.theme.text),
+    )];
+    if !session.tags.is_empty() {
+        second.push(Span::styled(
+            session
+                .tags
+                .iter()
+                .map(|tag| format!(" #{}", tag))
+                .collect::<String>(),
+            Style::default().fg(app.theme.info),
+        ));
+    }
+    if let Some(note) = &session.note {
+        second.push(Span::styled(
+            format!(" — {}", note),
+            Style::default()
+                .fg(app.theme.text_dim)
+                .add_modifier(Modifier::ITALIC),
+        ));
+    }
+
+    render_section(
+        frame,
+        app,
+        area,
+        " 🔍 Session ",
+        vec![Line::from(first), Line::from(second)],
+    );
+}
+
+// CPU and memory history side by side, each titled with its current value.
+fn render_history(frame: &mut Frame, app: &App, session: &TmuxSession, area: Rect) {
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let (cpu, mem) = match &session.stats {
+        Some(stats) => (
+            format!("{:.1}%", app.display_cpu(stats.cpu_percent)),
+            format!("{}MB", stats.mem_mb),
+        ),
+        None => ("n/a".to_string(), "n/a".to_string()),
+    };
+
+    for (area, title, history, color) in [
+        (
+            halves[0],
+            format!(" CPU {} ", cpu),
+            &session.cpu_history,
+            app.theme.success,
+        ),
+        (
+            halves[1],
+            format!(" MEM {} ", mem),
+            &session.mem_history,
+            app.theme.info,
+        ),
+    ] {
+        let block = section_block(app, title);
+        let width = block.inner(area).width as usize;
+        let sparkline = Sparkline::default()
+            .block(block)
+            .data(history[history.len().saturating_sub(width)..].to_vec())
+            .style(Style::default().fg(color))
+            .absent_value_symbol("·")
+            .absent_value_style(Style::default().fg(app.theme.text_dim));
+        frame.render_widget(sparkline, area);
+    }
+}
+
+fn git_lines(app: &App, session: &TmuxSession) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(app.theme.text_dim);
+    let Some(git) = session.git_status.as_ref().filter(|git| git.is_repo) else {
+        return vec![Line::styled("not a git repository", dim)];
+    };
+    let dirty_color = if git.dirty_count > 0 {
+        app.theme.warning
+    } else {
+        app.theme.success
+    };
+    let mut lines = vec![
+        Line::styled(
+            git.branch.clone().unwrap_or_else(|| "detached".to_string()),
+            Style::default().fg(app.theme.secondary),
+        ),
+        Line::styled(
+            format!("{} changed file(s)", git.dirty_count),
+            Style::default().fg(dirty_color),
+        ),
+        Line::styled(format!("↑{} ahead  ↓{} behind", git.ahead, git.behind), dim),
+    ];
+    if let Some((badge, color)) = app
+        .session_ci(session)
+        .and_then(|ci| Some((ci.badge()?, app.ci_color(&ci))))
+    {
+        lines.push(Line::styled(
+            format!("PR/CI {}", badge),
+            Style::default().fg(color),
+        ));
+    }
+    lines
+}
+
+// The health score and each factor's penalty against its configured maximum.
+fn health_lines(app: &App, session: &TmuxSession) -> Vec<Line<'static>> {
+    let health = app.session_health(session);
+    let weights = &app.health_weights;
+    let level_color = match health.level() {
+        HealthLevel::Healthy => app.theme.success,
+        HealthLevel::Warning => app.theme.warning,
+        HealthLevel::Critical => app.theme.error,
+    };
+    let factor = |label: &str, penalty: u8, weight: u8| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<10}", label),
+                Style::default().fg(app.theme.text_dim),
+            ),
+            Span::styled(
+                format!("-{:<3}", penalty),
+                Style::default().fg(if penalty == 0 {
+                    app.theme.success
+                } else {
+                    app.theme.warning
+                }),
+            ),
+            Span::styled(
+                format!(" (max {})", weight),
+                Style::default().fg(app.theme.text_dim),
+            ),
+        ])
+    };
+    vec![
+        Line::styled(
+            format!("{} {}/100", health.icon(), health.score),
+            Style::default()
+                .fg(level_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+        factor("CPU", health.penalties.cpu, weights.cpu),
+        factor("Memory", health.penalties.mem, weights.mem),
+        factor("Activity", health.penalties.activity, weights.activity),
+        factor("Multiplex", health.penalties.multiplex, weights.multiplex),
+    ]
+}
+
+fn section_block(app: &App, title: String) -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(title)
+}
+
+// A titled box of lines, or a dim `none` when there is nothing to list.
+fn render_section(frame: &mut Frame, app: &App, area: Rect, title: &str, lines: Vec<Line>) {
+    let lines = if lines.is_empty() {
+        vec![Line::styled(
+            "none",
+            Style::default().fg(app.theme.text_dim),
+        )]
+    } else {
+        lines
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(section_block(app, title.to_string())),
+        area,
+    );
+}
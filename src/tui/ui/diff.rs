@@ -0,0 +1,102 @@
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+
+/* Renders the diff overlay over normal mode: the `git diff --stat` summary
+ * followed by the untracked files, or with Tab the full unified diff with
+ * added, removed, and hunk lines colored. Either view scrolls. */
+pub fn render_diff(frame: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, frame.area());
+    frame.render_widget(Clear, area);
+
+    let session = app.diff_session.as_deref().unwrap_or_default();
+    let view = if app.diff_patch { "diff" } else { "stat" };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(format!(" 📝 Changes in {} ({}) ", session, view))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+
+    let mut lines: Vec<Line> = if app.diff_patch {
+        app.diff
+            .patch
+            .iter()
+            .map(|line| patch_line(app, line))
+            .collect()
+    } else {
+        app.diff
+            .stat
+            .iter()
+            .map(|line| stat_line(app, line))
+            .collect()
+    };
+    if !app.diff_patch && !app.diff.untracked.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            format!(" {} untracked file(s)", app.diff.untracked.len()),
+            Style::default()
+                .fg(app.theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        ));
+        lines.extend(
+            app.diff.untracked.iter().map(|path| {
+                Line::styled(format!(" ? {}", path), Style::default().fg(app.theme.info))
+            }),
+        );
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(app.theme.text))
+        .scroll((app.diff_scroll as u16, 0))
+        .block(block);
+    frame.render_widget(paragraph, area);
+}
+
+// A stat row with its `+++--` graph colored, or the dim summary row.
+fn stat_line<'a>(app: &App, line: &'a str) -> Line<'a> {
+    let Some((file, graph)) = line.rsplit_once('|') else {
+        return Line::styled(line, Style::default().fg(app.theme.text_dim));
+    };
+    // Binary files show `Bin 0 -> 12 bytes` where text files show the graph
+    let split = graph.find(['+', '-']).unwrap_or(graph.len());
+    let (count, marks) = graph.split_at(split);
+    if marks.contains(|c| c != '+' && c != '-') {
+        return Line::styled(line, Style::default().fg(app.theme.text));
+    }
+    let added = marks.chars().filter(|c| *c == '+').count();
+    Line::from(vec![
+        Span::styled(file, Style::default().fg(app.theme.text)),
+        Span::styled("|", Style::default().fg(app.theme.text_dim)),
+        Span::styled(count, Style::default().fg(app.theme.text)),
+        Span::styled(&marks[..added], Style::default().fg(app.theme.success)),
+        Span::styled(&marks[added..], Style::default().fg(app.theme.error)),
+    ])
+}
+
+fn patch_line<'a>(app: &App, line: &'a str) -> Line<'a> {
+    let style = if line.starts_with("diff --git") {
+        Style::default()
+            .fg(app.theme.primary)
+            .add_modifier(Modifier::BOLD)
+    } else if line.starts_with("+++") || line.starts_with("---") || line.starts_with("index ") {
+        Style::default().fg(app.theme.text_dim)
+    } else if line.starts_with("@@") {
+        Style::default().fg(app.theme.info)
+    } else if line.starts_with('+') {
+        Style::default().fg(app.theme.success)
+    } else if line.starts_with('-') {
+        Style::default().fg(app.theme.error)
+    } else {
+        Style::default().fg(app.theme.text)
+    };
+    Line::styled(line, style)
+}
@@ -3,16 +3,20 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Paragraph},
 };
 
-use crate::tui::app::App;
+use super::highlight::highlight_matches;
+use crate::directory::{Directory, SourceKind};
+use crate::project::ProjectConfig;
+use crate::tui::app::{App, line_key};
 
 /* Renders the directory selection mode layout.
  *
  * This function creates a three-section vertical layout:
  * - Top: Header with depth indicator and filter input
- * - Middle: Directory list with selection highlighting
+ * - Middle: Directory list with selection highlighting, and the highlighted
+ *   directory's preview on the right
  * - Bottom: Help line with available keybindings */
 pub fn render_directory_mode(frame: &mut Frame, app: &App) {
     let chunks = Layout::default()
@@ -24,29 +28,63 @@ pub fn render_directory_mode(frame: &mut Frame, app: &App) {
         ])
         .split(frame.area());
 
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[1]);
+
     render_header_dir(frame, app, chunks[0]);
-    render_directory_list(frame, app, chunks[1]);
+    render_directory_list(frame, app, body[0]);
+    render_directory_preview(frame, app, body[1]);
     render_help_dir(frame, app, chunks[2]);
 }
 
 /* Renders the header bar for directory selection mode.
  *
  * Displays:
- * - Title with current scan depth and filter input
- * - Bottom title with filtered directory count */
+ * - Title with current scan depth and filter input (and whether the
+ *   directory is for the agent launcher)
+ * - Bottom titles with the filtered directory count and the sources,
+ *   each with its toggle key */
 pub fn render_header_dir(frame: &mut Frame, app: &App, area: Rect) {
+    let purpose = if app.launching_agent {
+        " for agent"
+    } else {
+        ""
+    };
     let title = format!(
-        " Select directory (depth: {}) > {} ",
-        app.dir_scan_depth, app.dir_filter_input
+        " Select directory{} (depth: {}) > {} ",
+        purpose, app.dir_scan_depth, app.dir_filter_input
     );
-    let dir_count = format!(" {} dirs ", app.dir_filtered_indices.len());
+    let dir_count = format!(" {} dirs ", app.directories.len());
     let style = Style::default().fg(app.theme.border);
 
+    // Each source with the F key that toggles it, dimmed when off
+    let mut sources = vec![Span::raw(" ")];
+    for (idx, kind) in SourceKind::ALL.into_iter().enumerate() {
+        let on = app.dir_sources.contains(&kind);
+        sources.push(Span::styled(
+            format!("F{} ", idx + 1),
+            Style::default().fg(app.theme.text_dim),
+        ));
+        sources.push(Span::styled(
+            format!("{} ", kind.name()),
+            if on {
+                Style::default().fg(app.theme.secondary)
+            } else {
+                Style::default()
+                    .fg(app.theme.text_dim)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            },
+        ));
+    }
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(style)
         .title(title)
-        .title_bottom(dir_count);
+        .title_bottom(dir_count)
+        .title_bottom(Line::from(sources).right_aligned());
 
     frame.render_widget(block, area);
 }
@@ -54,64 +92,176 @@ pub fn render_header_dir(frame: &mut Frame, app: &App, area: Rect) {
 /* Renders the directory list with selection highlighting.
  *
  * Each directory item shows:
- * - Directory name (highlighted in yellow if selected)
+ * - Directory name (highlighted in yellow if selected), with the chars
+ *   matched by the filter underlined
+ * - ⚙ when the directory has a `.trex.toml`, which Enter creates directly
  * - Full path in cyan within square brackets
+ * - The source that listed it, dimmed, when more than one source is on
  *
  * Selected item has a dark gray background.
  * Empty state shows "No directories found" message. */
 pub fn render_directory_list(frame: &mut Frame, app: &App, area: Rect) {
-    if app.dir_filtered_indices.is_empty() {
-        let paragraph = Paragraph::new("No directories found")
+    if app.directories.is_empty() {
+        let message = if app.directories.items.is_empty() && app.dir_scan.is_some() {
+            "Scanning directories…".to_string()
+        } else if app.dir_filter_input.trim().is_empty()
+            || SourceKind::split_prefix(&app.dir_filter_input).is_some()
+        {
+            "No directories found".to_string()
+        } else {
+            format!(
+                "No directories found. Enter: create `{}` and start a session there",
+                app.dir_filter_input.trim()
+            )
+        };
+        let paragraph = Paragraph::new(message)
             .style(Style::default().fg(app.theme.text_dim))
             .block(Block::default().borders(Borders::ALL));
         frame.render_widget(paragraph, area);
         return;
     }
 
-    let items: Vec<ListItem> = app
-        .dir_filtered_indices
-        .iter()
-        .enumerate()
-        .map(|(idx, &dir_idx)| {
-            let dir = &app.directories[dir_idx];
-            let is_selected = idx == app.dir_selected_index;
-
-            let path_str = dir.path.display().to_string();
-            let display_name = dir
-                .path
-                .file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| path_str.clone());
-
-            let name_style = if is_selected {
-                Style::default()
-                    .fg(app.theme.warning)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(app.theme.text)
-            };
+    let block = Block::default().borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
 
-            let item_style = if is_selected {
-                Style::default().bg(app.theme.highlight)
-            } else {
-                Style::default()
-            };
-
-            let line = Line::from(vec![
-                Span::styled(display_name, name_style),
-                Span::styled(
-                    format!(" [{}]", path_str),
-                    Style::default().fg(app.theme.info),
-                ),
-            ]);
-
-            ListItem::new(line).style(item_style)
-        })
-        .collect();
+    // Only the rows on screen are drawn; each is formatted once and kept
+    // until it changes, since `⚙` checks the disk
+    let visible = app.directories.window(inner.height as usize);
+    let show_source = app.dir_sources.len() > 1;
+    let buffer = frame.buffer_mut();
+    for (row, (position, dir)) in app.directories.visible_range(visible).enumerate() {
+        let is_selected = position == app.directories.selected();
+        let highlights = app
+            .dir_highlights
+            .get(position)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let row_area = Rect {
+            y: inner.y + row as u16,
+            height: 1,
+            ..inner
+        };
+
+        let item_style = if is_selected {
+            Style::default().bg(app.theme.highlight)
+        } else {
+            Style::default()
+        };
+        buffer.set_style(row_area, item_style);
+        app.dir_lines.with_lines(
+            &dir.path.to_string_lossy(),
+            line_key((is_selected, highlights, show_source)),
+            || {
+                vec![directory_line(
+                    app,
+                    dir,
+                    is_selected,
+                    highlights,
+                    show_source,
+                )]
+            },
+            |lines| buffer.set_line(row_area.x, row_area.y, &lines[0], row_area.width),
+        );
+    }
+}
 
-    let list = List::new(items).block(Block::default().borders(Borders::ALL));
+/* A directory row: the name (the filter's matches underlined), `⚙` for a
+ * `.trex.toml`, the full path, and the source when several are on. */
+fn directory_line(
+    app: &App,
+    dir: &Directory,
+    is_selected: bool,
+    highlights: &[u32],
+    show_source: bool,
+) -> Line<'static> {
+    let name_style = if is_selected {
+        Style::default()
+            .fg(app.theme.warning)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(app.theme.text)
+    };
+
+    let mut spans = highlight_matches(
+        &dir.display_name(),
+        highlights,
+        name_style,
+        Style::default().fg(app.theme.primary),
+    );
+    if ProjectConfig::exists(&dir.path) {
+        spans.push(Span::styled(" ⚙", Style::default().fg(app.theme.secondary)));
+    }
+    spans.push(Span::styled(
+        format!(" [{}]", dir.path.display()),
+        Style::default().fg(app.theme.info),
+    ));
+    if let Some(source) = dir.source.filter(|_| show_source) {
+        spans.push(Span::styled(
+            format!(" {}", source.label()),
+            Style::default().fg(app.theme.text_dim),
+        ));
+    }
+    Line::from(spans)
+}
+
+/* Renders the preview of the highlighted directory.
+ *
+ * Shows the git branch in the title, then the first README lines and the
+ * top-level entries (directories first). Empty when nothing is highlighted. */
+pub fn render_directory_preview(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+
+    let Some((path, preview)) = app
+        .dir_preview
+        .as_ref()
+        .filter(|(path, _)| Some(path) == app.selected_directory().map(|dir| &dir.path))
+    else {
+        frame.render_widget(block.title(" Preview "), area);
+        return;
+    };
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+    let title = match &preview.branch {
+        Some(branch) => format!(" {} ⎇ {} ", name, branch),
+        None => format!(" {} ", name),
+    };
+
+    let mut lines: Vec<Line> = preview
+        .readme
+        .iter()
+        .map(|line| Line::styled(line.clone(), Style::default().fg(app.theme.text)))
+        .collect();
+    if !lines.is_empty() {
+        lines.push(Line::raw(""));
+    }
+    if preview.entries.is_empty() {
+        lines.push(Line::styled(
+            "(empty)",
+            Style::default().fg(app.theme.text_dim),
+        ));
+    }
+    lines.extend(preview.entries.iter().map(|entry| {
+        let color = if entry.ends_with('/') {
+            app.theme.info
+        } else {
+            app.theme.text_dim
+        };
+        Line::styled(entry.clone(), Style::default().fg(color))
+    }));
+    if preview.more_entries > 0 {
+        lines.push(Line::styled(
+            format!("… {} more", preview.more_entries),
+            Style::default().fg(app.theme.text_dim),
+        ));
+    }
 
-    frame.render_widget(list, area);
+    frame.render_widget(Paragraph::new(lines).block(block.title(title)), area);
 }
 
 /* Renders the help line for directory selection mode.
@@ -120,11 +270,26 @@ pub fn render_directory_list(frame: &mut Frame, app: &App, area: Rect) {
  * - Type: filter directories (fuzzy matching)
  * - Tab: complete filter with selected directory path
  * - +/-: increase/decrease scan depth
+ * - F1-F6: toggle directory sources
  * - Enter: proceed to session naming
- * - Esc: cancel and return to normal mode */
+ * - Esc: cancel and return to normal mode
+ * While a directory creation waits for confirmation, the line asks about it instead. */
 pub fn render_help_dir(frame: &mut Frame, app: &App, area: Rect) {
-    let help_text = "Type: filter | Tab: complete | +/-: depth | Enter: name session | Esc: cancel";
-    let paragraph = Paragraph::new(help_text).style(Style::default().fg(app.theme.text_dim));
+    let paragraph = match &app.dir_create {
+        Some(path) => Paragraph::new(format!(
+            "Create directory {} and start a session there? y: create | any key: cancel",
+            path.display()
+        ))
+        .style(
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ),
+        None => Paragraph::new(
+            "Type: filter | Tab: complete | +/-: depth | F1-F6: sources | Enter: name session | Esc: cancel",
+        )
+        .style(Style::default().fg(app.theme.text_dim)),
+    };
 
     frame.render_widget(paragraph, area);
 }
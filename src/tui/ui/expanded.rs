@@ -37,14 +37,29 @@ pub fn render_expanded_mode(frame: &mut Frame, app: &App) {
 
 pub fn render_window_list(frame: &mut Frame, app: &App, area: Rect) {
     let session_name = app.expanded_session.as_deref().unwrap_or("session");
-    let title = format!(
-        " 🪟 {} - {} windows ",
-        session_name,
-        app.expanded_windows.len()
-    );
+    let title = if app.window_filtering {
+        format!(
+            " 🪟 {} - {}/{} windows > {} ",
+            session_name,
+            app.expanded_windows.len(),
+            app.expanded_windows.items.len(),
+            app.window_filter_input
+        )
+    } else {
+        format!(
+            " 🪟 {} - {} windows ",
+            session_name,
+            app.expanded_windows.items.len()
+        )
+    };
 
     if app.expanded_windows.is_empty() {
-        let paragraph = Paragraph::new("No windows found")
+        let message = if app.expanded_windows.items.is_empty() {
+            "No windows found"
+        } else {
+            "No matching windows"
+        };
+        let paragraph = Paragraph::new(message)
             .style(Style::default().fg(app.theme.text_dim))
             .block(
                 Block::default()
@@ -57,15 +72,27 @@ pub fn render_window_list(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    let items: Vec<ListItem> = app
+    let visible = app
         .expanded_windows
-        .iter()
+        .window(area.height.saturating_sub(2) as usize); // minus borders
+    let items: Vec<ListItem> = app
+        .visible_windows()
         .enumerate()
+        .skip(visible.start)
+        .take(visible.len())
         .map(|(idx, window)| {
-            let is_selected = idx == app.selected_window_index;
+            let is_selected = idx == app.expanded_windows.selected();
 
-            let active_icon = if window.active { "⚡" } else { "○" };
-            let active_style = if window.active {
+            let active_icon = if window.dead {
+                "✗"
+            } else if window.active {
+                "⚡"
+            } else {
+                "○"
+            };
+            let active_style = if window.dead {
+                Style::default().fg(app.theme.error)
+            } else if window.active {
                 Style::default().fg(app.theme.primary)
             } else {
                 Style::default().fg(app.theme.text_dim)
@@ -79,7 +106,7 @@ pub fn render_window_list(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(app.theme.text)
             };
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::styled("  ", Style::default()),
                 Span::styled(active_icon, active_style),
                 Span::raw(" "),
@@ -92,7 +119,47 @@ pub fn render_window_list(frame: &mut Frame, app: &App, area: Rect) {
                     format!(" ⟨{}⟩", window.current_command),
                     Style::default().fg(app.theme.info),
                 ),
-            ]);
+            ];
+            if window.dead {
+                spans.push(Span::styled(
+                    "  exited",
+                    Style::default().fg(app.theme.error),
+                ));
+            }
+            // Monitoring set on the window, then the flags tmux raised from it
+            if window.monitor_activity {
+                spans.push(Span::styled("  👁", Style::default().fg(app.theme.info)));
+            }
+            if window.monitor_silence > 0 {
+                spans.push(Span::styled(
+                    format!("  ⏲{}s", window.monitor_silence),
+                    Style::default().fg(app.theme.info),
+                ));
+            }
+            if window.alerts.any() {
+                spans.push(Span::styled(
+                    format!("  {}", window.alerts.badge()),
+                    Style::default().fg(app.theme.warning),
+                ));
+            }
+            if let Some(stats) = app.window_stats.get(&window.index) {
+                let cpu_percent = app.display_cpu(stats.cpu_percent);
+                let mem_total_mb = (app.system_stats.mem_total_kb / 1024).max(1);
+                let mem_percent = stats.mem_mb as f64 / mem_total_mb as f64 * 100.0;
+                spans.extend([
+                    Span::styled("  CPU ", Style::default().fg(app.theme.text_dim)),
+                    Span::styled(
+                        format!("{} {:5.1}%", mini_gauge(cpu_percent), cpu_percent),
+                        Style::default().fg(app.theme.gradient_color(cpu_percent)),
+                    ),
+                    Span::styled("  MEM ", Style::default().fg(app.theme.text_dim)),
+                    Span::styled(
+                        format!("{} {:4}MB", mini_gauge(mem_percent), stats.mem_mb),
+                        Style::default().fg(app.theme.gradient_color(mem_percent)),
+                    ),
+                ]);
+            }
+            let line = Line::from(spans);
 
             let item_style = if is_selected {
                 Style::default().bg(app.theme.bg_highlight)
@@ -114,3 +181,10 @@ pub fn render_window_list(frame: &mut Frame, app: &App, area: Rect) {
 
     frame.render_widget(list, area);
 }
+
+// A fixed-width text gauge for a percentage, e.g. `▕███░░░▏`.
+fn mini_gauge(percent: f64) -> String {
+    const WIDTH: usize = 6;
+    let filled = ((percent / 100.0).clamp(0.0, 1.0) * WIDTH as f64).round() as usize;
+    format!("▕{}{}▏", "█".repeat(filled), "░".repeat(WIDTH - filled))
+}
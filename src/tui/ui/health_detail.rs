@@ -0,0 +1,125 @@
+use crate::health::HealthLevel;
+use crate::tmux::ActivityLevel;
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+
+/* Renders a popup explaining the selected session's health score.
+ *
+ * Lists each factor's measured value and the penalty it contributed,
+ * alongside the configured maximum for that factor. */
+pub fn render_health_detail(frame: &mut Frame, app: &App) {
+    let Some(session) = app.selected_session() else {
+        return;
+    };
+
+    let area = centered_rect(50, 40, frame.area());
+    frame.render_widget(Clear, area);
+
+    let health = app.session_health(session);
+    let weights = &app.health_weights;
+    let level_color = match health.level() {
+        HealthLevel::Healthy => app.theme.success,
+        HealthLevel::Warning => app.theme.warning,
+        HealthLevel::Critical => app.theme.error,
+    };
+
+    let (cpu_value, mem_value) = match &session.stats {
+        Some(stats) => (
+            format!("{:.1}%", stats.cpu_percent),
+            format!("{}MB", stats.mem_mb),
+        ),
+        None => ("n/a".to_string(), "n/a".to_string()),
+    };
+    let activity_value = match session.activity_level() {
+        Some(ActivityLevel::Active) => "active",
+        Some(ActivityLevel::Idle) => "idle",
+        Some(ActivityLevel::Dormant) => "dormant",
+        None => "unknown",
+    };
+
+    let factor_line = |label: &str, value: String, penalty: u8, weight: u8| {
+        let penalty_color = if penalty == 0 {
+            app.theme.success
+        } else if penalty >= weight {
+            app.theme.error
+        } else {
+            app.theme.warning
+        };
+        Line::from(vec![
+            Span::styled(
+                format!("  {:<10}", label),
+                Style::default().fg(app.theme.text_dim),
+            ),
+            Span::styled(
+                format!("{:>10}", value),
+                Style::default().fg(app.theme.text),
+            ),
+            Span::styled(
+                format!("{:>6}", format!("-{}", penalty)),
+                Style::default()
+                    .fg(penalty_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  (max {})", weight),
+                Style::default().fg(app.theme.text_dim),
+            ),
+        ])
+    };
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("  {} ", health.icon()),
+                Style::default().fg(level_color),
+            ),
+            Span::styled(
+                format!("{}/100", health.score),
+                Style::default()
+                    .fg(level_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        factor_line("CPU", cpu_value, health.penalties.cpu, weights.cpu),
+        factor_line("Memory", mem_value, health.penalties.mem, weights.mem),
+        factor_line(
+            "Activity",
+            activity_value.to_string(),
+            health.penalties.activity,
+            weights.activity,
+        ),
+        factor_line(
+            "Multiplex",
+            format!("{}p/{}c", session.panes, session.clients),
+            health.penalties.multiplex,
+            weights.multiplex,
+        ),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Weights are set in the [health] section of config.toml",
+            Style::default()
+                .fg(app.theme.text_dim)
+                .add_modifier(Modifier::ITALIC),
+        )),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(level_color))
+        .title(format!(" 🏥 Health: {} ", session.name))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,39 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::Span,
+};
+
+/* Splits `text` into spans for a list row, drawing the chars at `matched`
+ * (sorted char positions from the fuzzy filter) in `matched_style` layered
+ * over `base`. Runs of matched or unmatched chars share one span. */
+pub fn highlight_matches(
+    text: &str,
+    matched: &[u32],
+    base: Style,
+    matched_style: Style,
+) -> Vec<Span<'static>> {
+    if matched.is_empty() {
+        return vec![Span::styled(text.to_string(), base)];
+    }
+
+    let highlighted = base
+        .patch(matched_style)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (index, c) in text.chars().enumerate() {
+        let is_matched = matched.binary_search(&(index as u32)).is_ok();
+        if is_matched != run_matched && !run.is_empty() {
+            let style = if run_matched { highlighted } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_matched = is_matched;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let style = if run_matched { highlighted } else { base };
+        spans.push(Span::styled(run, style));
+    }
+    spans
+}
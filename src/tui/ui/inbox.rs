@@ -0,0 +1,72 @@
+use crate::tui::app::{App, AttentionReason, InboxEntry};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+
+// Inbox rows shown above the session list; the rest are counted in the title.
+pub const MAX_INBOX_ROWS: usize = 3;
+
+// Height of the inbox box for the current entries, 0 when nothing needs attention.
+pub fn inbox_height(entries: &[InboxEntry]) -> u16 {
+    if entries.is_empty() {
+        0
+    } else {
+        entries.len().min(MAX_INBOX_ROWS) as u16 + 2
+    }
+}
+
+/* Renders the needs-attention inbox: one row per session with a waiting
+ * agent, critical health, or an alert, listing every reason. The selected
+ * session's row is highlighted. */
+pub fn render_inbox(frame: &mut Frame, app: &App, area: Rect, entries: &[InboxEntry]) {
+    let hidden = entries.len().saturating_sub(MAX_INBOX_ROWS);
+    let title = if hidden > 0 {
+        format!(
+            " 📥 Needs attention ({}, {} more) • ! next ",
+            entries.len(),
+            hidden
+        )
+    } else {
+        format!(" 📥 Needs attention ({}) • ! next ", entries.len())
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.warning))
+        .title(title);
+
+    let selected = app.selected_session().map(|session| session.name.as_str());
+    let lines: Vec<Line> = entries
+        .iter()
+        .take(MAX_INBOX_ROWS)
+        .map(|entry| {
+            let mut name_style = Style::default()
+                .fg(app.theme.text)
+                .add_modifier(Modifier::BOLD);
+            if selected == Some(entry.session.as_str()) {
+                name_style = name_style.bg(app.theme.bg_highlight);
+            }
+            let mut spans = vec![Span::styled(entry.session.clone(), name_style)];
+            for reason in &entry.reasons {
+                let (text, color) = match reason {
+                    AttentionReason::Waiting(agents) => {
+                        (format!("⏳ {} waiting", agents.join(", ")), app.theme.info)
+                    }
+                    AttentionReason::Critical(score) => {
+                        (format!("🔴 health {}", score), app.theme.error)
+                    }
+                    AttentionReason::Alert(alert) => (format!("⚠ {}", alert), app.theme.warning),
+                };
+                spans.push(Span::styled("  ", Style::default()));
+                spans.push(Span::styled(text, Style::default().fg(color)));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,109 @@
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+
+/* Renders the agent launcher's agent picker over the directory picker.
+ *
+ * Lists the configured AI agents (dimmed when not in PATH), then the session
+ * that will be created, whether an editor opens in a second window, and, when the
+ * directory declares one, whether both start inside its dev environment. */
+pub fn render_agent_picker(frame: &mut Frame, app: &App) {
+    let Some(dir) = app.agent_launch_dir.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(50, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(format!(" 🤖 Launch agent in {} ", dir.display_name()))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(3 + u16::from(app.dev_env.is_some())),
+        ])
+        .split(inner);
+
+    let items: Vec<ListItem> = app
+        .agent_choices()
+        .iter()
+        .map(|agent| {
+            let installed = which::which(agent).is_ok();
+            let (style, note) = if installed {
+                (Style::default().fg(app.theme.text), "")
+            } else {
+                (Style::default().fg(app.theme.text_dim), "  not installed")
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {}", agent), style),
+                Span::styled(note, Style::default().fg(app.theme.text_dim)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(app.theme.bg_highlight)
+            .add_modifier(Modifier::BOLD),
+    );
+    let mut state = ListState::default().with_selected(Some(app.agent_choice_index));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let editor = if app.agent_launch_editor {
+        format!(
+            "[x] {} in a second window",
+            crate::tui::app::editor_command()
+        )
+    } else {
+        "[ ] editor in a second window".to_string()
+    };
+    let mut footer = vec![
+        Line::from(vec![
+            Span::styled("  Session  ", Style::default().fg(app.theme.text_dim)),
+            Span::styled(
+                app.agent_session_name().unwrap_or_default(),
+                Style::default()
+                    .fg(app.theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Editor   ", Style::default().fg(app.theme.text_dim)),
+            Span::styled(editor, Style::default().fg(app.theme.info)),
+        ]),
+    ];
+    if let Some(env) = app.dev_env {
+        let check = if app.use_dev_env { "[x]" } else { "[ ]" };
+        footer.push(Line::from(vec![
+            Span::styled("  Dev env  ", Style::default().fg(app.theme.text_dim)),
+            Span::styled(
+                format!("{} {} ({})", check, env.name(), env.marker()),
+                Style::default().fg(app.theme.info),
+            ),
+        ]));
+    }
+    footer.push(Line::styled(
+        if app.dev_env.is_some() {
+            "  j/k: agent | e: editor | d: dev env | ↵: launch | Esc: back"
+        } else {
+            "  j/k: agent | e: editor | ↵: launch | Esc: back"
+        },
+        Style::default().fg(app.theme.text_dim),
+    ));
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
@@ -0,0 +1,88 @@
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+use super::text::truncate_to_width;
+
+// Columns of the layout name before its pane count.
+const NAME_WIDTH: usize = 24;
+
+/* Renders a popup listing the saved layouts, each with its pane count and
+ * layout string, over normal mode. The first line shows the session the
+ * picker applies to and how many panes its active window has now. */
+pub fn render_layouts(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let session = app.layout_session.as_deref().unwrap_or_default();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(format!(" 🪟 Layouts for {} ", session))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Current layout
+            Constraint::Min(1),    // Saved layouts
+        ])
+        .split(inner);
+
+    let current = match app.current_layout_panes() {
+        Some(panes) => format!("  Active window: {} pane(s)", panes),
+        None => "  Active window layout unavailable".to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(current).style(Style::default().fg(app.theme.text_dim)),
+        chunks[0],
+    );
+
+    if app.layouts.is_empty() {
+        let empty = Paragraph::new("  No saved layouts yet: s saves this window's layout")
+            .style(Style::default().fg(app.theme.text_dim));
+        frame.render_widget(empty, chunks[1]);
+    } else {
+        let items: Vec<ListItem> = app
+            .layouts
+            .iter()
+            .map(|saved| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "  {:<NAME_WIDTH$} ",
+                            truncate_to_width(&saved.name, NAME_WIDTH)
+                        ),
+                        Style::default().fg(app.theme.text),
+                    ),
+                    Span::styled(
+                        format!("{:>2} panes  ", saved.panes()),
+                        Style::default().fg(app.theme.secondary),
+                    ),
+                    Span::styled(
+                        saved.layout.clone(),
+                        Style::default().fg(app.theme.text_dim),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(app.theme.bg_highlight)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = ListState::default().with_selected(Some(app.layouts_selected));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+    }
+}
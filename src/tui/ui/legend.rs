@@ -0,0 +1,158 @@
+use crate::health::{HEALTHY_SCORE, WARNING_SCORE};
+use crate::tmux::activity_thresholds;
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+use super::text::padding_to_width;
+
+// Columns the icons are padded to, by display width since emoji take two.
+const ICON_WIDTH: usize = 4;
+
+/* Renders a popup explaining the icons on session rows, with the activity
+ * and health thresholds behind them, over normal mode. It carries the full
+ * legend when `[ui] legend = false` drops it from the list title. */
+pub fn render_legend(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(" ❔ Legend ")
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+
+    let heading = |text: &'static str| {
+        Line::from(Span::styled(
+            text,
+            Style::default()
+                .fg(app.theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let entry = |icon: &'static str, color: Color, meaning: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {}{} ", icon, padding_to_width(icon, ICON_WIDTH)),
+                Style::default().fg(color),
+            ),
+            Span::styled(meaning, Style::default().fg(app.theme.text)),
+        ])
+    };
+
+    let activity = activity_thresholds();
+    let mut lines = vec![
+        heading("Activity"),
+        entry(
+            "●",
+            app.theme.success,
+            format!("active, output in the last {}m", activity.idle_after_mins),
+        ),
+        entry(
+            "○",
+            app.theme.warning,
+            format!(
+                "idle for {}-{}m",
+                activity.idle_after_mins, activity.dormant_after_mins
+            ),
+        ),
+        entry(
+            "◌",
+            app.theme.text_dim,
+            format!("dormant, idle over {}m", activity.dormant_after_mins),
+        ),
+        Line::from(""),
+        heading("Clients"),
+        entry("☆", app.theme.warning, "detached".to_string()),
+        entry(
+            "★ ★2",
+            app.theme.warning,
+            "attached, with the client count".to_string(),
+        ),
+        entry(
+            "⇲",
+            app.theme.error,
+            "a smaller client limits the window size".to_string(),
+        ),
+        Line::from(""),
+        heading("Health"),
+        entry(
+            "🟢",
+            app.theme.success,
+            format!("score {}-100", HEALTHY_SCORE),
+        ),
+        entry(
+            "🟡",
+            app.theme.warning,
+            format!("score {}-{}", WARNING_SCORE, HEALTHY_SCORE - 1),
+        ),
+        entry(
+            "🔴",
+            app.theme.error,
+            format!("score below {}", WARNING_SCORE),
+        ),
+        Line::from(""),
+        heading("Badges"),
+        entry("⚠", app.theme.error, "alert threshold exceeded".to_string()),
+        entry("🔔", app.theme.warning, "tmux bell in a window".to_string()),
+        entry("🔕", app.theme.warning, "tmux silence alert".to_string()),
+        entry("💬", app.theme.warning, "tmux activity alert".to_string()),
+        entry(
+            "⧗2",
+            app.theme.info,
+            "place in the review queue".to_string(),
+        ),
+        entry(
+            "⌛",
+            app.theme.warning,
+            "idle time matched by a policy".to_string(),
+        ),
+    ];
+    if app.ci_enabled {
+        lines.push(entry(
+            "#4✓",
+            app.theme.success,
+            "pull request, latest CI run ✓ passed ✗ failed ● running".to_string(),
+        ));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tmux::MockTmux;
+    use crate::tui::app::{App, AppMode};
+
+    #[test]
+    fn meanings_line_up_after_wide_and_long_icons() {
+        let mut app = App::for_test(&MockTmux::with_sessions(&["api"]));
+        app.mode = AppMode::Legend;
+        let buffer = crate::tui::render_to_buffer(&app, 120, 50).unwrap();
+        let width = buffer.area.width as usize;
+        let rows: Vec<String> = buffer
+            .content()
+            .chunks(width)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect();
+        // Cells before the meaning; a wide icon's second cell is blank
+        let column = |meaning: &str| {
+            rows.iter()
+                .find_map(|row| Some(row[..row.find(meaning)?].chars().count()))
+        };
+
+        let active = column("active, output").unwrap();
+        assert_eq!(column("attached, with the client count"), Some(active));
+        assert_eq!(column("score below"), Some(active));
+        assert_eq!(column("tmux bell"), Some(active));
+        assert!(rows.iter().any(|row| row.contains("★ ★2 attached")));
+    }
+}
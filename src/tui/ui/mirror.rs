@@ -0,0 +1,66 @@
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+
+/* Renders the mirror target picker over normal mode.
+ *
+ * Lists every other session; the chosen one gets the source session's
+ * active window linked in after its current window. */
+pub fn render_mirror_picker(frame: &mut Frame, app: &App) {
+    let Some(source) = app.mirror_source.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(50, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(format!(" 🪞 Mirror {} into… ", source))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+
+    let targets = app.mirror_targets();
+    if targets.is_empty() {
+        let empty = Paragraph::new("  No other sessions to mirror into")
+            .style(Style::default().fg(app.theme.text_dim))
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = targets
+        .iter()
+        .map(|session| {
+            let marker = if session.attached { "★ " } else { "☆ " };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {}", marker),
+                    Style::default().fg(app.theme.warning),
+                ),
+                Span::styled(session.name.clone(), Style::default().fg(app.theme.text)),
+                Span::styled(
+                    format!(" ({} win)", session.windows),
+                    Style::default().fg(app.theme.text_dim),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(app.theme.bg_highlight)
+            .add_modifier(Modifier::BOLD),
+    );
+    let mut state = ListState::default().with_selected(Some(app.mirror_target_index));
+    frame.render_stateful_widget(list, area, &mut state);
+}
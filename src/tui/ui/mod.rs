@@ -1,4 +1,4 @@
-use crate::tui::app::{App, AppMode};
+use crate::tui::app::{App, AppMode, MIN_TERMINAL_SIZE};
 use ratatui::Frame;
 
 // Submodules
@@ -6,30 +6,74 @@ mod agents;
 #[cfg(feature = "ascii-art")]
 mod background;
 mod barchart;
+mod clients;
 #[cfg(feature = "ascii-art")]
 mod constants;
+mod detail;
+mod diff;
 mod directory;
 mod expanded;
+mod health_detail;
+mod highlight;
+mod inbox;
+mod launcher;
+mod layouts;
+mod legend;
+mod mirror;
 mod naming;
 mod normal;
 mod overview;
+mod perf_hud;
+mod policy;
+mod process_tree;
+mod prompt;
+mod services;
 mod session_row;
 mod sessions;
 mod stats_overlay;
+mod text;
+mod timeline;
+mod too_small;
+mod window_search;
 
 // Re-export only the main rendering function that's called from render()
 // Helper functions are internal and not re-exported
 #[cfg(feature = "ascii-art")]
 use background::render_background_trex;
 use barchart::render_barchart_view;
+use clients::render_client_list;
+use detail::render_session_detail;
+use diff::render_diff;
 use directory::render_directory_mode;
 use expanded::render_expanded_mode;
+use health_detail::render_health_detail;
+use launcher::render_agent_picker;
+use layouts::render_layouts;
+use legend::render_legend;
+use mirror::render_mirror_picker;
 use naming::render_naming_mode;
 use normal::render_normal_mode;
+use perf_hud::render_perf_hud;
+use policy::render_policy_review;
+use process_tree::render_process_tree;
+use prompt::{
+    render_agent_message_prompt, render_checkpoint_prompt, render_layout_name_prompt,
+    render_note_prompt, render_tags_prompt,
+};
+use services::render_services;
 use stats_overlay::render_stats_overlay;
+use timeline::render_timeline_view;
+use too_small::render_too_small;
+use window_search::render_window_search;
 
 /// Renders the entire TUI based on the current app state.
 pub fn render(frame: &mut Frame, app: &App) {
+    let (min_width, min_height) = MIN_TERMINAL_SIZE;
+    if frame.area().width < min_width || frame.area().height < min_height {
+        render_too_small(frame, app);
+        return;
+    }
+
     // Render decorative T-Rex background first (behind everything)
     #[cfg(feature = "ascii-art")]
     render_background_trex(frame, app, frame.area());
@@ -37,9 +81,83 @@ pub fn render(frame: &mut Frame, app: &App) {
     match app.mode {
         AppMode::SelectingDirectory => render_directory_mode(frame, app),
         AppMode::NamingSession => render_naming_mode(frame, app),
+        AppMode::SelectingAgent => {
+            render_directory_mode(frame, app);
+            render_agent_picker(frame, app);
+        }
         AppMode::ExpandedSession => render_expanded_mode(frame, app),
         AppMode::BarChartView => render_barchart_view(frame, app),
         AppMode::StatsOverlay => render_stats_overlay(frame, app),
+        AppMode::Timeline => render_timeline_view(frame, app),
+        AppMode::HealthDetail => {
+            render_normal_mode(frame, app);
+            render_health_detail(frame, app);
+        }
+        AppMode::SessionDetail => render_session_detail(frame, app),
+        AppMode::Legend => {
+            render_normal_mode(frame, app);
+            render_legend(frame, app);
+        }
+        AppMode::Diff => {
+            render_normal_mode(frame, app);
+            render_diff(frame, app);
+        }
+        AppMode::Checkpointing => {
+            render_normal_mode(frame, app);
+            if app.diff_session.is_some() {
+                render_diff(frame, app);
+            }
+            render_checkpoint_prompt(frame, app);
+        }
+        AppMode::ClientList => {
+            render_normal_mode(frame, app);
+            render_client_list(frame, app);
+        }
+        AppMode::SelectingMirrorTarget => {
+            render_normal_mode(frame, app);
+            render_mirror_picker(frame, app);
+        }
+        AppMode::EditingNote => {
+            render_normal_mode(frame, app);
+            render_note_prompt(frame, app);
+        }
+        AppMode::MessagingAgents => {
+            render_normal_mode(frame, app);
+            render_agent_message_prompt(frame, app);
+        }
+        AppMode::PolicyReview => {
+            render_normal_mode(frame, app);
+            render_policy_review(frame, app);
+        }
+        AppMode::EditingTags => {
+            render_normal_mode(frame, app);
+            render_tags_prompt(frame, app);
+        }
+        AppMode::WindowSearch => {
+            render_normal_mode(frame, app);
+            render_window_search(frame, app);
+        }
+        AppMode::ProcessTree => {
+            render_normal_mode(frame, app);
+            render_process_tree(frame, app);
+        }
+        AppMode::Services => {
+            render_normal_mode(frame, app);
+            render_services(frame, app);
+        }
+        AppMode::Layouts => {
+            render_normal_mode(frame, app);
+            render_layouts(frame, app);
+        }
+        AppMode::NamingLayout => {
+            render_normal_mode(frame, app);
+            render_layouts(frame, app);
+            render_layout_name_prompt(frame, app);
+        }
         _ => render_normal_mode(frame, app),
     }
+
+    if app.perf.visible {
+        render_perf_hud(frame, app);
+    }
 }
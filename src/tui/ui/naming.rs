@@ -32,7 +32,8 @@ pub fn render_naming_mode(frame: &mut Frame, app: &App) {
 
 /* Renders the header for session naming mode.
  *
- * Shows the naming prompt with the current input and a blinking cursor.
+ * Shows the naming prompt with the current input, and a blinking cursor
+ * while the name (not the start command) has focus.
  * The header has a magenta border to indicate the naming mode. */
 pub fn render_header_naming(frame: &mut Frame, app: &App, area: Rect) {
     let cursor = "_";
@@ -51,7 +52,7 @@ pub fn render_header_naming(frame: &mut Frame, app: &App, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
-            cursor,
+            if app.naming_command_focus { "" } else { cursor },
             Style::default()
                 .fg(app.theme.secondary)
                 .add_modifier(Modifier::RAPID_BLINK),
@@ -73,6 +74,10 @@ pub fn render_header_naming(frame: &mut Frame, app: &App, area: Rect) {
  * - The selected directory path (with home directory abbreviated to ~)
  * - The sanitized session name that will be used
  * - Whether the name was sanitized (if different from input)
+ * - The optional start command for the first window, with recent commands
+ *   listed while it has focus
+ * - A warning when the name is taken, since Enter then attaches to that
+ *   session, with a free name to switch to
  * - Instructions for creating or going back */
 pub fn render_naming_preview(frame: &mut Frame, app: &App, area: Rect) {
     let path_display = app
@@ -89,11 +94,7 @@ pub fn render_naming_preview(frame: &mut Frame, app: &App, area: Rect) {
         })
         .unwrap_or_default();
 
-    let sanitized_name = if app.session_name_input.is_empty() {
-        "session".to_string()
-    } else {
-        crate::directory::sanitize_session_name(&app.session_name_input)
-    };
+    let sanitized_name = app.pending_session_name();
 
     let name_changed = app.session_name_input != sanitized_name;
     let selected_template = app.selected_template();
@@ -133,6 +134,36 @@ pub fn render_naming_preview(frame: &mut Frame, app: &App, area: Rect) {
                 Span::raw("")
             },
         ]),
+    ];
+
+    if let Some(existing) = app.existing_session_for_name() {
+        let location = existing
+            .path
+            .as_ref()
+            .map(|path| format!(" in {}", path.display()))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled("    ⚠ Exists   ", Style::default().fg(app.theme.warning)),
+            Span::styled(
+                format!("Enter attaches to the running session{}", location),
+                Style::default().fg(app.theme.warning),
+            ),
+        ]));
+        if let Some(suggestion) = app.suggested_session_name() {
+            lines.push(Line::from(vec![
+                Span::styled("    Suggested  ", Style::default().fg(app.theme.text_dim)),
+                Span::styled(
+                    suggestion,
+                    Style::default()
+                        .fg(app.theme.info)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("  Ctrl+n to use", Style::default().fg(app.theme.text_dim)),
+            ]));
+        }
+    }
+
+    lines.extend([
         Line::from(""),
         Line::from(vec![
             Span::styled("    Template   ", Style::default().fg(app.theme.text_dim)),
@@ -151,8 +182,45 @@ pub fn render_naming_preview(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("    Panes      ", Style::default().fg(app.theme.text_dim)),
             Span::styled(pane_summary, Style::default().fg(app.theme.info)),
         ]),
-        Line::from(""),
-    ];
+    ]);
+    if let Some(project) = app
+        .project_template()
+        .filter(|project| selected_template.is_none_or(|template| template.id != project.id))
+    {
+        lines.push(Line::from(vec![
+            Span::styled("    Project    ", Style::default().fg(app.theme.text_dim)),
+            Span::styled(
+                project.name.clone(),
+                Style::default().fg(app.theme.secondary),
+            ),
+            Span::styled(
+                " template available  Tab to use",
+                Style::default().fg(app.theme.text_dim),
+            ),
+        ]));
+    }
+    lines.push(render_command_line(app));
+
+    // Recent start commands, with the recalled one highlighted
+    if app.naming_command_focus && !app.recent_commands.is_empty() {
+        for (idx, command) in app.recent_commands.iter().enumerate() {
+            let style = if app.recent_command_index == Some(idx) {
+                Style::default()
+                    .fg(app.theme.warning)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text_dim)
+            };
+            lines.push(Line::from(vec![
+                Span::raw("               "),
+                Span::styled(command.clone(), style),
+            ]));
+        }
+    }
+    if let Some(line) = render_dev_env_line(app) {
+        lines.push(line);
+    }
+    lines.push(Line::from(""));
 
     if !app.template_warnings.is_empty() {
         lines.push(Line::from(vec![
@@ -195,14 +263,80 @@ pub fn render_naming_preview(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+// The start command row; a blinking cursor marks it while it has focus.
+fn render_command_line(app: &App) -> Line<'_> {
+    let mut spans = vec![Span::styled(
+        "    Command    ",
+        Style::default().fg(app.theme.text_dim),
+    )];
+    if app.naming_command_focus {
+        spans.push(Span::styled(
+            &app.session_command_input,
+            Style::default()
+                .fg(app.theme.text)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(
+            "_",
+            Style::default()
+                .fg(app.theme.secondary)
+                .add_modifier(Modifier::RAPID_BLINK),
+        ));
+        if !app.recent_commands.is_empty() {
+            spans.push(Span::styled(
+                "  ↑/↓ recent",
+                Style::default().fg(app.theme.text_dim),
+            ));
+        }
+    } else if app.session_command_input.is_empty() {
+        spans.push(Span::styled(
+            "shell  Ctrl+e to set a start command",
+            Style::default().fg(app.theme.text_dim),
+        ));
+    } else {
+        spans.push(Span::styled(
+            &app.session_command_input,
+            Style::default().fg(app.theme.info),
+        ));
+    }
+    Line::from(spans)
+}
+
+// The dev environment row, when the directory declares one.
+fn render_dev_env_line(app: &App) -> Option<Line<'static>> {
+    let env = app.dev_env?;
+    let (state, style) = if app.use_dev_env {
+        (
+            format!("{} ({})", env.name(), env.marker()),
+            Style::default().fg(app.theme.success),
+        )
+    } else {
+        (
+            format!("off, {} found", env.marker()),
+            Style::default().fg(app.theme.text_dim),
+        )
+    };
+    Some(Line::from(vec![
+        Span::styled("    Dev env    ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(state, style),
+        Span::styled(
+            "  Ctrl+d to toggle",
+            Style::default().fg(app.theme.text_dim),
+        ),
+    ]))
+}
+
 /* Renders the help line for session naming mode.
  *
  * Shows available keybindings:
  * - Type: Enter session name
+ * - Ctrl+e: Switch typing between the name and the start command
+ * - Ctrl+n: Use the suggested name when the typed one is taken
+ * - Ctrl+d: Start the session inside the directory's dev environment, or not
  * - Enter: Create the session
  * - Esc: Go back to directory selection */
 pub fn render_help_naming(frame: &mut Frame, app: &App, area: Rect) {
-    let help_text = "Type session name | Tab: template | Enter: create | Esc: back";
+    let help_text = "Type session name | Tab: template | Ctrl+e: command | Ctrl+n: suggested name | Enter: create | Esc: back";
     let paragraph = Paragraph::new(help_text).style(Style::default().fg(app.theme.text_dim));
 
     frame.render_widget(paragraph, area);
@@ -1,4 +1,5 @@
-use crate::tui::app::{App, AppMode};
+use crate::config::PreviewOrientation;
+use crate::tui::app::{App, AppMode, FocusArea, ToastLevel};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout},
@@ -8,66 +9,119 @@ use ratatui::{
 };
 
 use super::agents::render_agent_box;
+use super::inbox::{inbox_height, render_inbox};
 use super::overview::render_system_overview;
 use super::sessions::render_session_list;
 
 pub fn render_normal_mode(frame: &mut Frame, app: &App) {
     let visible_agents = app.visible_agents();
+    // A short popup drops the overview and shows fewer agents
+    let (overview_rows, max_agent_rows) = if app.popup_short() { (0, 2) } else { (3, 5) };
     let agent_rows = if visible_agents.is_empty() {
         1
     } else {
-        visible_agents.len().min(5)
+        visible_agents.len().min(max_agent_rows)
     } as u16;
 
+    let inbox = app.inbox();
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),              // System overview
-            Constraint::Length(agent_rows + 2), // Agent box (content + borders)
-            Constraint::Min(1),                 // Sessions
-            Constraint::Length(2),              // Enhanced help
+            Constraint::Length(overview_rows),        // System overview
+            Constraint::Length(agent_rows + 2),       // Agent box (content + borders)
+            Constraint::Length(inbox_height(&inbox)), // Needs-attention inbox, if any
+            Constraint::Min(1),                       // Sessions
+            Constraint::Length(2),                    // Enhanced help
         ])
         .split(frame.area());
 
-    render_system_overview(frame, app, chunks[0]);
+    if overview_rows > 0 {
+        render_system_overview(frame, app, chunks[0]);
+    }
     render_agent_box(frame, app, chunks[1]);
+    if !inbox.is_empty() {
+        render_inbox(frame, app, chunks[2], &inbox);
+    }
 
-    // If preview is enabled, split the session area
+    // If preview is enabled, split the session area beside or below the list
     if app.show_preview {
+        let direction = match app.preview_orientation {
+            PreviewOrientation::Horizontal if !app.popup_narrow() => Direction::Horizontal,
+            _ => Direction::Vertical,
+        };
         let main_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(chunks[2]);
+            .direction(direction)
+            .constraints([
+                Constraint::Percentage(100 - app.preview_size),
+                Constraint::Percentage(app.preview_size),
+            ])
+            .split(chunks[3]);
         render_session_list(frame, app, main_chunks[0]);
         render_preview(frame, app, main_chunks[1]);
     } else {
-        render_session_list(frame, app, chunks[2]);
+        render_session_list(frame, app, chunks[3]);
     }
-    render_help(frame, app, chunks[3]);
+    render_help(frame, app, chunks[4]);
 }
 
 fn render_preview(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    if app.mode == AppMode::PreviewCopy {
+        render_preview_copy(frame, app, area);
+        return;
+    }
+
     let session_name = app
         .selected_session()
         .map(|s| s.name.as_str())
         .unwrap_or("No session");
 
-    let title = format!(" Preview: {} ", session_name);
+    let title = match &app.follow {
+        Some(follow) => format!(" Following: {} • F stop ", follow.label),
+        None => format!(" Preview: {} ", session_name),
+    };
 
-    let content = if app.preview_lines.is_empty() {
-        vec![Line::from(Span::styled(
+    // The session note, if any, sits above the pane content
+    let mut content: Vec<Line> = app
+        .selected_session()
+        .and_then(|s| s.note.as_ref())
+        .map(|note| {
+            vec![
+                Line::from(Span::styled(
+                    format!("📝 {}", note),
+                    Style::default()
+                        .fg(app.theme.secondary)
+                        .add_modifier(Modifier::ITALIC),
+                )),
+                Line::from(""),
+            ]
+        })
+        .unwrap_or_default();
+
+    if app.preview_lines.is_empty() {
+        content.push(Line::from(Span::styled(
             "No content to preview",
             Style::default().fg(app.theme.text_dim),
-        ))]
+        )));
     } else {
-        app.preview_lines
-            .iter()
-            .map(|line| Line::from(Span::raw(line.as_str())))
-            .collect()
-    };
+        content.extend(
+            app.preview_lines
+                .iter()
+                .map(|line| Line::from(Span::raw(line.as_str()))),
+        );
+    }
 
+    // A followed pane stays scrolled to its newest output
+    let scroll = if app.follow.is_some() {
+        content
+            .len()
+            .saturating_sub(area.height.saturating_sub(2) as usize)
+    } else {
+        0
+    };
     let paragraph = Paragraph::new(content)
         .style(Style::default().fg(app.theme.text))
+        .scroll((scroll as u16, 0))
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -78,18 +132,87 @@ fn render_preview(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     frame.render_widget(paragraph, area);
 }
 
+/* Renders the frozen capture in copy mode: the cursor line and the
+ * selection are highlighted, and the view scrolls to keep the cursor in
+ * sight. */
+fn render_preview_copy(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let session_name = app
+        .selected_session()
+        .map(|s| s.name.as_str())
+        .unwrap_or("No session");
+    let title = format!(
+        " Copy: {} (line {}/{}) ",
+        session_name,
+        app.copy_cursor + 1,
+        app.preview_lines.len()
+    );
+
+    let selection = app.copy_range();
+    let content: Vec<Line> = app
+        .preview_lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let style = if idx == app.copy_cursor {
+                Style::default()
+                    .bg(app.theme.bg_highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else if app.copy_anchor.is_some() && selection.contains(&idx) {
+                Style::default().bg(app.theme.bg_highlight)
+            } else {
+                Style::default()
+            };
+            Line::styled(line.as_str(), style)
+        })
+        .collect();
+
+    let height = area.height.saturating_sub(2) as usize;
+    let scroll = app.copy_cursor.saturating_sub(height.saturating_sub(1));
+    let paragraph = Paragraph::new(content)
+        .style(Style::default().fg(app.theme.text))
+        .scroll((scroll as u16, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.warning))
+                .title(title),
+        );
+
+    frame.render_widget(paragraph, area);
+}
+
 pub fn render_help(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let help_items: Vec<(&str, &str)> = match app.mode {
+    let mut help_items: Vec<(&str, &str)> = match app.mode {
         AppMode::Normal => vec![
             ("j/k", "nav"),
             ("l", "expand"),
             ("p", "preview"),
+            ("V", "copy"),
             ("b", "charts"),
             ("s", "stats"),
+            ("v", "timeline"),
+            ("H", "health"),
+            ("i", "detail"),
+            ("f", "diff"),
+            ("+/-", "commit/stash"),
+            ("T", "procs"),
+            ("E", "env"),
+            ("O", "layouts"),
             ("↵", "attach"),
+            ("R", "watch"),
             ("c", "create"),
+            ("a", "agent"),
+            ("K", "clone"),
+            ("Q/>", "queue/next"),
+            ("I", "idle review"),
+            ("e", "note"),
+            ("t", "tags"),
+            ("y/Y", "copy path/cmd"),
             ("d", "delete"),
+            ("u", "undo"),
             ("/", "filter"),
+            ("w", "windows"),
+            ("?", "legend"),
             ("q", "quit"),
         ],
         AppMode::Filtering => vec![
@@ -97,6 +220,7 @@ pub fn render_help(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
             ("↵", "attach"),
             ("Esc", "clear"),
             ("Tab", "nav"),
+            ("^d/^x", "kill/detach #tag"),
         ],
         AppMode::SelectingDirectory => vec![
             ("type", "filter"),
@@ -108,21 +232,223 @@ pub fn render_help(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         AppMode::NamingSession => vec![
             ("type", "name"),
             ("Tab", "template"),
+            ("^e", "command"),
             ("↵", "create"),
             ("Esc", "back"),
         ],
+        AppMode::SelectingAgent => vec![
+            ("j/k", "agent"),
+            ("e", "editor"),
+            ("↵", "launch"),
+            ("Esc", "back"),
+        ],
+        AppMode::ExpandedSession if app.window_kill.is_some() => {
+            vec![("y", "confirm"), ("any key", "cancel")]
+        }
+        AppMode::ExpandedSession if app.window_filtering => vec![
+            ("type", "filter"),
+            ("↵", "attach"),
+            ("Esc", "clear"),
+            ("Tab", "nav"),
+        ],
         AppMode::ExpandedSession => vec![
             ("j/k", "nav"),
             ("↵", "attach"),
+            ("/", "filter"),
+            ("x/X", "kill window/pane"),
+            ("r", "respawn"),
+            ("a/s", "watch activity/silence"),
             ("h/Esc", "back"),
             ("q", "quit"),
         ],
-        AppMode::BarChartView => vec![("b/Esc", "back"), ("q", "quit")],
-        AppMode::StatsOverlay => vec![("s/Esc", "close"), ("q", "quit")],
+        AppMode::BarChartView => vec![
+            ("h/l", "select"),
+            ("↵", "attach"),
+            ("x", "kill"),
+            ("1-5", "charts"),
+            ("b/Esc", "back"),
+            ("q", "quit"),
+        ],
+        AppMode::Timeline => vec![
+            ("j/k", "nav"),
+            ("↵", "attach"),
+            ("+/-", "span"),
+            ("v/Esc", "back"),
+            ("q", "quit"),
+        ],
+        AppMode::StatsOverlay => vec![
+            ("↑/↓", "select"),
+            ("↵", "attach"),
+            ("x", "kill"),
+            ("c/J", "export csv/json"),
+            ("s/Esc", "close"),
+            ("q", "quit"),
+        ],
+        AppMode::HealthDetail => vec![("H/Esc", "close"), ("q", "quit")],
+        AppMode::Legend => vec![("?/Esc", "close"), ("q", "quit")],
+        AppMode::PreviewCopy => vec![
+            ("j/k", "move"),
+            ("g/G", "top/bottom"),
+            ("v", "select"),
+            ("y/↵", "yank"),
+            ("V/Esc", "cancel"),
+        ],
+        AppMode::SessionDetail => vec![("↵", "attach"), ("i/Esc", "back"), ("q", "quit")],
+        AppMode::Diff => vec![
+            ("j/k", "scroll"),
+            ("g/G", "top/bottom"),
+            ("Tab", if app.diff_patch { "stat" } else { "full diff" }),
+            ("c/s", "commit/stash"),
+            ("f/Esc", "close"),
+            ("q", "quit"),
+        ],
+        AppMode::ClientList => vec![
+            ("A", "take over"),
+            ("F", "fit"),
+            ("K", "kick smallest"),
+            ("C/Esc", "close"),
+            ("q", "quit"),
+        ],
+        AppMode::SelectingMirrorTarget => vec![
+            ("j/k", "nav"),
+            ("↵", "mirror"),
+            ("Esc", "cancel"),
+            ("q", "quit"),
+        ],
+        AppMode::EditingNote => vec![("type", "note"), ("↵", "save"), ("Esc", "cancel")],
+        AppMode::EditingTags => vec![("type", "tags"), ("↵", "save"), ("Esc", "cancel")],
+        AppMode::PolicyReview => vec![("y", "kill all"), ("any key", "keep")],
+        AppMode::MessagingAgents => vec![("type", "message"), ("↵", "send"), ("Esc", "cancel")],
+        AppMode::WindowSearch => vec![
+            ("type", "search"),
+            ("↵", "attach"),
+            ("Tab", "nav"),
+            ("Esc", "close"),
+        ],
+        AppMode::ProcessTree => vec![
+            ("j/k", "nav"),
+            ("x/X", "term/kill"),
+            ("+/-", "nice"),
+            ("N", "nice all"),
+            ("L", "limit"),
+            ("T/Esc", "close"),
+            ("q", "quit"),
+        ],
+        AppMode::Services => vec![
+            ("j/k", "nav"),
+            ("s", "start"),
+            ("x", "stop"),
+            ("r", "restart"),
+            ("↵", "status"),
+            ("U/Esc", "close"),
+            ("q", "quit"),
+        ],
+        AppMode::Layouts => vec![
+            ("j/k", "nav"),
+            ("↵", "apply"),
+            ("s", "save current"),
+            ("d", "delete"),
+            ("O/Esc", "close"),
+            ("q", "quit"),
+        ],
+        AppMode::NamingLayout => vec![("type", "name"), ("↵", "save"), ("Esc", "cancel")],
+        AppMode::Checkpointing => vec![("type", "message"), ("↵", "run"), ("Esc", "cancel")],
     };
 
+    // Preview layout keys only work while the preview is shown
+    if app.mode == AppMode::Normal
+        && app.show_preview
+        && let Some(index) = help_items.iter().position(|item| item.0 == "p")
+    {
+        help_items.splice(index + 1..index + 1, [("[/]", "size"), ("o", "split")]);
+    }
+
+    // Fleet actions are offered while the agent box has focus
+    if app.mode == AppMode::Normal && app.focus == FocusArea::Agents {
+        help_items.splice(
+            0..0,
+            [
+                ("space", "mark"),
+                ("m", "message"),
+                ("z/Z", "pause/resume"),
+                ("W", "next waiting"),
+                ("F", "follow"),
+            ],
+        );
+    } else if app.mode == AppMode::Normal && app.follow.is_some() {
+        help_items.insert(0, ("F", "unfollow"));
+    }
+
+    // The git UI launcher is only offered when the selected repo is dirty
+    let selected_dirty = app
+        .selected_session()
+        .and_then(|session| session.git_status.as_ref())
+        .is_some_and(|git| git.is_repo && git.dirty_count > 0);
+    if app.mode == AppMode::Normal && selected_dirty {
+        let program = app.git_ui.split_whitespace().next().unwrap_or_default();
+        help_items.insert(0, ("L", program));
+    }
+
+    // Agent services are only offered once `[services] units` is configured
+    if app.mode == AppMode::Normal
+        && app.service_counts().is_some()
+        && let Some(index) = help_items.iter().position(|item| item.0 == "T")
+    {
+        help_items.insert(index + 1, ("U", "services"));
+    }
+
     let mut spans = Vec::new();
 
+    if let Some(target) = app.window_kill.as_ref() {
+        spans.push(Span::styled(
+            format!("{} ", target.prompt()),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(" ┃ ", Style::default().fg(app.theme.text_dim)));
+    }
+
+    // Undo toast for the newest pending kill/detach, else the last result
+    if let Some(pending) = app.pending_actions.last() {
+        spans.push(Span::styled(
+            format!(
+                "{} {} in {}s ",
+                pending.kind.verb(),
+                pending.session,
+                pending.seconds_left(std::time::Instant::now())
+            ),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        ));
+        // Plain keys type into the filter, so undo is Ctrl-u there
+        let undo_key = if app.mode == AppMode::Filtering {
+            "^u"
+        } else {
+            "u"
+        };
+        spans.push(Span::styled(
+            undo_key,
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(" undo", Style::default().fg(app.theme.text)));
+        spans.push(Span::styled(" ┃ ", Style::default().fg(app.theme.text_dim)));
+    } else if let Some(toast) = app.current_toast() {
+        let (icon, color) = match toast.level {
+            ToastLevel::Info => ("ℹ", app.theme.info),
+            ToastLevel::Warn => ("⚠", app.theme.warning),
+            ToastLevel::Error => ("✖", app.theme.error),
+        };
+        spans.push(Span::styled(
+            format!("{} {} ", icon, toast.message),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(" ┃ ", Style::default().fg(app.theme.text_dim)));
+    }
+
     for (i, (key, action)) in help_items.iter().enumerate() {
         if i > 0 {
             spans.push(Span::styled(" │ ", Style::default().fg(app.theme.text_dim)));
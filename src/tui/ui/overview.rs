@@ -15,8 +15,10 @@ pub fn render_system_overview(frame: &mut Frame, app: &App, area: Rect) {
     let mut total_cpu = 0.0;
     let mut total_mem_mb = 0u64;
     let mut active_sessions = 0;
+    let mut total_clients = 0;
+    let mut total_panes = 0;
 
-    for session in &app.sessions {
+    for session in &app.sessions.items {
         if let Some(ref stats) = session.stats {
             total_cpu += stats.cpu_percent;
             total_mem_mb += stats.mem_mb;
@@ -24,12 +26,26 @@ pub fn render_system_overview(frame: &mut Frame, app: &App, area: Rect) {
         if session.attached {
             active_sessions += 1;
         }
+        total_clients += session.clients;
+        total_panes += session.panes;
     }
 
     // Pulsing spinner shows the app is alive and sampling
     let pulse = PULSE_FRAMES[(app.tick as usize / 2) % PULSE_FRAMES.len()];
 
-    let overview_line = Line::from(vec![
+    /* CPU thresholds are relative to the machine, so a 300% session total is
+     * busy on 4 cores but unremarkable on 32. */
+    let system = &app.system_stats;
+    let cores = system.num_cpus.max(1) as f64;
+    let machine_cpu = crate::sysinfo::normalize_cpu_percent(total_cpu, system.num_cpus);
+    let cpu_label = if app.normalize_cpu {
+        format!("CPU: {:.1}% of {} cores", machine_cpu, system.num_cpus)
+    } else {
+        format!("CPU: {:.1}%/{}%", total_cpu, system.num_cpus * 100)
+    };
+    let load = system.load_avg;
+
+    let mut overview_spans = vec![
         Span::styled(
             format!("{} ", pulse),
             Style::default().fg(app.theme.success),
@@ -42,20 +58,36 @@ pub fn render_system_overview(frame: &mut Frame, app: &App, area: Rect) {
         ),
         Span::styled("│ ", Style::default().fg(app.theme.text_dim)),
         Span::styled(
-            format!("{} sessions", app.sessions.len()),
+            format!("{} sessions", app.sessions.items.len()),
             Style::default().fg(app.theme.info),
         ),
         Span::styled(" │ ", Style::default().fg(app.theme.text_dim)),
         Span::styled(
-            format!("{} attached", active_sessions),
+            format!("{} attached ({} clients)", active_sessions, total_clients),
             Style::default().fg(app.theme.success),
         ),
         Span::styled(" │ ", Style::default().fg(app.theme.text_dim)),
         Span::styled(
-            format!("CPU: {:.1}%", total_cpu),
-            Style::default().fg(if total_cpu > 300.0 {
+            format!("{} panes", total_panes),
+            Style::default().fg(app.theme.info),
+        ),
+        Span::styled(" │ ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(
+            cpu_label,
+            Style::default().fg(if machine_cpu > 80.0 {
+                app.theme.error
+            } else if machine_cpu > 50.0 {
+                app.theme.warning
+            } else {
+                app.theme.success
+            }),
+        ),
+        Span::styled(" │ ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(
+            format!("load {:.2} {:.2} {:.2}", load[0], load[1], load[2]),
+            Style::default().fg(if load[0] > cores {
                 app.theme.error
-            } else if total_cpu > 150.0 {
+            } else if load[0] > cores * 0.7 {
                 app.theme.warning
             } else {
                 app.theme.success
@@ -63,7 +95,11 @@ pub fn render_system_overview(frame: &mut Frame, app: &App, area: Rect) {
         ),
         Span::styled(" │ ", Style::default().fg(app.theme.text_dim)),
         Span::styled(
-            format!("MEM: {}MB", total_mem_mb),
+            format!(
+                "MEM: {}MB ({:.1}G free)",
+                total_mem_mb,
+                system.mem_available_kb as f64 / (1024.0 * 1024.0)
+            ),
             Style::default().fg(if total_mem_mb > 4096 {
                 app.theme.error
             } else if total_mem_mb > 2048 {
@@ -74,10 +110,47 @@ pub fn render_system_overview(frame: &mut Frame, app: &App, area: Rect) {
         ),
         Span::styled(" │ ", Style::default().fg(app.theme.text_dim)),
         Span::styled(
-            format!("{} agents", app.ai_processes.len()),
+            format!("{} agents", app.agents.items.len()),
             Style::default().fg(app.theme.secondary),
         ),
-    ]);
+    ];
+
+    if app.sampling_paused() {
+        overview_spans.push(Span::styled(" │ ", Style::default().fg(app.theme.text_dim)));
+        overview_spans.push(Span::styled(
+            "⏸ paused",
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if app.power_saving {
+        overview_spans.push(Span::styled(" │ ", Style::default().fg(app.theme.text_dim)));
+        overview_spans.push(Span::styled(
+            "🔋 saver",
+            Style::default().fg(app.theme.warning),
+        ));
+    }
+
+    if !app.alerts.is_empty() {
+        let alert_style = if app.alert_flash_on() {
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD)
+        };
+        overview_spans.push(Span::styled(" │ ", Style::default().fg(app.theme.text_dim)));
+        overview_spans.push(Span::styled(
+            format!("⚠ {} alerts", app.alerts.len()),
+            alert_style,
+        ));
+    }
+
+    let overview_line = Line::from(overview_spans);
 
     let block = Block::default()
         .borders(Borders::ALL)
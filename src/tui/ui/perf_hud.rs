@@ -0,0 +1,90 @@
+use crate::tui::app::{App, PerfSample};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use std::time::Duration;
+
+const HUD_WIDTH: u16 = 60;
+
+/* Renders the F12 performance HUD in the top-right corner, over whatever
+ * mode is showing. Lists the last and slowest frame render and background
+ * refresh, with allocations made during the last run. */
+pub fn render_perf_hud(frame: &mut Frame, app: &App) {
+    let perf = &app.perf;
+    let full = frame.area();
+    let height = (perf.tasks.len() as u16 + 5).min(full.height);
+    let width = HUD_WIDTH.min(full.width);
+    let area = Rect::new(full.x + full.width - width, full.y, width, height);
+    frame.render_widget(Clear, area);
+
+    let ms_style = |elapsed: Duration| {
+        let color = match elapsed.as_millis() {
+            0..16 => app.theme.success,
+            16..50 => app.theme.warning,
+            _ => app.theme.error,
+        };
+        Style::default().fg(color)
+    };
+    let sample_line = |label: &str, sample: &PerfSample| {
+        Line::from(vec![
+            Span::styled(
+                format!(" {:<22}", label),
+                Style::default().fg(app.theme.text_dim),
+            ),
+            Span::styled(
+                format!("{:>7}", format_ms(sample.last)),
+                ms_style(sample.last),
+            ),
+            Span::styled(
+                format!("{:>8}", format_ms(sample.peak)),
+                ms_style(sample.peak),
+            ),
+            Span::styled(
+                format!("{:>9}", sample.allocations),
+                Style::default().fg(app.theme.text),
+            ),
+            Span::styled(
+                format!("{:>8}", sample.runs),
+                Style::default().fg(app.theme.text_dim),
+            ),
+        ])
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                " {:<22}{:>7}{:>8}{:>9}{:>8}",
+                "", "last", "peak", "allocs", "runs"
+            ),
+            Style::default()
+                .fg(app.theme.text_dim)
+                .add_modifier(Modifier::BOLD),
+        )),
+        sample_line("render", &perf.frame),
+    ];
+    lines.extend(
+        perf.tasks
+            .iter()
+            .map(|(name, sample)| sample_line(name, sample)),
+    );
+    lines.push(Line::from(Span::styled(
+        format!(" {} sessions · F12 hide", app.sessions.items.len()),
+        Style::default().fg(app.theme.text_dim),
+    )));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(" ⏱ perf ")
+        .style(Style::default().bg(app.theme.bg_overlay));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn format_ms(elapsed: Duration) -> String {
+    format!("{:.1}ms", elapsed.as_secs_f64() * 1000.0)
+}
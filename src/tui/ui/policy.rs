@@ -0,0 +1,72 @@
+use crate::tmux::format_elapsed;
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+
+/* Renders the idle policy review: every session a kill policy matches,
+ * how long it has been idle, and the policy that matched. Nothing is
+ * killed until the user confirms here. */
+pub fn render_policy_review(frame: &mut Frame, app: &App) {
+    let candidates = app.kill_candidates();
+
+    let area = centered_rect(60, 40, frame.area());
+    frame.render_widget(Clear, area);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {:<24} {:>6}  POLICY", "SESSION", "IDLE"),
+            Style::default()
+                .fg(app.theme.text_dim)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ];
+
+    for candidate in &candidates {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<24} ", candidate.session),
+                Style::default().fg(app.theme.text),
+            ),
+            Span::styled(
+                format!("{:>6}  ", format_elapsed(candidate.idle_secs)),
+                Style::default().fg(app.theme.warning),
+            ),
+            Span::styled(
+                candidate.policy.describe(),
+                Style::default().fg(app.theme.text_dim),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  Kill {} idle session(s)? (y/N)", candidates.len()),
+        Style::default()
+            .fg(app.theme.error)
+            .add_modifier(Modifier::BOLD),
+    )));
+    lines.push(Line::from(Span::styled(
+        "  Dismissed sessions stay flagged until trex restarts",
+        Style::default()
+            .fg(app.theme.text_dim)
+            .add_modifier(Modifier::ITALIC),
+    )));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.warning))
+        .title(" ⌛ Idle Sessions ")
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
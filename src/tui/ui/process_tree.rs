@@ -0,0 +1,99 @@
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+
+/* Renders a popup listing the selected session's processes as a tree.
+ *
+ * Each pane PID is followed by its descendants, indented by depth, with the
+ * CPU and memory from the latest stats sample. The last kill/renice result
+ * is shown under the list. */
+pub fn render_process_tree(frame: &mut Frame, app: &App) {
+    let Some(session_name) = app.process_tree_session.as_ref() else {
+        return;
+    };
+
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(format!(" 🌲 Processes: {} ", session_name))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Column header
+            Constraint::Min(1),    // Process list
+            Constraint::Length(1), // Action status
+        ])
+        .split(inner);
+
+    let header = Line::from(Span::styled(
+        format!("  {:>7} {:>7} {:>8}  COMMAND", "PID", "CPU%", "MEM"),
+        Style::default()
+            .fg(app.theme.text_dim)
+            .add_modifier(Modifier::BOLD),
+    ));
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    if app.process_tree.is_empty() {
+        let empty =
+            Paragraph::new("  No processes found").style(Style::default().fg(app.theme.text_dim));
+        frame.render_widget(empty, chunks[1]);
+    } else {
+        let items: Vec<ListItem> = app
+            .process_tree
+            .iter()
+            .map(|process| {
+                let branch = if process.depth == 0 {
+                    String::new()
+                } else {
+                    format!("{}└ ", "  ".repeat(process.depth - 1))
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("  {:>7} ", process.pid),
+                        Style::default().fg(app.theme.text_dim),
+                    ),
+                    Span::styled(
+                        format!("{:>7.1} ", process.cpu_percent),
+                        Style::default().fg(app.theme.gradient_color(process.cpu_percent)),
+                    ),
+                    Span::styled(
+                        format!("{:>6}MB  ", process.mem_mb),
+                        Style::default().fg(app.theme.info),
+                    ),
+                    Span::styled(branch, Style::default().fg(app.theme.text_dim)),
+                    Span::styled(process.name.clone(), Style::default().fg(app.theme.text)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(app.theme.bg_highlight)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = ListState::default().with_selected(Some(app.process_tree_selected));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+    }
+
+    if let Some(status) = &app.process_tree_status {
+        let status =
+            Paragraph::new(format!("  {}", status)).style(Style::default().fg(app.theme.warning));
+        frame.render_widget(status, chunks[2]);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::tui::app::{App, CheckpointKind};
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::stats_overlay::centered_rect;
+
+// Renders the note prompt for a session over normal mode.
+pub fn render_note_prompt(frame: &mut Frame, app: &App) {
+    if let Some(session) = app.note_session.as_ref() {
+        render_session_prompt(
+            frame,
+            app,
+            &format!(" 📝 Note for {} ", session),
+            &app.note_input,
+            "Enter save · empty clears · Esc cancel",
+        );
+    }
+}
+
+// Renders the tag prompt for a session over normal mode.
+pub fn render_tags_prompt(frame: &mut Frame, app: &App) {
+    if let Some(session) = app.tags_session.as_ref() {
+        render_session_prompt(
+            frame,
+            app,
+            &format!(" 🏷 Tags for {} ", session),
+            &app.tags_input,
+            "Comma or space separated · Enter save · Esc cancel",
+        );
+    }
+}
+
+// Renders the message prompt for the agent fleet over normal mode.
+pub fn render_agent_message_prompt(frame: &mut Frame, app: &App) {
+    render_session_prompt(
+        frame,
+        app,
+        &format!(" 📣 Message {} agent(s) ", app.fleet_agents().len()),
+        &app.agent_message_input,
+        "Typed into each agent's pane · Enter send · Esc cancel",
+    );
+}
+
+// Renders the name prompt for saving a session's layout over the layouts picker.
+pub fn render_layout_name_prompt(frame: &mut Frame, app: &App) {
+    render_session_prompt(
+        frame,
+        app,
+        " 🪟 Save layout as ",
+        &app.layout_name_input,
+        "Same name replaces · Enter save · Esc cancel",
+    );
+}
+
+// Renders the commit or stash message prompt for a session's repo.
+pub fn render_checkpoint_prompt(frame: &mut Frame, app: &App) {
+    let Some(checkpoint) = app.checkpoint.as_ref() else {
+        return;
+    };
+    let (title, hint) = match checkpoint.kind {
+        CheckpointKind::Commit => (
+            format!(
+                " 💾 Commit {} file(s) in {} ",
+                checkpoint.files, checkpoint.session
+            ),
+            "Stages everything with git add -A · Enter commit · Esc cancel",
+        ),
+        CheckpointKind::Stash => (
+            format!(
+                " 📦 Stash {} file(s) in {} ",
+                checkpoint.files, checkpoint.session
+            ),
+            "Includes untracked files · empty uses git's message · Enter stash · Esc cancel",
+        ),
+    };
+    render_session_prompt(frame, app, &title, &app.checkpoint_input, hint);
+}
+
+// Draws a one-line text input popup with a hint underneath.
+fn render_session_prompt(frame: &mut Frame, app: &App, title: &str, input: &str, hint: &str) {
+    let area = centered_rect(60, 20, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.secondary))
+        .title(title.to_string())
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(app.theme.secondary)),
+            Span::styled(
+                input,
+                Style::default()
+                    .fg(app.theme.text)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "_",
+                Style::default()
+                    .fg(app.theme.secondary)
+                    .add_modifier(Modifier::RAPID_BLINK),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(hint, Style::default().fg(app.theme.text_dim))),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,126 @@
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+use super::text::truncate_to_width;
+
+// Columns of the unit name before its state.
+const UNIT_WIDTH: usize = 32;
+
+/* Renders a popup listing the systemd user units matching `[services]
+ * units`, with their state and description. The `systemctl status` report
+ * of the highlighted unit, once requested, fills the lower half, and the
+ * last start/stop result is shown at the bottom. */
+pub fn render_services(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(format!(
+            " ⚙  Agent Services: {} ",
+            app.service_patterns.join(" ")
+        ))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let status_height = if app.service_status.is_empty() {
+        0
+    } else {
+        inner.height / 2
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),             // Column header
+            Constraint::Min(1),                // Unit list
+            Constraint::Length(status_height), // systemctl status
+            Constraint::Length(1),             // Action result
+        ])
+        .split(inner);
+
+    let header = Line::from(Span::styled(
+        format!("  {:<UNIT_WIDTH$} {:<18} DESCRIPTION", "UNIT", "STATE"),
+        Style::default()
+            .fg(app.theme.text_dim)
+            .add_modifier(Modifier::BOLD),
+    ));
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    if app.services.is_empty() {
+        let empty =
+            Paragraph::new("  No matching units").style(Style::default().fg(app.theme.text_dim));
+        frame.render_widget(empty, chunks[1]);
+    } else {
+        let items: Vec<ListItem> = app
+            .services
+            .iter()
+            .map(|unit| {
+                let state_color = if unit.is_running() {
+                    app.theme.success
+                } else if unit.is_failed() {
+                    app.theme.error
+                } else {
+                    app.theme.text_dim
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "  {:<UNIT_WIDTH$} ",
+                            truncate_to_width(&unit.name, UNIT_WIDTH)
+                        ),
+                        Style::default().fg(app.theme.text),
+                    ),
+                    Span::styled(
+                        format!("{:<18} ", format!("{} ({})", unit.active, unit.sub)),
+                        Style::default().fg(state_color),
+                    ),
+                    Span::styled(
+                        unit.description.clone(),
+                        Style::default().fg(app.theme.text_dim),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(app.theme.bg_highlight)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = ListState::default().with_selected(Some(app.services_selected));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+    }
+
+    if !app.service_status.is_empty() {
+        let lines: Vec<Line> = app
+            .service_status
+            .iter()
+            .map(|line| Line::from(format!("  {}", line)))
+            .collect();
+        let status = Paragraph::new(lines)
+            .style(Style::default().fg(app.theme.text))
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(app.theme.text_dim)),
+            );
+        frame.render_widget(status, chunks[2]);
+    }
+
+    if let Some(message) = &app.services_message {
+        let message =
+            Paragraph::new(format!("  {}", message)).style(Style::default().fg(app.theme.warning));
+        frame.render_widget(message, chunks[3]);
+    }
+}
@@ -1,5 +1,8 @@
-use crate::tmux::ActivityLevel;
-use crate::tui::app::App;
+use super::highlight::highlight_matches;
+use super::text::truncate_to_width;
+use crate::sysinfo::format_rate;
+use crate::tmux::{ActivityLevel, format_elapsed};
+use crate::tui::app::{App, QUICK_SWITCH_KEYS, line_key};
 use ratatui::{
     Frame,
     layout::Rect,
@@ -8,14 +11,105 @@ use ratatui::{
     widgets::{Block, Gauge, Paragraph, Sparkline},
 };
 
+// Width of the disk IO column to the right of the CPU/MEM gauges
+const IO_COLUMN_WIDTH: u16 = 22;
+
+// Longest session name shown in a header before it is cut with `…`
+const SESSION_NAME_WIDTH: usize = 32;
+
 pub fn render_session_header(
     frame: &mut Frame,
     app: &App,
     inner: Rect,
     y_offset: &mut u16,
     session: &crate::tmux::TmuxSession,
+    position: usize,
     is_selected: bool,
 ) {
+    let header_area = Rect {
+        x: inner.x,
+        y: inner.y + *y_offset,
+        width: inner.width,
+        height: 1,
+    };
+
+    let bg_style = if is_selected {
+        Style::default().bg(app.theme.bg_highlight)
+    } else {
+        Style::default()
+    };
+
+    let buffer = frame.buffer_mut();
+    buffer.set_style(header_area, bg_style);
+    app.line_cache.with_lines(
+        &session.name,
+        header_key(app, session, position, is_selected),
+        || vec![session_header_line(app, session, position, is_selected)],
+        |lines| buffer.set_line(header_area.x, header_area.y, &lines[0], header_area.width),
+    );
+    *y_offset += 1;
+}
+
+/* Everything a session header is built from, hashed. The activity age
+ * ticks with the clock, so a session with activity is rebuilt at most once
+ * a second. */
+fn header_key(
+    app: &App,
+    session: &crate::tmux::TmuxSession,
+    position: usize,
+    is_selected: bool,
+) -> u64 {
+    let activity_age = session.last_activity.map(|activity| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|now| now.as_secs().saturating_sub(activity))
+            .unwrap_or_default()
+    });
+    let has_alert = app.session_has_alert(&session.name);
+    let git = session.git_status.as_ref().map(|git| {
+        (
+            git.is_repo,
+            &git.branch,
+            git.dirty_count,
+            git.ahead,
+            git.behind,
+        )
+    });
+    line_key((
+        (position, is_selected, &session.name, activity_age),
+        (session.clients, session.size_constrained),
+        (session.windows, session.panes, &session.tags, &session.note),
+        (has_alert, has_alert && app.alert_flash_on()),
+        session.alerts.badge(),
+        app.session_health(session).icon(),
+        app.pending_for(&session.name).map(|pending| {
+            (
+                pending.kind.verb(),
+                pending.seconds_left(std::time::Instant::now()),
+            )
+        }),
+        app.queue_position(session),
+        app.policy_match(&session.name)
+            .map(|policy_match| policy_match.idle_secs),
+        git,
+        app.session_ci(session),
+        app.filter_highlights.get(position),
+    ))
+}
+
+fn session_header_line(
+    app: &App,
+    session: &crate::tmux::TmuxSession,
+    position: usize,
+    is_selected: bool,
+) -> Line<'static> {
+    // The first nine sessions in list order get a quick-switch number key
+    let quick_key = if position < QUICK_SWITCH_KEYS {
+        format!("{} ", position + 1)
+    } else {
+        "  ".to_string()
+    };
+
     let (activity_icon, activity_color) = match session.activity_level() {
         Some(ActivityLevel::Active) => ("●", app.theme.success),
         Some(ActivityLevel::Idle) => ("○", app.theme.warning),
@@ -23,7 +117,11 @@ pub fn render_session_header(
         None => ("○", app.theme.text_dim),
     };
 
-    let attached_indicator = if session.attached { "★" } else { "☆" };
+    let attached_indicator = match session.clients {
+        0 => "☆".to_string(),
+        1 => "★".to_string(),
+        n => format!("★{}", n),
+    };
     let name_style = if is_selected {
         Style::default()
             .fg(app.theme.primary)
@@ -40,7 +138,7 @@ pub fn render_session_header(
         .unwrap_or_default();
 
     // Calculate health score
-    let health = crate::health::HealthScore::calculate(session);
+    let health = app.session_health(session);
     let health_icon = health.icon();
     let health_color = match health.level() {
         crate::health::HealthLevel::Healthy => app.theme.success,
@@ -48,16 +146,109 @@ pub fn render_session_header(
         crate::health::HealthLevel::Critical => app.theme.error,
     };
 
-    let header_line = Line::from(vec![
+    let alert_span = if app.session_has_alert(&session.name) && app.alert_flash_on() {
+        Span::styled(
+            " ⚠",
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else if app.session_has_alert(&session.name) {
+        Span::raw("  ")
+    } else {
+        Span::raw("")
+    };
+
+    // A bell, activity, or silence tmux flagged in a window nobody is viewing
+    let bell_span = if session.alerts.any() {
+        Span::styled(
+            format!(" {}", session.alerts.badge()),
+            Style::default().fg(app.theme.warning),
+        )
+    } else {
+        Span::raw("")
+    };
+
+    // Kill or detach waiting out its undo grace period
+    let pending_span = match app.pending_for(&session.name) {
+        Some(pending) => Span::styled(
+            format!(
+                " {} in {}s",
+                pending.kind.verb().to_lowercase(),
+                pending.seconds_left(std::time::Instant::now())
+            ),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        ),
+        None => Span::raw(""),
+    };
+
+    // Place in the review queue
+    let queue_span = match app.queue_position(session) {
+        Some(position) => Span::styled(
+            format!(" ⧗{}", position),
+            Style::default().fg(app.theme.info),
+        ),
+        None => Span::raw(""),
+    };
+
+    // Idle time of sessions an idle policy matches
+    let policy_span = match app.policy_match(&session.name) {
+        Some(policy_match) => Span::styled(
+            format!(" ⌛{}", format_elapsed(policy_match.idle_secs)),
+            Style::default().fg(app.theme.warning),
+        ),
+        None => Span::raw(""),
+    };
+
+    // Chars matched by the fuzzy filter, drawn over the name style; matches
+    // in the part cut off by truncation (or on the ellipsis) are dropped
+    let name = truncate_to_width(&session.name, SESSION_NAME_WIDTH);
+    let kept_chars = match name {
+        std::borrow::Cow::Borrowed(name) => name.chars().count(),
+        std::borrow::Cow::Owned(ref name) => name.chars().count() - 1,
+    };
+    let matched: Vec<u32> = app
+        .filter_highlights
+        .get(position)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .copied()
+        .filter(|&index| (index as usize) < kept_chars)
+        .collect();
+    let name_spans = highlight_matches(
+        &name,
+        &matched,
+        name_style,
+        Style::default().fg(app.theme.warning),
+    );
+
+    let mut spans = vec![
+        Span::styled(quick_key, Style::default().fg(app.theme.text_dim)),
         Span::styled(activity_icon, Style::default().fg(activity_color)),
         Span::raw(" "),
         Span::styled(attached_indicator, Style::default().fg(app.theme.warning)),
+        // A smaller client is limiting this session's window size
+        if session.size_constrained {
+            Span::styled("⇲", Style::default().fg(app.theme.error))
+        } else {
+            Span::raw("")
+        },
         Span::raw(" "),
-        Span::styled(&session.name, name_style),
+    ];
+    spans.extend(name_spans);
+    spans.extend([
         Span::raw(" "),
         Span::styled(health_icon, Style::default().fg(health_color)),
+        alert_span,
+        bell_span,
+        pending_span,
+        queue_span,
+        policy_span,
         Span::styled(
-            format!(" ({} win)", session.windows),
+            format!(" ({} win, {} panes)", session.windows, session.panes),
             Style::default().fg(app.theme.text_dim),
         ),
         Span::styled(
@@ -72,24 +263,36 @@ pub fn render_session_header(
         } else {
             Span::raw("")
         },
+        match app
+            .session_ci(session)
+            .and_then(|ci| Some((ci.badge()?, app.ci_color(&ci))))
+        {
+            Some((badge, color)) => Span::styled(format!(" {}", badge), Style::default().fg(color)),
+            None => Span::raw(""),
+        },
+        if session.tags.is_empty() {
+            Span::raw("")
+        } else {
+            Span::styled(
+                session
+                    .tags
+                    .iter()
+                    .map(|tag| format!(" #{}", tag))
+                    .collect::<String>(),
+                Style::default().fg(app.theme.info),
+            )
+        },
+        match &session.note {
+            Some(note) => Span::styled(
+                format!(" — {}", note),
+                Style::default()
+                    .fg(app.theme.text_dim)
+                    .add_modifier(Modifier::ITALIC),
+            ),
+            None => Span::raw(""),
+        },
     ]);
-
-    let header_area = Rect {
-        x: inner.x,
-        y: inner.y + *y_offset,
-        width: inner.width,
-        height: 1,
-    };
-
-    let bg_style = if is_selected {
-        Style::default().bg(app.theme.bg_highlight)
-    } else {
-        Style::default()
-    };
-
-    let header_para = Paragraph::new(header_line).style(bg_style);
-    frame.render_widget(header_para, header_area);
-    *y_offset += 1;
+    Line::from(spans)
 }
 
 pub fn render_session_gauges(
@@ -100,20 +303,25 @@ pub fn render_session_gauges(
     session: &crate::tmux::TmuxSession,
 ) {
     if let Some(ref stats) = session.stats {
+        // Disk IO rates take a fixed column on the right; gauges split the rest
+        let io_width = IO_COLUMN_WIDTH.min(inner.width / 4);
+        let half_width = (inner.width - io_width) / 2;
+
         // CPU Gauge with smooth gradient color
-        let cpu_ratio = (stats.cpu_percent / 100.0).min(1.0);
-        let cpu_color = app.theme.gradient_color(stats.cpu_percent);
+        let cpu_percent = app.display_cpu(stats.cpu_percent);
+        let cpu_ratio = (cpu_percent / 100.0).min(1.0);
+        let cpu_color = app.theme.gradient_color(cpu_percent);
 
         let cpu_gauge = Gauge::default()
             .block(Block::default())
             .gauge_style(Style::default().fg(cpu_color).bg(app.theme.bg_primary))
-            .label(format!("CPU {:5.1}%", stats.cpu_percent))
+            .label(format!("CPU {:5.1}%", cpu_percent))
             .ratio(cpu_ratio);
 
         let cpu_area = Rect {
             x: inner.x,
             y: inner.y + *y_offset,
-            width: inner.width / 2,
+            width: half_width,
             height: 1,
         };
         frame.render_widget(cpu_gauge, cpu_area);
@@ -129,25 +337,58 @@ pub fn render_session_gauges(
             .ratio(mem_ratio);
 
         let mem_area = Rect {
-            x: inner.x + inner.width / 2,
+            x: inner.x + half_width,
             y: inner.y + *y_offset,
-            width: inner.width / 2,
+            width: half_width,
             height: 1,
         };
         frame.render_widget(mem_gauge, mem_area);
+
+        let io_line = Line::from(vec![
+            Span::styled(" ↓", Style::default().fg(app.theme.text_dim)),
+            Span::styled(
+                format_rate(stats.io_read_bps),
+                Style::default().fg(app.theme.info),
+            ),
+            Span::styled(" ↑", Style::default().fg(app.theme.text_dim)),
+            Span::styled(
+                format_rate(stats.io_write_bps),
+                Style::default().fg(app.theme.secondary),
+            ),
+        ]);
+        let io_area = Rect {
+            x: inner.x + half_width * 2,
+            y: inner.y + *y_offset,
+            width: io_width,
+            height: 1,
+        };
+        frame.render_widget(Paragraph::new(io_line), io_area);
         *y_offset += 1;
 
+        let sparkline_max = app.sparkline_max();
+
+        // Sparklines draw from the start of the data, so keep only the newest
+        // samples that fit when the history is longer than the row
+        let visible = |history: &[Option<u64>]| -> Vec<Option<u64>> {
+            history[history.len().saturating_sub(half_width as usize)..].to_vec()
+        };
+
         // CPU Sparkline
         if !session.cpu_history.is_empty() {
-            let cpu_sparkline = Sparkline::default()
+            let mut cpu_sparkline = Sparkline::default()
                 .block(Block::default())
-                .data(&session.cpu_history)
-                .style(Style::default().fg(cpu_color).bg(app.theme.bg_primary));
+                .data(visible(&session.cpu_history))
+                .style(Style::default().fg(cpu_color).bg(app.theme.bg_primary))
+                .absent_value_symbol("·")
+                .absent_value_style(Style::default().fg(app.theme.text_dim));
+            if let Some(max) = sparkline_max {
+                cpu_sparkline = cpu_sparkline.max(max.0);
+            }
 
             let cpu_spark_area = Rect {
                 x: inner.x,
                 y: inner.y + *y_offset,
-                width: inner.width / 2,
+                width: half_width,
                 height: 1,
             };
             frame.render_widget(cpu_sparkline, cpu_spark_area);
@@ -155,15 +396,20 @@ pub fn render_session_gauges(
 
         // Memory Sparkline
         if !session.mem_history.is_empty() {
-            let mem_sparkline = Sparkline::default()
+            let mut mem_sparkline = Sparkline::default()
                 .block(Block::default())
-                .data(&session.mem_history)
-                .style(Style::default().fg(mem_color).bg(app.theme.bg_primary));
+                .data(visible(&session.mem_history))
+                .style(Style::default().fg(mem_color).bg(app.theme.bg_primary))
+                .absent_value_symbol("·")
+                .absent_value_style(Style::default().fg(app.theme.text_dim));
+            if let Some(max) = sparkline_max {
+                mem_sparkline = mem_sparkline.max(max.1);
+            }
 
             let mem_spark_area = Rect {
-                x: inner.x + inner.width / 2,
+                x: inner.x + half_width,
                 y: inner.y + *y_offset,
-                width: inner.width / 2,
+                width: half_width,
                 height: 1,
             };
             frame.render_widget(mem_sparkline, mem_spark_area);
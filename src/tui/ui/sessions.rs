@@ -19,13 +19,14 @@ pub fn render_session_list(frame: &mut Frame, app: &App, area: Rect) {
     let title = match app.mode {
         AppMode::Filtering => format!(
             " ⚡ Sessions ({}) > {} ",
-            app.filtered_indices.len(),
+            app.sessions.len(),
             app.filter_input
         ),
-        _ => format!(
+        _ if app.title_legend => format!(
             " ⚡ Sessions ({}) • ●=active ○=idle ◌=dormant ★=attached 🟢🟡🔴=health ",
-            app.sessions.len()
+            app.sessions.items.len()
         ),
+        _ => format!(" ⚡ Sessions ({}) • ? legend ", app.sessions.items.len()),
     };
 
     let border_color = if app.focus == FocusArea::Sessions {
@@ -40,8 +41,8 @@ pub fn render_session_list(frame: &mut Frame, app: &App, area: Rect) {
         BorderType::Rounded
     };
 
-    if app.filtered_indices.is_empty() {
-        let empty_msg = if app.sessions.is_empty() {
+    if app.sessions.is_empty() {
+        let empty_msg = if app.sessions.items.is_empty() {
             "No tmux sessions found. Press 'c' to create one."
         } else {
             "No sessions match your filter"
@@ -64,20 +65,12 @@ pub fn render_session_list(frame: &mut Frame, app: &App, area: Rect) {
     let max_visible = (inner_height / LINES_PER_SESSION) as usize;
 
     // Create scrollable window
-    let start_idx = if app.selected_index >= max_visible {
-        app.selected_index - max_visible + 1
-    } else {
-        0
-    };
-    let end_idx = (start_idx + max_visible).min(app.filtered_indices.len());
+    let visible = app.sessions.window(max_visible);
+    let (start_idx, end_idx) = (visible.start, visible.end);
 
     // Scroll position indicator in title
-    let scroll_info = if app.filtered_indices.len() > max_visible {
-        format!(
-            " [{}/{}]",
-            app.selected_index + 1,
-            app.filtered_indices.len()
-        )
+    let scroll_info = if app.sessions.len() > max_visible {
+        format!(" [{}/{}]", app.sessions.selected() + 1, app.sessions.len())
     } else {
         String::new()
     };
@@ -97,11 +90,21 @@ pub fn render_session_list(frame: &mut Frame, app: &App, area: Rect) {
     let mut y_offset = 0;
 
     for display_idx in start_idx..end_idx {
-        let &session_idx = &app.filtered_indices[display_idx];
-        let session = &app.sessions[session_idx];
-        let is_selected = app.focus == FocusArea::Sessions && display_idx == app.selected_index;
-
-        render_session_header(frame, app, inner, &mut y_offset, session, is_selected);
+        let Some(session) = app.sessions.get(display_idx) else {
+            break;
+        };
+        let is_selected =
+            app.focus == FocusArea::Sessions && display_idx == app.sessions.selected();
+
+        render_session_header(
+            frame,
+            app,
+            inner,
+            &mut y_offset,
+            session,
+            display_idx,
+            is_selected,
+        );
         render_session_gauges(frame, app, inner, &mut y_offset, session);
 
         // Add spacing between sessions
@@ -116,7 +119,7 @@ fn render_scrollbar(
     start_idx: usize,
     max_visible: usize,
 ) {
-    let total = app.filtered_indices.len();
+    let total = app.sessions.len();
     if total <= max_visible || inner.height == 0 {
         return;
     }
@@ -1,11 +1,14 @@
 mod activity;
+mod agents;
 mod health;
 mod layout;
 mod resources;
 
 use activity::render_activity_timeline;
+use agents::render_agent_leaderboard;
 use health::render_health_summary;
-use layout::{centered_rect, render_overlay_help};
+pub(super) use layout::centered_rect;
+use layout::render_overlay_help;
 use resources::{render_top_cpu, render_top_memory};
 
 use crate::tui::app::App;
@@ -41,6 +44,7 @@ pub fn render_stats_overlay(frame: &mut Frame, app: &App) {
             Constraint::Length(8), // Top CPU consumers
             Constraint::Length(8), // Top memory consumers
             Constraint::Length(8), // Health status
+            Constraint::Length(8), // Agents by CPU and by time waiting
             Constraint::Min(1),    // Activity timeline
             Constraint::Length(2), // Help
         ])
@@ -49,6 +53,7 @@ pub fn render_stats_overlay(frame: &mut Frame, app: &App) {
     render_top_cpu(frame, app, chunks[0]);
     render_top_memory(frame, app, chunks[1]);
     render_health_summary(frame, app, chunks[2]);
-    render_activity_timeline(frame, app, chunks[3]);
-    render_overlay_help(frame, app, chunks[4]);
+    render_agent_leaderboard(frame, app, chunks[3]);
+    render_activity_timeline(frame, app, chunks[4]);
+    render_overlay_help(frame, app, chunks[5]);
 }
@@ -1,5 +1,6 @@
+use super::layout::highlight_row;
 use crate::tmux::ActivityLevel;
-use crate::tui::app::App;
+use crate::tui::app::{App, StatsPanel};
 use ratatui::{
     Frame,
     layout::Rect,
@@ -18,15 +19,8 @@ pub(super) fn render_activity_timeline(frame: &mut Frame, app: &App, area: Rect)
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let mut sessions_with_activity: Vec<_> = app
-        .sessions
-        .iter()
-        .filter_map(|s| s.last_activity.map(|ts| (s, ts)))
-        .collect();
-    sessions_with_activity.sort_by_key(|item| std::cmp::Reverse(item.1));
-
     let mut lines = Vec::new();
-    for (session, _) in sessions_with_activity.iter().take(10) {
+    for (i, session) in app.recently_active_sessions().into_iter().enumerate() {
         let activity_str = session
             .activity_ago_string()
             .unwrap_or_else(|| "unknown".to_string());
@@ -39,7 +33,7 @@ pub(super) fn render_activity_timeline(frame: &mut Frame, app: &App, area: Rect)
 
         let attach_icon = if session.attached { " ★" } else { "" };
 
-        lines.push(Line::from(vec![
+        let line = Line::from(vec![
             Span::styled(icon, Style::default().fg(color)),
             Span::styled(" ", Style::default()),
             Span::styled(
@@ -51,7 +45,8 @@ pub(super) fn render_activity_timeline(frame: &mut Frame, app: &App, area: Rect)
                 Style::default().fg(app.theme.info),
             ),
             Span::styled(attach_icon, Style::default().fg(app.theme.primary)),
-        ]));
+        ]);
+        lines.push(highlight_row(app, StatsPanel::Activity, i, line));
     }
 
     if lines.is_empty() {
@@ -0,0 +1,121 @@
+use super::layout::highlight_row;
+use crate::process::AiProcessInfo;
+use crate::tmux::format_elapsed;
+use crate::tui::app::{App, StatsPanel};
+use crate::tui::ui::text::truncate_to_width;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+use std::time::Instant;
+
+// Width of the `agent:project` label, padded with dots up to the value.
+const LABEL_WIDTH: usize = 20;
+
+/* Ranks agents two ways side by side: by CPU use, to spot one chewing
+ * resources, and by time spent waiting, to spot one stuck on input. */
+pub(super) fn render_agent_leaderboard(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(" 🤖 Agent Leaderboard ");
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+
+    let by_cpu = app.agents_by_cpu().into_iter().map(|(agent, cpu)| {
+        let color = if cpu > 100.0 {
+            app.theme.error
+        } else if cpu > 50.0 {
+            app.theme.warning
+        } else {
+            app.theme.success
+        };
+        (label(agent), format!("{:>7.1}%", cpu), color)
+    });
+    let by_waiting = app
+        .agents_by_waiting(Instant::now())
+        .into_iter()
+        .map(|(agent, waited)| {
+            let secs = waited.as_secs();
+            let color = if secs >= 600 {
+                app.theme.error
+            } else if secs >= 120 {
+                app.theme.warning
+            } else {
+                app.theme.info
+            };
+            (label(agent), format!("{:>8}", format_elapsed(secs)), color)
+        });
+
+    let cpu = ranking(
+        app,
+        StatsPanel::AgentCpu,
+        "By CPU",
+        by_cpu,
+        "No agents running",
+    );
+    let waiting = ranking(
+        app,
+        StatsPanel::AgentWaiting,
+        "Longest waiting",
+        by_waiting,
+        "No agents waiting",
+    );
+    frame.render_widget(Paragraph::new(cpu), columns[0]);
+    frame.render_widget(Paragraph::new(waiting), columns[1]);
+}
+
+// The agent as the agent box names it: `claude:core`.
+fn label(agent: &AiProcessInfo) -> String {
+    format!("{}:{}", agent.breadcrumb(), agent.project_name)
+}
+
+// A heading and the top five of a ranking, each row `n. label........value`.
+fn ranking(
+    app: &App,
+    panel: StatsPanel,
+    heading: &str,
+    rows: impl Iterator<Item = (String, String, Color)>,
+    empty: &str,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled(
+        heading.to_string(),
+        Style::default()
+            .fg(app.theme.text_dim)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    for (i, (label, value, color)) in rows.take(5).enumerate() {
+        let line = Line::from(vec![
+            Span::styled(
+                format!("{}. ", i + 1),
+                Style::default().fg(app.theme.text_dim),
+            ),
+            Span::styled(
+                format!("{:.<LABEL_WIDTH$}", truncate_to_width(&label, LABEL_WIDTH)),
+                Style::default().fg(app.theme.text),
+            ),
+            Span::styled(
+                value,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ]);
+        lines.push(highlight_row(app, panel, i, line));
+    }
+    if lines.len() == 1 {
+        lines.push(Line::from(Span::styled(
+            empty.to_string(),
+            Style::default().fg(app.theme.text_dim),
+        )));
+    }
+    lines
+}
@@ -1,5 +1,6 @@
-use crate::health::{HealthLevel, HealthScore};
-use crate::tui::app::App;
+use super::layout::highlight_row;
+use crate::health::HealthLevel;
+use crate::tui::app::{App, StatsPanel};
 use ratatui::{
     Frame,
     layout::Rect,
@@ -21,21 +22,15 @@ pub(super) fn render_health_summary(frame: &mut Frame, app: &App, area: Rect) {
     let mut healthy = 0;
     let mut warning = 0;
     let mut critical = 0;
-    let mut critical_sessions = Vec::new();
 
-    for session in &app.sessions {
-        let health = HealthScore::calculate(session);
-        match health.level() {
+    for session in &app.sessions.items {
+        match app.session_health(session).level() {
             HealthLevel::Healthy => healthy += 1,
             HealthLevel::Warning => warning += 1,
-            HealthLevel::Critical => {
-                critical += 1;
-                if critical_sessions.len() < 3 {
-                    critical_sessions.push((session.name.as_str(), health.score));
-                }
-            }
+            HealthLevel::Critical => critical += 1,
         }
     }
+    let critical_sessions = app.critical_sessions();
 
     let mut lines = vec![
         Line::from(vec![
@@ -71,15 +66,16 @@ pub(super) fn render_health_summary(frame: &mut Frame, app: &App, area: Rect) {
                 .fg(app.theme.error)
                 .add_modifier(Modifier::BOLD),
         )));
-        for (name, score) in critical_sessions {
-            lines.push(Line::from(vec![
+        for (i, (session, score)) in critical_sessions.into_iter().enumerate() {
+            let line = Line::from(vec![
                 Span::styled("  • ", Style::default().fg(app.theme.error)),
-                Span::styled(name, Style::default().fg(app.theme.text)),
+                Span::styled(session.name.as_str(), Style::default().fg(app.theme.text)),
                 Span::styled(
                     format!(" (score: {})", score),
                     Style::default().fg(app.theme.text_dim),
                 ),
-            ]));
+            ]);
+            lines.push(highlight_row(app, StatsPanel::Health, i, line));
         }
     }
 
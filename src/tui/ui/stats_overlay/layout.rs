@@ -1,4 +1,4 @@
-use crate::tui::app::App;
+use crate::tui::app::{App, StatsPanel, ToastLevel};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -8,7 +8,7 @@ use ratatui::{
 };
 
 pub(super) fn render_overlay_help(frame: &mut Frame, app: &App, area: Rect) {
-    let help = Paragraph::new(Line::from(vec![
+    let help = Line::from(vec![
         Span::styled("Press ", Style::default().fg(app.theme.text_dim)),
         Span::styled(
             "S",
@@ -23,13 +23,86 @@ pub(super) fn render_overlay_help(frame: &mut Frame, app: &App, area: Rect) {
                 .fg(app.theme.primary)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(" to close overlay", Style::default().fg(app.theme.text_dim)),
-    ]));
+        Span::styled(
+            " to close overlay, ",
+            Style::default().fg(app.theme.text_dim),
+        ),
+        Span::styled(
+            "↑↓",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" select, ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" attach, ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(
+            "x",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" kill, ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(
+            "c",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" / ", Style::default().fg(app.theme.text_dim)),
+        Span::styled(
+            "J",
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            " to export CSV / JSON",
+            Style::default().fg(app.theme.text_dim),
+        ),
+    ]);
+
+    // The overlay hides the help bar, so export results show here
+    let toast = app.current_toast().map(|toast| {
+        let color = match toast.level {
+            ToastLevel::Info => app.theme.info,
+            ToastLevel::Warn => app.theme.warning,
+            ToastLevel::Error => app.theme.error,
+        };
+        Line::from(Span::styled(
+            toast.message.as_str(),
+            Style::default().fg(color),
+        ))
+    });
+    let help = Paragraph::new(std::iter::once(help).chain(toast).collect::<Vec<_>>());
 
     frame.render_widget(help, area);
 }
 
-pub(super) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+// Marks a listed row as the one arrow keys have highlighted in its panel.
+pub(super) fn highlight_row<'a>(
+    app: &App,
+    panel: StatsPanel,
+    row: usize,
+    line: Line<'a>,
+) -> Line<'a> {
+    if app.stats_selection() == Some((panel, row)) {
+        line.style(
+            Style::default()
+                .bg(app.theme.bg_highlight)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        line
+    }
+}
+
+pub(in crate::tui::ui) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
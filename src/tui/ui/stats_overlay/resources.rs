@@ -1,4 +1,5 @@
-use crate::tui::app::App;
+use super::layout::highlight_row;
+use crate::tui::app::{App, StatsPanel};
 use ratatui::{
     Frame,
     layout::Rect,
@@ -17,15 +18,14 @@ pub(super) fn render_top_cpu(frame: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let mut sessions: Vec<_> = app
-        .sessions
-        .iter()
-        .filter_map(|s| s.stats.as_ref().map(|stats| (s, stats)))
-        .collect();
-    sessions.sort_by(|a, b| b.1.cpu_percent.total_cmp(&a.1.cpu_percent));
+    let sessions = app.top_sessions_by_cpu();
 
     let mut lines = Vec::new();
-    for (i, (session, stats)) in sessions.iter().take(5).enumerate() {
+    for (i, (session, stats)) in sessions
+        .iter()
+        .filter_map(|s| s.stats.as_ref().map(|stats| (s, stats)))
+        .enumerate()
+    {
         let color = if stats.cpu_percent > 200.0 {
             app.theme.error
         } else if stats.cpu_percent > 100.0 {
@@ -34,7 +34,7 @@ pub(super) fn render_top_cpu(frame: &mut Frame, app: &App, area: Rect) {
             app.theme.success
         };
 
-        lines.push(Line::from(vec![
+        let line = Line::from(vec![
             Span::styled(
                 format!("{}. ", i + 1),
                 Style::default().fg(app.theme.text_dim),
@@ -47,7 +47,8 @@ pub(super) fn render_top_cpu(frame: &mut Frame, app: &App, area: Rect) {
                 format!("{:>8.1}%", stats.cpu_percent),
                 Style::default().fg(color).add_modifier(Modifier::BOLD),
             ),
-        ]));
+        ]);
+        lines.push(highlight_row(app, StatsPanel::Cpu, i, line));
     }
 
     if lines.is_empty() {
@@ -71,15 +72,14 @@ pub(super) fn render_top_memory(frame: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let mut sessions: Vec<_> = app
-        .sessions
-        .iter()
-        .filter_map(|s| s.stats.as_ref().map(|stats| (s, stats)))
-        .collect();
-    sessions.sort_by_key(|item| std::cmp::Reverse(item.1.mem_mb));
+    let sessions = app.top_sessions_by_memory();
 
     let mut lines = Vec::new();
-    for (i, (session, stats)) in sessions.iter().take(5).enumerate() {
+    for (i, (session, stats)) in sessions
+        .iter()
+        .filter_map(|s| s.stats.as_ref().map(|stats| (s, stats)))
+        .enumerate()
+    {
         let color = if stats.mem_mb > 4096 {
             app.theme.error
         } else if stats.mem_mb > 2048 {
@@ -88,7 +88,7 @@ pub(super) fn render_top_memory(frame: &mut Frame, app: &App, area: Rect) {
             app.theme.info
         };
 
-        lines.push(Line::from(vec![
+        let line = Line::from(vec![
             Span::styled(
                 format!("{}. ", i + 1),
                 Style::default().fg(app.theme.text_dim),
@@ -101,7 +101,8 @@ pub(super) fn render_top_memory(frame: &mut Frame, app: &App, area: Rect) {
                 format!("{:>8} MB", stats.mem_mb),
                 Style::default().fg(color).add_modifier(Modifier::BOLD),
             ),
-        ]));
+        ]);
+        lines.push(highlight_row(app, StatsPanel::Memory, i, line));
     }
 
     if lines.is_empty() {
@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/* Shortens `text` to at most `max_width` terminal columns, ending it with
+ * `…` when anything was cut. Works on chars and their display width, so
+ * multi-byte and double-width names (CJK, emoji) are never split inside a
+ * char or counted as one column. */
+pub fn truncate_to_width(text: &str, max_width: usize) -> Cow<'_, str> {
+    if text.width() <= max_width {
+        return Cow::Borrowed(text);
+    }
+    if max_width == 0 {
+        return Cow::Borrowed("");
+    }
+
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        // Leave a column for the ellipsis
+        if width + char_width >= max_width {
+            break;
+        }
+        truncated.push(c);
+        width += char_width;
+    }
+    truncated.push('…');
+    Cow::Owned(truncated)
+}
+
+// Spaces needed after `text` to fill `width` columns.
+pub fn padding_to_width(text: &str, width: usize) -> String {
+    " ".repeat(width.saturating_sub(text.width()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncation_respects_char_boundaries_and_width() {
+        assert_eq!(truncate_to_width("api", 8), "api");
+        assert_eq!(truncate_to_width("backend-api", 8), "backend…");
+        // Double-width chars take two columns each
+        assert_eq!(truncate_to_width("日本語のプロジェクト", 8), "日本語…");
+        assert_eq!(truncate_to_width("🦖🦖🦖🦖🦖", 5), "🦖🦖…");
+        assert_eq!(truncate_to_width("ünïcödé-name", 6), "ünïcö…");
+        assert_eq!(truncate_to_width("anything", 0), "");
+    }
+
+    #[test]
+    fn padding_counts_columns() {
+        assert_eq!(padding_to_width("ab", 4), "  ");
+        assert_eq!(padding_to_width("日本", 6), "  ");
+        assert_eq!(padding_to_width("too long", 4), "");
+    }
+}
@@ -0,0 +1,194 @@
+use super::normal::render_help;
+use super::text::{padding_to_width, truncate_to_width};
+use crate::tmux::format_elapsed;
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+
+// Columns of the session name before its bar.
+const LABEL_WIDTH: usize = 16;
+
+pub fn render_timeline_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Session bars and time axis
+            Constraint::Length(2), // Help
+        ])
+        .split(frame.area());
+
+    render_timeline(frame, app, chunks[0]);
+    render_help(frame, app, chunks[1]);
+}
+
+/* One row per session: a bar from its creation to now, shaded by how
+ * often the recorded samples found it active. `·` marks stretches the
+ * session existed but trex wasn't running to sample it. */
+fn render_timeline(frame: &mut Frame, app: &App, area: Rect) {
+    let span = app.timeline_span_secs();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(format!(
+            " 🗓  Session Timeline: last {} ",
+            format_elapsed(span)
+        ));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let bar_width = (inner.width as usize).saturating_sub(LABEL_WIDTH + 1);
+    if bar_width == 0 || inner.height < 2 {
+        return;
+    }
+
+    let now = app.timeline_now();
+    let from = now.saturating_sub(span);
+    // Time covered by column `col`, as [start, end)
+    let column = |col: usize| {
+        let at = |col: usize| from + span * col as u64 / bar_width as u64;
+        (at(col), at(col + 1))
+    };
+
+    let rows = inner.height as usize - 1;
+    let range = app.sessions.window(rows);
+    let selected = app.sessions.selected();
+    let mut lines: Vec<Line> = Vec::new();
+    for (idx, session) in app.sessions.visible().enumerate() {
+        if !range.contains(&idx) {
+            continue;
+        }
+        let is_selected = idx == selected;
+        let label = truncate_to_width(&session.name, LABEL_WIDTH);
+        let label_style = if is_selected {
+            Style::default()
+                .fg(app.theme.primary)
+                .bg(app.theme.bg_highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.text)
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!("{}{}", label, padding_to_width(&label, LABEL_WIDTH)),
+                label_style,
+            ),
+            Span::raw(" "),
+        ];
+
+        let start = app.timeline_start(session);
+        let bar: String = (0..bar_width)
+            .map(|col| {
+                let (col_start, col_end) = column(col);
+                match start {
+                    Some(start) if col_end <= start => ' ',
+                    Some(start) => shade(app.timeline.intensity(
+                        &session.name,
+                        col_start.max(start),
+                        col_end,
+                    )),
+                    None => ' ',
+                }
+            })
+            .collect();
+        let bar_color = if is_selected {
+            app.theme.primary
+        } else {
+            app.theme.info
+        };
+        spans.push(Span::styled(bar, Style::default().fg(bar_color)));
+        lines.push(Line::from(spans));
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No sessions",
+            Style::default().fg(app.theme.text_dim),
+        )));
+    }
+    while lines.len() < rows {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::raw(" ".repeat(LABEL_WIDTH + 1)),
+        Span::styled(
+            axis(span, bar_width),
+            Style::default().fg(app.theme.text_dim),
+        ),
+    ]));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+// The character for a column's share of active samples; None when none were taken.
+fn shade(intensity: Option<f64>) -> char {
+    match intensity {
+        None => '·',
+        Some(share) if share <= 0.0 => '░',
+        Some(share) if share < 0.34 => '▒',
+        Some(share) if share < 0.67 => '▓',
+        Some(_) => '█',
+    }
+}
+
+/* Tick labels under the bars counting back from now (`-6d`, ..., `now`),
+ * one per day over days, else one per hour or per 6 hours. Labels that
+ * would overlap the previous one are left out. */
+fn axis(span: u64, width: usize) -> String {
+    let step = if span > 86400 {
+        86400
+    } else if span > 6 * 3600 {
+        6 * 3600
+    } else {
+        3600
+    };
+    let mut axis = vec![' '; width];
+    let mut free_from = 0;
+    for ago in (0..=span / step).rev().map(|tick| tick * step) {
+        let label = if ago == 0 {
+            "now".to_string()
+        } else {
+            format!("-{}", format_elapsed(ago))
+        };
+        let at = width as u64 * (span - ago) / span;
+        let at = (at as usize).min(width.saturating_sub(label.len()));
+        if at < free_from || at + label.len() > width {
+            continue;
+        }
+        for (offset, c) in label.chars().enumerate() {
+            axis[at + offset] = c;
+        }
+        free_from = at + label.len() + 1;
+    }
+    axis.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axis_labels_count_back_to_now() {
+        let week = axis(7 * 86400, 70);
+        assert!(week.starts_with("-7d"));
+        assert!(week.ends_with("now"));
+        assert!(week.contains("-1d"));
+        assert_eq!(week.chars().count(), 70);
+
+        // Too narrow for every label: overlapping ones are dropped
+        let narrow = axis(7 * 86400, 12);
+        assert!(narrow.starts_with("-7d"));
+        assert_eq!(narrow.chars().count(), 12);
+        assert_eq!(axis(86400, 2), "  ");
+
+        assert_eq!(shade(None), '·');
+        assert_eq!(shade(Some(0.0)), '░');
+        assert_eq!(shade(Some(0.9)), '█');
+    }
+}
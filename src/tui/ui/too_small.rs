@@ -0,0 +1,67 @@
+use crate::tui::app::{App, MIN_TERMINAL_SIZE};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+/* Renders in place of every view when the terminal is below
+ * MIN_TERMINAL_SIZE: the current and required size, with whichever
+ * dimension falls short highlighted, instead of clipped widgets. */
+pub fn render_too_small(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let (min_width, min_height) = MIN_TERMINAL_SIZE;
+    let dimension_style = |short: bool| {
+        if short {
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.success)
+        }
+    };
+
+    let lines = vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Current: ", Style::default().fg(app.theme.text_dim)),
+            Span::styled(
+                area.width.to_string(),
+                dimension_style(area.width < min_width),
+            ),
+            Span::styled("x", Style::default().fg(app.theme.text_dim)),
+            Span::styled(
+                area.height.to_string(),
+                dimension_style(area.height < min_height),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Required: ", Style::default().fg(app.theme.text_dim)),
+            Span::styled(
+                format!("{}x{}", min_width, min_height),
+                Style::default().fg(app.theme.text),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enlarge the window, or press q to quit",
+            Style::default().fg(app.theme.text_dim),
+        )),
+    ];
+
+    let [middle] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+        .flex(Flex::Center)
+        .areas(area);
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_primary));
+    frame.render_widget(paragraph, middle);
+}
@@ -0,0 +1,71 @@
+use crate::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use super::stats_overlay::centered_rect;
+
+/* Renders the global window search over normal mode.
+ *
+ * Every session's windows in one list as `session:index name ⟨command⟩`,
+ * narrowed by the fuzzy search in the title. */
+pub fn render_window_search(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(app.theme.primary))
+        .title(format!(
+            " 🔎 Windows ({}/{}) > {} ",
+            app.window_search_indices.len(),
+            app.window_search.len(),
+            app.window_search_input
+        ))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().bg(app.theme.bg_overlay));
+
+    if app.window_search_indices.is_empty() {
+        let empty = Paragraph::new("  No matching windows")
+            .style(Style::default().fg(app.theme.text_dim))
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .window_search_indices
+        .iter()
+        .filter_map(|&idx| app.window_search.get(idx))
+        .map(|(session, window)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {}", session),
+                    Style::default().fg(app.theme.secondary),
+                ),
+                Span::styled(
+                    format!(":{} ", window.index),
+                    Style::default().fg(app.theme.text_dim),
+                ),
+                Span::styled(window.name.clone(), Style::default().fg(app.theme.text)),
+                Span::styled(
+                    format!(" ⟨{}⟩", window.current_command),
+                    Style::default().fg(app.theme.info),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(app.theme.bg_highlight)
+            .add_modifier(Modifier::BOLD),
+    );
+    let mut state = ListState::default().with_selected(Some(app.window_search_selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
@@ -0,0 +1,135 @@
+/* Window manager integration: focusing the desktop window of the terminal
+ * that runs a tmux client, so attaching to a session already open in
+ * another terminal brings that terminal forward instead of attaching a
+ * second client. Hyprland and sway are driven over their IPC CLIs
+ * (`hyprctl`, `swaymsg`); other X11 window managers through `wmctrl`. */
+use crate::alert::glob_match;
+use crate::process::ancestor_pids;
+use std::process::{Command, Stdio};
+
+/* A `[[wm.workspaces]]` rule: sessions matching the glob live in a
+ * terminal on this workspace (a name or number as the window manager
+ * knows it; a desktop number from 0 for wmctrl). */
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceRule {
+    pub session_glob: String,
+    pub workspace: String,
+}
+
+impl WorkspaceRule {
+    pub fn matches_session(&self, session_name: &str) -> bool {
+        glob_match(&self.session_glob, session_name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowManager {
+    Hyprland,
+    Sway,
+    Wmctrl,
+}
+
+impl WindowManager {
+    // The window manager of the running desktop session whose CLI is installed.
+    pub fn detect() -> Option<Self> {
+        let has = |env: &str, tool: &str| {
+            std::env::var_os(env).is_some_and(|value| !value.is_empty())
+                && which::which(tool).is_ok()
+        };
+        if has("HYPRLAND_INSTANCE_SIGNATURE", "hyprctl") {
+            Some(WindowManager::Hyprland)
+        } else if has("SWAYSOCK", "swaymsg") {
+            Some(WindowManager::Sway)
+        } else if has("DISPLAY", "wmctrl") {
+            Some(WindowManager::Wmctrl)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindowManager::Hyprland => "Hyprland",
+            WindowManager::Sway => "sway",
+            WindowManager::Wmctrl => "wmctrl",
+        }
+    }
+
+    // Focuses the window owned by `pid`; false when it owns none.
+    fn focus_pid(&self, pid: u32) -> bool {
+        match self {
+            // hyprctl exits 0 either way and answers `ok` on success
+            WindowManager::Hyprland => output(
+                "hyprctl",
+                &["dispatch", "focuswindow", &format!("pid:{}", pid)],
+            )
+            .is_some_and(|out| out.trim() == "ok"),
+            WindowManager::Sway => output("swaymsg", &[&format!("[pid={}] focus", pid)]).is_some(),
+            WindowManager::Wmctrl => output("wmctrl", &["-lp"])
+                .and_then(|list| window_of_pid(&list, pid))
+                .is_some_and(|window| output("wmctrl", &["-ia", &window]).is_some()),
+        }
+    }
+
+    // Switches to the workspace; false when the window manager refused.
+    pub fn switch_workspace(&self, workspace: &str) -> bool {
+        match self {
+            WindowManager::Hyprland => output("hyprctl", &["dispatch", "workspace", workspace])
+                .is_some_and(|out| out.trim() == "ok"),
+            WindowManager::Sway => output("swaymsg", &["workspace", workspace]).is_some(),
+            WindowManager::Wmctrl => {
+                workspace.parse::<u32>().is_ok() && output("wmctrl", &["-s", workspace]).is_some()
+            }
+        }
+    }
+
+    /* Focuses the terminal running the process `pid` (a tmux client):
+     * the nearest of it and its ancestors that owns a window, which is
+     * the terminal emulator with a shell or two in between. */
+    pub fn focus_process(&self, pid: u32) -> bool {
+        ancestor_pids(pid)
+            .into_iter()
+            .any(|ancestor| self.focus_pid(ancestor))
+    }
+}
+
+// Runs a CLI quietly, returning its stdout when it succeeded.
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/* The id of the first window `wmctrl -lp` lists for `pid`. Lines read
+ * `<id> <desktop> <pid> <host> <title>`. */
+fn window_of_pid(list: &str, pid: u32) -> Option<String> {
+    list.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let window = fields.next()?;
+        let owner = fields.nth(1)?.parse::<u32>().ok()?;
+        (owner == pid).then(|| window.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_window_of_a_process() {
+        let list = "0x01e00003  0 1811   host nvim\n0x03a00007 -1 2042   host Alacritty\n";
+        assert_eq!(window_of_pid(list, 2042).as_deref(), Some("0x03a00007"));
+        assert_eq!(window_of_pid(list, 42), None);
+
+        let own = ancestor_pids(std::process::id());
+        assert_eq!(own.first(), Some(&std::process::id()));
+        assert!(!own.contains(&1));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::timeline::RETENTION_SECS;
+use std::path::PathBuf;
+
+/* Longest an attach with no recorded detach is assumed to last. Without
+ * `--hub`, attaching replaces trex with tmux, so the detach is never seen. */
+pub const OPEN_ATTACH_CAP_SECS: u64 = 3600;
+
+// One attach to a session through trex, in unix times; `end` is None when the detach wasn't seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attach {
+    pub session: String,
+    pub start: u64,
+    pub end: Option<u64>,
+}
+
+/* Attaches made through trex over the same week the activity timeline
+ * keeps, saved as `attaches.tsv` under the trex cache directory. Each line
+ * is `start<TAB>end<TAB>session name`, with `-` for an unseen detach and
+ * the name last since it may contain anything. */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AttachLog {
+    attaches: Vec<Attach>,
+}
+
+impl AttachLog {
+    // Loads the saved history; empty if missing or unreadable.
+    pub fn load() -> Self {
+        attach_log_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = attach_log_path() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.render())
+    }
+
+    // Adds an attach to the saved history, dropping entries older than the retention window.
+    pub fn append(attach: Attach, now: u64) -> std::io::Result<()> {
+        let mut log = Self::load();
+        log.record(attach, now);
+        log.save()
+    }
+
+    pub fn record(&mut self, attach: Attach, now: u64) {
+        self.attaches.push(attach);
+        self.attaches.sort_by_key(|attach| attach.start);
+        let oldest = now.saturating_sub(RETENTION_SECS);
+        self.attaches
+            .retain(|attach| attach.end.unwrap_or(attach.start) >= oldest);
+    }
+
+    /* Every attach with its end filled in: an unseen detach is taken to be
+     * the next attach through trex, at most `OPEN_ATTACH_CAP_SECS` later
+     * and no later than `now`. */
+    pub fn resolved(&self, now: u64) -> Vec<Attach> {
+        self.attaches
+            .iter()
+            .enumerate()
+            .map(|(idx, attach)| {
+                let end = attach.end.unwrap_or_else(|| {
+                    let next = self.attaches.get(idx + 1).map_or(now, |next| next.start);
+                    next.min(attach.start + OPEN_ATTACH_CAP_SECS)
+                        .min(now)
+                        .max(attach.start)
+                });
+                Attach {
+                    end: Some(end),
+                    ..attach.clone()
+                }
+            })
+            .collect()
+    }
+
+    fn parse(text: &str) -> Self {
+        let attaches = text
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let (start, end, session) = (fields.next()?, fields.next()?, fields.next()?);
+                let end = match end {
+                    "-" => None,
+                    end => Some(end.parse().ok()?),
+                };
+                Some(Attach {
+                    session: session.to_string(),
+                    start: start.parse().ok()?,
+                    end,
+                })
+            })
+            .collect();
+        Self { attaches }
+    }
+
+    fn render(&self) -> String {
+        self.attaches
+            .iter()
+            .map(|attach| {
+                let end = attach
+                    .end
+                    .map_or_else(|| "-".to_string(), |end| end.to_string());
+                format!("{}\t{}\t{}\n", attach.start, end, attach.session)
+            })
+            .collect()
+    }
+}
+
+fn attach_log_path() -> Option<PathBuf> {
+    crate::logging::cache_dir().map(|dir| dir.join("attaches.tsv"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn attach(session: &str, start: u64, end: Option<u64>) -> Attach {
+        Attach {
+            session: session.to_string(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn open_attaches_last_until_the_next_one() {
+        let mut log = AttachLog::default();
+        log.record(attach("web", NOW - 600, None), NOW);
+        log.record(attach("api", NOW - 9000, Some(NOW - 8000)), NOW);
+        log.record(attach("api", NOW - 7200, None), NOW);
+        log.record(attach("docs", NOW - 60, None), NOW);
+
+        let ends: Vec<Option<u64>> = log.resolved(NOW).iter().map(|a| a.end).collect();
+        assert_eq!(
+            ends,
+            [
+                Some(NOW - 8000),
+                Some(NOW - 7200 + OPEN_ATTACH_CAP_SECS),
+                Some(NOW - 60),
+                Some(NOW),
+            ]
+        );
+
+        // Entries older than the retention window are dropped
+        let later = NOW + RETENTION_SECS - 300;
+        log.record(attach("api", later, None), later);
+        let sessions: Vec<String> = log.resolved(later).into_iter().map(|a| a.session).collect();
+        assert_eq!(sessions, ["docs", "api"]);
+    }
+
+    #[test]
+    fn round_trips_and_skips_bad_lines() {
+        let mut log = AttachLog::default();
+        log.record(attach("a\tb c", NOW, None), NOW);
+        log.record(attach("api", NOW - 10, Some(NOW - 5)), NOW);
+        let text = log.render();
+        assert_eq!(AttachLog::parse(&text), log);
+
+        let parsed = AttachLog::parse(&format!("garbage\n1\tnever\tapi\n{}", text));
+        assert_eq!(parsed, log);
+    }
+}
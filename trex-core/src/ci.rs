@@ -0,0 +1,186 @@
+use std::path::Path;
+use std::process::Command;
+
+/* Pull request and CI state of a repo's branch on GitHub, read with the
+ * `gh` CLI: the branch's open (or last) pull request from `gh pr view`
+ * and its latest workflow run from `gh run list`. Both talk to the
+ * network, so callers run them off the UI thread and cache the result. */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrState {
+    Open,
+    Draft,
+    Merged,
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PullRequest {
+    pub number: u32,
+    pub state: PrState,
+}
+
+// Outcome of the branch's latest workflow run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RunState {
+    Pending, // queued or in progress
+    Passed,
+    Failed,
+    Cancelled, // cancelled, skipped, or neutral
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CiStatus {
+    pub pr: Option<PullRequest>,
+    pub run: Option<RunState>,
+}
+
+impl CiStatus {
+    /* Queries GitHub for the branch checked out at `path`. A branch with no
+     * pull request or no runs, or a repo gh can't reach, leaves that half
+     * empty. */
+    pub fn for_branch(path: &Path, branch: &str) -> Self {
+        let pr = gh_output(
+            path,
+            &[
+                "pr",
+                "view",
+                "--json",
+                "number,state,isDraft",
+                "--jq",
+                r#""\(.number) \(.state) \(.isDraft)""#,
+            ],
+        )
+        .and_then(|line| parse_pr(&line));
+        let run = gh_output(
+            path,
+            &[
+                "run",
+                "list",
+                "--branch",
+                branch,
+                "--limit",
+                "1",
+                "--json",
+                "status,conclusion",
+                "--jq",
+                r#".[] | "\(.status) \(.conclusion)""#,
+            ],
+        )
+        .and_then(|line| parse_run(&line));
+        Self { pr, run }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pr.is_none() && self.run.is_none()
+    }
+
+    // A short badge like `#42 ✓`, `#42 draft ●`, or `✗` for a branch without a PR.
+    pub fn badge(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(pr) = self.pr {
+            parts.push(format!("#{}", pr.number));
+            match pr.state {
+                PrState::Open => {}
+                PrState::Draft => parts.push("draft".to_string()),
+                PrState::Merged => parts.push("merged".to_string()),
+                PrState::Closed => parts.push("closed".to_string()),
+            }
+        }
+        if let Some(run) = self.run {
+            parts.push(
+                match run {
+                    RunState::Pending => "●",
+                    RunState::Passed => "✓",
+                    RunState::Failed => "✗",
+                    RunState::Cancelled => "⊘",
+                }
+                .to_string(),
+            );
+        }
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+pub fn gh_available() -> bool {
+    which::which("gh").is_ok()
+}
+
+// The first line gh prints, None when it fails or prints nothing.
+fn gh_output(path: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("gh")
+        .args(args)
+        .current_dir(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(str::to_string)
+}
+
+// Parses `<number> <state> <isDraft>`, as `42 OPEN false`.
+fn parse_pr(line: &str) -> Option<PullRequest> {
+    let mut fields = line.split_whitespace();
+    let number = fields.next()?.parse().ok()?;
+    let state = match (fields.next()?, fields.next()?) {
+        ("OPEN", "true") => PrState::Draft,
+        ("OPEN", _) => PrState::Open,
+        ("MERGED", _) => PrState::Merged,
+        ("CLOSED", _) => PrState::Closed,
+        _ => return None,
+    };
+    Some(PullRequest { number, state })
+}
+
+// Parses `<status> <conclusion>`, as `completed success` or `in_progress `.
+fn parse_run(line: &str) -> Option<RunState> {
+    let mut fields = line.split_whitespace();
+    let status = fields.next()?;
+    if status != "completed" {
+        return Some(RunState::Pending);
+    }
+    Some(match fields.next().unwrap_or_default() {
+        "success" => RunState::Passed,
+        "failure" | "timed_out" | "startup_failure" | "action_required" => RunState::Failed,
+        _ => RunState::Cancelled,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gh_output_into_a_badge() {
+        let pr = parse_pr("42 OPEN false");
+        assert_eq!(
+            pr,
+            Some(PullRequest {
+                number: 42,
+                state: PrState::Open
+            })
+        );
+        assert_eq!(parse_pr("7 OPEN true").unwrap().state, PrState::Draft);
+        assert_eq!(parse_pr("no pull requests found"), None);
+        assert_eq!(parse_run("in_progress "), Some(RunState::Pending));
+        assert_eq!(parse_run("completed failure"), Some(RunState::Failed));
+        assert_eq!(parse_run("completed skipped"), Some(RunState::Cancelled));
+        assert_eq!(parse_run(""), None);
+
+        let status = CiStatus {
+            pr,
+            run: Some(RunState::Passed),
+        };
+        assert_eq!(status.badge().as_deref(), Some("#42 ✓"));
+        let status = CiStatus {
+            pr: None,
+            run: Some(RunState::Failed),
+        };
+        assert_eq!(status.badge().as_deref(), Some("✗"));
+        assert_eq!(CiStatus::default().badge(), None);
+    }
+}
@@ -0,0 +1,198 @@
+use std::path::{Path, PathBuf};
+
+use crate::template::{SessionTemplate, TemplateLayout, TemplatePane, TemplateWindow};
+
+/* A project that runs in containers: a docker compose file or a
+ * devcontainer definition in the directory. The naming screen offers a
+ * template for it whose first window runs the containers and whose second
+ * is a shell inside the main one. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerProject {
+    // `docker compose`, with `-f <file>` when the file isn't a default name
+    Compose {
+        file: Option<PathBuf>,
+        service: String,
+    },
+    // A devcontainer.json without compose, through the devcontainer CLI
+    Devcontainer,
+}
+
+const COMPOSE_FILES: [&str; 4] = [
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+const DEVCONTAINER_FILES: [&str; 2] = [".devcontainer/devcontainer.json", ".devcontainer.json"];
+
+impl ContainerProject {
+    /* A devcontainer that names a compose file and service wins, since it
+     * says which service is the workspace; otherwise a compose file's
+     * first service; otherwise a plain devcontainer when the CLI is
+     * installed. */
+    pub fn detect(dir: &Path) -> Option<Self> {
+        let devcontainer = DEVCONTAINER_FILES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file());
+        if let Some(path) = &devcontainer
+            && let Ok(text) = std::fs::read_to_string(path)
+            && let Some(service) = json_string(&text, "service")
+            && let Some(file) = json_string(&text, "dockerComposeFile")
+        {
+            // Relative to the devcontainer.json, which is usually one level down
+            let file = normalize(&path.parent().unwrap_or(dir).join(file));
+            let file = file
+                .strip_prefix(dir)
+                .map(Path::to_path_buf)
+                .unwrap_or(file);
+            return Some(ContainerProject::Compose {
+                file: Some(file),
+                service,
+            });
+        }
+
+        let compose = COMPOSE_FILES
+            .iter()
+            .find_map(|name| std::fs::read_to_string(dir.join(name)).ok());
+        if let Some(service) = compose.as_deref().and_then(first_service) {
+            return Some(ContainerProject::Compose {
+                file: None,
+                service,
+            });
+        }
+
+        (devcontainer.is_some() && which::which("devcontainer").is_ok())
+            .then_some(ContainerProject::Devcontainer)
+    }
+
+    /* The template offered for the project: the first window runs the
+     * containers in the foreground, then a `shell` window waits for them
+     * and opens a shell inside the main one; the session lands there. */
+    pub fn template(&self) -> SessionTemplate {
+        let (name, up, shell) = match self {
+            ContainerProject::Compose { file, service } => {
+                let compose = match file {
+                    Some(file) => format!("docker compose -f '{}'", file.display()),
+                    None => "docker compose".to_string(),
+                };
+                (
+                    format!("Compose ({})", service),
+                    format!("{} up", compose),
+                    format!(
+                        "{compose} up --wait {service} >/dev/null && {compose} exec {service} sh"
+                    ),
+                )
+            }
+            ContainerProject::Devcontainer => (
+                "Devcontainer".to_string(),
+                "devcontainer up --workspace-folder .".to_string(),
+                "devcontainer up --workspace-folder . >/dev/null && \
+                 devcontainer exec --workspace-folder . sh"
+                    .to_string(),
+            ),
+        };
+        SessionTemplate {
+            id: SessionTemplate::PROJECT_ID.to_string(),
+            description: "Containers in the first window, a shell in the main one in the next"
+                .to_string(),
+            name,
+            layout: TemplateLayout::Single,
+            panes: vec![TemplatePane { command: up }],
+            focus_pane: 0,
+            windows: vec![TemplateWindow {
+                name: "shell".to_string(),
+                command: shell,
+            }],
+        }
+    }
+}
+
+// Resolves `..` lexically, so `.devcontainer/../compose.yml` reads `compose.yml`.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normal = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::ParentDir => {
+                normal.pop();
+            }
+            std::path::Component::CurDir => {}
+            component => normal.push(component),
+        }
+    }
+    normal
+}
+
+/* The first key under the top-level `services:` of a compose file. Read
+ * line by line, which is enough for the block style compose files use. */
+fn first_service(text: &str) -> Option<String> {
+    let mut lines = text
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+    lines.find(|line| line.trim_end() == "services:")?;
+    let line = lines.next()?;
+    if !line.starts_with([' ', '\t']) {
+        return None;
+    }
+    let key = line.trim().strip_suffix(':')?;
+    Some(key.trim_matches(['"', '\'']).to_string()).filter(|key| !key.is_empty())
+}
+
+/* The string value of `"key"` in a devcontainer.json, or the first string
+ * of its array. Comments and trailing commas, which the format allows, are
+ * no obstacle to this search. */
+fn json_string(text: &str, key: &str) -> Option<String> {
+    let after = text.split(&format!("\"{}\"", key)).nth(1)?;
+    let value = after.trim_start().strip_prefix(':')?.trim_start();
+    let value = value.strip_prefix('[').map_or(value, str::trim_start);
+    let value = value.strip_prefix('"')?;
+    Some(value[..value.find('"')?].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_main_service() {
+        let compose = "# dev stack\nversion: '3'\nservices:\n  api:\n    build: .\n  db:\n    image: postgres\n";
+        assert_eq!(first_service(compose).as_deref(), Some("api"));
+        assert_eq!(first_service("services: {}\n"), None);
+
+        let devcontainer = r#"{
+            // the workspace service
+            "dockerComposeFile": ["../docker-compose.yml"],
+            "service": "app",
+        }"#;
+        assert_eq!(json_string(devcontainer, "service").as_deref(), Some("app"));
+        assert_eq!(
+            json_string(devcontainer, "dockerComposeFile").as_deref(),
+            Some("../docker-compose.yml")
+        );
+
+        let root = std::env::temp_dir().join(format!("trex-compose-{}", std::process::id()));
+        std::fs::create_dir_all(root.join(".devcontainer")).unwrap();
+        std::fs::write(root.join("compose.yaml"), compose).unwrap();
+        assert_eq!(
+            ContainerProject::detect(&root),
+            Some(ContainerProject::Compose {
+                file: None,
+                service: "api".to_string()
+            })
+        );
+        std::fs::write(root.join(".devcontainer/devcontainer.json"), devcontainer).unwrap();
+        let project = ContainerProject::detect(&root).unwrap();
+        assert_eq!(
+            project,
+            ContainerProject::Compose {
+                file: Some(PathBuf::from("docker-compose.yml")),
+                service: "app".to_string()
+            }
+        );
+        let template = project.template();
+        assert_eq!(template.name, "Compose (app)");
+        assert!(template.windows[0].command.ends_with("exec app sh"));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
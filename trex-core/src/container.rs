@@ -0,0 +1,171 @@
+use std::fs;
+use std::process::Command;
+
+/* Container awareness for agent detection. An agent running in a docker or
+ * podman container is still a host process in /proc, but its stdin is a
+ * pty inside the container and it has no TMUX variable, so it can't be
+ * traced to a tmux pane directly. Instead the container is identified from
+ * the agent's cgroup, and the pane is the one running the `docker run` /
+ * `podman exec` client for that container. */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    pub fn command(&self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+        }
+    }
+}
+
+// A container found in a process's cgroup, by runtime and full 64-digit id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerRef {
+    pub runtime: ContainerRuntime,
+    pub id: String,
+}
+
+impl ContainerRef {
+    // The 12-digit id docker and podman print.
+    pub fn short_id(&self) -> &str {
+        &self.id[..12]
+    }
+
+    // The container's name, or None if the runtime can't be asked.
+    pub fn name(&self) -> Option<String> {
+        let output = Command::new(self.runtime.command())
+            .args(["inspect", "--format", "{{.Name}}", &self.id])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        // Docker prefixes names with `/`, podman doesn't
+        let name = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .trim_start_matches('/')
+            .to_string();
+        (!name.is_empty()).then_some(name)
+    }
+
+    /* Whether a runtime client's argv targets this container: by name
+     * (`exec dev`, `run --name dev`, `--name=dev`) or by an id prefix of
+     * at least 12 digits. */
+    pub fn is_target_of(&self, name: Option<&str>, cmdline: &[String]) -> bool {
+        cmdline.iter().skip(1).any(|arg| {
+            let arg = arg.strip_prefix("--name=").unwrap_or(arg);
+            name == Some(arg)
+                || (arg.len() >= 12
+                    && arg.chars().all(|c| c.is_ascii_hexdigit())
+                    && self.id.starts_with(arg))
+        })
+    }
+}
+
+// The container a process runs in, from /proc/<pid>/cgroup.
+pub fn container_of(pid: u32) -> Option<ContainerRef> {
+    parse_cgroup(&fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?)
+}
+
+/* Finds a docker or podman container id in cgroup lines such as
+ * `0::/system.slice/docker-<id>.scope` (systemd driver),
+ * `12:pids:/docker/<id>` (cgroupfs driver), or
+ * `0::/user.slice/.../libpod-<id>.scope/container` (rootless podman). */
+fn parse_cgroup(content: &str) -> Option<ContainerRef> {
+    content.lines().find_map(|line| {
+        let path = line.splitn(3, ':').nth(2)?;
+        let components: Vec<&str> = path.split('/').collect();
+        components.iter().enumerate().find_map(|(idx, component)| {
+            let component = component.strip_suffix(".scope").unwrap_or(component);
+            let parent = idx.checked_sub(1).map(|idx| components[idx]);
+            let (runtime, id) = if let Some(id) = component.strip_prefix("docker-") {
+                (ContainerRuntime::Docker, id)
+            } else if let Some(id) = component.strip_prefix("libpod-") {
+                (ContainerRuntime::Podman, id)
+            } else if parent == Some("docker") {
+                (ContainerRuntime::Docker, component)
+            } else {
+                return None;
+            };
+            (id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit())).then(|| ContainerRef {
+                runtime,
+                id: id.to_string(),
+            })
+        })
+    })
+}
+
+/* Whether a process is a container client that keeps a terminal attached
+ * to a container (`docker run`, `podman exec`, `docker attach`, ...), so
+ * the pane it runs in shows the container's agent. */
+pub fn is_container_client(comm: &str, cmdline: &[String]) -> bool {
+    matches!(comm, "docker" | "podman")
+        && cmdline
+            .iter()
+            .skip(1)
+            .any(|arg| matches!(arg.as_str(), "run" | "exec" | "attach" | "start"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "4f1c2b9a7d3e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8";
+
+    #[test]
+    fn finds_container_ids_in_cgroups() {
+        let systemd = format!("0::/system.slice/docker-{ID}.scope\n");
+        let cgroupfs = format!("12:pids:/docker/{ID}\n11:cpu:/docker/{ID}\n");
+        let podman = format!(
+            "0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{ID}.scope/container\n"
+        );
+
+        for (content, runtime) in [
+            (systemd, ContainerRuntime::Docker),
+            (cgroupfs, ContainerRuntime::Docker),
+            (podman, ContainerRuntime::Podman),
+        ] {
+            let container = parse_cgroup(&content).unwrap();
+            assert_eq!(container.runtime, runtime);
+            assert_eq!(container.id, ID);
+            assert_eq!(container.short_id(), &ID[..12]);
+        }
+
+        assert_eq!(
+            parse_cgroup("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            None
+        );
+        assert_eq!(parse_cgroup("0::/system.slice/docker-abc.scope\n"), None);
+    }
+
+    #[test]
+    fn matches_clients_by_name_or_id() {
+        let container = ContainerRef {
+            runtime: ContainerRuntime::Docker,
+            id: ID.to_string(),
+        };
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert!(container.is_target_of(
+            Some("dev"),
+            &args(&["docker", "exec", "-it", "dev", "bash"])
+        ));
+        assert!(
+            container.is_target_of(Some("dev"), &args(&["docker", "run", "--name=dev", "img"]))
+        );
+        assert!(container.is_target_of(None, &args(&["docker", "attach", &ID[..12]])));
+        assert!(!container.is_target_of(Some("dev"), &args(&["docker", "exec", "web", "sh"])));
+        assert!(!container.is_target_of(None, &args(&["docker", "attach", &ID[..4]])));
+
+        assert!(is_container_client(
+            "podman",
+            &args(&["podman", "run", "-it", "img"])
+        ));
+        assert!(!is_container_client("docker", &args(&["docker", "ps"])));
+    }
+}
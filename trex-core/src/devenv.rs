@@ -0,0 +1,82 @@
+use std::path::Path;
+
+/* A project's declared development environment: a direnv `.envrc` or a
+ * nix flake. New sessions in such a directory can start their first
+ * command inside it, so agents and editors get the project's toolchain
+ * rather than whatever the login shell has. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevEnv {
+    Direnv, // `.envrc`, run through `direnv exec`
+    Flake,  // `flake.nix`, run through `nix develop`
+}
+
+impl DevEnv {
+    /* The environment declared in `dir` whose tool is installed. An
+     * `.envrc` wins over a flake, since it usually loads the flake itself
+     * along with the project's own variables. */
+    pub fn detect(dir: &Path) -> Option<Self> {
+        [DevEnv::Direnv, DevEnv::Flake]
+            .into_iter()
+            .find(|env| dir.join(env.marker()).is_file() && which::which(env.program()).is_ok())
+    }
+
+    // The file that declares it.
+    pub fn marker(&self) -> &'static str {
+        match self {
+            DevEnv::Direnv => ".envrc",
+            DevEnv::Flake => "flake.nix",
+        }
+    }
+
+    fn program(&self) -> &'static str {
+        match self {
+            DevEnv::Direnv => "direnv",
+            DevEnv::Flake => "nix",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DevEnv::Direnv => "direnv",
+            DevEnv::Flake => "nix develop",
+        }
+    }
+
+    /* A tmux pane command running `command` inside the environment, or
+     * the user's shell when there is none. tmux hands pane commands to
+     * `sh -c`, so `$SHELL` expands there and a given command is quoted
+     * whole for a nested `sh -c`. */
+    pub fn wrap(&self, command: Option<&str>) -> String {
+        let inner = match command {
+            Some(command) => format!("sh -c {}", single_quote(command)),
+            None => "\"$SHELL\"".to_string(),
+        };
+        match self {
+            DevEnv::Direnv => format!("direnv exec . {}", inner),
+            DevEnv::Flake => format!("nix develop --command {}", inner),
+        }
+    }
+}
+
+fn single_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_commands_for_each_environment() {
+        assert_eq!(DevEnv::Direnv.wrap(None), "direnv exec . \"$SHELL\"");
+        assert_eq!(
+            DevEnv::Flake.wrap(Some("cargo watch -x 'test'")),
+            "nix develop --command sh -c 'cargo watch -x '\\''test'\\'''"
+        );
+
+        let dir = std::env::temp_dir().join(format!("trex-devenv-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(DevEnv::detect(&dir), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
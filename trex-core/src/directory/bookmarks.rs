@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+use super::{Directory, DirectorySource, SourceKind, expand_home, home_dir};
+
+/* Directories listed by hand in `~/.config/trex/bookmarks` (or under
+ * `$XDG_CONFIG_HOME`), one per line. Blank lines and `#` comments are
+ * skipped, `~` is expanded, and paths that no longer exist are left out. */
+pub struct BookmarksSource;
+
+impl DirectorySource for BookmarksSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::Bookmarks
+    }
+
+    fn discover(&self, _depth: u32) -> Vec<Directory> {
+        let Some(text) = bookmarks_path().and_then(|path| std::fs::read_to_string(path).ok())
+        else {
+            return Vec::new();
+        };
+        parse_bookmarks(&text, home_dir().as_deref())
+            .into_iter()
+            .filter(|path| path.is_dir())
+            .map(Directory::new)
+            .collect()
+    }
+
+    fn cached(&self, depth: u32) -> Vec<Directory> {
+        self.discover(depth)
+    }
+}
+
+pub fn bookmarks_path() -> Option<PathBuf> {
+    let xdg_config_home =
+        std::env::var_os("XDG_CONFIG_HOME").filter(|xdg| !xdg.to_string_lossy().trim().is_empty());
+    match xdg_config_home {
+        Some(xdg) => Some(PathBuf::from(xdg).join("trex/bookmarks")),
+        None => home_dir().map(|home| home.join(".config/trex/bookmarks")),
+    }
+}
+
+fn parse_bookmarks(text: &str, home: Option<&Path>) -> Vec<PathBuf> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| expand_home(line, home))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bookmarks_with_comments_and_home() {
+        let text = "# work\n~/src/api\n\n  /srv/site  \nrelative/path\n~\n";
+        assert_eq!(
+            parse_bookmarks(text, Some(Path::new("/home/me"))),
+            [
+                PathBuf::from("/home/me/src/api"),
+                PathBuf::from("/srv/site"),
+                PathBuf::from("/home/me"),
+            ]
+        );
+        assert_eq!(parse_bookmarks("~/src/api\n", None), Vec::<PathBuf>::new());
+    }
+}
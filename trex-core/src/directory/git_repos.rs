@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use super::scan::PROJECT_SUBDIRS;
+use super::{Directory, DirectorySource, SourceKind, home_dir};
+
+/* Git repositories under the home project directories (`~/projects`,
+ * `~/src`, ...), found up to the picker depth below each. A repository's
+ * own subdirectories aren't searched, and hidden directories are skipped. */
+pub struct GitReposSource;
+
+impl DirectorySource for GitReposSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::GitRepos
+    }
+
+    fn discover(&self, depth: u32) -> Vec<Directory> {
+        let Some(home) = home_dir() else {
+            return Vec::new();
+        };
+        let mut repos = Vec::new();
+        for subdir in PROJECT_SUBDIRS {
+            find_repos(&home.join(subdir), depth, &mut repos);
+        }
+        repos
+    }
+}
+
+// Collects repositories at `dir` or up to `depth` levels below it, sorted by path.
+fn find_repos(dir: &Path, depth: u32, repos: &mut Vec<Directory>) {
+    if dir.join(".git").exists() {
+        if let Ok(canonical) = std::fs::canonicalize(dir) {
+            repos.push(Directory::new(canonical));
+        }
+        return;
+    }
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut children: Vec<_> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .collect();
+    children.sort();
+    for child in children {
+        find_repos(&child, depth - 1, repos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_repositories_without_descending_into_them() {
+        let root = std::env::temp_dir().join(format!("trex-git-repos-{}", std::process::id()));
+        for dir in [
+            "api/.git",
+            "api/vendor/lib/.git",
+            "client/web/.git",
+            "notes",
+            "deep/a/b/.git",
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+
+        let mut repos = Vec::new();
+        find_repos(&root, 2, &mut repos);
+        let names: Vec<String> = repos.iter().map(|dir| dir.display_name()).collect();
+        assert_eq!(names, ["api", "web"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
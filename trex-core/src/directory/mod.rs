@@ -0,0 +1,269 @@
+use std::path::{Path, PathBuf};
+
+mod bookmarks;
+mod git_repos;
+mod recent;
+mod scan;
+mod source;
+mod tmuxinator;
+mod zoxide;
+
+pub use bookmarks::{BookmarksSource, bookmarks_path};
+pub use git_repos::GitReposSource;
+pub use recent::{MAX_RECENT_DIRECTORIES, RecentSource, record_recent_directory};
+pub use scan::{ScanSource, scan_project_directories};
+pub use source::{DirectorySource, SourceKind, cached_from, discover_from, merge_directories};
+pub use tmuxinator::TmuxinatorSource;
+pub use zoxide::ZoxideSource;
+
+/* Scan depth for directory discovery */
+pub const MIN_DEPTH: u32 = 1;
+pub const MAX_DEPTH: u32 = 6;
+pub const DEFAULT_DEPTH: u32 = 3;
+
+// A directory that can be used to create a new tmux session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Directory {
+    pub path: PathBuf,
+    pub source: Option<SourceKind>, // the source that listed it, if any
+}
+
+impl Directory {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, source: None }
+    }
+
+    // The basename shown in lists, or the full path for `/`.
+    pub fn display_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+
+    // Returns a string suitable for fuzzy matching (display name + full path).
+    pub fn match_string(&self) -> String {
+        format!("{} {}", self.display_name(), self.path.display())
+    }
+
+    /* Derives a session name from the directory's basename.
+     * Sanitizes the name for tmux compatibility (dots and colons are problematic). */
+    pub fn session_name(&self) -> String {
+        let name = self
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "session".to_string());
+        sanitize_session_name(&name)
+    }
+}
+
+/* Sanitizes a session name for tmux compatibility.
+ * Keeps alphanumeric chars, hyphens, and underscores; replaces others with underscores. */
+pub fn sanitize_session_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/* Suggests a free session name for a directory when `name` is taken:
+ * `<parent>-<name>` first, since it says where the directory lives, then
+ * `<name>-2`, `<name>-3`, ... Returns `name` unchanged when it is free. */
+pub fn suggest_session_name(name: &str, path: &Path, taken: &[&str]) -> String {
+    let is_free = |candidate: &str| !taken.contains(&candidate);
+    if is_free(name) {
+        return name.to_string();
+    }
+    if let Some(parent) = path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|parent| sanitize_session_name(&parent.to_string_lossy()))
+    {
+        let candidate = format!("{}-{}", parent, name);
+        if is_free(&candidate) {
+            return candidate;
+        }
+    }
+    (2..)
+        .map(|n| format!("{}-{}", name, n))
+        .find(|candidate| is_free(candidate))
+        .unwrap_or_default()
+}
+
+/* Lines of the directory's README and top-level entries shown in the picker
+ * preview */
+pub const PREVIEW_README_LINES: usize = 8;
+pub const PREVIEW_ENTRIES: usize = 40;
+
+// What the directory picker shows about the selected directory.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirectoryPreview {
+    pub readme: Vec<String>,    // first non-blank lines of the README, if any
+    pub branch: Option<String>, // git branch when the directory is in a repo
+    pub entries: Vec<String>,   // top-level names, directories first with a trailing `/`
+    pub more_entries: usize,    // entries left out past PREVIEW_ENTRIES
+}
+
+impl DirectoryPreview {
+    // Reads the preview for a directory. Unreadable parts are left empty.
+    pub fn load(path: &Path) -> Self {
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+        let mut readme_path = None;
+        if let Ok(entries) = std::fs::read_dir(path) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    dirs.push(format!("{}/", name));
+                } else {
+                    if readme_path.is_none() && name.to_lowercase().starts_with("readme") {
+                        readme_path = Some(entry.path());
+                    }
+                    files.push(name);
+                }
+            }
+        }
+        dirs.sort();
+        files.sort();
+        let mut entries: Vec<String> = dirs.into_iter().chain(files).collect();
+        let more_entries = entries.len().saturating_sub(PREVIEW_ENTRIES);
+        entries.truncate(PREVIEW_ENTRIES);
+
+        let readme = readme_path
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|text| readme_lines(&text))
+            .unwrap_or_default();
+
+        Self {
+            readme,
+            branch: crate::git::GitStatus::branch_for(path),
+            entries,
+            more_entries,
+        }
+    }
+}
+
+// The first non-blank README lines, trimmed on the right.
+fn readme_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .take(PREVIEW_README_LINES)
+        .map(str::to_string)
+        .collect()
+}
+
+/* Resolves typed picker input to a directory path to create: `~/` is
+ * expanded against `home`, absolute paths stay as typed, and anything else
+ * is taken relative to `cwd`. Returns None for blank input or a `~` path
+ * without a home directory. */
+pub fn resolve_new_directory(input: &str, cwd: &Path, home: Option<&Path>) -> Option<PathBuf> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if input == "~" {
+        return home.map(Path::to_path_buf);
+    }
+    if let Some(rest) = input.strip_prefix("~/") {
+        return home.map(|home| home.join(rest));
+    }
+    Some(cwd.join(input))
+}
+
+// Expands a leading `~` against `home`; other paths must be absolute.
+fn expand_home(input: &str, home: Option<&Path>) -> Option<PathBuf> {
+    let path = match input.strip_prefix('~') {
+        Some("") => home?.to_path_buf(),
+        Some(rest) => home?.join(rest.strip_prefix('/')?),
+        None => PathBuf::from(input),
+    };
+    path.is_absolute().then_some(path)
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
+// Creates a directory (and missing parents) and returns it canonicalized.
+pub fn create_directory(path: &Path) -> std::io::Result<Directory> {
+    std::fs::create_dir_all(path)?;
+    Ok(Directory::new(std::fs::canonicalize(path)?))
+}
+
+/* Discovers directories from the filesystem for session creation.
+ * Prioritizes the current working directory, home directory, and common
+ * subdirectories (projects, work, dev, code, src), then recursively scans
+ * from root up to `max_depth` levels. Skips symlinks to avoid infinite loops. */
+pub fn discover_directories_with_depth(max_depth: u32) -> Vec<Directory> {
+    with_current_dir(scan_project_directories(max_depth))
+}
+
+// Puts the current working directory first, dropping it from further down.
+pub fn with_current_dir(mut dirs: Vec<Directory>) -> Vec<Directory> {
+    if let Ok(cwd) = std::env::current_dir() {
+        dirs.retain(|dir| dir.path != cwd);
+        dirs.insert(0, Directory::new(cwd));
+    }
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_new_directory_input() {
+        let cwd = Path::new("/work");
+        let home = Some(Path::new("/home/me"));
+        assert_eq!(
+            resolve_new_directory("api", cwd, home),
+            Some(PathBuf::from("/work/api"))
+        );
+        assert_eq!(
+            resolve_new_directory("~/projects/new", cwd, home),
+            Some(PathBuf::from("/home/me/projects/new"))
+        );
+        assert_eq!(
+            resolve_new_directory("/srv/site ", cwd, home),
+            Some(PathBuf::from("/srv/site"))
+        );
+        assert_eq!(resolve_new_directory("~/x", cwd, None), None);
+        assert_eq!(resolve_new_directory("  ", cwd, home), None);
+    }
+
+    #[test]
+    fn suggests_free_session_names() {
+        let path = Path::new("/work/client/api");
+        assert_eq!(suggest_session_name("api", path, &["web"]), "api");
+        assert_eq!(suggest_session_name("api", path, &["api"]), "client-api");
+        assert_eq!(
+            suggest_session_name("api", path, &["api", "client-api", "api-2"]),
+            "api-3"
+        );
+        assert_eq!(suggest_session_name("x", Path::new("/"), &["x"]), "x-2");
+    }
+
+    #[test]
+    fn preview_lists_readme_and_entries() {
+        let root = std::env::temp_dir().join(format!("trex-dir-preview-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("README.md"), "# trex\n\ntmux session manager\n").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "").unwrap();
+
+        let preview = DirectoryPreview::load(&root);
+        assert_eq!(preview.readme, vec!["# trex", "tmux session manager"]);
+        assert_eq!(preview.entries, vec!["src/", "Cargo.toml", "README.md"]);
+        assert_eq!(preview.more_entries, 0);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+
+use super::{Directory, DirectorySource, SourceKind};
+
+/* Directories trex started sessions in, most recent first, kept in
+ * `recent-directories` under the trex cache directory (one per line).
+ * Paths that no longer exist are left out. */
+pub const MAX_RECENT_DIRECTORIES: usize = 10;
+
+pub struct RecentSource;
+
+impl DirectorySource for RecentSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::Recent
+    }
+
+    fn discover(&self, _depth: u32) -> Vec<Directory> {
+        load_recent_directories()
+            .into_iter()
+            .filter(|path| path.is_dir())
+            .map(Directory::new)
+            .collect()
+    }
+
+    fn cached(&self, depth: u32) -> Vec<Directory> {
+        self.discover(depth)
+    }
+}
+
+// Moves `path` to the front of the saved list, dropping the oldest past the limit.
+pub fn record_recent_directory(path: &Path) -> std::io::Result<()> {
+    let Some(file) = recent_directories_path() else {
+        return Ok(());
+    };
+    let mut paths = load_recent_directories();
+    push_recent(&mut paths, path);
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let text: String = paths
+        .iter()
+        .map(|path| format!("{}\n", path.display()))
+        .collect();
+    std::fs::write(file, text)
+}
+
+fn load_recent_directories() -> Vec<PathBuf> {
+    recent_directories_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|text| parse(&text))
+        .unwrap_or_default()
+}
+
+fn push_recent(paths: &mut Vec<PathBuf>, path: &Path) {
+    paths.retain(|existing| existing != path);
+    paths.insert(0, path.to_path_buf());
+    paths.truncate(MAX_RECENT_DIRECTORIES);
+}
+
+fn parse(text: &str) -> Vec<PathBuf> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('/'))
+        .take(MAX_RECENT_DIRECTORIES)
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn recent_directories_path() -> Option<PathBuf> {
+    crate::logging::cache_dir().map(|dir| dir.join("recent-directories"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_directories_stay_unique_and_bounded() {
+        let mut paths = parse("/src/api\n\nrelative\n  /src/web \n");
+        assert_eq!(
+            paths,
+            [PathBuf::from("/src/api"), PathBuf::from("/src/web")]
+        );
+
+        push_recent(&mut paths, Path::new("/src/web"));
+        assert_eq!(
+            paths,
+            [PathBuf::from("/src/web"), PathBuf::from("/src/api")]
+        );
+
+        for n in 0..MAX_RECENT_DIRECTORIES {
+            push_recent(&mut paths, &PathBuf::from(format!("/src/{}", n)));
+        }
+        assert_eq!(paths.len(), MAX_RECENT_DIRECTORIES);
+        assert_eq!(paths[0], PathBuf::from("/src/9"));
+    }
+}
@@ -0,0 +1,88 @@
+use super::{Directory, DirectorySource, SourceKind};
+
+// Home subdirectories where projects usually live, listed before the rest.
+pub(super) const PROJECT_SUBDIRS: [&str; 5] = ["projects", "work", "dev", "code", "src"];
+
+/* The original picker source: home, its common project directories, and
+ * everything under `/` up to the picker depth. The last scan per depth is
+ * kept by directory_cache, so it also lists instantly. */
+pub struct ScanSource;
+
+impl DirectorySource for ScanSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::Scan
+    }
+
+    fn discover(&self, depth: u32) -> Vec<Directory> {
+        crate::directory_cache::refresh_directories(depth)
+    }
+
+    fn cached(&self, depth: u32) -> Vec<Directory> {
+        crate::directory_cache::cached_directories(depth).unwrap_or_default()
+    }
+}
+
+/* The part of discovery that doesn't depend on where trex runs: home, its
+ * common project subdirectories, then everything under `/` up to
+ * `max_depth`. This is what the directory cache stores. */
+pub fn scan_project_directories(max_depth: u32) -> Vec<Directory> {
+    let mut dirs = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+
+    if let Ok(home) = std::env::var("HOME")
+        && let Ok(home_path) = std::fs::canonicalize(&home)
+    {
+        if seen.insert(home_path.clone()) {
+            dirs.push(Directory::new(home_path.clone()));
+        }
+
+        for subdir in PROJECT_SUBDIRS {
+            let path = home_path.join(subdir);
+            if path.is_dir()
+                && let Ok(canonical) = std::fs::canonicalize(&path)
+                && seen.insert(canonical.clone())
+            {
+                dirs.push(Directory::new(canonical));
+            }
+        }
+    }
+
+    scan_directories("/", 0, max_depth, &mut dirs, &mut seen);
+
+    dirs
+}
+
+// Recursively scans directories up to `max_depth`, collecting them into `dirs`.
+fn scan_directories(
+    path: &str,
+    current_depth: u32,
+    max_depth: u32,
+    dirs: &mut Vec<Directory>,
+    seen: &mut std::collections::HashSet<std::path::PathBuf>,
+) {
+    if current_depth >= max_depth {
+        return;
+    }
+
+    if let Ok(entries) = std::fs::read_dir(path) {
+        for entry in entries.flatten() {
+            if let Ok(file_type) = entry.file_type() {
+                if file_type.is_symlink() {
+                    continue;
+                }
+
+                if file_type.is_dir()
+                    && let Ok(canonical) = std::fs::canonicalize(entry.path())
+                {
+                    if seen.insert(canonical.clone()) {
+                        dirs.push(Directory::new(canonical));
+                    }
+
+                    if let Some(path_str) = entry.path().to_str() {
+                        scan_directories(path_str, current_depth + 1, max_depth, dirs, seen);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+
+use super::{
+    BookmarksSource, Directory, GitReposSource, RecentSource, ScanSource, TmuxinatorSource,
+    ZoxideSource,
+};
+
+/* A provider of directories for the picker. Sources are merged in
+ * SourceKind::ALL order with duplicates dropped, so a directory shows once,
+ * where the most deliberate source put it. */
+pub trait DirectorySource: Send {
+    fn kind(&self) -> SourceKind;
+
+    /* The source's directories now. May walk the filesystem or run a
+     * program, so the TUI calls it off the event loop. */
+    fn discover(&self, depth: u32) -> Vec<Directory>;
+
+    // What the source can list without slow work, shown while discover runs.
+    fn cached(&self, depth: u32) -> Vec<Directory> {
+        let _ = depth;
+        Vec::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SourceKind {
+    Recent,     // directories trex last started sessions in
+    Bookmarks,  // paths listed in ~/.config/trex/bookmarks
+    Zoxide,     // `zoxide query -l`, most frecent first
+    Tmuxinator, // `root:` of each tmuxinator project
+    GitRepos,   // git repositories under the home project directories
+    Scan,       // the filesystem scan from `/` and home, cached per depth
+}
+
+impl SourceKind {
+    pub const ALL: [SourceKind; 6] = [
+        SourceKind::Recent,
+        SourceKind::Bookmarks,
+        SourceKind::Zoxide,
+        SourceKind::Tmuxinator,
+        SourceKind::GitRepos,
+        SourceKind::Scan,
+    ];
+
+    // The name used in config.toml and the picker.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SourceKind::Recent => "recent",
+            SourceKind::Bookmarks => "bookmarks",
+            SourceKind::Zoxide => "zoxide",
+            SourceKind::Tmuxinator => "tmuxinator",
+            SourceKind::GitRepos => "git",
+            SourceKind::Scan => "scan",
+        }
+    }
+
+    // The singular tag shown beside each directory in the picker.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SourceKind::Bookmarks => "bookmark",
+            kind => kind.name(),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /* Splits a picker filter like `git:api` into the source it names and
+     * the query after it. Either the name or the label works as a prefix. */
+    pub fn split_prefix(filter: &str) -> Option<(Self, &str)> {
+        let (prefix, rest) = filter.split_once(':')?;
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name() == prefix || kind.label() == prefix)
+            .map(|kind| (kind, rest.trim_start()))
+    }
+
+    pub fn source(&self) -> Box<dyn DirectorySource> {
+        match self {
+            SourceKind::Recent => Box::new(RecentSource),
+            SourceKind::Bookmarks => Box::new(BookmarksSource),
+            SourceKind::Zoxide => Box::new(ZoxideSource),
+            SourceKind::Tmuxinator => Box::new(TmuxinatorSource),
+            SourceKind::GitRepos => Box::new(GitReposSource),
+            SourceKind::Scan => Box::new(ScanSource),
+        }
+    }
+}
+
+// Concatenates directory lists in order, keeping the first of each path.
+pub fn merge_directories(lists: impl IntoIterator<Item = Vec<Directory>>) -> Vec<Directory> {
+    let mut seen = HashSet::new();
+    lists
+        .into_iter()
+        .flatten()
+        .filter(|dir| seen.insert(dir.path.clone()))
+        .collect()
+}
+
+/* Runs every enabled source and merges what they find, each directory
+ * tagged with the source that listed it. Can take seconds. */
+pub fn discover_from(kinds: &[SourceKind], depth: u32) -> Vec<Directory> {
+    merge_directories(sorted(kinds).map(|kind| tagged(kind, kind.source().discover(depth))))
+}
+
+// Merges what the enabled sources list without slow work, tagged the same way.
+pub fn cached_from(kinds: &[SourceKind], depth: u32) -> Vec<Directory> {
+    merge_directories(sorted(kinds).map(|kind| tagged(kind, kind.source().cached(depth))))
+}
+
+fn tagged(kind: SourceKind, directories: Vec<Directory>) -> Vec<Directory> {
+    directories
+        .into_iter()
+        .map(|dir| Directory {
+            source: Some(kind),
+            ..dir
+        })
+        .collect()
+}
+
+// The kinds in SourceKind::ALL order, whatever order they were enabled in.
+fn sorted(kinds: &[SourceKind]) -> impl Iterator<Item = SourceKind> + '_ {
+    SourceKind::ALL
+        .into_iter()
+        .filter(move |kind| kinds.contains(kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn merges_sources_in_order_without_duplicates() {
+        let dir = |path: &str| Directory::new(PathBuf::from(path));
+        let merged = merge_directories([
+            vec![dir("/src/api"), dir("/src/web")],
+            vec![dir("/src/web"), dir("/home/me")],
+        ]);
+        assert_eq!(merged, [dir("/src/api"), dir("/src/web"), dir("/home/me")]);
+
+        for kind in SourceKind::ALL {
+            assert_eq!(SourceKind::from_name(kind.name()), Some(kind));
+            assert_eq!(kind.source().kind(), kind);
+        }
+        assert_eq!(SourceKind::from_name("bookmark"), None);
+        assert_eq!(
+            SourceKind::split_prefix("bookmark:api"),
+            Some((SourceKind::Bookmarks, "api"))
+        );
+        assert_eq!(
+            SourceKind::split_prefix("git: "),
+            Some((SourceKind::GitRepos, ""))
+        );
+        assert_eq!(SourceKind::split_prefix("/src/api"), None);
+        assert_eq!(SourceKind::split_prefix("C:api"), None);
+    }
+}
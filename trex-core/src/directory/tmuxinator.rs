@@ -0,0 +1,82 @@
+use std::path::{Path, PathBuf};
+
+use super::{Directory, DirectorySource, SourceKind, expand_home, home_dir};
+
+/* The root directory of each tmuxinator project, read from the `root:`
+ * (or older `project_root:`) key of the `*.yml` files in tmuxinator's
+ * config directories. */
+pub struct TmuxinatorSource;
+
+impl DirectorySource for TmuxinatorSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::Tmuxinator
+    }
+
+    fn discover(&self, _depth: u32) -> Vec<Directory> {
+        let home = home_dir();
+        let mut files: Vec<PathBuf> = project_dirs(home.as_deref())
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "yml"))
+            .collect();
+        files.sort();
+        files
+            .iter()
+            .filter_map(|file| std::fs::read_to_string(file).ok())
+            .filter_map(|text| project_root(&text, home.as_deref()))
+            .filter(|path| path.is_dir())
+            .map(Directory::new)
+            .collect()
+    }
+
+    fn cached(&self, depth: u32) -> Vec<Directory> {
+        self.discover(depth)
+    }
+}
+
+// Where tmuxinator keeps projects: `$XDG_CONFIG_HOME/tmuxinator`, `~/.config/tmuxinator`, `~/.tmuxinator`.
+fn project_dirs(home: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("XDG_CONFIG_HOME")
+        .map(|xdg| PathBuf::from(xdg).join("tmuxinator"))
+        .into_iter()
+        .collect();
+    if let Some(home) = home {
+        dirs.push(home.join(".config/tmuxinator"));
+        dirs.push(home.join(".tmuxinator"));
+    }
+    dirs.dedup();
+    dirs
+}
+
+// The project's top-level `root:` value, unquoted and with `~` expanded.
+fn project_root(text: &str, home: Option<&Path>) -> Option<PathBuf> {
+    text.lines()
+        .filter_map(|line| {
+            line.strip_prefix("root:")
+                .or_else(|| line.strip_prefix("project_root:"))
+        })
+        .map(|value| value.trim().trim_matches(['"', '\'']))
+        .find(|value| !value.is_empty())
+        .and_then(|value| expand_home(value, home))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_project_root() {
+        let home = Some(Path::new("/home/me"));
+        let project = "# ~/.config/tmuxinator/api.yml\nname: api\nroot: \"~/src/api\"\n\nwindows:\n  - editor:\n      root: ~/elsewhere\n";
+        assert_eq!(
+            project_root(project, home),
+            Some(PathBuf::from("/home/me/src/api"))
+        );
+        assert_eq!(
+            project_root("name: old\nproject_root: /srv/old\n", home),
+            Some(PathBuf::from("/srv/old"))
+        );
+        assert_eq!(project_root("name: bare\n", home), None);
+    }
+}
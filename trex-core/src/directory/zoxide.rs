@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use super::{Directory, DirectorySource, SourceKind};
+
+// Directories zoxide has seen, most frecent first, when zoxide is installed.
+pub struct ZoxideSource;
+
+impl DirectorySource for ZoxideSource {
+    fn kind(&self) -> SourceKind {
+        SourceKind::Zoxide
+    }
+
+    fn discover(&self, _depth: u32) -> Vec<Directory> {
+        let Ok(output) = Command::new("zoxide").args(["query", "-l"]).output() else {
+            return Vec::new();
+        };
+        if !output.status.success() {
+            return Vec::new();
+        }
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(PathBuf::from)
+            .filter(|path| path.is_dir())
+            .map(Directory::new)
+            .collect()
+    }
+
+    // One quick query, so it lists right away too
+    fn cached(&self, depth: u32) -> Vec<Directory> {
+        self.discover(depth)
+    }
+}
@@ -0,0 +1,153 @@
+use crate::directory::{Directory, scan_project_directories};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/* The directory picker's last scan, saved per scan depth under the trex
+ * cache directory (`directories-<depth>.tsv`) so the picker fills instantly
+ * on startup. Each line is `mtime<TAB>path` with the modification time in
+ * nanoseconds; the first line is `/`, where the scan starts. Adding,
+ * removing, or renaming a subdirectory changes its parent's mtime, so the
+ * scan is still exact while every recorded directory keeps its mtime. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryCache {
+    root_mtime: Option<u128>,
+    entries: Vec<(PathBuf, Option<u128>)>,
+}
+
+impl DirectoryCache {
+    // Records the current mtimes of `/` and the scanned directories.
+    pub fn from_directories(dirs: &[Directory]) -> Self {
+        Self {
+            root_mtime: mtime(Path::new("/")),
+            entries: dirs
+                .iter()
+                .map(|dir| (dir.path.clone(), mtime(&dir.path)))
+                .collect(),
+        }
+    }
+
+    // Loads the cache for a scan depth; None if missing or unreadable.
+    pub fn load(depth: u32) -> Option<Self> {
+        let text = std::fs::read_to_string(cache_path(depth)?).ok()?;
+        Self::parse(&text)
+    }
+
+    pub fn save(&self, depth: u32) -> std::io::Result<()> {
+        let Some(path) = cache_path(depth) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.render())
+    }
+
+    pub fn directories(&self) -> Vec<Directory> {
+        self.entries
+            .iter()
+            .map(|(path, _)| Directory::new(path.clone()))
+            .collect()
+    }
+
+    // True while no recorded directory changed or disappeared since the scan.
+    pub fn is_current(&self) -> bool {
+        self.root_mtime.is_some()
+            && mtime(Path::new("/")) == self.root_mtime
+            && self
+                .entries
+                .iter()
+                .all(|(path, recorded)| recorded.is_some() && mtime(path) == *recorded)
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines().filter_map(|line| {
+            let (mtime, path) = line.split_once('\t')?;
+            Some((PathBuf::from(path), mtime.parse().ok()))
+        });
+        let (root, root_mtime) = lines.next()?;
+        if root != Path::new("/") {
+            return None;
+        }
+        Some(Self {
+            root_mtime,
+            entries: lines.collect(),
+        })
+    }
+
+    fn render(&self) -> String {
+        std::iter::once((Path::new("/"), self.root_mtime))
+            .chain(
+                self.entries
+                    .iter()
+                    .map(|(path, mtime)| (path.as_path(), *mtime)),
+            )
+            .map(|(path, mtime)| {
+                let mtime = mtime.map(|m| m.to_string()).unwrap_or_default();
+                format!("{}\t{}\n", mtime, path.display())
+            })
+            .collect()
+    }
+}
+
+// The directories from the last scan at this depth, if one was cached.
+pub fn cached_directories(depth: u32) -> Option<Vec<Directory>> {
+    DirectoryCache::load(depth).map(|cache| cache.directories())
+}
+
+/* Returns the scan for this depth: the cached one while it is still
+ * current, else a fresh scan, which is saved. Can take seconds on a large
+ * tree, so the TUI runs it off the event loop. */
+pub fn refresh_directories(depth: u32) -> Vec<Directory> {
+    if let Some(cache) = DirectoryCache::load(depth)
+        && cache.is_current()
+    {
+        return cache.directories();
+    }
+    let dirs = scan_project_directories(depth);
+    if let Err(error) = DirectoryCache::from_directories(&dirs).save(depth) {
+        log::warn!("directory cache: {}", error);
+    }
+    dirs
+}
+
+fn cache_path(depth: u32) -> Option<PathBuf> {
+    crate::logging::cache_dir().map(|dir| dir.join(format!("directories-{}.tsv", depth)))
+}
+
+fn mtime(path: &Path) -> Option<u128> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_round_trips_and_notices_changes() {
+        let root = std::env::temp_dir().join(format!("trex-dir-cache-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("api")).unwrap();
+        let dirs = vec![
+            Directory::new(root.clone()),
+            Directory::new(root.join("api")),
+        ];
+        // Backdate the directory so the change below moves its mtime even
+        // on filesystems with coarse timestamps
+        std::fs::File::open(&root)
+            .unwrap()
+            .set_modified(UNIX_EPOCH + std::time::Duration::from_secs(1))
+            .unwrap();
+
+        let cache = DirectoryCache::from_directories(&dirs);
+        let parsed = DirectoryCache::parse(&cache.render()).unwrap();
+        assert_eq!(parsed, cache);
+        assert_eq!(parsed.directories(), dirs);
+        assert!(parsed.is_current());
+
+        std::fs::create_dir(root.join("web")).unwrap();
+        assert!(!parsed.is_current());
+
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(DirectoryCache::parse("1\t/home\n"), None);
+    }
+}
@@ -0,0 +1,336 @@
+use anyhow::{Result, bail};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, Default)]
+pub struct GitStatus {
+    pub is_repo: bool,
+    pub branch: Option<String>,
+    pub dirty_count: u32,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+impl GitStatus {
+    pub fn for_path(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+
+        // Check if it's a git repo
+        let is_repo = Command::new("git")
+            .args(["-C", &path.display().to_string(), "rev-parse", "--git-dir"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+
+        if !is_repo {
+            return Self::default();
+        }
+
+        let branch = Self::get_branch(path);
+        let dirty_count = Self::get_dirty_count(path);
+        let (ahead, behind) = Self::get_ahead_behind(path);
+
+        Self {
+            is_repo: true,
+            branch,
+            dirty_count,
+            ahead,
+            behind,
+        }
+    }
+
+    // The current branch (or short commit when detached); None outside a repo.
+    pub fn branch_for(path: &Path) -> Option<String> {
+        Self::get_branch(path)
+    }
+
+    fn get_branch(path: &Path) -> Option<String> {
+        let output = Command::new("git")
+            .args([
+                "-C",
+                &path.display().to_string(),
+                "rev-parse",
+                "--abbrev-ref",
+                "HEAD",
+            ])
+            .output()
+            .ok()?;
+
+        if output.status.success() {
+            let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if branch.is_empty() || branch == "HEAD" {
+                // Detached HEAD - get short commit hash instead
+                let hash_output = Command::new("git")
+                    .args([
+                        "-C",
+                        &path.display().to_string(),
+                        "rev-parse",
+                        "--short",
+                        "HEAD",
+                    ])
+                    .output()
+                    .ok()?;
+                if hash_output.status.success() {
+                    return Some(
+                        String::from_utf8_lossy(&hash_output.stdout)
+                            .trim()
+                            .to_string(),
+                    );
+                }
+                None
+            } else {
+                Some(branch)
+            }
+        } else {
+            None
+        }
+    }
+
+    // Counts dirty files (modified, staged, untracked).
+    fn get_dirty_count(path: &Path) -> u32 {
+        let output = Command::new("git")
+            .args(["-C", &path.display().to_string(), "status", "--porcelain"])
+            .output()
+            .ok();
+
+        output
+            .map(|o| {
+                String::from_utf8_lossy(&o.stdout)
+                    .lines()
+                    .filter(|l| !l.is_empty())
+                    .count() as u32
+            })
+            .unwrap_or(0)
+    }
+
+    // Gets commits ahead/behind upstream.
+    fn get_ahead_behind(path: &Path) -> (u32, u32) {
+        let upstream = Command::new("git")
+            .args([
+                "-C",
+                &path.display().to_string(),
+                "rev-parse",
+                "--abbrev-ref",
+                "@{upstream}",
+            ])
+            .output();
+
+        if upstream.map(|o| !o.status.success()).unwrap_or(true) {
+            return (0, 0);
+        }
+
+        let output = Command::new("git")
+            .args([
+                "-C",
+                &path.display().to_string(),
+                "rev-list",
+                "--left-right",
+                "--count",
+                "HEAD...@{upstream}",
+            ])
+            .output()
+            .ok();
+
+        output
+            .and_then(|o| {
+                if o.status.success() {
+                    let s = String::from_utf8_lossy(&o.stdout);
+                    let parts: Vec<&str> = s.split_whitespace().collect();
+                    if parts.len() == 2 {
+                        let ahead = parts[0].parse().unwrap_or(0);
+                        let behind = parts[1].parse().unwrap_or(0);
+                        return Some((ahead, behind));
+                    }
+                }
+                None
+            })
+            .unwrap_or((0, 0))
+    }
+
+    // Returns a formatted badge string for display.
+    // Format: "main +3 ↑2↓1" or just "main" if clean
+    pub fn badge(&self) -> Option<String> {
+        if !self.is_repo {
+            return None;
+        }
+
+        let branch = self.branch.as_ref()?;
+        let mut parts = vec![branch.clone()];
+
+        if self.dirty_count > 0 {
+            parts.push(format!("+{}", self.dirty_count));
+        }
+
+        if self.ahead > 0 || self.behind > 0 {
+            let mut sync = String::new();
+            if self.ahead > 0 {
+                sync.push_str(&format!("↑{}", self.ahead));
+            }
+            if self.behind > 0 {
+                sync.push_str(&format!("↓{}", self.behind));
+            }
+            if !sync.is_empty() {
+                parts.push(sync);
+            }
+        }
+
+        Some(parts.join(" "))
+    }
+}
+
+// The object id of an empty tree, to diff against in a repo with no commits.
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/* Uncommitted work in a repo against HEAD, for reviewing what an agent
+ * changed: the `git diff --stat` summary, the full unified diff, and the
+ * untracked files neither of them lists. */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GitDiff {
+    pub stat: Vec<String>,
+    pub patch: Vec<String>,
+    pub untracked: Vec<String>,
+}
+
+impl GitDiff {
+    /* Reads the diff of the work tree against HEAD, or against the empty
+     * tree in a repo with no commits yet. None outside a repo. */
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let base = match git_lines(path, &["rev-parse", "--verify", "-q", "HEAD"]) {
+            Some(_) => "HEAD",
+            None => EMPTY_TREE,
+        };
+        let stat = git_lines(path, &["diff", base, "--stat"])?;
+        let patch = git_lines(path, &["diff", base])?;
+        let untracked =
+            git_lines(path, &["ls-files", "--others", "--exclude-standard"]).unwrap_or_default();
+        Some(Self {
+            stat,
+            patch,
+            untracked,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patch.is_empty() && self.untracked.is_empty()
+    }
+}
+
+/* Stages every change, untracked files included, and commits it with the
+ * message. Returns the new commit's short hash. */
+pub fn commit_all(path: &Path, message: &str) -> Result<String> {
+    run_git(path, &["add", "-A"])?;
+    run_git(path, &["commit", "-q", "-m", message])?;
+    Ok(git_lines(path, &["rev-parse", "--short", "HEAD"])
+        .and_then(|lines| lines.into_iter().next())
+        .unwrap_or_default())
+}
+
+// Stashes every change, untracked files included; an empty message keeps git's.
+pub fn stash_all(path: &Path, message: &str) -> Result<()> {
+    let mut args = vec!["stash", "push", "-q", "--include-untracked"];
+    if !message.is_empty() {
+        args.extend(["-m", message]);
+    }
+    run_git(path, &args)
+}
+
+// Runs a git command in the repo, failing with its stderr.
+fn run_git(path: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .output()?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+// Stdout lines of a git command run in the repo, None when it fails.
+fn git_lines(path: &Path, args: &[&str]) -> Option<Vec<String>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["-c", "user.name=trex", "-c", "user.email=trex@localhost"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn diff_lists_changes_against_head_and_untracked_files() {
+        let root = std::env::temp_dir().join(format!("trex-git-diff-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        git(&root, &["init", "-q"]);
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+        git(&root, &["add", "main.rs"]);
+        git(&root, &["commit", "-qm", "init"]);
+        assert_eq!(
+            GitDiff::for_path(&root).map(|diff| diff.is_empty()),
+            Some(true)
+        );
+
+        std::fs::write(root.join("main.rs"), "fn main() {\n    run();\n}\n").unwrap();
+        std::fs::write(root.join("notes.md"), "todo\n").unwrap();
+        let diff = GitDiff::for_path(&root).unwrap();
+        assert!(diff.stat[0].contains("main.rs"));
+        assert!(diff.patch.contains(&"+    run();".to_string()));
+        assert_eq!(diff.untracked, ["notes.md"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(GitDiff::for_path(&root), None);
+    }
+
+    #[test]
+    fn commit_all_and_stash_all_leave_the_tree_clean() {
+        let root = std::env::temp_dir().join(format!("trex-git-commit-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        git(&root, &["init", "-q"]);
+        git(&root, &["config", "user.name", "trex"]);
+        git(&root, &["config", "user.email", "trex@localhost"]);
+
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+        let hash = commit_all(&root, "checkpoint").unwrap();
+        assert!(!hash.is_empty());
+        assert_eq!(GitStatus::for_path(&root).dirty_count, 0);
+        assert!(commit_all(&root, "nothing").is_err());
+
+        std::fs::write(root.join("notes.md"), "todo\n").unwrap();
+        stash_all(&root, "").unwrap();
+        assert_eq!(GitStatus::for_path(&root).dirty_count, 0);
+        assert!(!root.join("notes.md").exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,268 @@
+use crate::tmux::{ActivityLevel, TmuxSession};
+
+/// Health score from 0-100 based on multiple factors
+#[derive(Debug, Clone, Copy)]
+pub struct HealthScore {
+    pub score: u8,
+    pub penalties: HealthPenalties,
+}
+
+/// Points subtracted from 100 for each factor, kept so the UI can explain a score.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HealthPenalties {
+    pub cpu: u8,
+    pub mem: u8,
+    pub activity: u8,
+    pub multiplex: u8,
+}
+
+/// Maximum penalty per factor. Each factor's tiers scale with its weight,
+/// so the defaults reproduce the original 40/40/20 scoring for sessions
+/// that are not heavily multiplexed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthWeights {
+    pub cpu: u8,
+    pub mem: u8,
+    pub activity: u8,
+    pub multiplex: u8,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            cpu: 40,
+            mem: 40,
+            activity: 20,
+            multiplex: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HealthLevel {
+    Healthy,  // 70-100
+    Warning,  // 40-69
+    Critical, // 0-39
+}
+
+impl HealthLevel {
+    // Lowercase name for plain-text and JSON output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HealthLevel::Healthy => "healthy",
+            HealthLevel::Warning => "warning",
+            HealthLevel::Critical => "critical",
+        }
+    }
+}
+
+impl HealthScore {
+    /// Calculate health score for a session using the default weights
+    pub fn calculate(session: &TmuxSession) -> Self {
+        Self::calculate_with(session, &HealthWeights::default())
+    }
+
+    /// Calculate health score for a session using custom weights
+    pub fn calculate_with(session: &TmuxSession, weights: &HealthWeights) -> Self {
+        // CPU penalty (quarters of the CPU weight)
+        let cpu_quarters: u32 = if let Some(ref stats) = session.stats {
+            if stats.cpu_percent > 200.0 {
+                4
+            } else if stats.cpu_percent > 150.0 {
+                3
+            } else if stats.cpu_percent > 100.0 {
+                2
+            } else if stats.cpu_percent > 50.0 {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        };
+
+        // Memory penalty (quarters of the memory weight)
+        let mem_quarters: u32 = if let Some(ref stats) = session.stats {
+            if stats.mem_mb > 8192 {
+                4
+            } else if stats.mem_mb > 4096 {
+                3
+            } else if stats.mem_mb > 2048 {
+                2
+            } else if stats.mem_mb > 1024 {
+                1
+            } else {
+                0
+            }
+        } else {
+            0
+        };
+
+        // Activity penalty (quarters of the activity weight)
+        let activity_quarters: u32 = match session.activity_level() {
+            Some(ActivityLevel::Dormant) => 4,
+            Some(ActivityLevel::Idle) => 2,
+            Some(ActivityLevel::Active) => 0,
+            None => 1,
+        };
+
+        // Multiplexing penalty: many panes, plus one quarter per extra client
+        let pane_quarters: u32 = if session.panes > 32 {
+            4
+        } else if session.panes > 16 {
+            3
+        } else if session.panes > 12 {
+            2
+        } else if session.panes > 8 {
+            1
+        } else {
+            0
+        };
+        let multiplex_quarters = (pane_quarters + session.clients.saturating_sub(1)).min(4);
+
+        let penalties = HealthPenalties {
+            cpu: quarter_penalty(weights.cpu, cpu_quarters),
+            mem: quarter_penalty(weights.mem, mem_quarters),
+            activity: quarter_penalty(weights.activity, activity_quarters),
+            multiplex: quarter_penalty(weights.multiplex, multiplex_quarters),
+        };
+
+        let total_penalty = penalties
+            .cpu
+            .saturating_add(penalties.mem)
+            .saturating_add(penalties.activity)
+            .saturating_add(penalties.multiplex);
+        let score = 100u8.saturating_sub(total_penalty);
+
+        Self { score, penalties }
+    }
+
+    pub fn level(&self) -> HealthLevel {
+        if self.score >= HEALTHY_SCORE {
+            HealthLevel::Healthy
+        } else if self.score >= WARNING_SCORE {
+            HealthLevel::Warning
+        } else {
+            HealthLevel::Critical
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self.level() {
+            HealthLevel::Healthy => "🟢",
+            HealthLevel::Warning => "🟡",
+            HealthLevel::Critical => "🔴",
+        }
+    }
+}
+
+// Lowest scores still counted healthy, then warning; anything lower is critical.
+pub const HEALTHY_SCORE: u8 = 70;
+pub const WARNING_SCORE: u8 = 40;
+
+fn quarter_penalty(weight: u8, quarters: u32) -> u8 {
+    (weight as u32 * quarters / 4) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysinfo::SessionStats;
+    use std::path::PathBuf;
+
+    fn mock_session(cpu: f64, mem_mb: u64, activity: Option<ActivityLevel>) -> TmuxSession {
+        TmuxSession {
+            id: String::new(),
+            name: "test".to_string(),
+            attached: false,
+            clients: 0,
+            windows: 1,
+            panes: 1,
+            size_constrained: false,
+            path: Some(PathBuf::from("/tmp")),
+            created: None,
+            last_activity: if activity.is_some() { Some(0) } else { None },
+            tags: Vec::new(),
+            note: None,
+            alerts: Default::default(),
+            git_status: None,
+            stats: Some(SessionStats {
+                cpu_percent: cpu,
+                mem_mb,
+                mem_percent: 0.0,
+                io_read_bps: 0,
+                io_write_bps: 0,
+            }),
+            cpu_history: Vec::new(),
+            mem_history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_healthy_session() {
+        let session = mock_session(10.0, 500, Some(ActivityLevel::Active));
+        let health = HealthScore::calculate(&session);
+        assert_eq!(health.level(), HealthLevel::Healthy);
+        assert!(health.score >= 70);
+    }
+
+    #[test]
+    fn test_warning_session() {
+        let session = mock_session(150.0, 2500, Some(ActivityLevel::Idle));
+        let health = HealthScore::calculate(&session);
+        assert_eq!(health.level(), HealthLevel::Warning);
+    }
+
+    #[test]
+    fn test_critical_session() {
+        let session = mock_session(300.0, 9000, Some(ActivityLevel::Dormant));
+        let health = HealthScore::calculate(&session);
+        assert_eq!(health.level(), HealthLevel::Critical);
+    }
+
+    #[test]
+    fn test_default_penalties_are_reported() {
+        let session = mock_session(160.0, 1500, Some(ActivityLevel::Dormant));
+        let health = HealthScore::calculate(&session);
+        assert_eq!(
+            health.penalties,
+            HealthPenalties {
+                cpu: 30,
+                mem: 10,
+                activity: 20,
+                multiplex: 0,
+            }
+        );
+        assert_eq!(health.score, 40);
+    }
+
+    #[test]
+    fn test_custom_weights_scale_penalties() {
+        let session = mock_session(300.0, 9000, Some(ActivityLevel::Dormant));
+        let weights = HealthWeights {
+            cpu: 20,
+            mem: 0,
+            activity: 8,
+            multiplex: 0,
+        };
+        let health = HealthScore::calculate_with(&session, &weights);
+        assert_eq!(health.penalties.cpu, 20);
+        assert_eq!(health.penalties.mem, 0);
+        assert_eq!(health.penalties.activity, 8);
+        assert_eq!(health.score, 72);
+    }
+
+    #[test]
+    fn test_multiplexed_sessions_are_penalized() {
+        let mut session = mock_session(0.0, 0, None);
+        session.panes = 20;
+        session.clients = 2;
+        let health = HealthScore::calculate(&session);
+        assert_eq!(health.penalties.multiplex, 10);
+
+        session.panes = 4;
+        session.clients = 1;
+        let health = HealthScore::calculate(&session);
+        assert_eq!(health.penalties.multiplex, 0);
+    }
+}
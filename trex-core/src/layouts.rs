@@ -0,0 +1,95 @@
+use crate::tmux::parser::layout_pane_count;
+use std::path::PathBuf;
+
+// A window layout saved under a name, to arrange other sessions the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedLayout {
+    pub name: String,
+    pub layout: String, // a `#{window_layout}` string, as `select-layout` takes it
+}
+
+impl SavedLayout {
+    pub fn panes(&self) -> usize {
+        layout_pane_count(&self.layout)
+    }
+}
+
+/* Named layouts kept in `layouts.tsv` under the trex cache directory, one
+ * `layout<TAB>name` line each, sorted by name. The layout comes first since
+ * it never contains a tab, while a name may contain anything. */
+
+// The saved layouts; empty if none were saved or the file is unreadable.
+pub fn load_layouts() -> Vec<SavedLayout> {
+    layouts_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|text| parse(&text))
+        .unwrap_or_default()
+}
+
+pub fn save_layouts(layouts: &[SavedLayout]) -> std::io::Result<()> {
+    let Some(path) = layouts_path() else {
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, render(layouts))
+}
+
+// Adds a layout, replacing any saved under the same name, and keeps the list sorted.
+pub fn upsert_layout(layouts: &mut Vec<SavedLayout>, name: &str, layout: &str) {
+    let name = name.trim();
+    layouts.retain(|saved| saved.name != name);
+    layouts.push(SavedLayout {
+        name: name.to_string(),
+        layout: layout.to_string(),
+    });
+    layouts.sort_by(|a, b| a.name.cmp(&b.name));
+}
+
+fn parse(text: &str) -> Vec<SavedLayout> {
+    let mut layouts: Vec<SavedLayout> = text
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter(|(layout, name)| layout_pane_count(layout) > 0 && !name.trim().is_empty())
+        .map(|(layout, name)| SavedLayout {
+            name: name.to_string(),
+            layout: layout.to_string(),
+        })
+        .collect();
+    layouts.sort_by(|a, b| a.name.cmp(&b.name));
+    layouts
+}
+
+fn render(layouts: &[SavedLayout]) -> String {
+    layouts
+        .iter()
+        .map(|saved| format!("{}\t{}\n", saved.layout, saved.name))
+        .collect()
+}
+
+fn layouts_path() -> Option<PathBuf> {
+    crate::logging::cache_dir().map(|dir| dir.join("layouts.tsv"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPLIT: &str = "5e8b,159x40,0,0{79x40,0,0,2,79x40,80,0,3}";
+
+    #[test]
+    fn layouts_round_trip_sorted_by_name() {
+        let mut layouts = parse(&format!("{}\tside by side\nnot a layout\tbroken\n", SPLIT));
+        assert_eq!(layouts.len(), 1);
+        assert_eq!(layouts[0].panes(), 2);
+
+        upsert_layout(&mut layouts, " editor ", "b25d,80x24,0,0,1");
+        upsert_layout(&mut layouts, "side by side", "b25d,80x24,0,0,7");
+        let names: Vec<&str> = layouts.iter().map(|saved| saved.name.as_str()).collect();
+        assert_eq!(names, ["editor", "side by side"]);
+        assert_eq!(layouts[1].panes(), 1);
+
+        assert_eq!(parse(&render(&layouts)), layouts);
+    }
+}
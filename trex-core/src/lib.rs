@@ -0,0 +1,28 @@
+/* The tmux session model shared by the trex TUI and other tools (status
+ * bars, editor plugins): listing and driving tmux, per-session stats from
+ * /proc, AI agent detection (in tmux and as systemd user services), git
+ * status and GitHub PR/CI state, health scoring, saved window layouts, the
+ * activity timeline and attach history, project directory discovery, and
+ * per-project configs, dev environments, and containers, and whether the
+ * machine runs on battery. No terminal UI
+ * code lives here. */
+pub mod attach_log;
+pub mod ci;
+pub mod compose;
+pub mod container;
+pub mod devenv;
+pub mod directory;
+pub mod directory_cache;
+pub mod git;
+pub mod health;
+pub mod layouts;
+pub mod logging;
+pub mod power;
+pub mod process;
+pub mod project;
+pub mod recent_commands;
+pub mod service;
+pub mod sysinfo;
+pub mod template;
+pub mod timeline;
+pub mod tmux;
@@ -0,0 +1,305 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Size at which the log moves to `trex.log.1`, replacing the previous one.
+pub const MAX_LOG_BYTES: u64 = 4 * 1024 * 1024;
+
+// How often per-frame render timings are summarized at trace level.
+pub const FRAME_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+/* Debug logging to `~/.cache/trex/trex.log` (or `$XDG_CACHE_HOME/trex`).
+ * Never writes to stdout or stderr, since the TUI owns the terminal.
+ * Enabled by `--debug` (debug level) or `TREX_LOG=<level>`, where level is
+ * one of error, warn, info, debug, or trace. The file is rotated once it
+ * reaches MAX_LOG_BYTES, so at most two files are kept. Returns the log
+ * path when logging was enabled. */
+pub fn init(debug_flag: bool) -> Option<PathBuf> {
+    let level = std::env::var("TREX_LOG")
+        .ok()
+        .and_then(|value| parse_level(&value))
+        .or(debug_flag.then_some(LevelFilter::Debug))?;
+
+    let path = log_path()?;
+    fs::create_dir_all(path.parent()?).ok()?;
+    let file = LogFile::open(path.clone()).ok()?;
+
+    // The logger lives for the rest of the process
+    let logger = Box::leak(Box::new(FileLogger {
+        file: Mutex::new(file),
+    }));
+    log::set_logger(logger).ok()?;
+    log::set_max_level(level);
+    log::info!("trex {} logging at {}", env!("CARGO_PKG_VERSION"), level);
+    Some(path)
+}
+
+pub fn log_path() -> Option<PathBuf> {
+    cache_dir().map(|dir| dir.join("trex.log"))
+}
+
+// The trex cache directory: `$XDG_CACHE_HOME/trex`, else `~/.cache/trex`.
+pub fn cache_dir() -> Option<PathBuf> {
+    cache_dir_from_env(
+        std::env::var("XDG_CACHE_HOME").ok().as_deref(),
+        std::env::var("HOME").ok().as_deref(),
+    )
+}
+
+fn cache_dir_from_env(xdg_cache_home: Option<&str>, home: Option<&str>) -> Option<PathBuf> {
+    if let Some(xdg_cache_home) = xdg_cache_home
+        && !xdg_cache_home.trim().is_empty()
+    {
+        return Some(PathBuf::from(xdg_cache_home).join("trex"));
+    }
+
+    home.filter(|home| !home.trim().is_empty())
+        .map(|home| PathBuf::from(home).join(".cache/trex"))
+}
+
+// Parses a TREX_LOG value; unknown non-empty values mean debug.
+fn parse_level(value: &str) -> Option<LevelFilter> {
+    match value.trim().to_lowercase().as_str() {
+        "" | "0" | "off" => None,
+        "error" => Some(LevelFilter::Error),
+        "warn" => Some(LevelFilter::Warn),
+        "info" => Some(LevelFilter::Info),
+        "trace" => Some(LevelFilter::Trace),
+        _ => Some(LevelFilter::Debug),
+    }
+}
+
+// The previous log, kept after rotation: `trex.log.1` next to `trex.log`.
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+// The open log file and its size, for rotation.
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    len: u64,
+}
+
+impl LogFile {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self { path, file, len })
+    }
+
+    // Appends a line, first rotating the file if it has reached the cap.
+    fn write_line(&mut self, line: &str) {
+        if self.len >= MAX_LOG_BYTES {
+            self.rotate();
+        }
+        if writeln!(self.file, "{}", line).is_ok() {
+            self.len += line.len() as u64 + 1;
+        }
+    }
+
+    // Moves the file to `trex.log.1` and starts a new one. On failure the
+    // log is truncated instead, so it still can't grow without bound.
+    fn rotate(&mut self) {
+        let _ = self.file.flush();
+        let _ = fs::rename(&self.path, rotated_path(&self.path));
+        if let Ok(file) = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)
+        {
+            self.file = file;
+        }
+        self.len = 0;
+    }
+}
+
+struct FileLogger {
+    file: Mutex<LogFile>,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let line = format!(
+            "{}.{:03} {:5} {}: {}",
+            now.as_secs(),
+            now.subsec_millis(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if let Ok(mut file) = self.file.lock() {
+            file.write_line(&line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.file.flush();
+        }
+    }
+}
+
+// Logs how long a block took when dropped, e.g. a tmux call or a refresh.
+pub struct Timer {
+    label: String,
+    level: Level,
+    start: Instant,
+}
+
+// Starts a debug-level timer. Building the label is skipped when disabled.
+pub fn timed(label: impl FnOnce() -> String) -> Option<Timer> {
+    timed_at(Level::Debug, label)
+}
+
+pub fn timed_at(level: Level, label: impl FnOnce() -> String) -> Option<Timer> {
+    log::log_enabled!(level).then(|| Timer {
+        label: label(),
+        level,
+        start: Instant::now(),
+    })
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        log::log!(
+            self.level,
+            "{} took {:.1}ms",
+            self.label,
+            self.start.elapsed().as_secs_f64() * 1000.0
+        );
+    }
+}
+
+/* Render timings, summarized at trace level every FRAME_LOG_INTERVAL
+ * rather than logged per frame, since the TUI draws up to 10 times a
+ * second. */
+#[derive(Debug)]
+pub struct FrameLog {
+    since: Instant,
+    frames: u32,
+    total: Duration,
+    slowest: Duration,
+}
+
+impl FrameLog {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            since: now,
+            frames: 0,
+            total: Duration::ZERO,
+            slowest: Duration::ZERO,
+        }
+    }
+
+    // Records a frame, logging the summary once the interval has passed.
+    pub fn record(&mut self, elapsed: Duration, now: Instant) {
+        if !log::log_enabled!(Level::Trace) {
+            return;
+        }
+        if let Some(summary) = self.summarize(elapsed, now) {
+            log::trace!("{}", summary);
+        }
+    }
+
+    fn summarize(&mut self, elapsed: Duration, now: Instant) -> Option<String> {
+        self.frames += 1;
+        self.total += elapsed;
+        self.slowest = self.slowest.max(elapsed);
+        if now.duration_since(self.since) < FRAME_LOG_INTERVAL {
+            return None;
+        }
+
+        let summary = format!(
+            "render {} frames in {:.1}s, avg {:.1}ms, max {:.1}ms",
+            self.frames,
+            now.duration_since(self.since).as_secs_f64(),
+            self.total.as_secs_f64() * 1000.0 / f64::from(self.frames),
+            self.slowest.as_secs_f64() * 1000.0
+        );
+        *self = Self::new(now);
+        Some(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_cache_dir() {
+        assert_eq!(
+            cache_dir_from_env(Some("/tmp/cache"), Some("/home/me")),
+            Some(PathBuf::from("/tmp/cache/trex"))
+        );
+        assert_eq!(
+            cache_dir_from_env(None, Some("/home/me")),
+            Some(PathBuf::from("/home/me/.cache/trex"))
+        );
+        assert_eq!(cache_dir_from_env(Some(" "), None), None);
+    }
+
+    #[test]
+    fn parses_log_levels() {
+        assert_eq!(parse_level("trace"), Some(LevelFilter::Trace));
+        assert_eq!(parse_level("WARN"), Some(LevelFilter::Warn));
+        assert_eq!(parse_level("1"), Some(LevelFilter::Debug));
+        assert_eq!(parse_level("off"), None);
+        assert_eq!(parse_level(""), None);
+    }
+
+    #[test]
+    fn rotates_the_log_at_the_size_cap() {
+        let root = std::env::temp_dir().join(format!("trex-log-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("trex.log");
+        fs::write(&path, vec![b'x'; MAX_LOG_BYTES as usize]).unwrap();
+
+        let mut log = LogFile::open(path.clone()).unwrap();
+        log.write_line("after rotation");
+        log.write_line("second line");
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "after rotation\nsecond line\n"
+        );
+        assert_eq!(
+            fs::metadata(root.join("trex.log.1")).unwrap().len(),
+            MAX_LOG_BYTES
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn summarizes_frames_once_per_interval() {
+        let start = Instant::now();
+        let mut frames = FrameLog::new(start);
+        let ms = Duration::from_millis;
+
+        assert_eq!(frames.summarize(ms(2), start + ms(100)), None);
+        assert_eq!(frames.summarize(ms(6), start + ms(200)), None);
+        assert_eq!(
+            frames.summarize(ms(4), start + FRAME_LOG_INTERVAL),
+            Some("render 3 frames in 10.0s, avg 4.0ms, max 6.0ms".to_string())
+        );
+        // The next summary covers only the frames since
+        assert_eq!(frames.summarize(ms(1), start + FRAME_LOG_INTERVAL), None);
+    }
+}
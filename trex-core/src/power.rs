@@ -0,0 +1,77 @@
+use std::path::Path;
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+/* Whether the machine runs on mains power or its battery, from the
+ * supplies the kernel lists under /sys/class/power_supply. Machines
+ * without a battery, and anything unreadable, count as on AC. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerSource {
+    Ac,
+    Battery,
+}
+
+pub fn power_source() -> PowerSource {
+    power_source_in(Path::new(POWER_SUPPLY_DIR))
+}
+
+/* An online mains or USB supply means AC. Otherwise a system battery
+ * (not a peripheral's, which report `scope` Device) that is discharging
+ * means battery. */
+fn power_source_in(root: &Path) -> PowerSource {
+    let read = |dir: &Path, name: &str| {
+        std::fs::read_to_string(dir.join(name))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+    let mut discharging = false;
+    for entry in std::fs::read_dir(root).into_iter().flatten().flatten() {
+        let dir = entry.path();
+        match read(&dir, "type").as_str() {
+            "Mains" | "USB" if read(&dir, "online") == "1" => return PowerSource::Ac,
+            "Battery" if read(&dir, "scope") != "Device" => {
+                discharging |= read(&dir, "status") == "Discharging";
+            }
+            _ => {}
+        }
+    }
+    if discharging {
+        PowerSource::Battery
+    } else {
+        PowerSource::Ac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_power_source_from_sysfs() {
+        let root = std::env::temp_dir().join(format!("trex-power-{}", std::process::id()));
+        let supply = |name: &str, files: &[(&str, &str)]| {
+            let dir = root.join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            for (file, value) in files {
+                std::fs::write(dir.join(file), format!("{}\n", value)).unwrap();
+            }
+        };
+        assert_eq!(power_source_in(&root), PowerSource::Ac);
+
+        supply("BAT0", &[("type", "Battery"), ("status", "Discharging")]);
+        supply("AC", &[("type", "Mains"), ("online", "0")]);
+        supply(
+            "hidpp_battery_0",
+            &[
+                ("type", "Battery"),
+                ("scope", "Device"),
+                ("status", "Charging"),
+            ],
+        );
+        assert_eq!(power_source_in(&root), PowerSource::Battery);
+
+        supply("AC", &[("online", "1")]);
+        assert_eq!(power_source_in(&root), PowerSource::Ac);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::template::{SessionTemplate, TemplateLayout, TemplatePane, TemplateWindow};
+
+/* A `.trex.toml` checked into a project directory, describing the session
+ * trex should create there:
+ *
+ *     name = "api"
+ *     agent = "claude --continue"
+ *
+ *     [[windows]]
+ *     command = "nvim"
+ *
+ *     [[windows]]
+ *     name = "server"
+ *     command = "cargo watch -x run"
+ *
+ * The first window's command runs in the session's first window, which
+ * tmux names after it; later windows open in order with their names. */
+pub const PROJECT_FILE: &str = ".trex.toml";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectConfig {
+    pub name: Option<String>,  // session name; the directory's name when unset
+    pub agent: Option<String>, // command the agent launcher starts
+    pub windows: Vec<TemplateWindow>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawProject {
+    name: Option<String>,
+    agent: Option<String>,
+    windows: Option<Vec<RawWindow>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawWindow {
+    name: Option<String>,
+    command: Option<String>,
+}
+
+impl ProjectConfig {
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join(PROJECT_FILE)
+    }
+
+    pub fn exists(dir: &Path) -> bool {
+        Self::path(dir).is_file()
+    }
+
+    /* The directory's project config: None without a `.trex.toml`, an
+     * error naming the file when it can't be read or parsed. */
+    pub fn load(dir: &Path) -> Result<Option<Self>, String> {
+        let path = Self::path(dir);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text)
+                .map(Some)
+                .map_err(|error| format!("{}: {}", path.display(), error)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(format!("{}: {}", path.display(), error)),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let raw: RawProject = toml::from_str(text).map_err(|error| error.message().to_string())?;
+        let trimmed = |value: Option<String>| {
+            value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let windows = raw
+            .windows
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, window)| TemplateWindow {
+                name: trimmed(window.name).unwrap_or_else(|| format!("{}", idx + 1)),
+                command: window.command.unwrap_or_default().trim().to_string(),
+            })
+            .collect();
+        Ok(Self {
+            name: trimmed(raw.name),
+            agent: trimmed(raw.agent),
+            windows,
+        })
+    }
+
+    /* The session the config describes, as a template: its first window's
+     * command (the agent's without windows, else a shell) and the rest as
+     * extra windows. */
+    pub fn template(&self) -> SessionTemplate {
+        let mut windows = self.windows.iter().cloned();
+        let first = match windows.next() {
+            Some(window) => window.command,
+            None => self.agent.clone().unwrap_or_default(),
+        };
+        SessionTemplate {
+            id: SessionTemplate::PROJECT_CONFIG_ID.to_string(),
+            name: PROJECT_FILE.to_string(),
+            description: "The project's own session".to_string(),
+            layout: TemplateLayout::Single,
+            panes: vec![TemplatePane { command: first }],
+            focus_pane: 0,
+            windows: windows.collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_project_session() {
+        let config = ProjectConfig::parse(
+            "name = \" api \"\nagent = \"claude\"\n\n[[windows]]\ncommand = \"nvim\"\n\n[[windows]]\nname = \"server\"\ncommand = \"make run\"\n\n[[windows]]\n",
+        )
+        .unwrap();
+        assert_eq!(config.name.as_deref(), Some("api"));
+        let template = config.template();
+        assert_eq!(template.panes[0].command, "nvim");
+        assert_eq!(
+            template.windows,
+            [
+                TemplateWindow {
+                    name: "server".to_string(),
+                    command: "make run".to_string()
+                },
+                TemplateWindow {
+                    name: "3".to_string(),
+                    command: String::new()
+                }
+            ]
+        );
+
+        // Without windows the agent runs in the only one
+        let config = ProjectConfig::parse("agent = \"codex\"\n").unwrap();
+        assert_eq!(config.template().panes[0].command, "codex");
+        assert!(ProjectConfig::parse("windws = []\n").is_err());
+    }
+}
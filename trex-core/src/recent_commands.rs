@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+/* Start commands given to new sessions, most recent first, kept in
+ * `recent-commands` under the trex cache directory (one per line) so the
+ * naming screen can offer them again. */
+pub const MAX_RECENT_COMMANDS: usize = 10;
+
+// The remembered commands; empty if none were saved or the file is unreadable.
+pub fn load_recent_commands() -> Vec<String> {
+    recent_commands_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|text| parse(&text))
+        .unwrap_or_default()
+}
+
+pub fn save_recent_commands(commands: &[String]) -> std::io::Result<()> {
+    let Some(path) = recent_commands_path() else {
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let text: String = commands.iter().map(|cmd| format!("{}\n", cmd)).collect();
+    std::fs::write(path, text)
+}
+
+// Moves `command` to the front, dropping duplicates and the oldest past the limit.
+pub fn push_recent_command(commands: &mut Vec<String>, command: &str) {
+    let command = command.trim();
+    if command.is_empty() {
+        return;
+    }
+    commands.retain(|existing| existing != command);
+    commands.insert(0, command.to_string());
+    commands.truncate(MAX_RECENT_COMMANDS);
+}
+
+fn parse(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .take(MAX_RECENT_COMMANDS)
+        .map(str::to_string)
+        .collect()
+}
+
+fn recent_commands_path() -> Option<PathBuf> {
+    crate::logging::cache_dir().map(|dir| dir.join("recent-commands"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_commands_stay_unique_and_bounded() {
+        let mut commands = parse("nvim\n\n  claude \n");
+        assert_eq!(commands, vec!["nvim", "claude"]);
+
+        push_recent_command(&mut commands, "claude");
+        push_recent_command(&mut commands, "  ");
+        assert_eq!(commands, vec!["claude", "nvim"]);
+
+        for n in 0..MAX_RECENT_COMMANDS {
+            push_recent_command(&mut commands, &format!("make {}", n));
+        }
+        assert_eq!(commands.len(), MAX_RECENT_COMMANDS);
+        assert_eq!(commands[0], "make 9");
+    }
+}
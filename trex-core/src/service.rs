@@ -0,0 +1,205 @@
+use anyhow::{Context, Result, bail};
+use std::process::Command;
+
+/* Agents daemonized as systemd user services instead of run in tmux. They
+ * are found by the unit name patterns in the `[services]` config section
+ * (globs such as `agent-*.service`, as `systemctl` matches them), both
+ * loaded units and installed unit files that were never started. */
+
+// A systemd user unit matching a configured pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceUnit {
+    pub name: String,
+    pub active: String, // `active`, `inactive`, `failed`, ...
+    pub sub: String,    // `running`, `dead`, `exited`, ...
+    pub description: String,
+}
+
+impl ServiceUnit {
+    pub fn is_running(&self) -> bool {
+        self.active == "active"
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.active == "failed"
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ServiceAction {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "start",
+            ServiceAction::Stop => "stop",
+            ServiceAction::Restart => "restart",
+        }
+    }
+}
+
+pub fn systemctl_available() -> bool {
+    which::which("systemctl").is_ok()
+}
+
+// The user units matching any of `patterns`, sorted by name; none when no pattern is set.
+pub fn list_service_units(patterns: &[String]) -> Result<Vec<ServiceUnit>> {
+    if patterns.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !systemctl_available() {
+        bail!("systemctl not found");
+    }
+    let listing = ["--type=service", "--plain", "--no-legend", "--no-pager"];
+    let loaded = systemctl_output(&[&["list-units", "--all"], &listing[..]].concat(), patterns)?;
+    let files = systemctl_output(&[&["list-unit-files"], &listing[..]].concat(), patterns)?;
+    Ok(merge_units(parse_units(&loaded), &parse_unit_files(&files)))
+}
+
+// Starts, stops, or restarts a user unit, failing with systemctl's message.
+pub fn control_service(unit: &str, action: ServiceAction) -> Result<()> {
+    let output = Command::new("systemctl")
+        .args(["--user", action.verb(), unit])
+        .output()
+        .with_context(|| format!("Failed to {} {}", action.verb(), unit))?;
+    if !output.status.success() {
+        bail!(
+            "Failed to {} {}: {}",
+            action.verb(),
+            unit,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/* The `systemctl status` report for a unit with its last `lines` journal
+ * lines. systemctl exits non-zero for stopped units, so only a failure to
+ * run it is an error. */
+pub fn service_status(unit: &str, lines: usize) -> Result<Vec<String>> {
+    let output = Command::new("systemctl")
+        .args(["--user", "status", "--no-pager"])
+        .arg(format!("--lines={}", lines))
+        .arg(unit)
+        .output()
+        .with_context(|| format!("Failed to get the status of {}", unit))?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+fn systemctl_output(args: &[&str], patterns: &[String]) -> Result<String> {
+    let output = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .args(patterns)
+        .output()
+        .context("Failed to run systemctl")?;
+    if !output.status.success() {
+        bail!(
+            "systemctl {}: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/* Parses `systemctl list-units --plain --no-legend` lines:
+ * `UNIT LOAD ACTIVE SUB DESCRIPTION...`. Some versions still mark failed
+ * units with a leading `●`. */
+fn parse_units(output: &str) -> Vec<ServiceUnit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start().trim_start_matches('●').trim_start();
+            let mut fields = line.split_whitespace();
+            let (name, _load, active, sub) = (
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+            );
+            Some(ServiceUnit {
+                name: name.to_string(),
+                active: active.to_string(),
+                sub: sub.to_string(),
+                description: fields.collect::<Vec<_>>().join(" "),
+            })
+        })
+        .collect()
+}
+
+/* Parses `systemctl list-unit-files --plain --no-legend` lines into unit
+ * names, skipping templates (`agent@.service`), which can't be started
+ * without an instance name. */
+fn parse_unit_files(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|name| !name.contains("@."))
+        .map(str::to_string)
+        .collect()
+}
+
+// Loaded units plus the unit files that aren't loaded, shown as stopped.
+fn merge_units(mut units: Vec<ServiceUnit>, files: &[String]) -> Vec<ServiceUnit> {
+    for name in files {
+        if !units.iter().any(|unit| &unit.name == name) {
+            units.push(ServiceUnit {
+                name: name.clone(),
+                active: "inactive".to_string(),
+                sub: "dead".to_string(),
+                description: String::new(),
+            });
+        }
+    }
+    units.sort_by(|a, b| a.name.cmp(&b.name));
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_loaded_units_with_unit_files() {
+        let loaded = "\
+agent-review.service loaded active running Claude reviewing PRs
+● agent-docs.service loaded failed failed Docs agent
+";
+        let files = "\
+agent-docs.service enabled enabled
+agent-idle.service disabled enabled
+agent-review.service enabled enabled
+agent@.service static -
+";
+        let units = merge_units(parse_units(loaded), &parse_unit_files(files));
+        let names: Vec<&str> = units.iter().map(|unit| unit.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "agent-docs.service",
+                "agent-idle.service",
+                "agent-review.service"
+            ]
+        );
+
+        assert!(units[0].is_failed());
+        assert_eq!(units[1].active, "inactive");
+        assert!(!units[1].is_running());
+        assert!(units[2].is_running());
+        assert_eq!(units[2].sub, "running");
+        assert_eq!(units[2].description, "Claude reviewing PRs");
+    }
+
+    #[test]
+    fn no_patterns_lists_nothing() {
+        assert_eq!(list_service_units(&[]).unwrap(), Vec::new());
+    }
+}
@@ -0,0 +1,490 @@
+use crate::tmux::commands::query_command;
+use crate::tmux::parser::session_target;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Previous CPU and IO sample for delta-based calculation
+#[derive(Debug, Clone)]
+struct CpuSample {
+    total_ticks: u64, // utime + stime
+    io_read: u64,     // cumulative read_bytes
+    io_write: u64,    // cumulative write_bytes
+    timestamp: Instant,
+    uptime: f64, // system uptime at sample time
+}
+
+/// Global store of previous CPU samples keyed by PID
+static PREV_SAMPLES: Mutex<Option<HashMap<u32, CpuSample>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionStats {
+    pub cpu_percent: f64,
+    pub mem_mb: u64,
+    pub mem_percent: f64,
+    pub io_read_bps: u64,  // disk bytes read per second
+    pub io_write_bps: u64, // disk bytes written per second
+}
+
+/// One process in a session's pane process tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessStats {
+    pub pid: u32,
+    pub window: u32,  // index of the window whose pane started the tree
+    pub depth: usize, // 0 for pane PIDs, +1 per generation below
+    pub name: String,
+    pub cpu_percent: f64,
+    pub mem_mb: u64,
+}
+
+/// CPU and memory summed over the process trees of one window's panes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowStats {
+    pub cpu_percent: f64,
+    pub mem_mb: u64,
+}
+
+/// Get CPU and memory stats for all processes in a tmux session
+pub fn get_session_stats(session_name: &str) -> Result<SessionStats> {
+    get_session_processes(session_name).map(|(stats, _)| stats)
+}
+
+/// Get session totals plus the per-process breakdown they were summed from,
+/// in tree order (each pane PID followed by its descendants)
+pub fn get_session_processes(session_name: &str) -> Result<(SessionStats, Vec<ProcessStats>)> {
+    let tree = get_session_pid_tree(session_name)?;
+
+    if tree.is_empty() {
+        return Ok((SessionStats::default(), Vec::new()));
+    }
+
+    let now = Instant::now();
+    let uptime = get_system_uptime()?;
+
+    let mut prev_map = match PREV_SAMPLES.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let prev = prev_map.get_or_insert_with(HashMap::new);
+
+    let mut total_cpu = 0.0;
+    let mut total_mem_kb = 0u64;
+    let mut total_read_bps = 0.0;
+    let mut total_write_bps = 0.0;
+    let mut processes = Vec::with_capacity(tree.len());
+
+    for &(pid, window, depth) in &tree {
+        if let Ok((ticks, mem)) = get_process_raw(pid) {
+            total_mem_kb += mem;
+            let mut cpu = 0.0;
+            // IO counters are unreadable for other users' processes; count those as idle
+            let (io_read, io_write) = get_process_io(pid).unwrap_or((0, 0));
+
+            // Delta-based CPU and IO: compare with previous sample
+            if let Some(old) = prev.get(&pid) {
+                let dt = uptime - old.uptime;
+                if dt > 0.01 {
+                    let dticks = ticks.saturating_sub(old.total_ticks);
+                    let hertz = 100.0; // USER_HZ
+                    cpu = (dticks as f64 / hertz) / dt * 100.0;
+                    total_cpu += cpu;
+
+                    total_read_bps += io_read.saturating_sub(old.io_read) as f64 / dt;
+                    total_write_bps += io_write.saturating_sub(old.io_write) as f64 / dt;
+                }
+            }
+            // else: first sample for this PID, CPU and IO will be 0 this round
+
+            prev.insert(
+                pid,
+                CpuSample {
+                    total_ticks: ticks,
+                    io_read,
+                    io_write,
+                    timestamp: now,
+                    uptime,
+                },
+            );
+
+            processes.push(ProcessStats {
+                pid,
+                window,
+                depth,
+                name: get_process_name(pid),
+                cpu_percent: cpu,
+                mem_mb: mem / 1024,
+            });
+        }
+    }
+
+    // Clean stale PIDs (not seen in 30 seconds)
+    prev.retain(|_, sample| now.duration_since(sample.timestamp).as_secs() < 30);
+
+    let mem_mb = total_mem_kb / 1024;
+    let total_mem_kb_sys = get_total_memory_kb().unwrap_or(1);
+    let mem_percent = (total_mem_kb as f64 / total_mem_kb_sys as f64) * 100.0;
+
+    let stats = SessionStats {
+        cpu_percent: total_cpu,
+        mem_mb,
+        mem_percent,
+        io_read_bps: total_read_bps as u64,
+        io_write_bps: total_write_bps as u64,
+    };
+
+    Ok((stats, processes))
+}
+
+/// Sum a session's processes per window, keyed by window index
+pub fn window_totals(processes: &[ProcessStats]) -> HashMap<u32, WindowStats> {
+    let mut totals: HashMap<u32, WindowStats> = HashMap::new();
+    for process in processes {
+        let window = totals.entry(process.window).or_default();
+        window.cpu_percent += process.cpu_percent;
+        window.mem_mb += process.mem_mb;
+    }
+    totals
+}
+
+/// Machine-wide load and memory, shown in the overview bar
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SystemStats {
+    pub num_cpus: usize,
+    pub load_avg: [f64; 3], // 1, 5, and 15 minute load averages
+    pub mem_total_kb: u64,
+    pub mem_available_kb: u64,
+}
+
+/// Get load average and available memory from /proc
+pub fn get_system_stats() -> Result<SystemStats> {
+    let loadavg = fs::read_to_string("/proc/loadavg").context("Failed to read loadavg")?;
+    let meminfo = fs::read_to_string("/proc/meminfo").context("Failed to read meminfo")?;
+    let (mem_total_kb, mem_available_kb) = parse_meminfo(&meminfo);
+
+    Ok(SystemStats {
+        num_cpus: get_num_cpus(),
+        load_avg: parse_loadavg(&loadavg).context("Invalid loadavg")?,
+        mem_total_kb,
+        mem_available_kb,
+    })
+}
+
+/// Expresses a session CPU percentage (100% per core) as a share of the whole machine
+pub fn normalize_cpu_percent(cpu_percent: f64, num_cpus: usize) -> f64 {
+    cpu_percent / num_cpus.max(1) as f64
+}
+
+fn parse_loadavg(content: &str) -> Option<[f64; 3]> {
+    let mut fields = content.split_whitespace().map(|field| field.parse().ok());
+    Some([fields.next()??, fields.next()??, fields.next()??])
+}
+
+fn parse_meminfo(content: &str) -> (u64, u64) {
+    let mut total = 0;
+    let mut available = 0;
+
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let key = parts.next();
+        let value = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0);
+        match key {
+            Some("MemTotal:") => total = value,
+            Some("MemAvailable:") => available = value,
+            _ => {}
+        }
+    }
+
+    (total, available)
+}
+
+/// Get all PIDs for processes in a tmux session with their window index
+/// and tree depth. `-s` lists the panes of every window, not just the active one
+fn get_session_pid_tree(session_name: &str) -> Result<Vec<(u32, u32, usize)>> {
+    let output = query_command()
+        .args([
+            "list-panes",
+            "-s",
+            "-t",
+            &session_target(session_name),
+            "-F",
+            "#{window_index} #{pane_pid}",
+        ])
+        .output()
+        .context("Failed to get pane PIDs")?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut pids = Vec::new();
+
+    for (window, pid) in parse_pane_pids(&stdout) {
+        pids.push((pid, window, 0));
+        if let Ok(descendants) = get_descendant_pids(pid, 1) {
+            pids.extend(
+                descendants
+                    .into_iter()
+                    .map(|(child, depth)| (child, window, depth)),
+            );
+        }
+    }
+
+    Ok(pids)
+}
+
+/// Parse `window_index pane_pid` lines, skipping malformed ones
+fn parse_pane_pids(output: &str) -> Vec<(u32, u32)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (window, pid) = line.trim().split_once(' ')?;
+            Some((window.parse().ok()?, pid.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Get all descendant PIDs of a given PID, depth-first, starting at `depth`
+fn get_descendant_pids(pid: u32, depth: usize) -> Result<Vec<(u32, usize)>> {
+    let output = Command::new("pgrep")
+        .args(["-P", &pid.to_string()])
+        .output()?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut descendants = Vec::new();
+
+    for line in stdout.lines() {
+        if let Ok(child_pid) = line.trim().parse::<u32>() {
+            descendants.push((child_pid, depth));
+            if let Ok(grand_children) = get_descendant_pids(child_pid, depth + 1) {
+                descendants.extend(grand_children);
+            }
+        }
+    }
+
+    Ok(descendants)
+}
+
+/// Get raw CPU ticks (utime+stime) and memory (KB) for a process
+fn get_process_raw(pid: u32) -> Result<(u64, u64)> {
+    let stat_path = format!("/proc/{}/stat", pid);
+    let statm_path = format!("/proc/{}/statm", pid);
+
+    let stat_content = fs::read_to_string(&stat_path).context("Failed to read stat")?;
+    let ticks = parse_ticks_from_stat(&stat_content)?;
+
+    let statm_content = fs::read_to_string(&statm_path).context("Failed to read statm")?;
+    let mem_kb = parse_mem_from_statm(&statm_content)?;
+
+    Ok((ticks, mem_kb))
+}
+
+/// Get the command name of a process from /proc/<pid>/comm
+fn get_process_name(pid: u32) -> String {
+    fs::read_to_string(format!("/proc/{}/comm", pid))
+        .map(|comm| comm.trim().to_string())
+        .unwrap_or_else(|_| "?".to_string())
+}
+
+/// Get cumulative disk read and write bytes for a process
+fn get_process_io(pid: u32) -> Result<(u64, u64)> {
+    let content = fs::read_to_string(format!("/proc/{}/io", pid)).context("Failed to read io")?;
+    Ok(parse_io_bytes(&content))
+}
+
+fn parse_io_bytes(content: &str) -> (u64, u64) {
+    let mut read_bytes = 0;
+    let mut write_bytes = 0;
+
+    for line in content.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim().parse().unwrap_or(0);
+            match key {
+                "read_bytes" => read_bytes = value,
+                "write_bytes" => write_bytes = value,
+                _ => {}
+            }
+        }
+    }
+
+    (read_bytes, write_bytes)
+}
+
+/// Formats a bytes-per-second rate compactly, e.g. `1.2M/s`
+pub fn format_rate(bytes_per_sec: u64) -> String {
+    const KB: f64 = 1024.0;
+    let bps = bytes_per_sec as f64;
+
+    if bps >= KB * KB * KB {
+        format!("{:.1}G/s", bps / (KB * KB * KB))
+    } else if bps >= KB * KB {
+        format!("{:.1}M/s", bps / (KB * KB))
+    } else if bps >= KB {
+        format!("{:.0}K/s", bps / KB)
+    } else {
+        format!("{}B/s", bytes_per_sec)
+    }
+}
+
+fn parse_ticks_from_stat(content: &str) -> Result<u64> {
+    let rest = content.split(") ").nth(1).context("Invalid stat format")?;
+
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+
+    if fields.len() < 13 {
+        return Ok(0);
+    }
+
+    let utime: u64 = fields[11].parse().unwrap_or(0);
+    let stime: u64 = fields[12].parse().unwrap_or(0);
+
+    Ok(utime + stime)
+}
+
+fn parse_mem_from_statm(content: &str) -> Result<u64> {
+    let fields: Vec<&str> = content.split_whitespace().collect();
+    if fields.is_empty() {
+        return Ok(0);
+    }
+    // Use RSS (field 1) instead of total VM size (field 0) for actual memory usage
+    let pages: u64 = if fields.len() > 1 {
+        fields[1].parse().unwrap_or(0)
+    } else {
+        fields[0].parse().unwrap_or(0)
+    };
+    let page_size_kb = 4;
+    Ok(pages * page_size_kb)
+}
+
+fn get_system_uptime() -> Result<f64> {
+    let content = fs::read_to_string("/proc/uptime").context("Failed to read uptime")?;
+    let uptime_str = content
+        .split_whitespace()
+        .next()
+        .context("Invalid uptime")?;
+    uptime_str.parse().context("Failed to parse uptime")
+}
+
+fn get_total_memory_kb() -> Result<u64> {
+    let content = fs::read_to_string("/proc/meminfo").context("Failed to read meminfo")?;
+
+    for line in content.lines() {
+        if line.starts_with("MemTotal:") {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() >= 2 {
+                return parts[1].parse().context("Failed to parse MemTotal");
+            }
+        }
+    }
+
+    Ok(8 * 1024 * 1024) // Default to 8GB
+}
+
+pub fn get_num_cpus() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_total_memory() {
+        let mem = get_total_memory_kb();
+        assert!(mem.is_ok());
+        assert!(mem.unwrap() > 0);
+    }
+
+    #[test]
+    fn test_get_system_uptime() {
+        let uptime = get_system_uptime();
+        assert!(uptime.is_ok());
+        assert!(uptime.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_parse_loadavg() {
+        assert_eq!(
+            parse_loadavg("0.52 1.10 2.00 3/812 12345\n"),
+            Some([0.52, 1.10, 2.00])
+        );
+        assert_eq!(parse_loadavg("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        let content = "MemTotal:       16000000 kB\nMemFree:         1000000 kB\nMemAvailable:    8000000 kB\n";
+        assert_eq!(parse_meminfo(content), (16000000, 8000000));
+    }
+
+    #[test]
+    fn test_normalize_cpu_percent() {
+        assert_eq!(normalize_cpu_percent(400.0, 8), 50.0);
+        assert_eq!(normalize_cpu_percent(50.0, 0), 50.0);
+    }
+
+    #[test]
+    fn test_parse_io_bytes() {
+        let content = "rchar: 100\nwchar: 200\nsyscr: 3\nsyscw: 4\nread_bytes: 4096\nwrite_bytes: 8192\ncancelled_write_bytes: 0\n";
+        assert_eq!(parse_io_bytes(content), (4096, 8192));
+        assert_eq!(parse_io_bytes(""), (0, 0));
+    }
+
+    #[test]
+    fn test_parse_pane_pids() {
+        assert_eq!(
+            parse_pane_pids("0 4120\n0 4133\n2 5001\nbad\n"),
+            vec![(0, 4120), (0, 4133), (2, 5001)]
+        );
+    }
+
+    #[test]
+    fn test_window_totals() {
+        let process = |window, cpu_percent, mem_mb| ProcessStats {
+            pid: 1,
+            window,
+            depth: 0,
+            name: "zsh".to_string(),
+            cpu_percent,
+            mem_mb,
+        };
+        let totals = window_totals(&[
+            process(0, 1.5, 10),
+            process(1, 90.0, 400),
+            process(1, 5.0, 100),
+        ]);
+        assert_eq!(
+            totals[&1],
+            WindowStats {
+                cpu_percent: 95.0,
+                mem_mb: 500
+            }
+        );
+        assert_eq!(totals[&0].mem_mb, 10);
+    }
+
+    #[test]
+    fn test_format_rate() {
+        assert_eq!(format_rate(512), "512B/s");
+        assert_eq!(format_rate(2048), "2K/s");
+        assert_eq!(format_rate(3 * 1024 * 1024 / 2), "1.5M/s");
+        assert_eq!(format_rate(2 * 1024 * 1024 * 1024), "2.0G/s");
+    }
+
+    #[test]
+    fn test_delta_cpu_needs_two_samples() {
+        // Should be able to read raw stats for current process
+        let pid = std::process::id();
+        let result = get_process_raw(pid);
+        assert!(result.is_ok());
+    }
+}
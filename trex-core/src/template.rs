@@ -20,6 +20,7 @@ pub struct SessionTemplate {
     pub layout: TemplateLayout,
     pub panes: Vec<TemplatePane>,
     pub focus_pane: usize,
+    pub windows: Vec<TemplateWindow>, // opened after the first, the last one focused
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,6 +28,13 @@ pub struct TemplatePane {
     pub command: String,
 }
 
+// A further window of a template, with its name and command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateWindow {
+    pub name: String,
+    pub command: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TemplateLayout {
     Single,
@@ -65,7 +73,8 @@ impl TemplateCatalog {
         catalog
     }
 
-    fn builtins() -> Self {
+    // The built-in templates alone, without the user's templates file.
+    pub fn builtins() -> Self {
         Self {
             templates: builtin_templates(),
             warnings: Vec::new(),
@@ -137,13 +146,36 @@ impl SessionTemplate {
             layout,
             panes,
             focus_pane,
+            windows: Vec::new(),
         }
     }
 
+    // The id of the template offered for a directory's containers (see compose.rs).
+    pub const PROJECT_ID: &str = "project:containers";
+    // The id of the template read from a directory's `.trex.toml` (see project.rs).
+    pub const PROJECT_CONFIG_ID: &str = "project:config";
+
+    // The built-in single shell pane template.
+    pub fn terminal() -> Self {
+        Self::new(
+            "terminal",
+            "Terminal",
+            "One shell pane in the selected directory",
+            TemplateLayout::Single,
+            vec![TemplatePane::shell()],
+            0,
+        )
+    }
+
     pub fn is_terminal(&self) -> bool {
         self.id == "terminal"
     }
 
+    // A template made for one directory rather than loaded from the catalog.
+    pub fn is_project(&self) -> bool {
+        self.id == Self::PROJECT_ID || self.id == Self::PROJECT_CONFIG_ID
+    }
+
     pub fn split_percent(&self) -> Option<u8> {
         (self.id == "nvim-codex" || self.id == "nvim-gemini").then_some(70)
     }
@@ -155,11 +187,15 @@ impl SessionTemplate {
             TemplateLayout::Rows => " / ",
         };
 
-        self.panes
+        let panes = self
+            .panes
             .iter()
             .map(TemplatePane::label)
             .collect::<Vec<_>>()
-            .join(separator)
+            .join(separator);
+        self.windows.iter().fold(panes, |summary, window| {
+            format!("{}, {}: {}", summary, window.name, window.command)
+        })
     }
 }
 
@@ -235,14 +271,7 @@ fn user_templates_path_from_env(
 
 fn builtin_templates() -> Vec<SessionTemplate> {
     vec![
-        SessionTemplate::new(
-            "terminal",
-            "Terminal",
-            "One shell pane in the selected directory",
-            TemplateLayout::Single,
-            vec![TemplatePane::shell()],
-            0,
-        ),
+        SessionTemplate::terminal(),
         SessionTemplate::new(
             "two-columns",
             "Two Columns",
@@ -328,6 +357,7 @@ fn validate_user_template(
         layout,
         panes,
         focus_pane,
+        windows: Vec::new(),
     })
 }
 
@@ -0,0 +1,295 @@
+use crate::tmux::TmuxSession;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Seconds covered by one bucket of the activity timeline.
+pub const BUCKET_SECS: u64 = 3600;
+
+// How far back the timeline keeps buckets: one week.
+pub const RETENTION_SECS: u64 = 7 * 86400;
+
+// A sample counts as active when the session had output this recently.
+pub const ACTIVE_WINDOW_SECS: u64 = 60;
+
+/* Samples taken in one bucket, how many of them found the session active,
+ * and the agent and CPU time the samples add up to. */
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Bucket {
+    pub samples: u32,
+    pub active: u32,
+    pub agent_secs: u64, // summed over agents, so two agents for an hour are 7200
+    pub cpu_secs: f64,
+}
+
+impl Bucket {
+    fn add(&mut self, other: &Bucket) {
+        self.samples += other.samples;
+        self.active += other.active;
+        self.agent_secs += other.agent_secs;
+        self.cpu_secs += other.cpu_secs;
+    }
+}
+
+// The current unix time in seconds.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/* Per-session activity over the last week in hourly buckets, saved as
+ * `timeline.tsv` under the trex cache directory so it outlives the TUI.
+ * Samples are only taken while trex runs, so hours it was closed have no
+ * buckets. Sessions are keyed by name; a renamed session starts over.
+ * Each line is `bucket start<TAB>samples<TAB>active<TAB>agent seconds
+ * <TAB>CPU seconds<TAB>session name`, with the name last since it may
+ * contain anything. */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivityTimeline {
+    sessions: BTreeMap<String, BTreeMap<u64, Bucket>>,
+}
+
+impl ActivityTimeline {
+    // Loads the saved timeline; empty if missing or unreadable.
+    pub fn load() -> Self {
+        timeline_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = timeline_path() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.render())
+    }
+
+    /* Records one sample of every session at unix time `now`, then drops
+     * buckets older than the retention window. The sample stands for the
+     * `interval_secs` since the previous one: each agent listed in
+     * `agent_sessions` (by its session name) adds that much agent time, and
+     * the session's CPU share of it adds CPU time. */
+    pub fn record(
+        &mut self,
+        sessions: &[TmuxSession],
+        agent_sessions: &[&str],
+        interval_secs: u64,
+        now: u64,
+    ) {
+        let start = now - now % BUCKET_SECS;
+        for session in sessions {
+            let active = session
+                .last_activity
+                .is_some_and(|at| now.saturating_sub(at) <= ACTIVE_WINDOW_SECS);
+            let agents = agent_sessions
+                .iter()
+                .filter(|name| **name == session.name)
+                .count() as u64;
+            let cpu_percent = session
+                .stats
+                .as_ref()
+                .map(|stats| stats.cpu_percent)
+                .unwrap_or_default();
+            let bucket = self
+                .sessions
+                .entry(session.name.clone())
+                .or_default()
+                .entry(start)
+                .or_default();
+            bucket.samples += 1;
+            bucket.active += u32::from(active);
+            bucket.agent_secs += agents * interval_secs;
+            bucket.cpu_secs += cpu_percent / 100.0 * interval_secs as f64;
+        }
+        self.prune(now);
+    }
+
+    fn prune(&mut self, now: u64) {
+        let oldest = now.saturating_sub(RETENTION_SECS);
+        for buckets in self.sessions.values_mut() {
+            buckets.retain(|start, _| start + BUCKET_SECS > oldest);
+        }
+        self.sessions.retain(|_, buckets| !buckets.is_empty());
+    }
+
+    /* The share of samples that found a session active between `from` and
+     * `to` (unix times), over the buckets overlapping that span; None when
+     * no sample was taken then. */
+    pub fn intensity(&self, session: &str, from: u64, to: u64) -> Option<f64> {
+        let buckets = self.sessions.get(session)?;
+        let first = from - from % BUCKET_SECS;
+        let (samples, active) = buckets
+            .range(first..to.max(first + 1))
+            .fold((0, 0), |(samples, active), (_, bucket)| {
+                (samples + bucket.samples, active + bucket.active)
+            });
+        (samples > 0).then(|| active as f64 / samples as f64)
+    }
+
+    /* Each session's buckets between `from` and `to` (unix times) added
+     * together, for sessions with any. Buckets count whole, so the span is
+     * widened to the hours it touches. */
+    pub fn totals(&self, from: u64, to: u64) -> BTreeMap<String, Bucket> {
+        let first = from - from % BUCKET_SECS;
+        self.sessions
+            .iter()
+            .filter_map(|(name, buckets)| {
+                let mut total = Bucket::default();
+                for (_, bucket) in buckets.range(first..to.max(first + 1)) {
+                    total.add(bucket);
+                }
+                (total.samples > 0).then(|| (name.clone(), total))
+            })
+            .collect()
+    }
+
+    // The start of a session's oldest bucket, for sessions tmux has no creation time for.
+    pub fn first_seen(&self, session: &str) -> Option<u64> {
+        self.sessions.get(session)?.keys().next().copied()
+    }
+
+    fn parse(text: &str) -> Self {
+        let mut timeline = Self::default();
+        for line in text.lines() {
+            let fields: Vec<&str> = line.splitn(6, '\t').collect();
+            let [start, samples, active, agent_secs, cpu_secs, name] = fields[..] else {
+                continue;
+            };
+            let (Ok(start), Ok(samples), Ok(active), Ok(agent_secs), Ok(cpu_secs)) = (
+                start.parse(),
+                samples.parse(),
+                active.parse(),
+                agent_secs.parse(),
+                cpu_secs.parse(),
+            ) else {
+                continue;
+            };
+            let bucket = Bucket {
+                samples,
+                active,
+                agent_secs,
+                cpu_secs,
+            };
+            timeline
+                .sessions
+                .entry(name.to_string())
+                .or_default()
+                .insert(start, bucket);
+        }
+        timeline
+    }
+
+    fn render(&self) -> String {
+        self.sessions
+            .iter()
+            .flat_map(|(name, buckets)| {
+                buckets.iter().map(move |(start, bucket)| {
+                    format!(
+                        "{}\t{}\t{}\t{}\t{}\t{}\n",
+                        start,
+                        bucket.samples,
+                        bucket.active,
+                        bucket.agent_secs,
+                        bucket.cpu_secs,
+                        name
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+fn timeline_path() -> Option<PathBuf> {
+    crate::logging::cache_dir().map(|dir| dir.join("timeline.tsv"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmux::MockTmux;
+    use crate::tmux::TmuxBackend;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn sessions(activity: &[(&str, Option<u64>)]) -> Vec<TmuxSession> {
+        let names: Vec<&str> = activity.iter().map(|(name, _)| *name).collect();
+        let mut sessions = MockTmux::with_sessions(&names).list_sessions().unwrap();
+        for (session, (_, last_activity)) in sessions.iter_mut().zip(activity) {
+            session.last_activity = *last_activity;
+        }
+        sessions
+    }
+
+    #[test]
+    fn records_activity_share_per_bucket() {
+        let mut timeline = ActivityTimeline::default();
+        timeline.record(&sessions(&[("api", Some(NOW)), ("web", None)]), &[], 2, NOW);
+        timeline.record(
+            &sessions(&[("api", Some(NOW - 600)), ("web", None)]),
+            &[],
+            2,
+            NOW + 1,
+        );
+
+        assert_eq!(timeline.intensity("api", NOW, NOW + 1), Some(0.5));
+        assert_eq!(timeline.intensity("web", NOW, NOW + 1), Some(0.0));
+        assert_eq!(timeline.intensity("api", NOW - 86400, NOW - 3600 * 2), None);
+        assert_eq!(timeline.intensity("docs", NOW, NOW + 1), None);
+        assert_eq!(timeline.first_seen("api"), Some(NOW - NOW % BUCKET_SECS));
+
+        // A week later the old buckets are gone
+        let later = NOW + RETENTION_SECS + BUCKET_SECS;
+        timeline.record(&sessions(&[("web", None)]), &[], 2, later);
+        assert_eq!(timeline.first_seen("api"), None);
+        assert_eq!(
+            timeline.first_seen("web"),
+            Some(later - later % BUCKET_SECS)
+        );
+    }
+
+    #[test]
+    fn totals_add_up_agent_and_cpu_time() {
+        let mut timeline = ActivityTimeline::default();
+        let mut sampled = sessions(&[("api", None), ("web", None)]);
+        sampled[0].stats = Some(crate::sysinfo::SessionStats {
+            cpu_percent: 50.0,
+            ..Default::default()
+        });
+        for offset in [0, 2, BUCKET_SECS] {
+            timeline.record(&sampled, &["api", "api", "docs"], 2, NOW + offset);
+        }
+
+        let totals = timeline.totals(NOW, NOW + 2 * BUCKET_SECS);
+        assert_eq!(totals["api"].samples, 3);
+        assert_eq!(totals["api"].agent_secs, 12);
+        assert_eq!(totals["api"].cpu_secs, 3.0);
+        assert_eq!(totals["web"].agent_secs, 0);
+        assert!(!totals.contains_key("docs"));
+
+        // Only the hour the span touches counts
+        let first_hour = timeline.totals(NOW, NOW + 1);
+        assert_eq!(first_hour["api"].samples, 2);
+        assert!(
+            timeline
+                .totals(NOW - 86400, NOW - 2 * BUCKET_SECS)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn round_trips_and_skips_bad_lines() {
+        let mut timeline = ActivityTimeline::default();
+        timeline.record(&sessions(&[("a\tb c", Some(NOW))]), &["a\tb c"], 2, NOW);
+        let text = timeline.render();
+        assert_eq!(ActivityTimeline::parse(&text), timeline);
+
+        let parsed = ActivityTimeline::parse(&format!("garbage\nx\t1\t1\t0\t0\tapi\n{}", text));
+        assert_eq!(parsed, timeline);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::template::SessionTemplate;
+use crate::tmux::client::AttachedClient;
+use crate::tmux::commands::{AttachMode, TmuxClient};
+use crate::tmux::session::TmuxSession;
+use crate::tmux::window::TmuxWindow;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+/* The tmux operations the TUI and action dispatch depend on. TmuxClient is
+ * the real implementation over the tmux CLI; MockTmux keeps sessions in
+ * memory so event handling and actions can be tested without a server.
+ * Environment checks (check_installed, is_inside_tmux) stay on TmuxClient. */
+pub trait TmuxBackend {
+    fn list_sessions(&self) -> Result<Vec<TmuxSession>>;
+    fn list_windows(&self, session_name: &str) -> Result<Vec<TmuxWindow>>;
+    fn list_all_windows(&self) -> Result<Vec<(String, TmuxWindow)>>;
+    fn list_clients(&self, session_name: &str) -> Result<Vec<AttachedClient>>;
+    fn capture_pane(&self, session_name: &str, lines: usize) -> Result<Vec<String>>;
+    fn capture_pane_id(&self, pane_id: &str, lines: usize) -> Result<Vec<String>>;
+
+    fn new_session_from_template(
+        &self,
+        name: &str,
+        working_dir: &Path,
+        template: &SessionTemplate,
+    ) -> Result<()>;
+    fn new_session_with_command(
+        &self,
+        name: &str,
+        working_dir: &Path,
+        template: &SessionTemplate,
+        command: &str,
+    ) -> Result<()>;
+    fn new_window_with_command(
+        &self,
+        session_name: &str,
+        window_name: &str,
+        working_dir: &Path,
+        command: &str,
+    ) -> Result<()>;
+    fn clone_session(&self, source_session: &str, name: &str) -> Result<()>;
+    fn link_active_window(&self, source_session: &str, target_session: &str) -> Result<()>;
+    fn set_session_note(&self, session_name: &str, note: &str) -> Result<()>;
+    fn set_session_tags(&self, session_name: &str, tags: &[String]) -> Result<()>;
+    fn review_queue(&self) -> Result<Vec<String>>;
+    fn set_review_queue(&self, targets: &[String]) -> Result<()>;
+    fn fit_windows_to_largest_client(&self, session_name: &str) -> Result<()>;
+    fn send_to_pane(&self, pane_id: &str, text: &str) -> Result<()>;
+    fn session_environment(&self, session_name: &str) -> Result<HashMap<String, String>>;
+    fn set_environment(
+        &self,
+        session_name: &str,
+        variable: &str,
+        value: Option<&str>,
+    ) -> Result<()>;
+    fn list_panes(&self, session_name: &str) -> Result<Vec<(String, String)>>;
+    fn window_layout(&self, session_name: &str) -> Result<String>;
+    fn apply_layout(&self, session_name: &str, layout: &str) -> Result<()>;
+
+    fn delete_session(&self, session_name: &str) -> Result<()>;
+    fn delete_all_sessions(&self) -> Result<()>;
+    fn kill_window(&self, session_name: &str, window_index: u32) -> Result<()>;
+    fn kill_pane(&self, session_name: &str, window_index: u32) -> Result<()>;
+    fn respawn_pane(&self, session_name: &str, window_index: u32) -> Result<()>;
+    fn set_window_option(
+        &self,
+        session_name: &str,
+        window_index: u32,
+        option: &str,
+        value: &str,
+    ) -> Result<()>;
+    fn detach_session(&self, session_name: &str) -> Result<()>;
+    fn detach_all_sessions(&self) -> Result<()>;
+    fn detach_client(&self, tty: &str) -> Result<()>;
+
+    fn attach(&self, session_name: &str, mode: AttachMode) -> Result<()>;
+    fn attach_read_only(&self, session_name: &str, mode: AttachMode) -> Result<()>;
+    fn attach_detach_others(&self, session_name: &str, mode: AttachMode) -> Result<()>;
+    fn attach_or_switch(&self, session_name: &str, mode: AttachMode) -> Result<()>;
+    fn attach_or_switch_window(
+        &self,
+        session_name: &str,
+        window_index: u32,
+        mode: AttachMode,
+    ) -> Result<()>;
+    // Attaches to a listed window by its id, which survives renumbering.
+    fn attach_or_switch_to(
+        &self,
+        session_name: &str,
+        window: &TmuxWindow,
+        mode: AttachMode,
+    ) -> Result<()>;
+}
+
+// Delegates to the associated functions, which shell out to tmux.
+impl TmuxBackend for TmuxClient {
+    fn list_sessions(&self) -> Result<Vec<TmuxSession>> {
+        TmuxClient::list_sessions()
+    }
+
+    fn list_windows(&self, session_name: &str) -> Result<Vec<TmuxWindow>> {
+        TmuxClient::list_windows(session_name)
+    }
+
+    fn list_all_windows(&self) -> Result<Vec<(String, TmuxWindow)>> {
+        TmuxClient::list_all_windows()
+    }
+
+    fn list_clients(&self, session_name: &str) -> Result<Vec<AttachedClient>> {
+        TmuxClient::list_clients(session_name)
+    }
+
+    fn capture_pane(&self, session_name: &str, lines: usize) -> Result<Vec<String>> {
+        TmuxClient::capture_pane(session_name, lines)
+    }
+
+    fn capture_pane_id(&self, pane_id: &str, lines: usize) -> Result<Vec<String>> {
+        TmuxClient::capture_pane_id(pane_id, lines)
+    }
+
+    fn new_session_from_template(
+        &self,
+        name: &str,
+        working_dir: &Path,
+        template: &SessionTemplate,
+    ) -> Result<()> {
+        TmuxClient::new_session_from_template(name, working_dir, template)
+    }
+
+    fn new_session_with_command(
+        &self,
+        name: &str,
+        working_dir: &Path,
+        template: &SessionTemplate,
+        command: &str,
+    ) -> Result<()> {
+        TmuxClient::new_session_with_command(name, working_dir, template, command)
+    }
+
+    fn new_window_with_command(
+        &self,
+        session_name: &str,
+        window_name: &str,
+        working_dir: &Path,
+        command: &str,
+    ) -> Result<()> {
+        TmuxClient::new_window_with_command(session_name, window_name, working_dir, command)
+    }
+
+    fn clone_session(&self, source_session: &str, name: &str) -> Result<()> {
+        TmuxClient::clone_session(source_session, name)
+    }
+
+    fn link_active_window(&self, source_session: &str, target_session: &str) -> Result<()> {
+        TmuxClient::link_active_window(source_session, target_session)
+    }
+
+    fn set_session_note(&self, session_name: &str, note: &str) -> Result<()> {
+        TmuxClient::set_session_note(session_name, note)
+    }
+
+    fn set_session_tags(&self, session_name: &str, tags: &[String]) -> Result<()> {
+        TmuxClient::set_session_tags(session_name, tags)
+    }
+
+    fn review_queue(&self) -> Result<Vec<String>> {
+        TmuxClient::review_queue()
+    }
+
+    fn set_review_queue(&self, targets: &[String]) -> Result<()> {
+        TmuxClient::set_review_queue(targets)
+    }
+
+    fn fit_windows_to_largest_client(&self, session_name: &str) -> Result<()> {
+        TmuxClient::fit_windows_to_largest_client(session_name)
+    }
+
+    fn send_to_pane(&self, pane_id: &str, text: &str) -> Result<()> {
+        TmuxClient::send_command_to_pane(pane_id, text)
+    }
+
+    fn session_environment(&self, session_name: &str) -> Result<HashMap<String, String>> {
+        TmuxClient::session_environment(session_name)
+    }
+
+    fn set_environment(
+        &self,
+        session_name: &str,
+        variable: &str,
+        value: Option<&str>,
+    ) -> Result<()> {
+        TmuxClient::set_environment(session_name, variable, value)
+    }
+
+    fn list_panes(&self, session_name: &str) -> Result<Vec<(String, String)>> {
+        TmuxClient::list_panes(session_name)
+    }
+
+    fn window_layout(&self, session_name: &str) -> Result<String> {
+        TmuxClient::window_layout(session_name)
+    }
+
+    fn apply_layout(&self, session_name: &str, layout: &str) -> Result<()> {
+        TmuxClient::apply_layout(session_name, layout)
+    }
+
+    fn delete_session(&self, session_name: &str) -> Result<()> {
+        TmuxClient::delete_session(session_name)
+    }
+
+    fn delete_all_sessions(&self) -> Result<()> {
+        TmuxClient::delete_all_sessions()
+    }
+
+    fn kill_window(&self, session_name: &str, window_index: u32) -> Result<()> {
+        TmuxClient::kill_window(session_name, window_index)
+    }
+
+    fn kill_pane(&self, session_name: &str, window_index: u32) -> Result<()> {
+        TmuxClient::kill_pane(session_name, window_index)
+    }
+
+    fn respawn_pane(&self, session_name: &str, window_index: u32) -> Result<()> {
+        TmuxClient::respawn_pane(session_name, window_index)
+    }
+
+    fn set_window_option(
+        &self,
+        session_name: &str,
+        window_index: u32,
+        option: &str,
+        value: &str,
+    ) -> Result<()> {
+        TmuxClient::set_window_option(session_name, window_index, option, value)
+    }
+
+    fn detach_session(&self, session_name: &str) -> Result<()> {
+        TmuxClient::detach_session(session_name)
+    }
+
+    fn detach_all_sessions(&self) -> Result<()> {
+        TmuxClient::detach_all_sessions()
+    }
+
+    fn detach_client(&self, tty: &str) -> Result<()> {
+        TmuxClient::detach_client(tty)
+    }
+
+    fn attach(&self, session_name: &str, mode: AttachMode) -> Result<()> {
+        TmuxClient::attach(session_name, mode)
+    }
+
+    fn attach_read_only(&self, session_name: &str, mode: AttachMode) -> Result<()> {
+        TmuxClient::attach_read_only(session_name, mode)
+    }
+
+    fn attach_detach_others(&self, session_name: &str, mode: AttachMode) -> Result<()> {
+        TmuxClient::attach_detach_others(session_name, mode)
+    }
+
+    fn attach_or_switch(&self, session_name: &str, mode: AttachMode) -> Result<()> {
+        TmuxClient::attach_or_switch(session_name, mode)
+    }
+
+    fn attach_or_switch_window(
+        &self,
+        session_name: &str,
+        window_index: u32,
+        mode: AttachMode,
+    ) -> Result<()> {
+        TmuxClient::attach_or_switch_window(session_name, window_index, mode)
+    }
+
+    fn attach_or_switch_to(
+        &self,
+        session_name: &str,
+        window: &TmuxWindow,
+        mode: AttachMode,
+    ) -> Result<()> {
+        TmuxClient::attach_or_switch_to(session_name, window, mode)
+    }
+}
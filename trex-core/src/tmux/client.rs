@@ -0,0 +1,134 @@
+use crate::tmux::parser::FIELD_SEPARATOR;
+use crate::tmux::session::format_elapsed;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttachedClient {
+    pub tty: String,
+    pub width: u32,
+    pub height: u32,
+    pub readonly: bool,
+    pub activity: Option<u64>, // Unix timestamp of the client's last input
+    pub pid: Option<u32>,      // the client process, a child of the terminal running it
+}
+
+impl AttachedClient {
+    // Returns a human-readable string for time since the client's last input.
+    pub fn activity_ago_string(&self) -> Option<String> {
+        let activity_ts = self.activity?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        Some(format_elapsed(now.saturating_sub(activity_ts)))
+    }
+}
+
+// Format string passed to `tmux list-clients -F`.
+pub const CLIENT_FORMAT: &str = "#{client_tty}\x1f#{client_width}\x1f#{client_height}\x1f#{client_readonly}\x1f#{client_activity}\x1f#{client_pid}";
+
+// Format string for listing every client, prefixed with the client's session.
+pub const SESSION_CLIENT_FORMAT: &str = "#{client_session}\x1f#{client_tty}\x1f#{client_width}\x1f#{client_height}\x1f#{client_readonly}\x1f#{client_activity}\x1f#{client_pid}";
+
+/// Size mismatch among a session's clients. With tmux's default
+/// `window-size` the smallest client limits every window in the session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeMismatch<'a> {
+    pub smallest: &'a AttachedClient,
+    pub largest: &'a AttachedClient,
+}
+
+// Returns the smallest and largest clients when attached clients differ in size.
+pub fn size_mismatch(clients: &[AttachedClient]) -> Option<SizeMismatch<'_>> {
+    let area = |client: &&AttachedClient| client.width * client.height;
+    let smallest = clients.iter().min_by_key(area)?;
+    let largest = clients.iter().max_by_key(area)?;
+    (smallest.width < largest.width || smallest.height < largest.height)
+        .then_some(SizeMismatch { smallest, largest })
+}
+
+// Parses the output of `tmux list-clients` into a list of clients.
+pub fn parse_clients(output: &str) -> Vec<AttachedClient> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .filter_map(parse_client_line)
+        .collect()
+}
+
+// Groups the output of `tmux list-clients` (all sessions) by session name.
+pub fn parse_clients_by_session(output: &str) -> HashMap<String, Vec<AttachedClient>> {
+    let mut clients: HashMap<String, Vec<AttachedClient>> = HashMap::new();
+    for line in output.lines() {
+        if let Some((session, rest)) = line.split_once(FIELD_SEPARATOR)
+            && let Some(client) = parse_client_line(rest)
+        {
+            clients.entry(session.to_string()).or_default().push(client);
+        }
+    }
+    clients
+}
+
+// Parses a single line of client output.
+// Format: CLIENT_FORMAT, `tty width height readonly activity pid`
+fn parse_client_line(line: &str) -> Option<AttachedClient> {
+    let parts: Vec<&str> = line.split(FIELD_SEPARATOR).collect();
+
+    if parts.len() < 5 {
+        return None;
+    }
+
+    Some(AttachedClient {
+        tty: parts[0].to_string(),
+        width: parts[1].parse().ok()?,
+        height: parts[2].parse().ok()?,
+        readonly: parts[3] == "1",
+        activity: parts[4].parse().ok(),
+        pid: parts.get(5).and_then(|pid| pid.parse().ok()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_client_line() {
+        let client =
+            parse_client_line("/dev/pts/3\x1f212\x1f54\x1f0\x1f1700000000\x1f4242").unwrap();
+        assert_eq!(client.tty, "/dev/pts/3");
+        assert_eq!((client.width, client.height), (212, 54));
+        assert!(!client.readonly);
+        assert_eq!(client.activity, Some(1700000000));
+        assert_eq!(client.pid, Some(4242));
+    }
+
+    #[test]
+    fn test_parse_clients_by_session() {
+        let output = "api\x1f/dev/pts/3\x1f212\x1f54\x1f0\x1f1\napi\x1f/dev/pts/9\x1f80\x1f24\x1f0\x1f2\nweb|2\x1f/dev/pts/4\x1f100\x1f30\x1f0\x1f3\n";
+        let clients = parse_clients_by_session(output);
+        assert_eq!(clients["api"].len(), 2);
+        assert_eq!(clients["web|2"].len(), 1);
+    }
+
+    #[test]
+    fn test_size_mismatch() {
+        let clients =
+            parse_clients("/dev/pts/3\x1f212\x1f54\x1f0\x1f1\n/dev/pts/9\x1f80\x1f24\x1f0\x1f2\n");
+        let mismatch = size_mismatch(&clients).unwrap();
+        assert_eq!(mismatch.smallest.tty, "/dev/pts/9");
+        assert_eq!(mismatch.largest.tty, "/dev/pts/3");
+
+        let same =
+            parse_clients("/dev/pts/3\x1f80\x1f24\x1f0\x1f1\n/dev/pts/9\x1f80\x1f24\x1f0\x1f2\n");
+        assert!(size_mismatch(&same).is_none());
+        assert!(size_mismatch(&[]).is_none());
+    }
+
+    #[test]
+    fn test_parse_clients_skips_malformed_lines() {
+        let output = "/dev/pts/3\x1f212\x1f54\x1f0\x1f1700000000\ngarbage\n/dev/pts/7\x1f80\x1f24\x1f1\x1f\n";
+        let clients = parse_clients(output);
+        assert_eq!(clients.len(), 2);
+        assert!(clients[1].readonly);
+        assert_eq!(clients[1].activity, None);
+    }
+}
@@ -0,0 +1,914 @@
+use crate::logging;
+use crate::template::{SessionTemplate, TemplateLayout};
+use crate::tmux::client::{
+    AttachedClient, CLIENT_FORMAT, SESSION_CLIENT_FORMAT, parse_clients, parse_clients_by_session,
+    size_mismatch,
+};
+use crate::tmux::error::TmuxError;
+use crate::tmux::parser::{
+    FIELD_SEPARATOR, PANE_FORMAT, SESSION_FORMAT, current_window_target, layout_pane_count,
+    parse_environment, parse_pane_counts, parse_panes, parse_sessions, session_target,
+    validate_session_name, window_target,
+};
+use crate::tmux::session::TmuxSession;
+use crate::tmux::window::{
+    ALL_WINDOWS_FORMAT, TmuxWindow, WINDOW_FORMAT, WINDOW_RECIPE_FORMAT, parse_all_windows,
+    parse_window_recipes, parse_windows,
+};
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+pub struct TmuxClient;
+
+/* `tmux` for a command whose output trex parses. `-u` tells tmux this
+ * client takes UTF-8: without it, under a non-UTF-8 locale (LANG=C or
+ * unset, as under cron, systemd, or ssh without locale forwarding), tmux
+ * prints every byte outside printable ASCII as `_`, the field separator
+ * and non-ASCII names alike. Attaches run plain `tmux`, so the terminal
+ * is still drawn for its own locale. */
+pub fn query_command() -> Command {
+    let mut command = Command::new("tmux");
+    command.arg("-u");
+    command
+}
+
+// How attach commands run: replacing trex via exec, or as a child process so
+// trex resumes once the user detaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AttachMode {
+    #[default]
+    Exec,
+    Child,
+}
+
+impl TmuxClient {
+    pub fn check_installed() -> Result<()> {
+        which::which("tmux")
+            .map_err(|_| anyhow::anyhow!("tmux is not installed or not in PATH"))?;
+        Ok(())
+    }
+
+    pub fn is_inside_tmux() -> bool {
+        std::env::var("TMUX").is_ok()
+    }
+
+    // Runs a tmux query and returns its stdout, or a classified TmuxError.
+    fn query(args: &[&str], target: Option<&str>) -> Result<String> {
+        let _timer = logging::timed(|| format!("tmux {}", args.join(" ")));
+        let output = query_command().args(args).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let error = TmuxError::from_stderr(args[0], target, &stderr);
+            log::warn!("tmux {}: {}", args[0], error);
+            return Err(error.into());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    // Lists all tmux sessions with their metadata. No running server means
+    // no sessions; other failures are returned as a TmuxError.
+    pub fn list_sessions() -> Result<Vec<TmuxSession>> {
+        let stdout = match Self::query(&["list-sessions", "-F", SESSION_FORMAT], None) {
+            Ok(stdout) => stdout,
+            Err(error) if error.downcast_ref() == Some(&TmuxError::ServerNotRunning) => {
+                return Ok(Vec::new());
+            }
+            Err(error) => return Err(error),
+        };
+        let mut sessions = parse_sessions(&stdout)?;
+
+        let pane_counts = Self::list_pane_counts()?;
+        let clients = Self::list_all_clients()?;
+        for session in &mut sessions {
+            session.panes = pane_counts.get(&session.name).copied().unwrap_or(0);
+            session.size_constrained = clients
+                .get(&session.name)
+                .is_some_and(|clients| size_mismatch(clients).is_some());
+        }
+
+        Ok(sessions)
+    }
+
+    // Lists attached clients across the whole server, grouped by session.
+    fn list_all_clients() -> Result<std::collections::HashMap<String, Vec<AttachedClient>>> {
+        let stdout = Self::query(&["list-clients", "-F", SESSION_CLIENT_FORMAT], None)?;
+        Ok(parse_clients_by_session(&stdout))
+    }
+
+    // Counts panes per session across the whole server.
+    fn list_pane_counts() -> Result<std::collections::HashMap<String, u32>> {
+        let stdout = Self::query(&["list-panes", "-a", "-F", "#{session_name}"], None)?;
+        Ok(parse_pane_counts(&stdout))
+    }
+
+    // Runs a tmux attach command, either via exec or as a child that returns on detach.
+    fn run_attach(args: &[&str], mode: AttachMode, failure: &str) -> Result<()> {
+        let mut command = Command::new("tmux");
+        command.args(args);
+
+        match mode {
+            AttachMode::Exec => {
+                let err = command.exec();
+                bail!("{}: {}", failure, err);
+            }
+            AttachMode::Child => {
+                let status = command.status()?;
+                if !status.success() {
+                    bail!("{}", failure);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // Attaches to a session.
+    pub fn attach(session_name: &str, mode: AttachMode) -> Result<()> {
+        Self::run_attach(
+            &["attach-session", "-t", &session_target(session_name)],
+            mode,
+            &format!("Failed to attach to session: {}", session_name),
+        )
+    }
+
+    // Attaches to a session read-only (`attach-session -r`).
+    // Keystrokes other than detach are ignored, so the session can be watched safely.
+    pub fn attach_read_only(session_name: &str, mode: AttachMode) -> Result<()> {
+        Self::run_attach(
+            &["attach-session", "-r", "-t", &session_target(session_name)],
+            mode,
+            &format!("Failed to attach read-only to session: {}", session_name),
+        )
+    }
+
+    // Attaches to a session and detaches every other client (`attach-session -d`),
+    // so a stale client with a smaller terminal no longer constrains the window size.
+    pub fn attach_detach_others(session_name: &str, mode: AttachMode) -> Result<()> {
+        Self::run_attach(
+            &["attach-session", "-d", "-t", &session_target(session_name)],
+            mode,
+            &format!("Failed to take over session: {}", session_name),
+        )
+    }
+
+    // Lists the clients attached to a session.
+    pub fn list_clients(session_name: &str) -> Result<Vec<AttachedClient>> {
+        let stdout = Self::query(
+            &[
+                "list-clients",
+                "-t",
+                &session_target(session_name),
+                "-F",
+                CLIENT_FORMAT,
+            ],
+            Some(session_name),
+        )?;
+        Ok(parse_clients(&stdout))
+    }
+
+    // Resizes every window in a session to fit its largest client (`resize-window -A`).
+    pub fn fit_windows_to_largest_client(session_name: &str) -> Result<()> {
+        for window in Self::list_windows(session_name)? {
+            let target = window_target(session_name, window.index);
+            let status = Command::new("tmux")
+                .args(["resize-window", "-A", "-t", &target])
+                .status()?;
+
+            if !status.success() {
+                bail!("Failed to resize window: {}", target);
+            }
+        }
+        Ok(())
+    }
+
+    // Detaches one client, identified by its tty.
+    pub fn detach_client(tty: &str) -> Result<()> {
+        let status = Command::new("tmux")
+            .args(["detach-client", "-t", tty])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to detach client: {}", tty);
+        }
+        Ok(())
+    }
+
+    // Switches the current tmux client to a different session.
+    pub fn switch_client(session_name: &str) -> Result<()> {
+        let status = Command::new("tmux")
+            .args(["switch-client", "-t", &session_target(session_name)])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to switch to session: {}", session_name);
+        }
+        Ok(())
+    }
+
+    // Attaches or switches to a session depending on whether we're inside tmux.
+    pub fn attach_or_switch(session_name: &str, mode: AttachMode) -> Result<()> {
+        if Self::is_inside_tmux() {
+            Self::switch_client(session_name)
+        } else {
+            Self::attach(session_name, mode)
+        }
+    }
+
+    // Creates a new tmux session with the given name and working directory.
+    pub fn new_session(name: &str, working_dir: &std::path::Path, detached: bool) -> Result<()> {
+        validate_session_name(name)?;
+        let dir_str = working_dir.to_string_lossy();
+        let mut args = vec!["new-session", "-s", name, "-c", &dir_str];
+
+        if detached {
+            args.push("-d");
+        }
+
+        let status = Command::new("tmux").args(&args).status()?;
+
+        if !status.success() {
+            bail!("Failed to create session: {}", name);
+        }
+        Ok(())
+    }
+
+    pub fn new_session_from_template(
+        name: &str,
+        working_dir: &std::path::Path,
+        template: &SessionTemplate,
+    ) -> Result<()> {
+        if template.is_terminal() {
+            return Self::new_session(name, working_dir, true);
+        }
+        Self::build_template_session(name, working_dir, template, None)
+    }
+
+    /* Like new_session_from_template, but the first pane runs `command`
+     * (given to `new-session`) in place of its shell and template command. */
+    pub fn new_session_with_command(
+        name: &str,
+        working_dir: &std::path::Path,
+        template: &SessionTemplate,
+        command: &str,
+    ) -> Result<()> {
+        Self::build_template_session(name, working_dir, template, Some(command))
+    }
+
+    fn build_template_session(
+        name: &str,
+        working_dir: &std::path::Path,
+        template: &SessionTemplate,
+        command: Option<&str>,
+    ) -> Result<()> {
+        let base_pane = Self::new_session_with_pane_id(name, working_dir, command)?;
+        let mut pane_ids = vec![base_pane];
+
+        if template.layout != TemplateLayout::Single {
+            let split_percent = template.split_percent();
+            let split_flag = template
+                .layout
+                .split_flag()
+                .expect("split templates have a split flag");
+            let target = pane_ids[0].clone();
+
+            for _ in template.panes.iter().skip(1) {
+                let pane_id = Self::split_pane(&target, split_flag, split_percent, working_dir)?;
+                pane_ids.push(pane_id);
+            }
+
+            if split_percent.is_none()
+                && let Some(layout) = template.layout.tmux_even_layout()
+            {
+                Self::select_layout(name, layout)?;
+            }
+        }
+
+        let skip = usize::from(command.is_some());
+        for (pane_id, pane) in pane_ids.iter().zip(template.panes.iter()).skip(skip) {
+            Self::send_command_to_pane(pane_id, &pane.command)?;
+        }
+
+        if let Some(focus_pane) = pane_ids.get(template.focus_pane) {
+            Self::select_pane(focus_pane)?;
+        }
+
+        for window in &template.windows {
+            Self::new_window_with_command(name, &window.name, working_dir, &window.command)?;
+        }
+
+        Ok(())
+    }
+
+    fn new_session_with_pane_id(
+        name: &str,
+        working_dir: &std::path::Path,
+        command: Option<&str>,
+    ) -> Result<String> {
+        validate_session_name(name)?;
+        let dir_str = working_dir.to_string_lossy().to_string();
+        let mut args = vec![
+            "new-session",
+            "-d",
+            "-s",
+            name,
+            "-c",
+            &dir_str,
+            "-P",
+            "-F",
+            "#{pane_id}",
+        ];
+        args.extend(command);
+        let output = query_command().args(&args).output()?;
+
+        if !output.status.success() {
+            bail!("Failed to create session: {}", name);
+        }
+
+        let pane_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if pane_id.is_empty() {
+            bail!("Failed to create session: tmux did not return a pane id");
+        }
+
+        Ok(pane_id)
+    }
+
+    fn split_pane(
+        target_pane: &str,
+        split_flag: &str,
+        split_percent: Option<u8>,
+        working_dir: &std::path::Path,
+    ) -> Result<String> {
+        let dir_str = working_dir.to_string_lossy().to_string();
+        let mut command = query_command();
+        command.args(["split-window", split_flag]);
+
+        if let Some(percent) = split_percent {
+            command.args(["-p", &percent.to_string()]);
+        }
+
+        let output = command
+            .args(["-P", "-F", "#{pane_id}", "-t", target_pane, "-c", &dir_str])
+            .output()?;
+
+        if !output.status.success() {
+            bail!("Failed to split pane: {}", target_pane);
+        }
+
+        let pane_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if pane_id.is_empty() {
+            bail!("Failed to split pane: tmux did not return a pane id");
+        }
+
+        Ok(pane_id)
+    }
+
+    // Types `command` into a pane and presses Enter. Blank commands are skipped.
+    pub fn send_command_to_pane(pane_id: &str, command: &str) -> Result<()> {
+        if command.trim().is_empty() {
+            return Ok(());
+        }
+
+        let status = Command::new("tmux")
+            .args(["send-keys", "-t", pane_id, "--", command, "C-m"])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to send command to pane: {}", pane_id);
+        }
+
+        Ok(())
+    }
+
+    fn select_layout(session_name: &str, layout: &str) -> Result<()> {
+        let status = Command::new("tmux")
+            .args([
+                "select-layout",
+                "-t",
+                &current_window_target(session_name),
+                layout,
+            ])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to select layout for session: {}", session_name);
+        }
+
+        Ok(())
+    }
+
+    fn select_pane(pane_id: &str) -> Result<()> {
+        let status = Command::new("tmux")
+            .args(["select-pane", "-t", pane_id])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to select pane: {}", pane_id);
+        }
+
+        Ok(())
+    }
+
+    /* Creates session `name` in the working directory of `source_session`
+     * with the same window names, restarting each window's active pane with
+     * the command it was started with. Windows started without a command get
+     * the default shell; panes and splits are not copied. */
+    pub fn clone_session(source_session: &str, name: &str) -> Result<()> {
+        validate_session_name(name)?;
+        let target = current_window_target(source_session);
+        let working_dir = Self::query(
+            &["display-message", "-p", "-t", &target, "#{session_path}"],
+            Some(source_session),
+        )?;
+        let working_dir = working_dir.trim();
+        let recipes = parse_window_recipes(&Self::query(
+            &[
+                "list-windows",
+                "-t",
+                &session_target(source_session),
+                "-F",
+                WINDOW_RECIPE_FORMAT,
+            ],
+            Some(source_session),
+        )?);
+        let Some((first, rest)) = recipes.split_first() else {
+            bail!("Session {} has no windows to clone", source_session);
+        };
+
+        let mut args = vec![
+            "new-session",
+            "-d",
+            "-s",
+            name,
+            "-n",
+            &first.name,
+            "-c",
+            working_dir,
+        ];
+        if !first.start_command.is_empty() {
+            args.push(&first.start_command);
+        }
+        let status = Command::new("tmux").args(&args).status()?;
+        if !status.success() {
+            bail!("Failed to create session: {}", name);
+        }
+
+        let new_target = current_window_target(name);
+        for recipe in rest {
+            let mut args = vec![
+                "new-window",
+                "-d",
+                "-t",
+                &new_target,
+                "-n",
+                &recipe.name,
+                "-c",
+                working_dir,
+            ];
+            if !recipe.start_command.is_empty() {
+                args.push(&recipe.start_command);
+            }
+            let status = Command::new("tmux").args(&args).status()?;
+            if !status.success() {
+                bail!("Failed to open {} in session: {}", recipe.name, name);
+            }
+        }
+        Ok(())
+    }
+
+    // Links the active window of `source_session` into `target_session`, after
+    // its current window. Both sessions then show the same window and panes.
+    pub fn link_active_window(source_session: &str, target_session: &str) -> Result<()> {
+        let source = current_window_target(source_session);
+        let target = current_window_target(target_session);
+        let status = Command::new("tmux")
+            .args(["link-window", "-a", "-s", &source, "-t", &target])
+            .status()?;
+
+        if !status.success() {
+            bail!(
+                "Failed to mirror window from {} into {}",
+                source_session,
+                target_session
+            );
+        }
+        Ok(())
+    }
+
+    // Deletes a session by name.
+    pub fn delete_session(session_name: &str) -> Result<()> {
+        let status = Command::new("tmux")
+            .args(["kill-session", "-t", &session_target(session_name)])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to delete session: {}", session_name);
+        }
+        Ok(())
+    }
+
+    // Kills one window of a session. Killing the last window ends the session.
+    pub fn kill_window(session_name: &str, window_index: u32) -> Result<()> {
+        let target = window_target(session_name, window_index);
+        let status = Command::new("tmux")
+            .args(["kill-window", "-t", &target])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to kill window: {}", target);
+        }
+        Ok(())
+    }
+
+    // Kills the active pane of a window. Killing its last pane closes the window.
+    pub fn kill_pane(session_name: &str, window_index: u32) -> Result<()> {
+        let target = window_target(session_name, window_index);
+        let status = Command::new("tmux")
+            .args(["kill-pane", "-t", &target])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to kill pane in window: {}", target);
+        }
+        Ok(())
+    }
+
+    // Restarts the exited process of a window's active pane with its original command.
+    pub fn respawn_pane(session_name: &str, window_index: u32) -> Result<()> {
+        let target = window_target(session_name, window_index);
+        let status = Command::new("tmux")
+            .args(["respawn-pane", "-t", &target])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to respawn pane in window: {}", target);
+        }
+        Ok(())
+    }
+
+    /* Sets a window option such as `monitor-activity` or `monitor-silence`
+     * on one window of a session. */
+    pub fn set_window_option(
+        session_name: &str,
+        window_index: u32,
+        option: &str,
+        value: &str,
+    ) -> Result<()> {
+        let target = window_target(session_name, window_index);
+        let status = Command::new("tmux")
+            .args(["set-option", "-w", "-t", &target, option, value])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to set {} on window: {}", option, target);
+        }
+        Ok(())
+    }
+
+    // Deletes all sessions by killing the tmux server.
+    pub fn delete_all_sessions() -> Result<()> {
+        let status = Command::new("tmux").args(["kill-server"]).status()?;
+
+        if !status.success() {
+            bail!("Failed to delete all sessions");
+        }
+        Ok(())
+    }
+
+    // Opens a new window in a session running a shell command in the given directory.
+    pub fn new_window_with_command(
+        session_name: &str,
+        window_name: &str,
+        working_dir: &std::path::Path,
+        command: &str,
+    ) -> Result<()> {
+        let target = current_window_target(session_name);
+        let dir_str = working_dir.to_string_lossy();
+        let mut args = vec![
+            "new-window",
+            "-t",
+            &target,
+            "-n",
+            window_name,
+            "-c",
+            &dir_str,
+        ];
+        // A blank command opens the default shell
+        args.extend((!command.trim().is_empty()).then_some(command));
+        let status = Command::new("tmux").args(&args).status()?;
+
+        if !status.success() {
+            bail!(
+                "Failed to open {} in session: {}",
+                window_name,
+                session_name
+            );
+        }
+        Ok(())
+    }
+
+    // Stores a free-text note on a session in the `@trex_note` user option.
+    // An empty note unsets the option.
+    pub fn set_session_note(session_name: &str, note: &str) -> Result<()> {
+        Self::set_user_option(session_name, "@trex_note", note)
+    }
+
+    // Stores a session's tags, comma-separated, in the `@trex_tags` user option.
+    pub fn set_session_tags(session_name: &str, tags: &[String]) -> Result<()> {
+        Self::set_user_option(session_name, "@trex_tags", &tags.join(","))
+    }
+
+    /* The review queue: session targets in visiting order, kept in the
+     * server-wide `@trex_queue` user option so it outlives each TUI run. */
+    pub fn review_queue() -> Result<Vec<String>> {
+        let stdout = Self::query(&["show-options", "-gqv", "@trex_queue"], None)?;
+        Ok(stdout.split_whitespace().map(str::to_string).collect())
+    }
+
+    pub fn set_review_queue(targets: &[String]) -> Result<()> {
+        let value = targets.join(" ");
+        let mut args = vec!["set-option", "-g"];
+        if value.is_empty() {
+            args.extend(["-u", "@trex_queue"]);
+        } else {
+            args.extend(["@trex_queue", &value]);
+        }
+
+        let status = Command::new("tmux").args(&args).status()?;
+
+        if !status.success() {
+            bail!("Failed to save the review queue");
+        }
+        Ok(())
+    }
+
+    // A session's environment, as new panes in it would get it.
+    pub fn session_environment(session_name: &str) -> Result<HashMap<String, String>> {
+        let stdout = Self::query(
+            &["show-environment", "-t", &session_target(session_name)],
+            Some(session_name),
+        )?;
+        Ok(parse_environment(&stdout))
+    }
+
+    // Sets a variable in a session's environment, or removes it when `value` is None.
+    pub fn set_environment(session_name: &str, variable: &str, value: Option<&str>) -> Result<()> {
+        let target = session_target(session_name);
+        let mut args = vec!["set-environment", "-t", &target];
+        match value {
+            Some(value) => args.extend([variable, value]),
+            None => args.extend(["-u", variable]),
+        }
+
+        let status = Command::new("tmux").args(&args).status()?;
+
+        if !status.success() {
+            bail!("Failed to set {} in session: {}", variable, session_name);
+        }
+        Ok(())
+    }
+
+    // Every pane of a session as (pane id, current command), across its windows.
+    pub fn list_panes(session_name: &str) -> Result<Vec<(String, String)>> {
+        let stdout = Self::query(
+            &[
+                "list-panes",
+                "-s",
+                "-t",
+                &session_target(session_name),
+                "-F",
+                PANE_FORMAT,
+            ],
+            Some(session_name),
+        )?;
+        Ok(parse_panes(&stdout))
+    }
+
+    // The layout string of a session's active window, as `select-layout` takes it.
+    pub fn window_layout(session_name: &str) -> Result<String> {
+        let stdout = Self::query(
+            &[
+                "display-message",
+                "-p",
+                "-t",
+                &current_window_target(session_name),
+                "#{window_layout}",
+            ],
+            Some(session_name),
+        )?;
+        Ok(stdout.trim().to_string())
+    }
+
+    /* Arranges a session's active window by a saved layout string. tmux
+     * only applies a layout to a window with as many panes as it describes,
+     * so missing panes are split off the active pane first, started in its
+     * directory; a window with more panes than the layout is refused. */
+    pub fn apply_layout(session_name: &str, layout: &str) -> Result<()> {
+        let target = current_window_target(session_name);
+        let needed = layout_pane_count(layout);
+        if needed == 0 {
+            bail!("Not a tmux window layout: {}", layout);
+        }
+        let stdout = Self::query(
+            &[
+                "display-message",
+                "-p",
+                "-t",
+                &target,
+                "#{window_panes}\x1f#{pane_id}\x1f#{pane_current_path}",
+            ],
+            Some(session_name),
+        )?;
+        let fields: Vec<&str> = stdout.trim_end().split(FIELD_SEPARATOR).collect();
+        let [panes, pane_id, path] = fields[..] else {
+            bail!("Failed to read the panes of session: {}", session_name);
+        };
+        let panes: usize = panes.parse().unwrap_or(1);
+        if panes > needed {
+            bail!(
+                "{} has {} panes, the layout only {}; close some first",
+                session_name,
+                panes,
+                needed
+            );
+        }
+        for _ in panes..needed {
+            Self::split_pane(pane_id, "-h", None, std::path::Path::new(path))?;
+        }
+        Self::select_layout(session_name, layout)
+    }
+
+    // Sets a session-level user option, unsetting it when the value is empty.
+    fn set_user_option(session_name: &str, option: &str, value: &str) -> Result<()> {
+        let target = session_target(session_name);
+        let mut args = vec!["set-option", "-t", &target];
+        if value.is_empty() {
+            args.extend(["-u", option]);
+        } else {
+            args.extend([option, value]);
+        }
+
+        let status = Command::new("tmux").args(&args).status()?;
+
+        if !status.success() {
+            bail!("Failed to set {} on session: {}", option, session_name);
+        }
+        Ok(())
+    }
+
+    // Detaches all clients from a specific session.
+    pub fn detach_session(session_name: &str) -> Result<()> {
+        let status = Command::new("tmux")
+            .args(["detach-client", "-s", &session_target(session_name)])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to detach from session: {}", session_name);
+        }
+        Ok(())
+    }
+
+    // Detaches all clients from all sessions.
+    pub fn detach_all_sessions() -> Result<()> {
+        let status = Command::new("tmux")
+            .args(["detach-client", "-a"])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to detach all clients");
+        }
+        Ok(())
+    }
+
+    // Lists all windows in a session.
+    pub fn list_windows(session_name: &str) -> Result<Vec<TmuxWindow>> {
+        let stdout = Self::query(
+            &[
+                "list-windows",
+                "-t",
+                &session_target(session_name),
+                "-F",
+                WINDOW_FORMAT,
+            ],
+            Some(session_name),
+        )?;
+        Ok(parse_windows(&stdout)?)
+    }
+
+    // Lists every window of every session as (session name, window) pairs.
+    pub fn list_all_windows() -> Result<Vec<(String, TmuxWindow)>> {
+        let stdout = Self::query(&["list-windows", "-a", "-F", ALL_WINDOWS_FORMAT], None)?;
+        Ok(parse_all_windows(&stdout)?)
+    }
+
+    // Attaches to a specific window in a session.
+    pub fn attach_window(session_name: &str, window_index: u32, mode: AttachMode) -> Result<()> {
+        Self::attach_window_target(&window_target(session_name, window_index), mode)
+    }
+
+    // Switches to a specific window in a session.
+    pub fn switch_to_window(session_name: &str, window_index: u32) -> Result<()> {
+        Self::switch_to_window_target(&window_target(session_name, window_index))
+    }
+
+    // Attaches or switches to a specific window depending on whether we're inside tmux.
+    pub fn attach_or_switch_window(
+        session_name: &str,
+        window_index: u32,
+        mode: AttachMode,
+    ) -> Result<()> {
+        Self::attach_or_switch_window_target(&window_target(session_name, window_index), mode)
+    }
+
+    /* Like attach_or_switch_window, for a window as listed: targeted by its
+     * id, so it is still found after windows were renumbered. */
+    pub fn attach_or_switch_to(
+        session_name: &str,
+        window: &TmuxWindow,
+        mode: AttachMode,
+    ) -> Result<()> {
+        Self::attach_or_switch_window_target(&window.target(session_name), mode)
+    }
+
+    fn attach_or_switch_window_target(target: &str, mode: AttachMode) -> Result<()> {
+        if Self::is_inside_tmux() {
+            Self::switch_to_window_target(target)
+        } else {
+            Self::attach_window_target(target, mode)
+        }
+    }
+
+    fn attach_window_target(target: &str, mode: AttachMode) -> Result<()> {
+        Self::run_attach(
+            &["attach-session", "-t", target],
+            mode,
+            &format!("Failed to attach to window: {}", target),
+        )
+    }
+
+    fn switch_to_window_target(target: &str) -> Result<()> {
+        let status = Command::new("tmux")
+            .args(["switch-client", "-t", target])
+            .status()?;
+
+        if !status.success() {
+            bail!("Failed to switch to window: {}", target);
+        }
+        Ok(())
+    }
+
+    // Captures the content of the current pane in a session.
+    pub fn capture_pane(session_name: &str, lines: usize) -> Result<Vec<String>> {
+        let start_line = format!("-{}", lines);
+        let target = current_window_target(session_name);
+        let stdout = Self::query(
+            &["capture-pane", "-t", &target, "-p", "-S", &start_line],
+            Some(session_name),
+        )?;
+        Ok(stdout.lines().map(|l| l.to_string()).collect())
+    }
+
+    // Captures the content of one pane by id (`%3`), wherever it lives.
+    pub fn capture_pane_id(pane_id: &str, lines: usize) -> Result<Vec<String>> {
+        let start_line = format!("-{}", lines);
+        let stdout = Self::query(
+            &["capture-pane", "-t", pane_id, "-p", "-S", &start_line],
+            None,
+        )?;
+        Ok(stdout.lines().map(|l| l.to_string()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_keep_separators_and_names_under_a_c_locale() {
+        if TmuxClient::check_installed().is_err() {
+            return;
+        }
+        // A private server, with the environment cron or a bare ssh gives
+        let socket = format!("trex-test-{}", std::process::id());
+        let tmux = |args: &[&str]| {
+            query_command()
+                .env_clear()
+                .env("PATH", std::env::var_os("PATH").unwrap_or_default())
+                .env("LANG", "C")
+                .args(["-L", &socket, "-f", "/dev/null"])
+                .args(args)
+                .output()
+                .unwrap()
+        };
+        tmux(&["new-session", "-d", "-s", "h\u{e9}llo", "-c", "/tmp"]);
+        let output = tmux(&["list-sessions", "-F", SESSION_FORMAT]);
+        tmux(&["kill-server"]);
+
+        let sessions = parse_sessions(&String::from_utf8_lossy(&output.stdout)).unwrap();
+        let names: Vec<&str> = sessions.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["h\u{e9}llo"]);
+        assert_eq!(sessions[0].path, Some(std::path::PathBuf::from("/tmp")));
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+
+// A tmux failure, classified so callers can tell "nothing running" apart
+// from real errors and show something useful instead of an empty list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TmuxError {
+    ServerNotRunning,
+    NoSuchSession(String),
+    InvalidSessionName(String),
+    ParseError { line: String },
+    CommandFailed { command: String, stderr: String },
+}
+
+impl TmuxError {
+    // Classifies a failed tmux command from its stderr. `target` is the
+    // session the command was aimed at, if any.
+    pub fn from_stderr(command: &str, target: Option<&str>, stderr: &str) -> Self {
+        let stderr = stderr.trim();
+        if stderr.starts_with("no server running") || stderr.starts_with("error connecting to") {
+            return TmuxError::ServerNotRunning;
+        }
+        if stderr.starts_with("can't find session") || stderr.starts_with("session not found") {
+            let session = target
+                .map(str::to_string)
+                .or_else(|| stderr.rsplit_once(": ").map(|(_, name)| name.to_string()))
+                .unwrap_or_default();
+            return TmuxError::NoSuchSession(session);
+        }
+        TmuxError::CommandFailed {
+            command: command.to_string(),
+            stderr: stderr.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TmuxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TmuxError::ServerNotRunning => write!(f, "tmux server is not running"),
+            TmuxError::NoSuchSession(name) => write!(f, "no such session: {}", name),
+            TmuxError::InvalidSessionName(name) => write!(
+                f,
+                "invalid session name {:?}: names can't be empty, contain ':' or '.', or start with '='",
+                name
+            ),
+            TmuxError::ParseError { line } => write!(f, "could not parse tmux output: {:?}", line),
+            TmuxError::CommandFailed { command, stderr } if stderr.is_empty() => {
+                write!(f, "tmux {} failed", command)
+            }
+            TmuxError::CommandFailed { command, stderr } => {
+                write!(f, "tmux {} failed: {}", command, stderr)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TmuxError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_server_not_running() {
+        let stderr = "no server running on /tmp/tmux-1000/default\n";
+        assert_eq!(
+            TmuxError::from_stderr("list-sessions", None, stderr),
+            TmuxError::ServerNotRunning
+        );
+        let stderr = "error connecting to /tmp/tmux-1000/default (No such file or directory)";
+        assert_eq!(
+            TmuxError::from_stderr("list-sessions", None, stderr),
+            TmuxError::ServerNotRunning
+        );
+    }
+
+    #[test]
+    fn classifies_missing_session() {
+        assert_eq!(
+            TmuxError::from_stderr("list-windows", Some("api"), "can't find session: api"),
+            TmuxError::NoSuchSession("api".to_string())
+        );
+        assert_eq!(
+            TmuxError::from_stderr("list-windows", None, "can't find session: web"),
+            TmuxError::NoSuchSession("web".to_string())
+        );
+    }
+
+    #[test]
+    fn keeps_other_failures_with_stderr() {
+        let error = TmuxError::from_stderr("capture-pane", Some("api"), "bad flag\n");
+        assert_eq!(error.to_string(), "tmux capture-pane failed: bad flag");
+    }
+}
@@ -0,0 +1,557 @@
+use crate::template::SessionTemplate;
+use crate::tmux::backend::TmuxBackend;
+use crate::tmux::client::AttachedClient;
+use crate::tmux::commands::AttachMode;
+use crate::tmux::error::TmuxError;
+use crate::tmux::parser::validate_session_name;
+use crate::tmux::session::TmuxSession;
+use crate::tmux::window::TmuxWindow;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+// In-memory tmux state behind a MockTmux.
+#[derive(Debug, Default)]
+pub struct MockState {
+    pub sessions: Vec<TmuxSession>,
+    pub windows: HashMap<String, Vec<TmuxWindow>>,
+    pub clients: HashMap<String, Vec<AttachedClient>>,
+    pub panes: HashMap<String, Vec<String>>, // captured lines per session, or per pane id
+    pub review_queue: Vec<String>,
+    pub environment: HashMap<String, HashMap<String, String>>, // per session
+    pub pane_commands: HashMap<String, Vec<(String, String)>>, // (pane id, command) per session
+    pub layouts: HashMap<String, String>,                      // active window layout per session
+    pub calls: Vec<String>, // every operation, as the tmux command it stands for
+    pub list_error: Option<TmuxError>, // list-sessions fails with this while set
+    next_id: u32,
+}
+
+impl MockState {
+    // Adds a detached, single-window session with the next `$N` id.
+    pub fn add_session(&mut self, name: &str) -> &mut TmuxSession {
+        let mut session = mock_session(name);
+        session.id = format!("${}", self.next_id);
+        self.next_id += 1;
+        self.sessions.push(session);
+        self.sessions.last_mut().expect("session was just pushed")
+    }
+
+    // The name of the session a target (a name or `$id`) refers to, which keys the maps.
+    fn name_of(&self, target: &str) -> String {
+        self.sessions
+            .iter()
+            .find(|session| is_target(session, target))
+            .map_or_else(|| target.to_string(), |session| session.name.clone())
+    }
+}
+
+/* A TmuxBackend that keeps sessions in memory and records each call.
+ * Clones share state, so a test can hand one clone to the app and inspect
+ * the other. Operations on a missing session fail with NoSuchSession. */
+#[derive(Debug, Clone, Default)]
+pub struct MockTmux {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockTmux {
+    // Creates a mock server with detached, single-window sessions.
+    pub fn with_sessions(names: &[&str]) -> Self {
+        let mock = Self::default();
+        let mut state = mock.state();
+        for name in names {
+            state.add_session(name);
+        }
+        drop(state);
+        mock
+    }
+
+    pub fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn calls(&self) -> Vec<String> {
+        self.state().calls.clone()
+    }
+
+    pub fn session_names(&self) -> Vec<String> {
+        self.state()
+            .sessions
+            .iter()
+            .map(|session| session.name.clone())
+            .collect()
+    }
+
+    // Renames a session as `tmux rename-session` run elsewhere would,
+    // keeping its id and moving its windows, clients, and pane text.
+    pub fn rename_session(&self, old_name: &str, new_name: &str) {
+        let mut state = self.state();
+        if let Some(session) = state.sessions.iter_mut().find(|s| s.name == old_name) {
+            session.name = new_name.to_string();
+        }
+        if let Some(windows) = state.windows.remove(old_name) {
+            state.windows.insert(new_name.to_string(), windows);
+        }
+        if let Some(clients) = state.clients.remove(old_name) {
+            state.clients.insert(new_name.to_string(), clients);
+        }
+        if let Some(panes) = state.panes.remove(old_name) {
+            state.panes.insert(new_name.to_string(), panes);
+        }
+    }
+
+    fn record(&self, call: String) -> MutexGuard<'_, MockState> {
+        let mut state = self.state();
+        state.calls.push(call);
+        state
+    }
+
+    // Records a call that must target an existing session, by name or id.
+    fn record_on(&self, call: String, session_name: &str) -> Result<MutexGuard<'_, MockState>> {
+        let state = self.record(call);
+        if !state.sessions.iter().any(|s| is_target(s, session_name)) {
+            return Err(TmuxError::NoSuchSession(session_name.to_string()).into());
+        }
+        Ok(state)
+    }
+
+    fn with_session(
+        &self,
+        call: String,
+        session_name: &str,
+        update: impl FnOnce(&mut TmuxSession),
+    ) -> Result<()> {
+        let mut state = self.record_on(call, session_name)?;
+        if let Some(session) = state
+            .sessions
+            .iter_mut()
+            .find(|s| is_target(s, session_name))
+        {
+            update(session);
+        }
+        Ok(())
+    }
+}
+
+// Whether a tmux target (a session name or `$id`) refers to the session.
+fn is_target(session: &TmuxSession, target: &str) -> bool {
+    session.name == target || session.id == target
+}
+
+fn mock_session(name: &str) -> TmuxSession {
+    TmuxSession {
+        id: String::new(),
+        name: name.to_string(),
+        attached: false,
+        clients: 0,
+        windows: 1,
+        panes: 1,
+        size_constrained: false,
+        path: None,
+        created: None,
+        last_activity: None,
+        tags: Vec::new(),
+        note: None,
+        alerts: Default::default(),
+        git_status: None,
+        stats: None,
+        cpu_history: Vec::new(),
+        mem_history: Vec::new(),
+    }
+}
+
+impl TmuxBackend for MockTmux {
+    fn list_sessions(&self) -> Result<Vec<TmuxSession>> {
+        let state = self.record("list-sessions".to_string());
+        match &state.list_error {
+            Some(error) => Err(error.clone().into()),
+            None => Ok(state.sessions.clone()),
+        }
+    }
+
+    fn list_windows(&self, session_name: &str) -> Result<Vec<TmuxWindow>> {
+        let state = self.record_on(format!("list-windows {}", session_name), session_name)?;
+        let name = state.name_of(session_name);
+        Ok(state.windows.get(&name).cloned().unwrap_or_default())
+    }
+
+    fn list_all_windows(&self) -> Result<Vec<(String, TmuxWindow)>> {
+        let state = self.record("list-windows -a".to_string());
+        Ok(state
+            .sessions
+            .iter()
+            .flat_map(|session| {
+                let windows = state
+                    .windows
+                    .get(&session.name)
+                    .cloned()
+                    .unwrap_or_default();
+                windows
+                    .into_iter()
+                    .map(|window| (session.name.clone(), window))
+            })
+            .collect())
+    }
+
+    fn list_clients(&self, session_name: &str) -> Result<Vec<AttachedClient>> {
+        let state = self.record_on(format!("list-clients {}", session_name), session_name)?;
+        let name = state.name_of(session_name);
+        Ok(state.clients.get(&name).cloned().unwrap_or_default())
+    }
+
+    fn capture_pane(&self, session_name: &str, _lines: usize) -> Result<Vec<String>> {
+        let state = self.record_on(format!("capture-pane {}", session_name), session_name)?;
+        let name = state.name_of(session_name);
+        Ok(state.panes.get(&name).cloned().unwrap_or_default())
+    }
+
+    // Pane contents by pane id share `panes` with the per-session captures.
+    fn capture_pane_id(&self, pane_id: &str, _lines: usize) -> Result<Vec<String>> {
+        let state = self.record(format!("capture-pane {}", pane_id));
+        match state.panes.get(pane_id) {
+            Some(lines) => Ok(lines.clone()),
+            None => anyhow::bail!("can't find pane: {}", pane_id),
+        }
+    }
+
+    fn new_session_from_template(
+        &self,
+        name: &str,
+        working_dir: &Path,
+        template: &SessionTemplate,
+    ) -> Result<()> {
+        validate_session_name(name)?;
+        let mut state = self.record(format!("new-session {} ({})", name, template.name));
+        state.add_session(name).path = Some(working_dir.to_path_buf());
+        Ok(())
+    }
+
+    fn new_session_with_command(
+        &self,
+        name: &str,
+        working_dir: &Path,
+        template: &SessionTemplate,
+        command: &str,
+    ) -> Result<()> {
+        validate_session_name(name)?;
+        let call = format!("new-session {} ({}) {}", name, template.name, command);
+        let mut state = self.record(call);
+        state.add_session(name).path = Some(working_dir.to_path_buf());
+        Ok(())
+    }
+
+    fn new_window_with_command(
+        &self,
+        session_name: &str,
+        window_name: &str,
+        _working_dir: &Path,
+        command: &str,
+    ) -> Result<()> {
+        let call = format!("new-window {} {} {}", session_name, window_name, command);
+        self.with_session(call, session_name, |session| session.windows += 1)
+    }
+
+    // Copies the source's path, window count, and window list.
+    fn clone_session(&self, source_session: &str, name: &str) -> Result<()> {
+        validate_session_name(name)?;
+        let call = format!("clone-session {} {}", source_session, name);
+        let mut state = self.record_on(call, source_session)?;
+        let source = state
+            .sessions
+            .iter()
+            .find(|s| is_target(s, source_session))
+            .map(|s| (s.path.clone(), s.windows));
+        let session = state.add_session(name);
+        if let Some((path, windows)) = source {
+            session.path = path;
+            session.windows = windows;
+        }
+        if let Some(windows) = state.windows.get(source_session).cloned() {
+            state.windows.insert(name.to_string(), windows);
+        }
+        Ok(())
+    }
+
+    fn link_active_window(&self, source_session: &str, target_session: &str) -> Result<()> {
+        let call = format!("link-window {} {}", source_session, target_session);
+        let mut state = self.record_on(call, source_session)?;
+        match state
+            .sessions
+            .iter_mut()
+            .find(|s| is_target(s, target_session))
+        {
+            Some(session) => {
+                session.windows += 1;
+                Ok(())
+            }
+            None => Err(TmuxError::NoSuchSession(target_session.to_string()).into()),
+        }
+    }
+
+    fn set_session_note(&self, session_name: &str, note: &str) -> Result<()> {
+        let call = format!("set-option {} @trex_note {}", session_name, note);
+        self.with_session(call, session_name, |session| {
+            session.note = (!note.is_empty()).then(|| note.to_string());
+        })
+    }
+
+    fn set_session_tags(&self, session_name: &str, tags: &[String]) -> Result<()> {
+        let call = format!("set-option {} @trex_tags {}", session_name, tags.join(","));
+        self.with_session(call, session_name, |session| session.tags = tags.to_vec())
+    }
+
+    fn review_queue(&self) -> Result<Vec<String>> {
+        Ok(self.state().review_queue.clone())
+    }
+
+    fn set_review_queue(&self, targets: &[String]) -> Result<()> {
+        let mut state = self.record(format!("set-option -g @trex_queue {}", targets.join(" ")));
+        state.review_queue = targets.to_vec();
+        Ok(())
+    }
+
+    fn fit_windows_to_largest_client(&self, session_name: &str) -> Result<()> {
+        let call = format!("resize-window -A {}", session_name);
+        self.with_session(call, session_name, |session| {
+            session.size_constrained = false
+        })
+    }
+
+    fn send_to_pane(&self, pane_id: &str, text: &str) -> Result<()> {
+        drop(self.record(format!("send-keys {} {}", pane_id, text)));
+        Ok(())
+    }
+
+    fn session_environment(&self, session_name: &str) -> Result<HashMap<String, String>> {
+        let state = self.record_on(format!("show-environment {}", session_name), session_name)?;
+        Ok(state
+            .environment
+            .get(session_name)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn set_environment(
+        &self,
+        session_name: &str,
+        variable: &str,
+        value: Option<&str>,
+    ) -> Result<()> {
+        let call = match value {
+            Some(value) => format!("set-environment {} {} {}", session_name, variable, value),
+            None => format!("set-environment {} -u {}", session_name, variable),
+        };
+        let mut state = self.record_on(call, session_name)?;
+        let environment = state
+            .environment
+            .entry(session_name.to_string())
+            .or_default();
+        match value {
+            Some(value) => environment.insert(variable.to_string(), value.to_string()),
+            None => environment.remove(variable),
+        };
+        Ok(())
+    }
+
+    fn list_panes(&self, session_name: &str) -> Result<Vec<(String, String)>> {
+        let state = self.record_on(format!("list-panes {}", session_name), session_name)?;
+        Ok(state
+            .pane_commands
+            .get(session_name)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn window_layout(&self, session_name: &str) -> Result<String> {
+        let state = self.record_on(format!("display-message {}", session_name), session_name)?;
+        Ok(state
+            .layouts
+            .get(session_name)
+            .cloned()
+            .unwrap_or_else(|| "b25d,80x24,0,0,1".to_string()))
+    }
+
+    fn apply_layout(&self, session_name: &str, layout: &str) -> Result<()> {
+        let call = format!("select-layout {} {}", session_name, layout);
+        let mut state = self.record_on(call, session_name)?;
+        state
+            .layouts
+            .insert(session_name.to_string(), layout.to_string());
+        Ok(())
+    }
+
+    fn delete_session(&self, session_name: &str) -> Result<()> {
+        let mut state = self.record_on(format!("kill-session {}", session_name), session_name)?;
+        state
+            .sessions
+            .retain(|session| !is_target(session, session_name));
+        Ok(())
+    }
+
+    fn delete_all_sessions(&self) -> Result<()> {
+        self.record("kill-server".to_string()).sessions.clear();
+        Ok(())
+    }
+
+    // Removes the window; the mock does not track panes, so the last
+    // window going away ends the session as in tmux.
+    fn kill_window(&self, session_name: &str, window_index: u32) -> Result<()> {
+        let call = format!("kill-window {}:{}", session_name, window_index);
+        let mut state = self.record_on(call, session_name)?;
+        let windows = state.windows.entry(session_name.to_string()).or_default();
+        windows.retain(|window| window.index != window_index);
+        if windows.is_empty() {
+            state
+                .sessions
+                .retain(|session| session.name != session_name);
+        }
+        Ok(())
+    }
+
+    fn kill_pane(&self, session_name: &str, window_index: u32) -> Result<()> {
+        let call = format!("kill-pane {}:{}", session_name, window_index);
+        self.with_session(call, session_name, |session| {
+            session.panes = session.panes.saturating_sub(1)
+        })
+    }
+
+    fn respawn_pane(&self, session_name: &str, window_index: u32) -> Result<()> {
+        let call = format!("respawn-pane {}:{}", session_name, window_index);
+        let mut state = self.record_on(call, session_name)?;
+        if let Some(windows) = state.windows.get_mut(session_name) {
+            for window in windows.iter_mut().filter(|w| w.index == window_index) {
+                window.dead = false;
+            }
+        }
+        Ok(())
+    }
+
+    fn set_window_option(
+        &self,
+        session_name: &str,
+        window_index: u32,
+        option: &str,
+        value: &str,
+    ) -> Result<()> {
+        let call = format!(
+            "set-option -w {}:{} {} {}",
+            session_name, window_index, option, value
+        );
+        let mut state = self.record_on(call, session_name)?;
+        if let Some(windows) = state.windows.get_mut(session_name) {
+            for window in windows.iter_mut().filter(|w| w.index == window_index) {
+                match option {
+                    "monitor-activity" => window.monitor_activity = value == "on",
+                    "monitor-silence" => window.monitor_silence = value.parse().unwrap_or(0),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn detach_session(&self, session_name: &str) -> Result<()> {
+        let call = format!("detach-client -s {}", session_name);
+        self.with_session(call, session_name, |session| {
+            session.attached = false;
+            session.clients = 0;
+        })
+    }
+
+    fn detach_all_sessions(&self) -> Result<()> {
+        let mut state = self.record("detach-client -a".to_string());
+        for session in &mut state.sessions {
+            session.attached = false;
+            session.clients = 0;
+        }
+        Ok(())
+    }
+
+    fn detach_client(&self, tty: &str) -> Result<()> {
+        let mut state = self.record(format!("detach-client -t {}", tty));
+        for clients in state.clients.values_mut() {
+            clients.retain(|client| client.tty != tty);
+        }
+        Ok(())
+    }
+
+    fn attach(&self, session_name: &str, _mode: AttachMode) -> Result<()> {
+        drop(self.record_on(format!("attach {}", session_name), session_name)?);
+        Ok(())
+    }
+
+    fn attach_read_only(&self, session_name: &str, _mode: AttachMode) -> Result<()> {
+        drop(self.record_on(format!("attach -r {}", session_name), session_name)?);
+        Ok(())
+    }
+
+    fn attach_detach_others(&self, session_name: &str, _mode: AttachMode) -> Result<()> {
+        drop(self.record_on(format!("attach -d {}", session_name), session_name)?);
+        Ok(())
+    }
+
+    fn attach_or_switch(&self, session_name: &str, mode: AttachMode) -> Result<()> {
+        self.attach(session_name, mode)
+    }
+
+    fn attach_or_switch_window(
+        &self,
+        session_name: &str,
+        window_index: u32,
+        _mode: AttachMode,
+    ) -> Result<()> {
+        let call = format!("attach {}:{}", session_name, window_index);
+        drop(self.record_on(call, session_name)?);
+        Ok(())
+    }
+
+    fn attach_or_switch_to(
+        &self,
+        session_name: &str,
+        window: &TmuxWindow,
+        _mode: AttachMode,
+    ) -> Result<()> {
+        let call = format!(
+            "attach {}",
+            window.target(session_name).trim_start_matches('=')
+        );
+        drop(self.record_on(call, session_name)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kills_and_notes_update_state() {
+        let mock = MockTmux::with_sessions(&["dev", "api"]);
+        mock.set_session_note("api", "waiting on review").unwrap();
+        mock.delete_session("dev").unwrap();
+
+        let sessions = mock.list_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].note.as_deref(), Some("waiting on review"));
+        assert_eq!(
+            mock.calls(),
+            vec![
+                "set-option api @trex_note waiting on review",
+                "kill-session dev",
+                "list-sessions",
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_session_is_an_error() {
+        let mock = MockTmux::with_sessions(&["dev"]);
+        let error = mock.delete_session("gone").unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<TmuxError>(),
+            Some(&TmuxError::NoSuchSession("gone".to_string()))
+        );
+    }
+}
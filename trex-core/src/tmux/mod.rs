@@ -0,0 +1,19 @@
+pub mod backend;
+pub mod client;
+pub mod commands;
+pub mod error;
+pub mod mock;
+pub mod parser;
+pub mod session;
+pub mod window;
+
+pub use backend::TmuxBackend;
+pub use client::AttachedClient;
+pub use commands::{AttachMode, TmuxClient};
+pub use error::TmuxError;
+pub use mock::{MockState, MockTmux};
+pub use session::{
+    ActivityLevel, ActivityThresholds, DORMANT_AFTER_MINS, IDLE_AFTER_MINS, TmuxSession,
+    activity_thresholds, find_matching_session_index, format_elapsed, set_activity_thresholds,
+};
+pub use window::{TmuxWindow, WindowAlerts};
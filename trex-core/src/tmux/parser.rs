@@ -0,0 +1,422 @@
+use crate::tmux::error::TmuxError;
+use crate::tmux::session::TmuxSession;
+use crate::tmux::window::WindowAlerts;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/* Separates the fields of every `-F` format trex asks tmux for. It is the
+ * ASCII unit separator, which can't be typed into a session or window name
+ * the way `|` can, so names and notes never split into extra fields.
+ * Queries run through `query_command`, so a non-UTF-8 locale can't make
+ * tmux print it as `_`. */
+pub const FIELD_SEPARATOR: char = '\x1f';
+
+// Format string passed to `tmux list-sessions -F`.
+pub const SESSION_FORMAT: &str = "#{session_name}\x1f#{session_attached}\x1f#{session_windows}\x1f#{session_path}\x1f#{session_activity}\x1f#{@trex_tags}\x1f#{session_id}\x1f#{session_created}\x1f#{session_alerts}\x1f#{@trex_note}";
+
+/* Parses the output of `tmux list-sessions` into a list of sessions. A
+ * malformed line is logged and skipped, so one odd session doesn't hide
+ * the rest; only output where no line parses is an error. */
+pub fn parse_sessions(output: &str) -> Result<Vec<TmuxSession>, TmuxError> {
+    let mut sessions = Vec::new();
+    let mut first_bad = None;
+    for line in output.lines().filter(|line| !line.is_empty()) {
+        match parse_session_line(line) {
+            Some(session) => sessions.push(session),
+            None => {
+                log::warn!("skipping unparsable tmux session line: {:?}", line);
+                first_bad.get_or_insert(line);
+            }
+        }
+    }
+    match first_bad {
+        Some(line) if sessions.is_empty() => Err(TmuxError::ParseError {
+            line: line.to_string(),
+        }),
+        _ => Ok(sessions),
+    }
+}
+
+// Expected format: SESSION_FORMAT, `name attached windows path activity tags id created alerts note`.
+// The note is free text, so it is the last field and keeps any separator.
+fn parse_session_line(line: &str) -> Option<TmuxSession> {
+    let parts: Vec<&str> = line.splitn(10, FIELD_SEPARATOR).collect();
+
+    if parts.len() < 4 {
+        return None;
+    }
+
+    let name = parts[0].to_string();
+    let clients: u32 = parts[1].parse().unwrap_or(0);
+    let windows: u32 = parts[2].parse().unwrap_or(0);
+    let path = if parts[3].is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(parts[3]))
+    };
+    let last_activity = parts.get(4).and_then(|s| s.parse().ok());
+    let tags = parts
+        .get(5)
+        .map(|tags| parse_tags(tags))
+        .unwrap_or_default();
+    let id = parts.get(6).map(|id| id.to_string()).unwrap_or_default();
+    let created = parts.get(7).and_then(|s| s.parse().ok());
+    let alerts = parts
+        .get(8)
+        .map(|alerts| WindowAlerts::from_session_alerts(alerts))
+        .unwrap_or_default();
+    let note = parts
+        .get(9)
+        .filter(|note| !note.is_empty())
+        .map(|note| note.to_string());
+
+    Some(TmuxSession {
+        id,
+        name,
+        attached: clients > 0,
+        clients,
+        windows,
+        panes: 0,
+        size_constrained: false,
+        path,
+        created,
+        last_activity,
+        tags,
+        note,
+        alerts,
+        git_status: None,
+        stats: None,
+        cpu_history: Vec::new(),
+        mem_history: Vec::new(),
+    })
+}
+
+/* Splits user-entered or stored tags on commas and whitespace. A leading
+ * `#` is dropped, tags are lowercased, and characters outside letters,
+ * digits, `-`, `_`, `.`, and `/` are removed so tags stay safe to store in a
+ * comma-separated tmux option. Duplicates are dropped, keeping first order. */
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for raw in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        let tag: String = raw
+            .trim_start_matches('#')
+            .chars()
+            .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+            .flat_map(char::to_lowercase)
+            .collect();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/* The `-t` target for a session. A bare name is matched loosely by tmux
+ * (an unambiguous prefix, then an fnmatch pattern), so `api` can act on
+ * `api-v2` once `api` is gone; the `=` prefix asks for an exact match.
+ * Session ids (`$3`) are already exact and pass through unchanged. */
+pub fn session_target(session: &str) -> String {
+    let is_id = session
+        .strip_prefix('$')
+        .is_some_and(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()));
+    if is_id {
+        session.to_string()
+    } else {
+        format!("={}", session)
+    }
+}
+
+// The target for one window of a session, by index.
+pub fn window_target(session: &str, window_index: u32) -> String {
+    format!("{}:{}", session_target(session), window_index)
+}
+
+// The target for a session's current window (or pane), for window commands.
+pub fn current_window_target(session: &str) -> String {
+    format!("{}:", session_target(session))
+}
+
+/* Checks a name before a session is created with it. tmux parses `:` and
+ * `.` in targets as window and pane separators and a leading `=` as the
+ * exact-match marker, so sessions named that way can't be targeted
+ * reliably; the directory picker's sanitize_session_name avoids all three. */
+pub fn validate_session_name(name: &str) -> Result<(), TmuxError> {
+    if name.is_empty() || name.contains([':', '.']) || name.starts_with('=') {
+        return Err(TmuxError::InvalidSessionName(name.to_string()));
+    }
+    Ok(())
+}
+
+// Counts panes per session from `tmux list-panes -a -F '#{session_name}'`.
+pub fn parse_pane_counts(output: &str) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for line in output.lines().filter(|line| !line.is_empty()) {
+        *counts.entry(line.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/* Parses `tmux show-environment` output: `NAME=value` lines. Variables
+ * marked for removal from the global environment (`-NAME`) are left out. */
+pub fn parse_environment(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with('-'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+// Format string for listing a session's panes with `tmux list-panes -s -F`.
+pub const PANE_FORMAT: &str = "#{pane_id}\x1f#{pane_current_command}";
+
+// Parses `tmux list-panes` output into (pane id, current command) pairs.
+pub fn parse_panes(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(FIELD_SEPARATOR))
+        .map(|(id, command)| (id.to_string(), command.to_string()))
+        .collect()
+}
+
+/* Counts the panes a `#{window_layout}` string describes, the number a
+ * window needs before `select-layout` accepts it. The string is a checksum
+ * then nested cells, `WxH,X,Y` followed by either a pane id (a pane) or
+ * `{...}`/`[...]` children (a split); with the brackets read as commas, a
+ * pane is a size whose third field after it isn't another size. */
+pub fn layout_pane_count(layout: &str) -> usize {
+    let Some((_checksum, cells)) = layout.split_once(',') else {
+        return 0;
+    };
+    let fields: Vec<&str> = cells
+        .split([',', '{', '}', '[', ']'])
+        .filter(|field| !field.is_empty())
+        .collect();
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(idx, field)| {
+            field.contains('x') && fields.get(idx + 3).is_some_and(|next| !next.contains('x'))
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_session_line() {
+        let line = "dev\x1f1\x1f3\x1f/home/user/project\x1f1700000000";
+        let session = parse_session_line(line).unwrap();
+        assert_eq!(session.name, "dev");
+        assert!(session.attached);
+        assert_eq!(session.clients, 1);
+        assert_eq!(session.windows, 3);
+        assert_eq!(session.path, Some(PathBuf::from("/home/user/project")));
+        assert_eq!(session.last_activity, Some(1700000000));
+    }
+
+    #[test]
+    fn test_parse_session_no_path() {
+        let line = "scratch\x1f0\x1f1\x1f\x1f";
+        let session = parse_session_line(line).unwrap();
+        assert_eq!(session.name, "scratch");
+        assert!(!session.attached);
+        assert_eq!(session.windows, 1);
+        assert_eq!(session.path, None);
+        assert_eq!(session.last_activity, None);
+    }
+
+    #[test]
+    fn test_parse_session_no_activity() {
+        let line = "test\x1f0\x1f2\x1f/tmp";
+        let session = parse_session_line(line).unwrap();
+        assert_eq!(session.name, "test");
+        assert_eq!(session.path, Some(PathBuf::from("/tmp")));
+        assert_eq!(session.last_activity, None);
+    }
+
+    #[test]
+    fn test_parse_session_note() {
+        let line = "api\x1f0\x1f1\x1f/tmp\x1f1700000000\x1f\x1f$4\x1f1690000000\x1f2!\x1ffixing CI | waiting on review";
+        let session = parse_session_line(line).unwrap();
+        assert_eq!(
+            session.note.as_deref(),
+            Some("fixing CI | waiting on review")
+        );
+        assert_eq!(session.created, Some(1690000000));
+        assert!(session.alerts.bell);
+
+        let session =
+            parse_session_line("api\x1f0\x1f1\x1f/tmp\x1f1700000000\x1f\x1f\x1f\x1f\x1f").unwrap();
+        assert_eq!(session.note, None);
+        assert!(!session.alerts.any());
+    }
+
+    #[test]
+    fn test_parse_environment_and_panes() {
+        let env = parse_environment("DISPLAY=:1\n-SSH_AUTH_SOCK\nPATH=/bin:/usr/bin\n");
+        assert_eq!(env.get("DISPLAY").map(String::as_str), Some(":1"));
+        assert_eq!(env.get("PATH").map(String::as_str), Some("/bin:/usr/bin"));
+        assert!(!env.contains_key("SSH_AUTH_SOCK"));
+
+        assert_eq!(
+            parse_panes("%1\x1fzsh\n%4\x1fclaude\n"),
+            [
+                ("%1".to_string(), "zsh".to_string()),
+                ("%4".to_string(), "claude".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_layout_pane_count() {
+        assert_eq!(layout_pane_count("b25d,80x24,0,0,1"), 1);
+        assert_eq!(
+            layout_pane_count(
+                "5e8b,159x40,0,0{79x40,0,0,2,79x40,80,0[79x20,80,0,3,79x19,80,21,4]}"
+            ),
+            3
+        );
+        assert_eq!(layout_pane_count("even-horizontal"), 0);
+    }
+
+    #[test]
+    fn test_parse_session_tags() {
+        let session =
+            parse_session_line("api\x1f0\x1f1\x1f/tmp\x1f1700000000\x1fwork,ai\x1f").unwrap();
+        assert_eq!(session.tags, vec!["work", "ai"]);
+
+        let session = parse_session_line("api\x1f0\x1f1\x1f/tmp\x1f1700000000\x1f\x1f").unwrap();
+        assert!(session.tags.is_empty());
+    }
+
+    #[test]
+    fn test_parse_session_rejects_substituted_separators() {
+        // tmux without `-u` under LANG=C prints the separator as `_`
+        assert!(parse_session_line("api_0_1_/tmp_1700000000___$0").is_none());
+        assert!(parse_sessions("api_0_1_/tmp_1700000000___$0\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_session_id() {
+        let session =
+            parse_session_line("api\x1f0\x1f1\x1f/tmp\x1f1700000000\x1fwork\x1f$12\x1f").unwrap();
+        assert_eq!(session.id, "$12");
+        assert_eq!(session.target(), "$12");
+
+        let session = parse_session_line("api\x1f0\x1f1\x1f/tmp").unwrap();
+        assert!(session.id.is_empty());
+        assert_eq!(session.target(), "api");
+    }
+
+    #[test]
+    fn test_parse_tags_normalizes_input() {
+        assert_eq!(
+            parse_tags("#Work, ai  experiments work \x1fx"),
+            vec!["work", "ai", "experiments", "x"]
+        );
+        assert!(parse_tags(" , # ").is_empty());
+    }
+
+    #[test]
+    fn test_parse_session_multiple_clients() {
+        let line = "pair\x1f2\x1f1\x1f/tmp\x1f1700000000";
+        let session = parse_session_line(line).unwrap();
+        assert!(session.attached);
+        assert_eq!(session.clients, 2);
+    }
+
+    #[test]
+    fn test_parse_pane_counts() {
+        let counts = parse_pane_counts("dev\ndev\nscratch\ndev\n");
+        assert_eq!(counts.get("dev"), Some(&3));
+        assert_eq!(counts.get("scratch"), Some(&1));
+        assert_eq!(counts.get("missing"), None);
+    }
+
+    #[test]
+    fn test_parse_session_adversarial_names() {
+        let line = [
+            "a|b",
+            "0",
+            "1",
+            "/tmp/x|y",
+            "1700000000",
+            "",
+            "$3",
+            "1690000000",
+            "",
+            "note: a|b",
+        ]
+        .join("\x1f");
+        let session = parse_session_line(&line).unwrap();
+        assert_eq!(session.name, "a|b");
+        assert_eq!(session.path, Some(PathBuf::from("/tmp/x|y")));
+        assert_eq!(session.id, "$3");
+        assert_eq!(session.note.as_deref(), Some("note: a|b"));
+
+        // Unicode, spaces, and an empty name stay in their own field
+        let sessions = parse_sessions("日本 語\x1f1\x1f2\x1f/tmp\n\x1f0\x1f1\x1f/tmp\n").unwrap();
+        assert_eq!(sessions[0].name, "日本 語");
+        assert_eq!(sessions[0].windows, 2);
+        assert_eq!(sessions[1].name, "");
+
+        // The old `|`-separated output is reported, not misread
+        assert!(parse_sessions("dev|1|3|/tmp|1700000000\n").is_err());
+    }
+
+    #[test]
+    fn test_targets_match_sessions_exactly() {
+        assert_eq!(session_target("api"), "=api");
+        assert_eq!(session_target("=odd"), "==odd");
+        assert_eq!(session_target("$12"), "$12");
+        assert_eq!(session_target("$name"), "=$name");
+        assert_eq!(window_target("api", 3), "=api:3");
+        assert_eq!(window_target("$4", 0), "$4:0");
+        assert_eq!(current_window_target("my-api"), "=my-api:");
+    }
+
+    #[test]
+    fn test_validate_session_name() {
+        assert!(validate_session_name("api-v2_x").is_ok());
+        assert!(validate_session_name("日本").is_ok());
+        for name in ["", "a:b", "v1.2", "=api"] {
+            assert_eq!(
+                validate_session_name(name),
+                Err(TmuxError::InvalidSessionName(name.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_sessions() {
+        let output =
+            "dev\x1f1\x1f3\x1f/home/user/project\x1f1700000000\nscratch\x1f0\x1f1\x1f\x1f\n";
+        let sessions = parse_sessions(output).unwrap();
+        assert_eq!(sessions.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_sessions_skips_bad_lines() {
+        let output = "dev\x1f1\x1f3\x1f/tmp\x1f1700000000
+garbage
+scratch\x1f0\x1f1\x1f\x1f
+";
+        let names: Vec<String> = parse_sessions(output)
+            .unwrap()
+            .into_iter()
+            .map(|session| session.name)
+            .collect();
+        assert_eq!(names, ["dev", "scratch"]);
+
+        // Only output where nothing parses is an error
+        assert_eq!(
+            parse_sessions("garbage\nmore garbage\n").unwrap_err(),
+            TmuxError::ParseError {
+                line: "garbage".to_string()
+            }
+        );
+    }
+}
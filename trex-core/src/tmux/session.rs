@@ -0,0 +1,177 @@
+use crate::git::GitStatus;
+use crate::sysinfo::SessionStats;
+use crate::tmux::window::WindowAlerts;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Activity level based on time since last activity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActivityLevel {
+    Active,  // < idle_after_mins
+    Idle,    // idle_after_mins to dormant_after_mins
+    Dormant, // > dormant_after_mins
+}
+
+// Default minutes without activity before a session counts as idle, then dormant.
+pub const IDLE_AFTER_MINS: u64 = 5;
+pub const DORMANT_AFTER_MINS: u64 = 30;
+
+// Minutes without activity before a session counts as idle, then dormant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivityThresholds {
+    pub idle_after_mins: u64,
+    pub dormant_after_mins: u64,
+}
+
+impl Default for ActivityThresholds {
+    fn default() -> Self {
+        Self {
+            idle_after_mins: IDLE_AFTER_MINS,
+            dormant_after_mins: DORMANT_AFTER_MINS,
+        }
+    }
+}
+
+impl ActivityThresholds {
+    // Classifies a session by the seconds since its last activity.
+    pub fn level(&self, elapsed_secs: u64) -> ActivityLevel {
+        let elapsed_mins = elapsed_secs / 60;
+        if elapsed_mins < self.idle_after_mins {
+            ActivityLevel::Active
+        } else if elapsed_mins < self.dormant_after_mins {
+            ActivityLevel::Idle
+        } else {
+            ActivityLevel::Dormant
+        }
+    }
+}
+
+// Thresholds from the user's config, set once at startup. Sessions are
+// classified in many places, so they're process-wide rather than threaded
+// through every caller.
+static ACTIVITY_THRESHOLDS: OnceLock<ActivityThresholds> = OnceLock::new();
+
+// Sets the thresholds `activity_level` uses. Only the first call takes effect.
+pub fn set_activity_thresholds(thresholds: ActivityThresholds) {
+    let _ = ACTIVITY_THRESHOLDS.set(thresholds);
+}
+
+// The thresholds in effect: the configured ones, or the defaults if unset.
+pub fn activity_thresholds() -> ActivityThresholds {
+    ACTIVITY_THRESHOLDS.get().copied().unwrap_or_default()
+}
+
+#[derive(Debug, Clone)]
+pub struct TmuxSession {
+    pub id: String, // tmux `session_id` (`$3`); stable across renames, empty if unknown
+    pub name: String,
+    pub attached: bool,
+    pub clients: u32, // Number of clients attached to the session
+    pub windows: u32,
+    pub panes: u32,             // Total panes across all windows
+    pub size_constrained: bool, // Attached clients differ in size, so the smallest limits windows
+    pub path: Option<PathBuf>,
+    pub created: Option<u64>, // unix time the session was created
+    pub last_activity: Option<u64>,
+    pub tags: Vec<String>, // Lowercase tags from the `@trex_tags` user option
+    pub note: Option<String>, // Free-text note from the `@trex_note` user option
+    pub alerts: WindowAlerts, // Bell/activity/silence flags raised in any of its windows
+    pub git_status: Option<GitStatus>,
+    pub stats: Option<SessionStats>,
+    pub cpu_history: Vec<Option<u64>>, // Last 20 samples for sparkline, None = gap
+    pub mem_history: Vec<Option<u64>>, // Last 20 samples for sparkline, None = gap
+}
+
+impl TmuxSession {
+    // The tmux target for commands: the session id when known, so a rename
+    // done elsewhere doesn't redirect them, else the name.
+    pub fn target(&self) -> &str {
+        if self.id.is_empty() {
+            &self.name
+        } else {
+            &self.id
+        }
+    }
+
+    // Whether two snapshots describe the same tmux session, by id when both
+    // have one and by name otherwise.
+    pub fn is_same(&self, other: &TmuxSession) -> bool {
+        if self.id.is_empty() || other.id.is_empty() {
+            self.name == other.name
+        } else {
+            self.id == other.id
+        }
+    }
+
+    // Returns a string suitable for fuzzy matching (name + path + note).
+    pub fn match_string(&self) -> String {
+        let mut haystack = match &self.path {
+            Some(p) => format!("{} {}", self.name, p.display()),
+            None => self.name.clone(),
+        };
+        if let Some(note) = &self.note {
+            haystack.push(' ');
+            haystack.push_str(note);
+        }
+        haystack
+    }
+
+    // Returns the activity level based on time since last activity.
+    pub fn activity_level(&self) -> Option<ActivityLevel> {
+        let activity_ts = self.last_activity?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+        Some(activity_thresholds().level(now.saturating_sub(activity_ts)))
+    }
+
+    // Returns a human-readable string for time since last activity.
+    pub fn activity_ago_string(&self) -> Option<String> {
+        let activity_ts = self.last_activity?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+        Some(format_elapsed(now.saturating_sub(activity_ts)))
+    }
+}
+
+// Formats elapsed seconds compactly, e.g. `42s`, `5m`, `3h`, or `2d`.
+pub fn format_elapsed(elapsed_secs: u64) -> String {
+    if elapsed_secs < 60 {
+        format!("{}s", elapsed_secs)
+    } else if elapsed_secs < 3600 {
+        format!("{}m", elapsed_secs / 60)
+    } else if elapsed_secs < 86400 {
+        format!("{}h", elapsed_secs / 3600)
+    } else {
+        format!("{}d", elapsed_secs / 86400)
+    }
+}
+
+/* Finds the index of a session matching the current working directory.
+ * First tries an exact path match, then falls back to matching the directory
+ * name against session names. Returns 0 if no match is found. */
+pub fn find_matching_session_index(sessions: &[TmuxSession]) -> usize {
+    let cwd = match std::env::current_dir() {
+        Ok(p) => p,
+        Err(_) => return 0,
+    };
+
+    for (idx, session) in sessions.iter().enumerate() {
+        if let Some(ref session_path) = session.path
+            && session_path == &cwd
+        {
+            return idx;
+        }
+    }
+
+    if let Some(dir_name) = cwd.file_name() {
+        let dir_name_str = dir_name.to_string_lossy();
+        for (idx, session) in sessions.iter().enumerate() {
+            if session.name == dir_name_str {
+                return idx;
+            }
+        }
+    }
+
+    0
+}
@@ -0,0 +1,286 @@
+use crate::tmux::error::TmuxError;
+use crate::tmux::parser::{FIELD_SEPARATOR, session_target, window_target};
+
+#[derive(Debug, Clone)]
+pub struct TmuxWindow {
+    pub index: u32,
+    pub name: String,
+    pub active: bool,
+    pub current_command: String,
+    pub dead: bool, // the active pane's process exited (kept by remain-on-exit)
+    pub alerts: WindowAlerts,
+    pub monitor_activity: bool, // `monitor-activity` is on
+    pub monitor_silence: u32,   // `monitor-silence` seconds, 0 = off
+    pub id: String,             // `@N`, stable while indexes shift; empty when unknown
+}
+
+impl TmuxWindow {
+    /* The tmux target for this window. Indexes follow `base-index` and
+     * shift under `renumber-windows` (or a `move-window`) after the window
+     * list was read, so the window's id is used when it is known. */
+    pub fn target(&self, session: &str) -> String {
+        if self.id.starts_with('@') {
+            format!("{}:{}", session_target(session), self.id)
+        } else {
+            window_target(session, self.index)
+        }
+    }
+}
+
+/* tmux's alert flags (`monitor-bell`, `monitor-activity`,
+ * `monitor-silence`), set on a window nobody is looking at and cleared once
+ * a client views it. On a session they are the union over its windows. */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowAlerts {
+    pub bell: bool,
+    pub activity: bool,
+    pub silence: bool,
+}
+
+impl WindowAlerts {
+    pub fn any(&self) -> bool {
+        self.bell || self.activity || self.silence
+    }
+
+    /* Reads `#{session_alerts}`, window indexes each followed by the flags
+     * tmux uses in the status line: `!` bell, `#` activity, `~` silence
+     * (`1#,3!~`). */
+    pub fn from_session_alerts(alerts: &str) -> Self {
+        Self {
+            bell: alerts.contains('!'),
+            activity: alerts.contains('#'),
+            silence: alerts.contains('~'),
+        }
+    }
+
+    // Badge for a row: the most urgent flag, bell first, empty without one.
+    pub fn badge(&self) -> &'static str {
+        if self.bell {
+            "🔔"
+        } else if self.silence {
+            "🔕"
+        } else if self.activity {
+            "💬"
+        } else {
+            ""
+        }
+    }
+}
+
+// A window's name and the command its active pane was started with, enough
+// to recreate the window in another session. An empty command means the
+// pane started the default shell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowRecipe {
+    pub name: String,
+    pub start_command: String,
+}
+
+// Format string passed to `tmux list-windows -F`.
+pub const WINDOW_FORMAT: &str = "#{window_index}\x1f#{window_name}\x1f#{window_active}\x1f#{pane_current_command}\x1f#{pane_dead}\x1f#{window_bell_flag}\x1f#{window_activity_flag}\x1f#{window_silence_flag}\x1f#{monitor-activity}\x1f#{monitor-silence}\x1f#{window_id}";
+
+// Format string for listing every window, prefixed with the window's session.
+pub const ALL_WINDOWS_FORMAT: &str = "#{session_name}\x1f#{window_index}\x1f#{window_name}\x1f#{window_active}\x1f#{pane_current_command}\x1f#{pane_dead}\x1f#{window_bell_flag}\x1f#{window_activity_flag}\x1f#{window_silence_flag}\x1f#{monitor-activity}\x1f#{monitor-silence}\x1f#{window_id}";
+
+// Format string for reading window recipes with `tmux list-windows -F`.
+pub const WINDOW_RECIPE_FORMAT: &str = "#{window_name}\x1f#{pane_start_command}";
+
+// Parses the output of `tmux list-windows` into a list of windows.
+// A malformed line is reported rather than skipped.
+pub fn parse_windows(output: &str) -> Result<Vec<TmuxWindow>, TmuxError> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            parse_window_line(line).ok_or_else(|| TmuxError::ParseError {
+                line: line.to_string(),
+            })
+        })
+        .collect()
+}
+
+// Parses `tmux list-windows -a` output into (session name, window) pairs.
+// Format: ALL_WINDOWS_FORMAT, the session followed by WINDOW_FORMAT
+pub fn parse_all_windows(output: &str) -> Result<Vec<(String, TmuxWindow)>, TmuxError> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.split_once(FIELD_SEPARATOR)
+                .and_then(|(session, rest)| Some((session.to_string(), parse_window_line(rest)?)))
+                .ok_or_else(|| TmuxError::ParseError {
+                    line: line.to_string(),
+                })
+        })
+        .collect()
+}
+
+// Parses `tmux list-windows` output into window recipes.
+// Format: WINDOW_RECIPE_FORMAT (the command is last, so it keeps any separator)
+pub fn parse_window_recipes(output: &str) -> Vec<WindowRecipe> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (name, start_command) = line.split_once(FIELD_SEPARATOR).unwrap_or((line, ""));
+            WindowRecipe {
+                name: name.to_string(),
+                start_command: start_command.to_string(),
+            }
+        })
+        .collect()
+}
+
+// Parses a single line of window output.
+// Format: WINDOW_FORMAT, `index name active command dead bell activity silence
+// monitor-activity monitor-silence id` (the fields after the command are optional)
+fn parse_window_line(line: &str) -> Option<TmuxWindow> {
+    let parts: Vec<&str> = line.split(FIELD_SEPARATOR).collect();
+
+    if parts.len() < 4 {
+        return None;
+    }
+
+    let index: u32 = parts[0].parse().ok()?;
+    let name = parts[1].to_string();
+    let active = parts[2] == "1";
+    let current_command = parts[3].to_string();
+    let dead = parts.get(4) == Some(&"1");
+    let alerts = WindowAlerts {
+        bell: parts.get(5) == Some(&"1"),
+        activity: parts.get(6) == Some(&"1"),
+        silence: parts.get(7) == Some(&"1"),
+    };
+    let monitor_activity = parts.get(8) == Some(&"on");
+    let monitor_silence = parts.get(9).and_then(|secs| secs.parse().ok()).unwrap_or(0);
+    let id = parts.get(10).unwrap_or(&"").to_string();
+
+    Some(TmuxWindow {
+        index,
+        name,
+        active,
+        current_command,
+        dead,
+        alerts,
+        monitor_activity,
+        monitor_silence,
+        id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_window_line() {
+        let line = "0\x1fvim\x1f1\x1fnvim";
+        let window = parse_window_line(line).unwrap();
+        assert_eq!(window.index, 0);
+        assert_eq!(window.name, "vim");
+        assert!(window.active);
+        assert_eq!(window.current_command, "nvim");
+        assert!(!window.dead);
+
+        let window = parse_window_line("2\x1fbuild\x1f0\x1fmake\x1f1").unwrap();
+        assert!(window.dead);
+        assert!(!window.alerts.any());
+    }
+
+    #[test]
+    fn test_parse_window_alerts() {
+        let window = parse_window_line("1\x1flogs\x1f0\x1ftail\x1f0\x1f1\x1f1\x1f0").unwrap();
+        assert!(window.alerts.bell && window.alerts.activity && !window.alerts.silence);
+        assert_eq!(window.alerts.badge(), "🔔");
+        assert!(!window.monitor_activity);
+
+        let window =
+            parse_window_line("1\x1flogs\x1f0\x1ftail\x1f0\x1f0\x1f0\x1f1\x1fon\x1f30").unwrap();
+        assert!(window.alerts.silence);
+        assert!(window.monitor_activity);
+        assert_eq!(window.monitor_silence, 30);
+        assert_eq!(window.target("api"), "=api:1");
+
+        let window =
+            parse_window_line("3\x1flogs\x1f0\x1ftail\x1f0\x1f0\x1f0\x1f0\x1foff\x1f0\x1f@12")
+                .unwrap();
+        assert_eq!(window.id, "@12");
+        assert_eq!(window.target("api"), "=api:@12");
+
+        let alerts = WindowAlerts::from_session_alerts("1#,3~");
+        assert_eq!(
+            alerts,
+            WindowAlerts {
+                bell: false,
+                activity: true,
+                silence: true
+            }
+        );
+        assert_eq!(alerts.badge(), "🔕");
+        assert!(!WindowAlerts::from_session_alerts("").any());
+    }
+
+    #[test]
+    fn test_parse_windows() {
+        let output = "0\x1fvim\x1f1\x1fnvim\n1\x1fshell\x1f0\x1fzsh\n";
+        let windows = parse_windows(output).unwrap();
+        assert_eq!(windows.len(), 2);
+        assert!(windows[0].active);
+        assert!(!windows[1].active);
+    }
+
+    #[test]
+    fn test_parse_all_windows() {
+        let windows =
+            parse_all_windows("dev\x1f0\x1fvim\x1f1\x1fnvim\napi\x1f2\x1ftests\x1f0\x1fcargo\n")
+                .unwrap();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[1].0, "api");
+        assert_eq!(windows[1].1.index, 2);
+        assert_eq!(windows[1].1.current_command, "cargo");
+        assert!(parse_all_windows("0\x1fvim\x1f1\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_window_recipes() {
+        let recipes = parse_window_recipes(
+            "agent\x1fclaude --resume\nshell\x1f\nlogs\x1ftail -f a | grep x\n",
+        );
+        assert_eq!(recipes.len(), 3);
+        assert_eq!(recipes[0].start_command, "claude --resume");
+        assert_eq!(recipes[1].name, "shell");
+        assert!(recipes[1].start_command.is_empty());
+        assert_eq!(recipes[2].start_command, "tail -f a | grep x");
+    }
+
+    #[test]
+    fn test_parse_windows_adversarial_names() {
+        let window = parse_window_line("1\x1fa|b || c\x1f1\x1fsh|x").unwrap();
+        assert_eq!(window.name, "a|b || c");
+        assert_eq!(window.current_command, "sh|x");
+
+        let windows = parse_all_windows("s|1\x1f0\x1f日本\x1f0\x1fzsh\x1f0\n").unwrap();
+        assert_eq!(windows[0].0, "s|1");
+        assert_eq!(windows[0].1.name, "日本");
+
+        let recipes = parse_window_recipes("a|b\x1fecho \x1f\n");
+        assert_eq!(recipes[0].name, "a|b");
+        assert_eq!(recipes[0].start_command, "echo \x1f");
+    }
+
+    #[test]
+    fn test_parse_windows_reports_bad_line() {
+        let error = parse_windows(
+            "0\x1fvim\x1f1\x1fnvim
+x\x1fshell\x1f0\x1fzsh
+",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            TmuxError::ParseError {
+                line: "x\x1fshell\x1f0\x1fzsh".to_string()
+            }
+        );
+    }
+}